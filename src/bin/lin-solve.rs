@@ -0,0 +1,256 @@
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::process;
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+
+use mat_solve::solver::{CoefficientMatrix, Equation, SolveError};
+
+const USAGE: &str = "\
+Usage: lin-solve [FILE]
+       lin-solve --serve
+
+Reads an augmented matrix from FILE (or stdin if FILE is missing or '-'),
+one equation per line with the right-hand side last. Values are separated
+by commas or whitespace; empty lines and lines starting with '#' are
+skipped. Prints one solution value per line.";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SOLVE_ERROR: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    // A missing id makes a notification, an explicit `null` doesn't
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+// Parameters of the `solve` method: one row of coefficients and one
+// right-hand side per equation
+#[derive(Deserialize)]
+struct System {
+    rows: Vec<Vec<f64>>,
+    rhs: Vec<f64>,
+}
+
+// Solve the system the same way the wasm `MatrixSolver` does without
+// hooks, progress callbacks or a method set, see `solve_default`
+fn solve(system: System) -> Result<Vec<f64>, SolveError> {
+    let size = system.rows.first().map_or(0, Vec::len);
+    let mut matrix = CoefficientMatrix::new(size);
+    for (coefficients, result) in system.rows.into_iter().zip(system.rhs) {
+        matrix = matrix.add_equation(Equation::new(coefficients, result));
+    }
+    matrix.solve_default()
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// Handle a single request line. Notifications (requests without an id)
+// don't get a response.
+fn handle(line: &str) -> Option<Value> {
+    let request: Request = match serde_json::from_str::<Value>(line) {
+        Err(err) => return Some(error(Value::Null, PARSE_ERROR, &err.to_string())),
+        Ok(value) => match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(err) => return Some(error(Value::Null, INVALID_REQUEST, &err.to_string())),
+        },
+    };
+    if request.jsonrpc != "2.0" {
+        let id = request.id.unwrap_or(Value::Null);
+        return Some(error(id, INVALID_REQUEST, "Only JSON-RPC 2.0 is supported"));
+    }
+    let id = request.id?;
+
+    if request.method != "solve" {
+        return Some(error(id, METHOD_NOT_FOUND, &format!("Unknown method '{}'", request.method)));
+    }
+    let system: System = match serde_json::from_value(request.params) {
+        Ok(system) => system,
+        Err(err) => return Some(error(id, INVALID_PARAMS, &err.to_string())),
+    };
+    if system.rows.len() != system.rhs.len() {
+        return Some(error(id, INVALID_PARAMS, "rows and rhs differ in length"));
+    }
+
+    Some(match solve(system) {
+        Ok(solution) => json!({ "jsonrpc": "2.0", "id": id, "result": { "solution": solution } }),
+        Err(err) => error(id, SOLVE_ERROR, &err.to_string()),
+    })
+}
+
+// Read newline-delimited JSON-RPC requests from stdin and answer each on its
+// own line on stdout until stdin is closed
+fn serve() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle(&line) {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+// Parse an augmented matrix from CSV or whitespace-separated text
+fn parse(text: &str) -> Result<Vec<Vec<f64>>, String> {
+    let mut rows = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let row = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<f64>()
+                .map_err(|_| format!("line {}: invalid number '{}'", number + 1, value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+// Solve the augmented matrix read from `path` (stdin for `None`) and print
+// the solution
+fn run(path: Option<&str>) -> io::Result<()> {
+    let mut text = String::new();
+    match path {
+        Some(path) => text = fs::read_to_string(path)?,
+        None => {
+            io::stdin().read_to_string(&mut text)?;
+        },
+    }
+
+    let rows = parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let solved = CoefficientMatrix::try_from(rows)
+        .and_then(|matrix| matrix.convert())
+        .and_then(|matrix| matrix.solve())
+        .map_err(io::Error::other)?;
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    for value in solved.solution() {
+        writeln!(stdout, "{}", value)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>().as_slice() {
+        ["--serve"] => serve(),
+        [] | ["-"] => run(None),
+        [path] if !path.starts_with("--") => run(Some(*path)),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+
+    if let Err(err) = result {
+        eprintln!("lin-solve: {}", err);
+        process::exit(1);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(response: Option<Value>) -> Value {
+        response.unwrap()["error"]["code"].clone()
+    }
+
+    #[test]
+    fn answer_requests() {
+        let request = r#"{"jsonrpc": "2.0", "id": 1, "method": "solve", "params": {"rows": [[1, 1], [1, -1]], "rhs": [3, 1]}}"#;
+        assert_eq!(
+            handle(request),
+            Some(json!({ "jsonrpc": "2.0", "id": 1, "result": { "solution": [2.0, 1.0] } }))
+        );
+
+        // A null id is still answered
+        let request = r#"{"jsonrpc": "2.0", "id": null, "method": "solve", "params": {"rows": [[2]], "rhs": [1]}}"#;
+        assert_eq!(
+            handle(request),
+            Some(json!({ "jsonrpc": "2.0", "id": null, "result": { "solution": [0.5] } }))
+        );
+
+        // More equations than unknowns are solved in the least squares sense
+        let request = r#"{"jsonrpc": "2.0", "id": 2, "method": "solve", "params": {"rows": [[1], [1]], "rhs": [1, 3]}}"#;
+        assert_eq!(
+            handle(request),
+            Some(json!({ "jsonrpc": "2.0", "id": 2, "result": { "solution": [2.0] } }))
+        );
+    }
+
+    #[test]
+    fn ignore_notifications() {
+        assert_eq!(handle(r#"{"jsonrpc": "2.0", "method": "solve", "params": {"rows": [[2]], "rhs": [1]}}"#), None);
+        assert_eq!(handle(r#"{"jsonrpc": "2.0", "method": "unknown"}"#), None);
+    }
+
+    #[test]
+    fn report_errors() {
+        assert_eq!(code(handle("{")), json!(PARSE_ERROR));
+        assert_eq!(code(handle(r#"{"jsonrpc": "2.0", "id": 1}"#)), json!(INVALID_REQUEST));
+        assert_eq!(code(handle(r#"{"id": 1, "method": "solve"}"#)), json!(INVALID_REQUEST));
+        assert_eq!(code(handle(r#"{"jsonrpc": "1.0", "id": 1, "method": "solve"}"#)), json!(INVALID_REQUEST));
+        assert_eq!(code(handle(r#"{"jsonrpc": "2.0", "id": 1, "method": "invert"}"#)), json!(METHOD_NOT_FOUND));
+
+        assert_eq!(code(handle(r#"{"jsonrpc": "2.0", "id": 1, "method": "solve"}"#)), json!(INVALID_PARAMS));
+        let mismatched = r#"{"jsonrpc": "2.0", "id": 1, "method": "solve", "params": {"rows": [[1, 0], [0, 1]], "rhs": [1]}}"#;
+        assert_eq!(code(handle(mismatched)), json!(INVALID_PARAMS));
+
+        let singular = r#"{"jsonrpc": "2.0", "id": "a", "method": "solve", "params": {"rows": [[1, 1], [2, 2]], "rhs": [1, 2]}}"#;
+        let response = handle(singular).unwrap();
+        assert_eq!(response["id"], json!("a"));
+        assert_eq!(response["error"]["code"], json!(SOLVE_ERROR));
+    }
+
+    #[test]
+    fn parse_rows() {
+        let text = "# x + y = 3, x - y = 1\n1, 1, 3\n\n  1 -1\t1  \n";
+        assert_eq!(parse(text).unwrap(), vec![vec![1.0, 1.0, 3.0], vec![1.0, -1.0, 1.0]]);
+        assert_eq!(parse("").unwrap(), Vec::<Vec<f64>>::new());
+    }
+
+    #[test]
+    fn parse_ragged_rows() {
+        // Parsing keeps the rows as they are, building the matrix rejects them
+        let rows = parse("1, 1, 3\n1, 1\n").unwrap();
+        assert_eq!(rows, vec![vec![1.0, 1.0, 3.0], vec![1.0, 1.0]]);
+        assert!(matches!(CoefficientMatrix::try_from(rows), Err(SolveError::UnfittingRows(..))));
+    }
+
+    #[test]
+    fn parse_non_numeric() {
+        assert_eq!(parse("1, 2, 3\n\n4, x, 6\n").unwrap_err(), "line 3: invalid number 'x'");
+        assert!(parse("1,,2;3").is_err());
+    }
+}
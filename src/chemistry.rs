@@ -0,0 +1,221 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::iter::Peekable;
+use core::str::Chars;
+use num::Integer;
+
+use crate::solver::Matrix;
+
+// Balancing chemical equations. Each element gives one linear equation in
+// the coefficients of the species, counting reactants positive and products
+// negative, and the balanced equation is the smallest positive integer
+// vector of the kernel:
+//
+//     let balanced = balance("Fe + O2 -> Fe2O3")?;
+//     assert_eq!(balanced.to_string(), "4 Fe + 3 O2 -> 2 Fe2O3");
+//
+// The kernel is computed exactly with `Matrix::solve_integer`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChemistryError {
+    // There is no `->`, `=` or `→` between reactants and products
+    MissingArrow,
+    // The formula of this species can't be parsed
+    Formula(String),
+    // Only the trivial solution balances the elements
+    Unbalanceable,
+    // The balanced coefficients aren't all positive, so some species can't
+    // take part in the reaction as written
+    NotPositive,
+    // There are this many independent ways to balance the equation, e.g.
+    // when it combines several reactions
+    Ambiguous(usize),
+}
+
+impl fmt::Display for ChemistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChemistryError::MissingArrow => write!(f, "Expected '->' between reactants and products"),
+            ChemistryError::Formula(species) => write!(f, "Invalid formula '{}'", species),
+            ChemistryError::Unbalanceable => write!(f, "The equation can't be balanced"),
+            ChemistryError::NotPositive => write!(f, "The equation can't be balanced with positive coefficients"),
+            ChemistryError::Ambiguous(ways) => write!(f, "The equation can be balanced in {} independent ways", ways),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChemistryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalancedEquation {
+    reactants: Vec<(String, i64)>,
+    products: Vec<(String, i64)>,
+}
+
+impl BalancedEquation {
+    // Formula and coefficient of each reactant
+    pub fn reactants(&self) -> &[(String, i64)] {
+        &self.reactants
+    }
+
+    pub fn products(&self) -> &[(String, i64)] {
+        &self.products
+    }
+}
+
+impl fmt::Display for BalancedEquation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = |f: &mut fmt::Formatter, species: &[(String, i64)]| -> fmt::Result {
+            for (idx, (formula, coefficient)) in species.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, " + ")?;
+                }
+                if *coefficient != 1 {
+                    write!(f, "{} ", coefficient)?;
+                }
+                write!(f, "{}", formula)?;
+            }
+            Ok(())
+        };
+        side(f, &self.reactants)?;
+        write!(f, " -> ")?;
+        side(f, &self.products)
+    }
+}
+
+// Count after an element or group, one if it's left out
+fn count(chars: &mut Peekable<Chars>) -> Option<i64> {
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+        digits.push(c);
+        chars.next();
+    }
+    if digits.is_empty() { Some(1) } else { digits.parse::<i64>().ok().filter(|&n| n > 0) }
+}
+
+// Count of each element in a formula like `Ca(OH)2`. Brackets may nest.
+fn parse_formula(formula: &str) -> Option<BTreeMap<String, i64>> {
+    let mut chars = formula.chars().peekable();
+    let mut groups = vec![BTreeMap::new()];
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | '[' => groups.push(BTreeMap::new()),
+            ')' | ']' => {
+                let group = groups.pop()?;
+                let factor = count(&mut chars)?;
+                let outer = groups.last_mut()?;
+                for (element, n) in group {
+                    *outer.entry(element).or_insert(0) += n * factor;
+                }
+            },
+            c if c.is_ascii_uppercase() => {
+                let mut element = c.to_string();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_lowercase()) {
+                    element.push(c);
+                    chars.next();
+                }
+                let n = count(&mut chars)?;
+                *groups.last_mut()?.entry(element).or_insert(0) += n;
+            },
+            _ => return None,
+        }
+    }
+    if groups.len() != 1 || groups[0].is_empty() {
+        return None;
+    }
+    groups.pop()
+}
+
+fn species(side: &str) -> Vec<String> {
+    side.split('+').map(|species| species.trim().to_string()).collect()
+}
+
+// Balance an equation like `Fe + O2 -> Fe2O3`. Coefficients already in
+// the input aren't understood, the formulas must stand alone.
+pub fn balance(equation: &str) -> Result<BalancedEquation, ChemistryError> {
+    let (left, right) = ["->", "→", "="].iter()
+        .find_map(|arrow| {
+            let mut sides = equation.splitn(2, arrow);
+            Some((sides.next()?, sides.next()?))
+        })
+        .ok_or(ChemistryError::MissingArrow)?;
+    let (reactants, products) = (species(left), species(right));
+
+    let mut elements: Vec<String> = Vec::new();
+    let mut counts = Vec::with_capacity(reactants.len() + products.len());
+    for formula in reactants.iter().chain(&products) {
+        let count = parse_formula(formula).ok_or_else(|| ChemistryError::Formula(formula.clone()))?;
+        for element in count.keys() {
+            if !elements.contains(element) {
+                elements.push(element.clone());
+            }
+        }
+        counts.push(count);
+    }
+
+    // One row per element, one column per species
+    let rows: Vec<Vec<i64>> = elements.iter()
+        .map(|element| counts.iter()
+            .enumerate()
+            .map(|(column, count)| {
+                let n = count.get(element).copied().unwrap_or(0);
+                if column < reactants.len() { n } else { -n }
+            })
+            .collect())
+        .collect();
+    let matrix = Matrix::from_rows(rows).map_err(|_| ChemistryError::Unbalanceable)?;
+    let solution = matrix.solve_integer(&vec![0; elements.len()])
+        .ok()
+        .flatten()
+        .ok_or(ChemistryError::Unbalanceable)?;
+
+    let mut coefficients = match solution.kernel() {
+        [] => return Err(ChemistryError::Unbalanceable),
+        [vector] => vector.clone(),
+        kernel => return Err(ChemistryError::Ambiguous(kernel.len())),
+    };
+    let divisor = coefficients.iter().fold(0, |divisor, c| divisor.gcd(c));
+    let sign = if coefficients.iter().any(|&c| c < 0) { -1 } else { 1 };
+    for c in coefficients.iter_mut() {
+        *c = *c / divisor * sign;
+    }
+    if coefficients.iter().any(|&c| c <= 0) {
+        return Err(ChemistryError::NotPositive);
+    }
+
+    let mut coefficients = coefficients.into_iter();
+    Ok(BalancedEquation {
+        reactants: reactants.into_iter().zip(&mut coefficients).collect(),
+        products: products.into_iter().zip(coefficients).collect(),
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_equations() {
+        assert_eq!(balance("Fe + O2 -> Fe2O3").unwrap().to_string(), "4 Fe + 3 O2 -> 2 Fe2O3");
+        assert_eq!(balance("CH4 + O2 = CO2 + H2O").unwrap().to_string(), "CH4 + 2 O2 -> CO2 + 2 H2O");
+        let balanced = balance("Ca(OH)2 + H3PO4 -> Ca3(PO4)2 + H2O").unwrap();
+        let coefficients: Vec<i64> = balanced.reactants().iter().chain(balanced.products())
+            .map(|&(_, c)| c)
+            .collect();
+        assert_eq!(coefficients, vec![3, 2, 1, 6]);
+        assert_eq!(balanced.products()[0].0, "Ca3(PO4)2");
+    }
+
+    #[test]
+    fn reject_unbalanceable() {
+        assert_eq!(balance("H2 + O2"), Err(ChemistryError::MissingArrow));
+        assert_eq!(balance("H2 + o2 -> H2O"), Err(ChemistryError::Formula("o2".to_string())));
+        assert_eq!(balance("Fe(OH -> Fe"), Err(ChemistryError::Formula("Fe(OH".to_string())));
+        assert_eq!(balance("H2 -> O2"), Err(ChemistryError::Unbalanceable));
+        assert_eq!(balance("H2 + O2 -> H2O + H2O2"), Err(ChemistryError::Ambiguous(2)));
+    }
+}
@@ -0,0 +1,281 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use core::str::FromStr;
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, SolveError};
+
+// DC circuits of resistors and sources, solved by modified nodal analysis.
+// Node 0 is ground, the other nodes are numbered from 1:
+//
+//     let solution = Circuit::new()
+//         .voltage_source(1, 0, 10.0)
+//         .resistor(1, 2, 1000.0)
+//         .resistor(2, 0, 1000.0)
+//         .solve()?;
+//     // solution.voltages() == [0.0, 10.0, 5.0]
+//
+// The unknowns are the voltages of nodes 1 to n followed by the current
+// through each voltage source. Each node contributes Kirchhoff's current
+// law, each voltage source the voltage between its nodes.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Element<T> {
+    Resistor { a: usize, b: usize, ohms: T },
+    // Keeps `positive` `volts` above `negative`
+    VoltageSource { positive: usize, negative: usize, volts: T },
+    // Drives `amps` through itself from node `from` to node `to`
+    CurrentSource { from: usize, to: usize, amps: T },
+}
+
+#[derive(Debug)]
+pub enum CircuitError {
+    SolveError(SolveError),
+    // The resistor at this index of the netlist has no positive resistance
+    Resistance(usize),
+}
+
+impl From<SolveError> for CircuitError {
+    fn from(err: SolveError) -> Self {
+        CircuitError::SolveError(err)
+    }
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::SolveError(SolveError::DependentSolutionSet) =>
+                write!(f, "Some node voltages aren't determined, is every node connected to ground?"),
+            CircuitError::SolveError(SolveError::EmptySolutionSet) =>
+                write!(f, "The voltage sources contradict each other"),
+            CircuitError::SolveError(err) => write!(f, "{}", err),
+            CircuitError::Resistance(index) => write!(f, "Element {} needs a positive resistance", index + 1),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CircuitError {}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Circuit<T> {
+    elements: Vec<Element<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitSolution<T> {
+    voltages: Vec<T>,
+    currents: Vec<T>,
+}
+
+impl<T> CircuitSolution<T> {
+    // Voltage of each node against ground, starting with ground itself
+    pub fn voltages(&self) -> &[T] {
+        &self.voltages
+    }
+
+    // Current each voltage source drives out of its positive node, in the
+    // order the sources were added
+    pub fn source_currents(&self) -> &[T] {
+        &self.currents
+    }
+}
+
+impl<T> Circuit<T> {
+    pub fn new() -> Circuit<T> {
+        Circuit { elements: Vec::new() }
+    }
+
+    pub fn elements(&self) -> &[Element<T>] {
+        &self.elements
+    }
+
+    pub fn element(mut self, element: Element<T>) -> Self {
+        self.elements.push(element);
+        self
+    }
+
+    pub fn resistor(self, a: usize, b: usize, ohms: T) -> Self {
+        self.element(Element::Resistor { a, b, ohms })
+    }
+
+    pub fn voltage_source(self, positive: usize, negative: usize, volts: T) -> Self {
+        self.element(Element::VoltageSource { positive, negative, volts })
+    }
+
+    pub fn current_source(self, from: usize, to: usize, amps: T) -> Self {
+        self.element(Element::CurrentSource { from, to, amps })
+    }
+
+    // Highest node number, the number of nodes besides ground
+    pub fn nodes(&self) -> usize {
+        self.elements.iter()
+            .map(|element| match *element {
+                Element::Resistor { a, b, .. } => a.max(b),
+                Element::VoltageSource { positive, negative, .. } => positive.max(negative),
+                Element::CurrentSource { from, to, .. } => from.max(to),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<T> Circuit<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // The nodal-analysis system, for showing or solving it step by step
+    pub fn matrix(&self) -> Result<CoefficientMatrix<T>, CircuitError> {
+        let nodes = self.nodes();
+        let sources = self.elements.iter().filter(|element| matches!(element, Element::VoltageSource { .. })).count();
+        let size = nodes + sources;
+        let mut rows = vec![vec![T::zero(); size + 1]; size];
+
+        // Add `value` at the row and column of two nodes, skipping ground
+        let mut add = |row: usize, column: usize, value: T| {
+            if row > 0 && column > 0 {
+                rows[row - 1][column - 1] = rows[row - 1][column - 1] + value;
+            }
+        };
+        let mut source = nodes + 1;
+        for (index, element) in self.elements.iter().enumerate() {
+            match *element {
+                Element::Resistor { a, b, ohms } => {
+                    if ohms <= T::zero() {
+                        return Err(CircuitError::Resistance(index));
+                    }
+                    let conductance = ohms.recip();
+                    add(a, a, conductance);
+                    add(b, b, conductance);
+                    add(a, b, -conductance);
+                    add(b, a, -conductance);
+                },
+                Element::VoltageSource { positive, negative, volts } => {
+                    add(positive, source, T::one());
+                    add(negative, source, -T::one());
+                    add(source, positive, T::one());
+                    add(source, negative, -T::one());
+                    add(source, size + 1, volts);
+                    source += 1;
+                },
+                Element::CurrentSource { from, to, amps } => {
+                    add(from, size + 1, -amps);
+                    add(to, size + 1, amps);
+                },
+            }
+        }
+        Ok(CoefficientMatrix::try_from(rows)?)
+    }
+
+    pub fn solve(&self) -> Result<CircuitSolution<T>, CircuitError> {
+        let nodes = self.nodes();
+        let solved = self.matrix()?.validate()?.convert()?.solve()?;
+        let solution: Vec<T> = solved.solution().collect();
+        let mut voltages = vec![T::zero()];
+        voltages.extend_from_slice(&solution[..nodes]);
+        // The unknown is the current into the positive node
+        let currents = solution[nodes..].iter().map(|&current| -current).collect();
+        Ok(CircuitSolution { voltages, currents })
+    }
+}
+
+// Error returned when parsing a netlist fails, carrying the offending line
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseNetlistError(pub usize);
+
+impl fmt::Display for ParseNetlistError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid netlist at line {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseNetlistError {}
+
+// SPICE-like netlist, one element per line as name, two nodes and value.
+// The first letter of the name selects the element, the nodes are ordered
+// as in `Element`. Empty lines and lines starting with `*` are skipped.
+//
+//     * voltage divider
+//     V1 1 0 10
+//     R1 1 2 1000
+//     R2 2 0 1000
+impl<T: Real + FromStr> FromStr for Circuit<T> {
+    type Err = ParseNetlistError;
+
+    fn from_str(input: &str) -> Result<Self, ParseNetlistError> {
+        let mut circuit = Circuit::new();
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('*') {
+                continue;
+            }
+            let error = ParseNetlistError(idx + 1);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 4 {
+                return Err(error);
+            }
+            let a = fields[1].parse::<usize>().map_err(|_| ParseNetlistError(idx + 1))?;
+            let b = fields[2].parse::<usize>().map_err(|_| ParseNetlistError(idx + 1))?;
+            let value = fields[3].parse::<T>().map_err(|_| ParseNetlistError(idx + 1))?;
+            circuit = match fields[0].chars().next().map(|c| c.to_ascii_uppercase()) {
+                Some('R') => circuit.resistor(a, b, value),
+                Some('V') => circuit.voltage_source(a, b, value),
+                Some('I') => circuit.current_source(a, b, value),
+                _ => return Err(error),
+            };
+        }
+        Ok(circuit)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn voltage_divider() {
+        let solution = Circuit::new()
+            .voltage_source(1, 0, 10.0)
+            .resistor(1, 2, 1000.0)
+            .resistor(2, 0, 1000.0)
+            .solve().unwrap();
+        assert_close(solution.voltages(), &[0.0, 10.0, 5.0]);
+        assert_close(solution.source_currents(), &[0.005]);
+
+        let parsed: Circuit<f64> = "* divider\nV1 1 0 10\nr1 1 2 1000\n\nR2 2 0 1000".parse().unwrap();
+        assert_eq!(parsed.solve().unwrap(), solution);
+        assert_eq!(parsed.matrix().unwrap().size(), 3);
+    }
+
+    #[test]
+    fn current_sources() {
+        // 2 mA into node 1, which has 1 kΩ to ground and 1 kΩ to node 2,
+        // which is held at 1 V
+        let solution = Circuit::new()
+            .current_source(0, 1, 0.002)
+            .resistor(1, 0, 1000.0)
+            .resistor(1, 2, 1000.0)
+            .voltage_source(2, 0, 1.0)
+            .solve().unwrap();
+        assert_close(solution.voltages(), &[0.0, 1.5, 1.0]);
+        assert_close(solution.source_currents(), &[-0.0005]);
+    }
+
+    #[test]
+    fn reject_invalid_circuits() {
+        assert!(matches!(Circuit::new().resistor(1, 0, 0.0).solve(), Err(CircuitError::Resistance(0))));
+        let floating = Circuit::new().resistor(1, 0, 1.0).resistor(2, 3, 1.0);
+        assert!(matches!(floating.solve(), Err(CircuitError::SolveError(SolveError::DependentSolutionSet))));
+        assert_eq!("R1 1 0".parse::<Circuit<f64>>(), Err(ParseNetlistError(1)));
+        assert_eq!("R1 1 0 1\nC1 1 0 1".parse::<Circuit<f64>>(), Err(ParseNetlistError(2)));
+    }
+}
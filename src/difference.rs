@@ -0,0 +1,187 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+// Finite difference approximations of gradients and Jacobians for functions
+// of several variables, given as closures over a slice:
+//
+//     let gradient = Differences::central().gradient(|x| x[0] * x[1], &[1.0, 2.0]);
+//
+// Each variable is shifted by its own step, relative to the size of the
+// variable so that large and small values lose about the same digits.
+
+#[derive(Debug)]
+pub enum DifferenceError {
+    // The function returned a different number of values after a shift
+    UnfittingDimension(usize, usize),
+}
+
+impl fmt::Display for DifferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DifferenceError::UnfittingDimension(actual, expected) => {
+                write!(f, "Expected {} values but got {}", expected, actual)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DifferenceError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scheme {
+    // (f(x + h) - f(x)) / h, one evaluation per variable, error of order h
+    Forward,
+    // (f(x + h) - f(x - h)) / 2h, two evaluations per variable, error of
+    // order h^2
+    Central,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Differences<T> {
+    scheme: Scheme,
+    step: T,
+}
+
+impl<T: Real> Differences<T> {
+    // Forward differences with the step √ε balancing truncation against
+    // rounding errors
+    pub fn forward() -> Differences<T> {
+        Differences { scheme: Scheme::Forward, step: T::epsilon().sqrt() }
+    }
+
+    // Central differences with the step ∛ε
+    pub fn central() -> Differences<T> {
+        Differences { scheme: Scheme::Central, step: T::epsilon().cbrt() }
+    }
+
+    // Step relative to max(|x|, 1)
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    fn step_at(&self, x: T) -> T {
+        self.step * x.abs().max(T::one())
+    }
+
+    // Partial derivatives of a scalar function at x
+    pub fn gradient<F>(&self, f: F, x: &[T]) -> Vec<T>
+        where F: Fn(&[T]) -> T {
+        let fx = match self.scheme {
+            Scheme::Forward => f(x),
+            Scheme::Central => T::zero(),
+        };
+        let mut x = x.to_vec();
+        (0..x.len())
+            .map(|j| {
+                let original = x[j];
+                let h = self.step_at(original);
+                x[j] = original + h;
+                let after = f(&x);
+                let derivative = match self.scheme {
+                    Scheme::Forward => (after - fx) / h,
+                    Scheme::Central => {
+                        x[j] = original - h;
+                        (after - f(&x)) / (h + h)
+                    },
+                };
+                x[j] = original;
+                derivative
+            })
+            .collect()
+    }
+
+    // Jacobian of a vector function at x, with one row of partial
+    // derivatives per value of f
+    pub fn jacobian<F>(&self, f: F, x: &[T]) -> Result<Vec<Vec<T>>, DifferenceError>
+        where F: Fn(&[T]) -> Vec<T> {
+        let fx = f(x);
+        self.jacobian_with(&f, x, &fx)
+    }
+
+    // The same with f(x) already known, which forward differences reuse
+    pub(crate) fn jacobian_with<F>(&self, f: &F, x: &[T], fx: &[T]) -> Result<Vec<Vec<T>>, DifferenceError>
+        where F: Fn(&[T]) -> Vec<T> {
+        let evaluate = |x: &[T]| {
+            let values = f(x);
+            if values.len() != fx.len() {
+                return Err(DifferenceError::UnfittingDimension(values.len(), fx.len()));
+            }
+            Ok(values)
+        };
+        let mut x = x.to_vec();
+        let mut rows = vec![Vec::with_capacity(x.len()); fx.len()];
+        for j in 0..x.len() {
+            let original = x[j];
+            let h = self.step_at(original);
+            x[j] = original + h;
+            let after = evaluate(&x)?;
+            let (before, width) = match self.scheme {
+                Scheme::Forward => (fx.to_vec(), h),
+                Scheme::Central => {
+                    x[j] = original - h;
+                    (evaluate(&x)?, h + h)
+                },
+            };
+            x[j] = original;
+            for (row, (a, b)) in rows.iter_mut().zip(after.iter().zip(&before)) {
+                row.push((*a - *b) / width);
+            }
+        }
+        Ok(rows)
+    }
+}
+
+impl<T: Real> Default for Differences<T> {
+    fn default() -> Self {
+        Differences::central()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient() {
+        // f(x, y) = x^2 y + sin y with gradient (2xy, x^2 + cos y)
+        let f = |x: &[f64]| x[0] * x[0] * x[1] + x[1].sin();
+        let expected = [2.0 * 3.0 * 0.5, 9.0 + 0.5f64.cos()];
+        let central = Differences::central().gradient(f, &[3.0, 0.5]);
+        let forward = Differences::forward().gradient(f, &[3.0, 0.5]);
+        for ((c, f), e) in central.iter().zip(&forward).zip(&expected) {
+            assert!((c - e).abs() < 1e-9);
+            assert!((f - e).abs() < 1e-6);
+        }
+        // A coarse step shows the difference in order
+        let coarse = |differences: Differences<f64>| differences.step(1e-2).gradient(|x| x[0].exp(), &[0.0])[0] - 1.0;
+        assert!(coarse(Differences::central()).abs() < 1e-4);
+        assert!(coarse(Differences::forward()).abs() > 1e-3);
+    }
+
+    #[test]
+    fn jacobian() {
+        let f = |x: &[f64]| vec![x[0] * x[1], x[0] + 2.0 * x[1], x[1] * x[1]];
+        let jacobian = Differences::central().jacobian(f, &[2.0, -1.0]).unwrap();
+        let expected = [[-1.0, 2.0], [1.0, 2.0], [0.0, -2.0]];
+        assert_eq!(jacobian.len(), 3);
+        for (row, expected) in jacobian.iter().zip(&expected) {
+            for (a, b) in row.iter().zip(expected) {
+                assert!((a - b).abs() < 1e-9);
+            }
+        }
+
+        let changing = |x: &[f64]| vec![0.0; if x[0] == 0.0 { 1 } else { 2 }];
+        assert!(matches!(
+            Differences::forward().jacobian(changing, &[0.0]),
+            Err(DifferenceError::UnfittingDimension(2, 1))
+        ));
+    }
+}
@@ -0,0 +1,487 @@
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::complex::Complex;
+use num::traits::real::Real;
+
+use crate::solver::{dot, norm2, CoefficientMatrix, SolveError};
+
+// Eigenvalues of real square matrices by the shifted QR algorithm on the
+// Hessenberg form, and eigenvectors by inverse iteration
+
+#[derive(Debug)]
+pub enum EigenError {
+    SolveError(SolveError),
+    // The QR iteration didn't converge for the eigenvalue at this index
+    NoConvergence(usize),
+}
+
+impl From<SolveError> for EigenError {
+    fn from(err: SolveError) -> Self {
+        EigenError::SolveError(err)
+    }
+}
+
+impl fmt::Display for EigenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EigenError::SolveError(err) => write!(f, "{}", err),
+            EigenError::NoConvergence(index) => write!(f, "No convergence for eigenvalue {}", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EigenError {}
+
+// QR iterations spent on one eigenvalue before giving up
+const MAX_ITERATIONS: usize = 30;
+
+// Inverse iteration steps per eigenvector
+const INVERSE_ITERATIONS: usize = 3;
+
+fn check_square<T>(a: &[Vec<T>]) -> Result<(), SolveError> {
+    if a.is_empty() {
+        return Err(SolveError::TooSmall(0));
+    }
+    match a.iter().find(|row| row.len() != a.len()) {
+        Some(row) => Err(SolveError::UnfittingCoefficientAmount(row.len(), a.len())),
+        None => Ok(()),
+    }
+}
+
+// `a` with the sign of `b`
+fn sign<T: Real>(a: T, b: T) -> T {
+    if b >= T::zero() { a.abs() } else { -a.abs() }
+}
+
+// Reduce to upper Hessenberg form by elimination with pivoting, which is a
+// similarity transformation and keeps the eigenvalues. Works on 1-based
+// indices like the classic formulation of the algorithm.
+#[allow(clippy::needless_range_loop)]
+fn hessenberg<T: Real>(a: &mut [Vec<T>], n: usize) {
+    for m in 2..n {
+        let mut x = T::zero();
+        let mut i = m;
+        for j in m..=n {
+            if a[j][m - 1].abs() > x.abs() {
+                x = a[j][m - 1];
+                i = j;
+            }
+        }
+        if i != m {
+            for j in m - 1..=n {
+                let t = a[i][j];
+                a[i][j] = a[m][j];
+                a[m][j] = t;
+            }
+            for row in a.iter_mut().skip(1) {
+                row.swap(i, m);
+            }
+        }
+        if !x.is_zero() {
+            for i in m + 1..=n {
+                let y = a[i][m - 1];
+                if !y.is_zero() {
+                    let y = y / x;
+                    a[i][m - 1] = T::zero();
+                    for j in m..=n {
+                        let t = y * a[m][j];
+                        a[i][j] = a[i][j] - t;
+                    }
+                    for row in a.iter_mut().skip(1) {
+                        row[m] = row[m] + y * row[i];
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Francis double shift QR iteration on an upper Hessenberg matrix with
+// 1-based indices, deflating one real eigenvalue or a complex pair at a time
+#[allow(clippy::needless_range_loop)]
+fn hessenberg_qr<T: Real>(a: &mut [Vec<T>], n: usize) -> Result<Vec<Complex<T>>, EigenError> {
+    let half = T::one() / (T::one() + T::one());
+    let mut values = vec![Complex::new(T::zero(), T::zero()); n + 1];
+
+    let mut norm = T::zero();
+    for i in 1..=n {
+        for j in (i - 1).max(1)..=n {
+            norm = norm + a[i][j].abs();
+        }
+    }
+
+    let mut nn = n;
+    let mut t = T::zero();
+    while nn >= 1 {
+        let mut iterations = 0;
+        loop {
+            // Look for a negligible subdiagonal element to split at
+            let mut l = nn;
+            while l >= 2 {
+                let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+                if s.is_zero() {
+                    s = norm;
+                }
+                if a[l][l - 1].abs() + s == s {
+                    a[l][l - 1] = T::zero();
+                    break;
+                }
+                l -= 1;
+            }
+
+            let mut x = a[nn][nn];
+            if l == nn {
+                // One real root found
+                values[nn] = Complex::new(x + t, T::zero());
+                nn -= 1;
+                break;
+            }
+            let mut y = a[nn - 1][nn - 1];
+            let mut w = a[nn][nn - 1] * a[nn - 1][nn];
+            if l == nn - 1 {
+                // Two roots found, a real or a complex conjugate pair
+                let p = half * (y - x);
+                let q = p * p + w;
+                let z = q.abs().sqrt();
+                x = x + t;
+                if q >= T::zero() {
+                    let z = p + sign(z, p);
+                    values[nn - 1] = Complex::new(x + z, T::zero());
+                    values[nn] = Complex::new(if z.is_zero() { x + z } else { x - w / z }, T::zero());
+                } else {
+                    values[nn - 1] = Complex::new(x + p, -z);
+                    values[nn] = Complex::new(x + p, z);
+                }
+                nn -= 2;
+                break;
+            }
+
+            if iterations == MAX_ITERATIONS {
+                return Err(EigenError::NoConvergence(nn - 1));
+            }
+            if iterations == 10 || iterations == 20 {
+                // Exceptional shift to break cycles
+                t = t + x;
+                for i in 1..=nn {
+                    a[i][i] = a[i][i] - x;
+                }
+                let s = a[nn][nn - 1].abs() + a[nn - 1][nn - 2].abs();
+                let quarter = half * half;
+                x = (half + quarter) * s;
+                y = x;
+                w = -(quarter + quarter * half + quarter * quarter) * s * s;
+            }
+            iterations += 1;
+
+            // Form the shift and look for two consecutive small subdiagonal
+            // elements
+            let (mut p, mut q, mut r);
+            let mut m = nn - 2;
+            loop {
+                let z = a[m][m];
+                let rr = x - z;
+                let ss = y - z;
+                p = (rr * ss - w) / a[m + 1][m] + a[m][m + 1];
+                q = a[m + 1][m + 1] - z - rr - ss;
+                r = a[m + 2][m + 1];
+                let s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+                if m == l {
+                    break;
+                }
+                let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+                if u + v == v {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in m + 2..=nn {
+                a[i][i - 2] = T::zero();
+                if i != m + 2 {
+                    a[i][i - 3] = T::zero();
+                }
+            }
+
+            // Double QR step on rows l to nn and columns m to nn
+            for k in m..nn {
+                if k != m {
+                    p = a[k][k - 1];
+                    q = a[k + 1][k - 1];
+                    r = if k != nn - 1 { a[k + 2][k - 1] } else { T::zero() };
+                    x = p.abs() + q.abs() + r.abs();
+                    if !x.is_zero() {
+                        p = p / x;
+                        q = q / x;
+                        r = r / x;
+                    }
+                }
+                let s = sign((p * p + q * q + r * r).sqrt(), p);
+                if s.is_zero() {
+                    continue;
+                }
+                if k == m {
+                    if l != m {
+                        a[k][k - 1] = -a[k][k - 1];
+                    }
+                } else {
+                    a[k][k - 1] = -s * x;
+                }
+                p = p + s;
+                x = p / s;
+                y = q / s;
+                let z = r / s;
+                q = q / p;
+                r = r / p;
+                for j in k..=nn {
+                    let mut p = a[k][j] + q * a[k + 1][j];
+                    if k != nn - 1 {
+                        p = p + r * a[k + 2][j];
+                        a[k + 2][j] = a[k + 2][j] - p * z;
+                    }
+                    a[k + 1][j] = a[k + 1][j] - p * y;
+                    a[k][j] = a[k][j] - p * x;
+                }
+                for row in a.iter_mut().take(nn.min(k + 3) + 1).skip(l) {
+                    let mut p = x * row[k] + y * row[k + 1];
+                    if k != nn - 1 {
+                        p = p + z * row[k + 2];
+                        row[k + 2] = row[k + 2] - p * r;
+                    }
+                    row[k + 1] = row[k + 1] - p * q;
+                    row[k] = row[k] - p;
+                }
+            }
+        }
+    }
+
+    values.remove(0);
+    Ok(values)
+}
+
+// Eigenvalues of the square matrix with the given rows, sorted by
+// descending real and then imaginary part. Complex eigenvalues come in
+// conjugate pairs.
+pub fn eigenvalues<T: Real>(rows: &[Vec<T>]) -> Result<Vec<Complex<T>>, EigenError> {
+    check_square(rows)?;
+    let n = rows.len();
+    let mut a = vec![vec![T::zero(); n + 1]; n + 1];
+    for (i, row) in rows.iter().enumerate() {
+        a[i + 1][1..].copy_from_slice(row);
+    }
+
+    hessenberg(&mut a, n);
+    let mut values = hessenberg_qr(&mut a, n)?;
+    values.sort_by(|a, b| {
+        b.re.partial_cmp(&a.re)
+            .and_then(|ordering| match ordering {
+                Ordering::Equal => b.im.partial_cmp(&a.im),
+                ordering => Some(ordering),
+            })
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(values)
+}
+
+// Eigenvector for a real eigenvalue by inverse iteration: repeatedly solve
+// (A - mu I) x_next = x with mu slightly off the eigenvalue so that the
+// system stays regular. The result has unit length.
+pub fn eigenvector<T>(rows: &[Vec<T>], eigenvalue: T) -> Result<Vec<T>, EigenError>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    check_square(rows)?;
+    let n = rows.len();
+    let shift = eigenvalue + T::epsilon().sqrt() * eigenvalue.abs().max(T::one());
+
+    // Unequal start values so that the start isn't orthogonal to the
+    // eigenvector of symmetric examples
+    let mut x: Vec<T> = (1..=n).map(|i| T::from(i).map_or(T::one(), |i| i.recip())).collect();
+    for _ in 0..INVERSE_ITERATIONS {
+        let system = rows.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut row = row.clone();
+                row[i] -= shift;
+                row.push(x[i]);
+                row
+            })
+            .collect::<Vec<_>>();
+        let solved = CoefficientMatrix::try_from(system)?.convert()?.solve()?;
+        x = solved.solution().collect();
+        let length = x.iter().fold(T::zero(), |sum, value| sum + *value * *value).sqrt();
+        for value in x.iter_mut() {
+            *value = *value / length;
+        }
+    }
+
+    // Fix the sign so that the largest component is positive
+    let largest = x.iter().fold(T::zero(), |largest, value| {
+        if value.abs() > largest.abs() { *value } else { largest }
+    });
+    if largest < T::zero() {
+        for value in x.iter_mut() {
+            *value = -*value;
+        }
+    }
+    Ok(x)
+}
+
+
+// Start vector of the power iterations, unit length and with unequal
+// entries for the same reason as in `eigenvector`
+fn start_vector<T: Real>(n: usize) -> Vec<T> {
+    let mut x: Vec<T> = (1..=n).map(|i| T::from(i).map_or(T::one(), |i| i.recip())).collect();
+    let length = norm2(&x);
+    for value in x.iter_mut() {
+        *value = *value / length;
+    }
+    x
+}
+
+fn product<T: Real>(rows: &[Vec<T>], x: &[T]) -> Vec<T> {
+    rows.iter().map(|row| dot(row, x)).collect()
+}
+
+// Iterate x ← next(x) / |next(x)| until the Rayleigh quotient xᵀ A x has a
+// residual |A x - λ x| of at most `tolerance` relative to |λ|
+fn power_iteration<T, F>(rows: &[Vec<T>], max_iterations: usize, tolerance: T, mut next: F) -> Result<(T, Vec<T>), EigenError>
+    where T: Real, F: FnMut(&[T]) -> Result<Vec<T>, SolveError> {
+    let mut x = start_vector(rows.len());
+    for _ in 0..max_iterations {
+        let mut y = next(&x)?;
+        let length = norm2(&y);
+        if length.is_zero() {
+            return Err(EigenError::NoConvergence(0));
+        }
+        for value in y.iter_mut() {
+            *value = *value / length;
+        }
+        x = y;
+
+        let ax = product(rows, &x);
+        let value = dot(&x, &ax);
+        let residual: Vec<T> = ax.iter().zip(&x).map(|(&a, &x)| a - value * x).collect();
+        if norm2(&residual) <= tolerance * value.abs().max(T::one()) {
+            return Ok((value, x));
+        }
+    }
+    Err(EigenError::NoConvergence(0))
+}
+
+// Eigenvalue of largest magnitude and its unit eigenvector by power
+// iteration. Cheaper than `eigenvalues` when only the spectral radius is of
+// interest, but it converges slowly when the two largest magnitudes are
+// close and not at all when they differ in sign only or form a complex
+// pair, which is reported as `NoConvergence`.
+pub fn dominant_eigen<T: Real>(rows: &[Vec<T>], max_iterations: usize, tolerance: T) -> Result<(T, Vec<T>), EigenError> {
+    check_square(rows)?;
+    power_iteration(rows, max_iterations, tolerance, |x| Ok(product(rows, x)))
+}
+
+// Eigenvalue of smallest magnitude and its unit eigenvector by inverse
+// power iteration, factorizing A once and solving A y = x in each step. A
+// singular matrix, whose smallest eigenvalue is zero, fails with the
+// solver's error.
+pub fn smallest_eigen<T>(rows: &[Vec<T>], max_iterations: usize, tolerance: T) -> Result<(T, Vec<T>), EigenError>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    check_square(rows)?;
+    let system = rows.iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.push(T::zero());
+            row
+        })
+        .collect::<Vec<_>>();
+    let lu = CoefficientMatrix::try_from(system)?.validate()?.lu()?;
+    power_iteration(rows, max_iterations, tolerance, |x| lu.solve(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[Complex<f64>], expected: &[(f64, f64)]) {
+        assert_eq!(actual.len(), expected.len());
+        for (value, &(re, im)) in actual.iter().zip(expected) {
+            assert!((value.re - re).abs() < 1e-9 && (value.im - im).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn real_eigenvalues() {
+        assert_close(&eigenvalues(&[vec![2.0, 1.0], vec![1.0, 2.0]]).unwrap(), &[(3.0, 0.0), (1.0, 0.0)]);
+        assert_close(&eigenvalues(&[vec![5.0]]).unwrap(), &[(5.0, 0.0)]);
+
+        // Companion matrix of (x - 1)(x - 2)(x - 3)
+        let companion = [vec![6.0, -11.0, 6.0], vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]];
+        assert_close(&eigenvalues(&companion).unwrap(), &[(3.0, 0.0), (2.0, 0.0), (1.0, 0.0)]);
+
+        let rows = [
+            vec![4.0, -2.0, 1.0, 0.5],
+            vec![1.0, 3.0, 0.0, -1.0],
+            vec![0.5, 0.0, 2.0, 1.0],
+            vec![0.0, 1.0, -1.0, 1.0],
+        ];
+        let values = eigenvalues(&rows).unwrap();
+        // Trace and determinant are sums and products of the eigenvalues
+        let sum = values.iter().fold(Complex::new(0.0, 0.0), |sum, value| sum + *value);
+        assert!((sum.re - 10.0).abs() < 1e-9 && sum.im.abs() < 1e-9);
+        let product = values.iter().fold(Complex::new(1.0, 0.0), |product, value| product * *value);
+        let determinant = 193.0 / 4.0;
+        assert!((product.re - determinant).abs() < 1e-8 && product.im.abs() < 1e-8);
+    }
+
+    #[test]
+    fn complex_eigenvalues() {
+        assert_close(&eigenvalues(&[vec![0.0, -1.0], vec![1.0, 0.0]]).unwrap(), &[(0.0, 1.0), (0.0, -1.0)]);
+        assert_close(
+            &eigenvalues(&[vec![1.0, -2.0, 0.0], vec![2.0, 1.0, 0.0], vec![0.0, 0.0, 3.0]]).unwrap(),
+            &[(3.0, 0.0), (1.0, 2.0), (1.0, -2.0)],
+        );
+    }
+
+    #[test]
+    fn inverse_iteration() {
+        let rows = [vec![2.0, 1.0], vec![1.0, 2.0]];
+        let vector = eigenvector(&rows, 3.0).unwrap();
+        let expected = 0.5f64.sqrt();
+        assert!((vector[0] - expected).abs() < 1e-9 && (vector[1] - expected).abs() < 1e-9);
+        let vector = eigenvector(&rows, 1.0).unwrap();
+        assert!((vector[0] + vector[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn power_iterations() {
+        let rows = [vec![2.0, 1.0], vec![1.0, 2.0]];
+        let (value, vector) = dominant_eigen(&rows, 100, 1e-10).unwrap();
+        assert!((value - 3.0).abs() < 1e-9);
+        assert!((vector[0] - vector[1]).abs() < 1e-9);
+        let (value, vector) = smallest_eigen(&rows, 100, 1e-10).unwrap();
+        assert!((value - 1.0).abs() < 1e-9);
+        assert!((vector[0] + vector[1]).abs() < 1e-9);
+
+        let negative = [vec![-5.0, 0.0], vec![0.0, 1.0]];
+        assert!((dominant_eigen(&negative, 100, 1e-10).unwrap().0 + 5.0).abs() < 1e-9);
+
+        let rotation = [vec![0.0, -1.0], vec![1.0, 0.0]];
+        assert!(matches!(dominant_eigen(&rotation, 100, 1e-10), Err(EigenError::NoConvergence(0))));
+        assert!(matches!(
+            smallest_eigen(&[vec![1.0, 2.0], vec![2.0, 4.0]], 100, 1e-10),
+            Err(EigenError::SolveError(_))
+        ));
+    }
+
+    #[test]
+    fn reject_non_square() {
+        assert!(matches!(
+            eigenvalues(&[vec![1.0, 2.0]]),
+            Err(EigenError::SolveError(SolveError::UnfittingCoefficientAmount(2, 1)))
+        ));
+        assert!(matches!(eigenvalues::<f64>(&[]), Err(EigenError::SolveError(SolveError::TooSmall(0)))));
+    }
+}
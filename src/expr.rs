@@ -0,0 +1,433 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, string::ToString, vec, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+use num::traits::real::Real;
+
+use crate::function::{Error, Evaluate, Polynomial};
+
+mod diff;
+mod system;
+
+pub use system::{parse_system, ParseSystemError};
+
+// Arithmetic expressions such as "3x^2 - sin(2 y) / 4" parsed into a tree
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Func {
+    Sin,
+    Cos,
+    Exp,
+    Log,
+}
+
+impl Func {
+    fn name(&self) -> &'static str {
+        match self {
+            Func::Sin => "sin",
+            Func::Cos => "cos",
+            Func::Exp => "exp",
+            Func::Log => "log",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Func> {
+        match name {
+            "sin" => Some(Func::Sin),
+            "cos" => Some(Func::Cos),
+            "exp" => Some(Func::Exp),
+            "log" | "ln" => Some(Func::Log),
+            _ => None,
+        }
+    }
+
+    fn apply<T: Real>(&self, x: T) -> T {
+        match self {
+            Func::Sin => x.sin(),
+            Func::Cos => x.cos(),
+            Func::Exp => x.exp(),
+            Func::Log => x.ln(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<T> {
+    Number(T),
+    Variable(String),
+    Neg(Box<Expr<T>>),
+    Add(Box<Expr<T>>, Box<Expr<T>>),
+    Sub(Box<Expr<T>>, Box<Expr<T>>),
+    Mul(Box<Expr<T>>, Box<Expr<T>>),
+    Div(Box<Expr<T>>, Box<Expr<T>>),
+    Pow(Box<Expr<T>>, Box<Expr<T>>),
+    Call(Func, Box<Expr<T>>),
+}
+
+impl<T: Real> Expr<T> {
+    // Evaluate with a value for every variable in the expression
+    pub fn eval_with(&self, assignment: &BTreeMap<String, T>) -> Result<T, Error> {
+        Ok(match self {
+            Expr::Number(value) => *value,
+            Expr::Variable(name) => match assignment.get(name) {
+                Some(value) => *value,
+                None => return Err(Error::UnknownVariable(name.clone())),
+            },
+            Expr::Neg(a) => -a.eval_with(assignment)?,
+            Expr::Add(a, b) => a.eval_with(assignment)? + b.eval_with(assignment)?,
+            Expr::Sub(a, b) => a.eval_with(assignment)? - b.eval_with(assignment)?,
+            Expr::Mul(a, b) => a.eval_with(assignment)? * b.eval_with(assignment)?,
+            Expr::Div(a, b) => a.eval_with(assignment)? / b.eval_with(assignment)?,
+            Expr::Pow(a, b) => a.eval_with(assignment)?.powf(b.eval_with(assignment)?),
+            Expr::Call(func, a) => func.apply(a.eval_with(assignment)?),
+        })
+    }
+}
+
+impl<T> Expr<T> {
+    // Names of the variables, sorted and without duplicates
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_variables(&mut names);
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    fn collect_variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Expr::Number(_) => {},
+            Expr::Variable(name) => names.push(name),
+            Expr::Neg(a) | Expr::Call(_, a) => a.collect_variables(names),
+            Expr::Add(a, b) | Expr::Sub(a, b) | Expr::Mul(a, b) | Expr::Div(a, b) | Expr::Pow(a, b) => {
+                a.collect_variables(names);
+                b.collect_variables(names);
+            },
+        }
+    }
+
+    // Binding strength used to decide where parentheses are needed
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Add(..) | Expr::Sub(..) => 1,
+            Expr::Mul(..) | Expr::Div(..) => 2,
+            Expr::Neg(..) => 3,
+            Expr::Pow(..) => 4,
+            Expr::Number(..) | Expr::Variable(..) | Expr::Call(..) => 5,
+        }
+    }
+}
+
+// A single-variable expression is a function of `x`
+impl<T: Real + fmt::Display> Evaluate<T> for Expr<T> {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        let mut assignment = BTreeMap::new();
+        assignment.insert("x".to_string(), x);
+        self.eval_with(&assignment)
+    }
+
+    // Exact derivatives by symbolic differentiation
+    fn taylor(&self, at: T, degree: usize) -> Result<Polynomial<T>, Error>
+        where T: Real {
+        let mut derivative = self.clone();
+        let mut derivatives = vec![derivative.eval(at)?];
+        for _ in 0..degree {
+            derivative = derivative.differentiate("x");
+            derivatives.push(derivative.eval(at)?);
+        }
+        Ok(Polynomial::from_derivatives(&derivatives, at))
+    }
+}
+
+// Write `expr`, in parentheses if it binds weaker than `precedence`
+fn write_operand<T: fmt::Display>(f: &mut fmt::Formatter, expr: &Expr<T>, precedence: u8) -> fmt::Result {
+    if expr.precedence() < precedence {
+        write!(f, "({})", expr)
+    } else {
+        write!(f, "{}", expr)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Expr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, operator, b, left, right) = match self {
+            Expr::Number(value) => return write!(f, "{}", value),
+            Expr::Variable(name) => return write!(f, "{}", name),
+            Expr::Call(func, a) => return write!(f, "{}({})", func.name(), a),
+            Expr::Neg(a) => {
+                write!(f, "-")?;
+                return write_operand(f, a, 4);
+            },
+            Expr::Add(a, b) => (a, "+", b, 1, 1),
+            Expr::Sub(a, b) => (a, "-", b, 1, 2),
+            Expr::Mul(a, b) => (a, "*", b, 2, 3),
+            Expr::Div(a, b) => (a, "/", b, 2, 3),
+            Expr::Pow(a, b) => (a, "^", b, 5, 3),
+        };
+        write_operand(f, a, left)?;
+        write!(f, " {} ", operator)?;
+        write_operand(f, b, right)
+    }
+}
+
+// Byte offset in the input at which parsing failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseExprError(pub usize);
+
+impl fmt::Display for ParseExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid expression at position {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseExprError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    Number(&'a str),
+    Identifier(&'a str),
+    Operator(char),
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token<'_>)>, ParseExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if "+-*/^()".contains(c) {
+            chars.next();
+            tokens.push((start, Token::Operator(c)));
+            continue;
+        }
+        let number = c.is_ascii_digit() || c == '.';
+        if !number && !c.is_alphabetic() {
+            return Err(ParseExprError(start));
+        }
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            let continues = if number { c.is_ascii_digit() || c == '.' } else { c.is_alphanumeric() || c == '_' };
+            if !continues {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        // An exponent as in "1e-5", if the "e" is followed by digits. Else
+        // it is a name multiplied with the number, as in "2e".
+        if number {
+            let mut exponent = chars.clone();
+            if let Some((_, 'e')) | Some((_, 'E')) = exponent.next() {
+                if let Some(&(_, '+')) | Some(&(_, '-')) = exponent.peek() {
+                    exponent.next();
+                }
+                if exponent.peek().is_some_and(|&(_, c)| c.is_ascii_digit()) {
+                    while let Some(&(i, c)) = exponent.peek() {
+                        if !c.is_ascii_digit() {
+                            break;
+                        }
+                        end = i + 1;
+                        exponent.next();
+                    }
+                    chars = exponent;
+                }
+            }
+        }
+        let text = &s[start..end];
+        tokens.push((start, if number { Token::Number(text) } else { Token::Identifier(text) }));
+    }
+    Ok(tokens)
+}
+
+// Recursive descent over the grammar
+//
+//     sum     = product (("+" | "-") product)*
+//     product = unary (("*" | "/")? unary)*
+//     unary   = "-" unary | power
+//     power   = atom ("^" unary)?
+//     atom    = number | name | name "(" sum ")" | "(" sum ")"
+//
+// A missing operator between two factors, as in "3x" or "2 sin(x)", is a
+// multiplication.
+struct Parser<'a> {
+    tokens: Vec<(usize, Token<'a>)>,
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.position).map(|&(_, token)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens.get(self.position).map_or(self.end, |&(offset, _)| offset)
+    }
+
+    fn eat(&mut self, operator: char) -> bool {
+        if self.peek() == Some(Token::Operator(operator)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, operator: char) -> Result<(), ParseExprError> {
+        if self.eat(operator) { Ok(()) } else { Err(ParseExprError(self.offset())) }
+    }
+
+    fn sum<T: Real + FromStr>(&mut self) -> Result<Expr<T>, ParseExprError> {
+        let mut expr = self.product()?;
+        loop {
+            if self.eat('+') {
+                expr = Expr::Add(Box::new(expr), Box::new(self.product()?));
+            } else if self.eat('-') {
+                expr = Expr::Sub(Box::new(expr), Box::new(self.product()?));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn product<T: Real + FromStr>(&mut self) -> Result<Expr<T>, ParseExprError> {
+        let mut expr = self.unary()?;
+        loop {
+            if self.eat('*') {
+                expr = Expr::Mul(Box::new(expr), Box::new(self.unary()?));
+            } else if self.eat('/') {
+                expr = Expr::Div(Box::new(expr), Box::new(self.unary()?));
+            } else if let Some(Token::Number(_)) | Some(Token::Identifier(_)) | Some(Token::Operator('(')) = self.peek() {
+                expr = Expr::Mul(Box::new(expr), Box::new(self.unary()?));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn unary<T: Real + FromStr>(&mut self) -> Result<Expr<T>, ParseExprError> {
+        if self.eat('-') {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power<T: Real + FromStr>(&mut self) -> Result<Expr<T>, ParseExprError> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            Ok(Expr::Pow(Box::new(base), Box::new(self.unary()?)))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn atom<T: Real + FromStr>(&mut self) -> Result<Expr<T>, ParseExprError> {
+        let offset = self.offset();
+        let token = self.peek().ok_or(ParseExprError(offset))?;
+        self.position += 1;
+        match token {
+            Token::Number(text) => text.parse().map(Expr::Number).map_err(|_| ParseExprError(offset)),
+            Token::Identifier(name) => match Func::from_name(name) {
+                Some(func) => {
+                    self.expect('(')?;
+                    let argument = self.sum()?;
+                    self.expect(')')?;
+                    Ok(Expr::Call(func, Box::new(argument)))
+                },
+                None => Ok(Expr::Variable(name.to_string())),
+            },
+            Token::Operator('(') => {
+                let expr = self.sum()?;
+                self.expect(')')?;
+                Ok(expr)
+            },
+            Token::Operator(_) => Err(ParseExprError(offset)),
+        }
+    }
+}
+
+impl<T: Real + FromStr> FromStr for Expr<T> {
+    type Err = ParseExprError;
+
+    fn from_str(s: &str) -> Result<Expr<T>, ParseExprError> {
+        let mut parser = Parser { tokens: tokenize(s)?, position: 0, end: s.len() };
+        let expr = parser.sum()?;
+        if parser.position < parser.tokens.len() {
+            return Err(ParseExprError(parser.offset()));
+        }
+        Ok(expr)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn parse(s: &str) -> Expr<f64> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn evaluate_single_variable() {
+        assert_eq!(parse("3x^2 - 2x + 1").eval(2.0).unwrap(), 9.0);
+        assert_eq!(parse("-x^2").eval(3.0).unwrap(), -9.0);
+        assert_eq!(parse("2^3^2").eval(0.0).unwrap(), 512.0);
+        assert_eq!(parse("8 / 4 / 2").eval(0.0).unwrap(), 1.0);
+        assert_eq!(parse("(1 + x)(1 - x)").eval(3.0).unwrap(), -8.0);
+        assert_eq!(parse("1e-5 x + 2.5E3").eval(2.0).unwrap(), 2500.00002);
+        assert_eq!(parse("2e").variables(), vec!["e"]);
+        assert_eq!(parse("2e-x").variables(), vec!["e", "x"]);
+        assert!((parse("sin(x)^2 + cos(x)^2").eval(0.7).unwrap() - 1.0).abs() < 1e-12);
+        assert!((parse("log(exp(2 x))").eval(1.5).unwrap() - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn evaluate_with_assignment() {
+        let expr = parse("a * b - c");
+        assert_eq!(expr.variables(), vec!["a", "b", "c"]);
+        let mut assignment = BTreeMap::new();
+        assignment.insert("a".to_string(), 2.0);
+        assignment.insert("b".to_string(), 3.0);
+        assert!(matches!(expr.eval_with(&assignment), Err(Error::UnknownVariable(name)) if name == "c"));
+        assignment.insert("c".to_string(), 1.0);
+        assert_eq!(expr.eval_with(&assignment).unwrap(), 5.0);
+        assert!(expr.eval(1.0).is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for s in ["3 * x ^ 2 - 2 * x + 1", "a - b - (c - d)", "-(x + 1) ^ 2", "2 ^ 3 ^ 2", "(2 ^ 3) ^ 2", "sin(x / (1 + y))"] {
+            let expr = parse(s);
+            assert_eq!(expr.to_string(), s);
+            assert_eq!(parse(&expr.to_string()), expr);
+        }
+    }
+
+    #[test]
+    fn taylor_series() {
+        // x - x^3 / 6 + x^5 / 120
+        let series = parse("sin(x)").taylor(0.0, 5).unwrap();
+        let expected = [1.0 / 120.0, 0.0, -1.0 / 6.0, 0.0, 1.0, 0.0];
+        assert_eq!(series.coefficients().len(), expected.len());
+        for (a, b) in series.coefficients().iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-15);
+        }
+        assert!(matches!(parse("x + y").taylor(0.0, 1), Err(Error::UnknownVariable(_))));
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        assert_eq!("".parse::<Expr<f64>>(), Err(ParseExprError(0)));
+        assert_eq!("1 +".parse::<Expr<f64>>(), Err(ParseExprError(3)));
+        assert_eq!("(1 + 2".parse::<Expr<f64>>(), Err(ParseExprError(6)));
+        assert_eq!("sin x".parse::<Expr<f64>>(), Err(ParseExprError(4)));
+        assert_eq!("1 ) 2".parse::<Expr<f64>>(), Err(ParseExprError(2)));
+        assert_eq!("2 $ 3".parse::<Expr<f64>>(), Err(ParseExprError(2)));
+        assert_eq!("1..2".parse::<Expr<f64>>(), Err(ParseExprError(0)));
+    }
+}
@@ -0,0 +1,230 @@
+use alloc::boxed::Box;
+use num::traits::real::Real;
+
+use super::{Expr, Func};
+
+// Symbolic differentiation and the simplification needed to keep its
+// results readable
+
+impl<T: Real> Expr<T> {
+    // Derivative with respect to `variable`, simplified
+    pub fn differentiate(&self, variable: &str) -> Expr<T> {
+        self.derive(variable).simplify()
+    }
+
+    fn derive(&self, variable: &str) -> Expr<T> {
+        let d = |expr: &Expr<T>| expr.derive(variable);
+        match self {
+            Expr::Number(_) => Expr::Number(T::zero()),
+            Expr::Variable(name) if name == variable => Expr::Number(T::one()),
+            Expr::Variable(_) => Expr::Number(T::zero()),
+            Expr::Neg(a) => neg(d(a)),
+            Expr::Add(a, b) => add(d(a), d(b)),
+            Expr::Sub(a, b) => sub(d(a), d(b)),
+            Expr::Mul(a, b) => add(mul(d(a), clone(b)), mul(clone(a), d(b))),
+            Expr::Div(a, b) => div(
+                sub(mul(d(a), clone(b)), mul(clone(a), d(b))),
+                pow(clone(b), Expr::Number(T::one() + T::one())),
+            ),
+            // Power rule for constant exponents, otherwise
+            // (a^b)' = a^b (b' ln a + b a' / a)
+            Expr::Pow(a, b) if !b.contains(variable) => mul(
+                mul(clone(b), pow(clone(a), sub(clone(b), Expr::Number(T::one())))),
+                d(a),
+            ),
+            Expr::Pow(a, b) => mul(
+                clone(self),
+                add(
+                    mul(d(b), Expr::Call(Func::Log, a.clone())),
+                    div(mul(clone(b), d(a)), clone(a)),
+                ),
+            ),
+            Expr::Call(func, a) => {
+                let outer = match func {
+                    Func::Sin => Expr::Call(Func::Cos, a.clone()),
+                    Func::Cos => neg(Expr::Call(Func::Sin, a.clone())),
+                    Func::Exp => clone(self),
+                    Func::Log => div(Expr::Number(T::one()), clone(a)),
+                };
+                mul(outer, d(a))
+            },
+        }
+    }
+
+    fn contains(&self, variable: &str) -> bool {
+        self.variables().contains(&variable)
+    }
+
+    // Value of a constant, including negated ones
+    fn constant(&self) -> Option<T> {
+        match self {
+            Expr::Number(value) => Some(*value),
+            Expr::Neg(a) => a.constant().map(|value| -value),
+            _ => None,
+        }
+    }
+
+    // Fold constants and drop terms that don't contribute, such as `+ 0`,
+    // `* 1` or `^ 1`
+    pub fn simplify(self) -> Expr<T> {
+        match self {
+            Expr::Number(_) | Expr::Variable(_) => self,
+            Expr::Neg(a) => match a.simplify() {
+                Expr::Neg(inner) => *inner,
+                a => match a.constant() {
+                    Some(value) => number(-value),
+                    None => neg(a),
+                },
+            },
+            Expr::Add(a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                match (a.constant(), b.constant()) {
+                    (Some(x), Some(y)) => number(x + y),
+                    (Some(x), None) if x.is_zero() => b,
+                    (None, Some(y)) if y.is_zero() => a,
+                    _ => add(a, b),
+                }
+            },
+            Expr::Sub(a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                match (a.constant(), b.constant()) {
+                    (Some(x), Some(y)) => number(x - y),
+                    (Some(x), None) if x.is_zero() => neg(b).simplify(),
+                    (None, Some(y)) if y.is_zero() => a,
+                    _ if a == b => Expr::Number(T::zero()),
+                    _ => sub(a, b),
+                }
+            },
+            Expr::Mul(a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                match (a.constant(), b.constant()) {
+                    (Some(x), Some(y)) => number(x * y),
+                    (Some(x), _) | (_, Some(x)) if x.is_zero() => Expr::Number(T::zero()),
+                    (Some(x), None) if x == T::one() => b,
+                    (None, Some(y)) if y == T::one() => a,
+                    // Gather constant factors in front: c * (d * e) = (c d) * e
+                    (Some(x), None) => match b {
+                        Expr::Mul(c, e) => match c.constant() {
+                            Some(y) => mul(number(x * y), *e),
+                            None => mul(a, Expr::Mul(c, e)),
+                        },
+                        Expr::Neg(e) => mul(number(-x), *e).simplify(),
+                        b => mul(a, b),
+                    },
+                    (None, Some(_)) => mul(b, a).simplify(),
+                    _ => mul(a, b),
+                }
+            },
+            Expr::Div(a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                match (a.constant(), b.constant()) {
+                    (Some(x), Some(y)) if !y.is_zero() => number(x / y),
+                    (Some(x), None) if x.is_zero() => Expr::Number(T::zero()),
+                    (None, Some(y)) if y == T::one() => a,
+                    _ => div(a, b),
+                }
+            },
+            Expr::Pow(a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                match (a.constant(), b.constant()) {
+                    (Some(x), Some(y)) => number(x.powf(y)),
+                    (_, Some(y)) if y.is_zero() => Expr::Number(T::one()),
+                    (_, Some(y)) if y == T::one() => a,
+                    _ => pow(a, b),
+                }
+            },
+            Expr::Call(func, a) => {
+                let a = a.simplify();
+                match a.constant() {
+                    Some(value) => number(func.apply(value)),
+                    None => Expr::Call(func, Box::new(a)),
+                }
+            },
+        }
+    }
+}
+
+// Constants are kept non-negative so that they print the way they parse
+fn number<T: Real>(value: T) -> Expr<T> {
+    if value < T::zero() {
+        neg(Expr::Number(-value))
+    } else {
+        Expr::Number(value)
+    }
+}
+
+fn clone<T: Clone>(expr: &Expr<T>) -> Expr<T> {
+    expr.clone()
+}
+
+fn neg<T>(a: Expr<T>) -> Expr<T> {
+    Expr::Neg(Box::new(a))
+}
+
+fn add<T>(a: Expr<T>, b: Expr<T>) -> Expr<T> {
+    Expr::Add(Box::new(a), Box::new(b))
+}
+
+fn sub<T>(a: Expr<T>, b: Expr<T>) -> Expr<T> {
+    Expr::Sub(Box::new(a), Box::new(b))
+}
+
+fn mul<T>(a: Expr<T>, b: Expr<T>) -> Expr<T> {
+    Expr::Mul(Box::new(a), Box::new(b))
+}
+
+fn div<T>(a: Expr<T>, b: Expr<T>) -> Expr<T> {
+    Expr::Div(Box::new(a), Box::new(b))
+}
+
+fn pow<T>(a: Expr<T>, b: Expr<T>) -> Expr<T> {
+    Expr::Pow(Box::new(a), Box::new(b))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use crate::function::Evaluate;
+
+    fn derivative(s: &str) -> Expr<f64> {
+        s.parse::<Expr<f64>>().unwrap().differentiate("x")
+    }
+
+    #[test]
+    fn differentiate_polynomials() {
+        assert_eq!(derivative("3x^2 - 2x + 1").to_string(), "6 * x - 2");
+        assert_eq!(derivative("x^3").to_string(), "3 * x ^ 2");
+        assert_eq!(derivative("5").to_string(), "0");
+        assert_eq!(derivative("y x").to_string(), "y");
+        assert_eq!(derivative("-x").to_string(), "-1");
+    }
+
+    #[test]
+    fn differentiate_functions() {
+        assert_eq!(derivative("sin(x)").to_string(), "cos(x)");
+        assert_eq!(derivative("cos(2x)").to_string(), "-2 * sin(2 * x)");
+        assert_eq!(derivative("exp(x)").to_string(), "exp(x)");
+        assert_eq!(derivative("log(x)").to_string(), "1 / x");
+
+        // Compare the more involved rules with a central difference
+        for s in ["x^x", "sin(x) / (1 + x^2)", "exp(-x^2) * log(x)", "2^x"] {
+            let expr = s.parse::<Expr<f64>>().unwrap();
+            let d = expr.differentiate("x");
+            let (x, h) = (1.3, 1e-6);
+            let numeric = (expr.eval(x + h).unwrap() - expr.eval(x - h).unwrap()) / (2.0 * h);
+            assert!((d.eval(x).unwrap() - numeric).abs() < 1e-6, "{}: {}", s, d);
+        }
+    }
+
+    #[test]
+    fn simplify_constants() {
+        let simplified = |s: &str| s.parse::<Expr<f64>>().unwrap().simplify().to_string();
+        assert_eq!(simplified("2 * 3 + x * 1 - 0"), "6 + x");
+        assert_eq!(simplified("0 * sin(x) + x ^ 1"), "x");
+        assert_eq!(simplified("1 - 3"), "-2");
+        assert_eq!(simplified("x - x"), "0");
+        assert_eq!(simplified("--x"), "x");
+    }
+}
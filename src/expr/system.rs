@@ -0,0 +1,116 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+use num::traits::real::Real;
+
+use super::{Expr, ParseExprError};
+use crate::solver::{CoefficientMatrix, Equation, NamedSystem};
+
+// Linear systems written as equations, e.g. ["2x + 3y = 5", "x - y = 0"].
+// The unknowns are the variables in alphabetical order.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseSystemError {
+    // The equation at the index failed to parse
+    Expr(usize, ParseExprError),
+    // The equation at the index hasn't exactly one "="
+    Equals(usize),
+    // The equation at the index isn't linear in the variable
+    Nonlinear(usize, String),
+    // Fewer or more equations than unknowns
+    Unknowns(usize, usize),
+}
+
+impl fmt::Display for ParseSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSystemError::Expr(index, err) => write!(f, "Equation {}: {}", index + 1, err),
+            ParseSystemError::Equals(index) => write!(f, "Equation {} needs exactly one \"=\"", index + 1),
+            ParseSystemError::Nonlinear(index, name) => write!(f, "Equation {} isn't linear in {}", index + 1, name),
+            ParseSystemError::Unknowns(equations, unknowns) =>
+                write!(f, "{} equations don't determine {} unknowns", equations, unknowns),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseSystemError {}
+
+// Parse both sides of each equation and read off the coefficient of every
+// variable as the derivative of left minus right side, which must be
+// constant. The right-hand side is minus its value at zero.
+pub fn parse_system<T: Real + FromStr, S: AsRef<str>>(equations: &[S]) -> Result<NamedSystem<T>, ParseSystemError> {
+    let mut differences = Vec::with_capacity(equations.len());
+    for (index, equation) in equations.iter().enumerate() {
+        let text = equation.as_ref();
+        let mut sides = text.splitn(3, '=');
+        let (left, right) = match (sides.next(), sides.next(), sides.next()) {
+            (Some(left), Some(right), None) => (left, right),
+            _ => return Err(ParseSystemError::Equals(index)),
+        };
+        let offset = left.len() + 1;
+        let left: Expr<T> = left.parse().map_err(|err| ParseSystemError::Expr(index, err))?;
+        let right: Expr<T> = right.parse()
+            .map_err(|ParseExprError(position)| ParseSystemError::Expr(index, ParseExprError(offset + position)))?;
+        differences.push(Expr::Sub(left.into(), right.into()));
+    }
+
+    let mut names: Vec<String> = differences.iter()
+        .flat_map(|difference| difference.variables())
+        .map(String::from)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    if names.len() != equations.len() {
+        return Err(ParseSystemError::Unknowns(equations.len(), names.len()));
+    }
+
+    let zero: BTreeMap<String, T> = names.iter().map(|name| (name.clone(), T::zero())).collect();
+    let mut matrix = CoefficientMatrix::new(names.len());
+    for (index, difference) in differences.iter().enumerate() {
+        let mut coefficients = Vec::with_capacity(names.len());
+        for name in &names {
+            let derivative = difference.differentiate(name);
+            let value = if derivative.variables().is_empty() {
+                derivative.eval_with(&zero).ok()
+            } else {
+                None
+            };
+            coefficients.push(value.ok_or_else(|| ParseSystemError::Nonlinear(index, name.clone()))?);
+        }
+        let constant = difference.eval_with(&zero)
+            .map_err(|_| ParseSystemError::Nonlinear(index, String::new()))?;
+        matrix.push_equation(Equation::new(coefficients, -constant));
+    }
+    Ok(matrix.with_vars(&names).expect("names are distinct and one per unknown"))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_equations() {
+        let system = parse_system::<f64, _>(&["2x + 3y = 5", "x - y = 2 - 2"]).unwrap();
+        assert_eq!(system.names(), &["x", "y"]);
+        let solution = system.solve().unwrap();
+        assert!((solution["x"] - 1.0).abs() < 1e-12);
+        assert!((solution["y"] - 1.0).abs() < 1e-12);
+
+        // Terms on both sides and named unknowns
+        let system = parse_system::<f64, _>(&["U = R1 I + 2", "I / 2 = 1", "R1 = 3"]);
+        assert!(matches!(system, Err(ParseSystemError::Nonlinear(0, ref name)) if name == "I"));
+        let solution = parse_system::<f64, _>(&["U = 3 I + 2", "I / 2 = 1"]).unwrap().solve().unwrap();
+        assert_eq!(solution["U"], 8.0);
+    }
+
+    #[test]
+    fn reject_malformed_equations() {
+        assert_eq!(parse_system::<f64, _>(&["x + 1"]).unwrap_err(), ParseSystemError::Equals(0));
+        assert_eq!(parse_system::<f64, _>(&["x = 1 = 1"]).unwrap_err(), ParseSystemError::Equals(0));
+        assert_eq!(parse_system::<f64, _>(&["x = 1", "x = )"]).unwrap_err(), ParseSystemError::Expr(1, ParseExprError(4)));
+        assert_eq!(parse_system::<f64, _>(&["x + y = 1"]).unwrap_err(), ParseSystemError::Unknowns(1, 2));
+        assert_eq!(parse_system::<f64, _>(&["x^2 = 1"]).unwrap_err(), ParseSystemError::Nonlinear(0, "x".into()));
+    }
+}
@@ -1,10 +1,49 @@
-use std::str;
-use std::fmt;
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::convert::TryFrom;
+use core::str;
+use core::fmt;
+use core::ops::{Add, Mul, Sub, SubAssign};
 use num::Num;
+use num::traits::real::Real;
 
+use crate::solver::{CoefficientMatrix, SolveError};
+
+mod basis;
+mod chebyshev;
+mod closure;
+mod extrema;
+mod families;
+mod fourier;
+mod linear;
+mod roots;
+mod spline;
+
+pub use basis::LinearModel;
+pub use chebyshev::Chebyshev;
+pub use closure::FnFunction;
+pub use extrema::{CriticalKind, CriticalPoint};
+pub use fourier::Fourier;
+pub use linear::LinearForm;
+pub use roots::RootFinder;
+pub use spline::{Boundary, Spline};
+
+#[derive(Debug)]
 pub enum Error {
     EvaluationError,
     BuildError,
+    DivisionByZero,
+    SolveError(SolveError),
+    InvalidSamples,
+    UnknownVariable(String),
+    InvalidInterval,
+    NoSignChange,
+    NoConvergence(usize),
+}
+
+impl From<SolveError> for Error {
+    fn from(err: SolveError) -> Self {
+        Error::SolveError(err)
+    }
 }
 
 impl fmt::Display for Error {
@@ -12,13 +51,20 @@ impl fmt::Display for Error {
         match self {
             Error::EvaluationError => write!(f, "Failed to evalue function"),
             Error::BuildError => write!(f, "Invalid input coefficient"),
+            Error::DivisionByZero => write!(f, "Division by the zero polynomial"),
+            Error::SolveError(err) => write!(f, "{}", err),
+            Error::InvalidSamples => write!(f, "Samples need at least two points with strictly increasing x values"),
+            Error::UnknownVariable(name) => write!(f, "Unknown variable '{}'", name),
+            Error::InvalidInterval => write!(f, "Interval needs a start below its end"),
+            Error::NoSignChange => write!(f, "Function doesn't change its sign over the interval"),
+            Error::NoConvergence(iterations) => write!(f, "No root found after {} iterations", iterations),
         }
     }
 }
 
 pub trait Function<T>: fmt::Display 
     where T: Num + Copy {
-    fn coefficients<'a>(&'a self) -> &'a Vec<T>;
+    fn coefficients(&self) -> &Vec<T>;
     fn eval(&self, x: T) -> Result<T, Error> {
         let mut coefficients = self.coefficients().iter();
         let mut sum = match coefficients.next() {
@@ -34,7 +80,145 @@ pub trait Function<T>: fmt::Display
     }
 }
 
+// Anything that can be evaluated at a point, such as polynomials, parsed
+// expressions, splines or closures, with the numerical methods that only
+// need its values
+pub trait Evaluate<T>: fmt::Display 
+    where T: Num + Copy {
+    fn eval(&self, x: T) -> Result<T, Error>;
+
+    // Evaluate at `samples` evenly spaced points from `start` to `end`,
+    // both included, e.g. for plotting
+    fn eval_range(&self, start: T, end: T, samples: usize) -> Result<Vec<T>, Error> {
+        match samples {
+            0 => return Ok(Vec::new()),
+            1 => return Ok(vec![self.eval(start)?]),
+            _ => {}
+        }
+        let step = (end - start) / power(samples - 1);
+        // Count the index up alongside rather than converting it with
+        // `power` each time, which takes as many additions as the index
+        let mut index = T::zero();
+        let mut values = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            values.push(self.eval(start + step * index)?);
+            index = index + T::one();
+        }
+        Ok(values)
+    }
+
+    // Integral from `a` to `b` by the composite trapezoid rule
+    fn trapezoid(&self, a: T, b: T, subdivisions: usize) -> Result<T, Error> {
+        let n = subdivisions.max(1);
+        let h = (b - a) / power(n);
+        let mut sum = (self.eval(a)? + self.eval(b)?) / power(2);
+        for i in 1..n {
+            sum = sum + self.eval(a + h * power(i))?;
+        }
+        Ok(sum * h)
+    }
+
+    // Integral from `a` to `b` by the composite Simpson rule, rounding the
+    // number of subdivisions up to an even one
+    fn simpson(&self, a: T, b: T, subdivisions: usize) -> Result<T, Error> {
+        let n = subdivisions.max(2).next_multiple_of(2);
+        let h = (b - a) / power(n);
+        let mut sum = self.eval(a)? + self.eval(b)?;
+        for i in 1..n {
+            let weight: T = power(if i % 2 == 1 { 4 } else { 2 });
+            sum = sum + weight * self.eval(a + h * power(i))?;
+        }
+        Ok(sum * h / power(3))
+    }
+
+    // Adaptive Simpson integration, subdividing until the estimated error of
+    // every piece is within its share of `tolerance`. Returns the integral
+    // together with the estimated absolute error.
+    fn integrate_adaptive(&self, a: T, b: T, tolerance: T) -> Result<(T, T), Error>
+        where T: Real {
+        let (fa, fm, fb) = (self.eval(a)?, self.eval((a + b) / power(2))?, self.eval(b)?);
+        let whole = (b - a) / power(6) * (fa + power::<T>(4) * fm + fb);
+        adaptive_simpson(self, (a, fa), (b, fb), fm, whole, tolerance, MAX_DEPTH)
+    }
+
+    // Integral from `a` to `b` to about single precision
+    fn integrate(&self, a: T, b: T) -> Result<T, Error>
+        where T: Real {
+        self.integrate_adaptive(a, b, T::epsilon().sqrt())
+            .map(|(integral, _)| integral)
+    }
+
+    // Taylor polynomial of the given degree around `at`, with derivatives
+    // from central differences. Every order loses digits, so beyond degree
+    // four or so only `Polynomial` and `Expr`, which differentiate exactly,
+    // give useful results.
+    fn taylor(&self, at: T, degree: usize) -> Result<Polynomial<T>, Error>
+        where T: Real {
+        let mut derivatives = vec![self.eval(at)?];
+        for order in 1..=degree {
+            // Step balancing the truncation error against rounding, which
+            // grows with the order of the difference
+            let h = T::epsilon().powf(T::one() / power(order + 2)) * at.abs().max(T::one());
+            let (mut sum, mut binomial) = (T::zero(), T::one());
+            for j in 0..=order {
+                let offset = (power::<T>(order) / power(2) - power(j)) * h;
+                let term = binomial * self.eval(at + offset)?;
+                sum = if j % 2 == 0 { sum + term } else { sum - term };
+                binomial = binomial * power(order - j) / power(j + 1);
+            }
+            derivatives.push(sum / h.powi(order as i32));
+        }
+        Ok(Polynomial::from_derivatives(&derivatives, at))
+    }
+
+    // Root between `a` and `b` by Brent's method, which needs the function
+    // to change its sign over the interval
+    fn root(&self, a: T, b: T) -> Result<T, Error>
+        where T: Real {
+        RootFinder::new().brent(self, a, b)
+    }
+}
+
+// Depth at which adaptive integration stops subdividing
+const MAX_DEPTH: usize = 40;
 
+// Refine the Simpson estimate `whole` of the interval between `a` and `b`,
+// `fm` being the value at its midpoint
+fn adaptive_simpson<T, F>(f: &F, (a, fa): (T, T), (b, fb): (T, T), fm: T, whole: T, tolerance: T, depth: usize)
+    -> Result<(T, T), Error>
+    where T: Real, F: Evaluate<T> + ?Sized {
+    let m = (a + b) / power(2);
+    let (lm, rm) = ((a + m) / power(2), (m + b) / power(2));
+    let (flm, frm) = (f.eval(lm)?, f.eval(rm)?);
+    let four: T = power(4);
+    let left = (m - a) / power(6) * (fa + four * flm + fm);
+    let right = (b - m) / power(6) * (fm + four * frm + fb);
+    // Richardson extrapolation: the error of the refined estimate is about
+    // a fifteenth of the difference between both estimates
+    let difference = left + right - whole;
+    let error = difference.abs() / power(15);
+    if depth == 0 || error <= tolerance {
+        return Ok((left + right + difference / power(15), error));
+    }
+    let half = tolerance / power(2);
+    let (left, left_error) = adaptive_simpson(f, (a, fa), (m, fm), flm, left, half, depth - 1)?;
+    let (right, right_error) = adaptive_simpson(f, (m, fm), (b, fb), frm, right, half, depth - 1)?;
+    Ok((left + right, left_error + right_error))
+}
+
+
+// Order in which coefficients are given. A polynomial always stores its
+// coefficients in descending order, highest power first, which is what
+// `new`, `build` and `polynomial!` expect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    // Constant term first: [1, 2, 3] is 1 + 2x + 3x^2
+    Ascending,
+    // Highest power first: [1, 2, 3] is x^2 + 2x + 3
+    Descending,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Polynomial<T> {
     coefficients: Vec<T>,
 }
@@ -62,30 +246,773 @@ impl<T: Num + str::FromStr> Polynomial<T> {
     }
 }
 
+// Highest power accepted when parsing, since the coefficients of all powers
+// below it are allocated
+const MAX_PARSED_DEGREE: usize = 1 << 16;
+
+// Parse polynomials written in the usual notation such as "3x^2 - 2x + 1".
+// Coefficients of 1 may be omitted, missing powers are zero, terms may come
+// in any order and repeated powers are summed up.
+impl<T: Num + Copy + str::FromStr> str::FromStr for Polynomial<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Polynomial<T>, Error> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if s.is_empty() {
+            return Err(Error::BuildError);
+        }
+
+        // Split into terms at each sign that isn't part of a power
+        let mut terms = Vec::new();
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            if (c == '+' || c == '-') && i > 0 && !s[..i].ends_with('^') {
+                terms.push(&s[start..i]);
+                start = i;
+            }
+        }
+        terms.push(&s[start..]);
+
+        let mut powers = Vec::new();
+        for term in terms {
+            let (negative, body) = match term.as_bytes()[0] {
+                b'-' => (true, &term[1..]),
+                b'+' => (false, &term[1..]),
+                _ => (false, term),
+            };
+            let (coefficient, power) = match body.find('x') {
+                Some(index) => {
+                    let coefficient = body[..index].trim_end_matches('*');
+                    let coefficient = if coefficient.is_empty() {
+                        T::one()
+                    } else {
+                        coefficient.parse::<T>().map_err(|_| Error::BuildError)?
+                    };
+                    let power = match &body[index + 1..] {
+                        "" => 1,
+                        rest => match rest.strip_prefix('^') {
+                            Some(power) => power.parse::<usize>().ok()
+                                .filter(|&power| power <= MAX_PARSED_DEGREE)
+                                .ok_or(Error::BuildError)?,
+                            None => return Err(Error::BuildError),
+                        },
+                    };
+                    (coefficient, power)
+                },
+                None => (body.parse::<T>().map_err(|_| Error::BuildError)?, 0),
+            };
+            let coefficient = if negative { T::zero() - coefficient } else { coefficient };
+            powers.push((power, coefficient));
+        }
+
+        let degree = powers.iter().map(|(power, _)| *power).max().unwrap_or(0);
+        let mut coefficients = vec![T::zero(); degree + 1];
+        for (power, coefficient) in powers {
+            coefficients[degree - power] = coefficients[degree - power] + coefficient;
+        }
+        Ok(Polynomial { coefficients }.trim())
+    }
+}
+
+// Convert a small power into `T` using only the `Num` operations
+fn power<T: Num + Copy>(n: usize) -> T {
+    (0..n).fold(T::zero(), |acc, _| acc + T::one())
+}
+
+impl<T: Num + Copy> Polynomial<T> {
+    // Coefficients are stored from the highest power down, so the
+    // coefficient at `i` belongs to x^(degree - i)
+    pub fn derivative(&self) -> Polynomial<T> {
+        let degree = self.coefficients.len().saturating_sub(1);
+        if degree == 0 {
+            return Polynomial { coefficients: vec![T::zero()] };
+        }
+        let coefficients = self.coefficients[..degree].iter()
+            .enumerate()
+            .map(|(i, coefficient)| *coefficient * power(degree - i))
+            .collect();
+        Polynomial { coefficients }
+    }
+}
+
+impl<T: Num> Polynomial<T> {
+    // Drop leading zero coefficients, keeping a single zero for the zero
+    // polynomial
+    fn trim(mut self) -> Polynomial<T> {
+        let leading = self.coefficients.iter().take_while(|c| c.is_zero()).count();
+        self.coefficients.drain(..leading);
+        if self.coefficients.is_empty() {
+            self.coefficients.push(T::zero());
+        }
+        self
+    }
+
+    // Combine the coefficients of two polynomials power by power, aligning
+    // them at the constant term
+    fn zip_with(self, other: Polynomial<T>, f: impl Fn(T, T) -> T) -> Polynomial<T> {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let pad = |coefficients: Vec<T>| {
+            let mut padded: Vec<T> = (coefficients.len()..len).map(|_| T::zero()).collect();
+            padded.extend(coefficients);
+            padded
+        };
+        let coefficients = pad(self.coefficients).into_iter()
+            .zip(pad(other.coefficients))
+            .map(|(a, b)| f(a, b))
+            .collect();
+        Polynomial { coefficients }.trim()
+    }
+}
+
+// Division needs exact arithmetic or a tolerance to tell a vanishing
+// remainder from rounding noise. Integer coefficients have neither, since
+// their division truncates, so it is limited to real numbers.
+impl<T: Real> Polynomial<T> {
+    fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| c.is_zero())
+    }
+
+    // Long division clearing remainder coefficients within `relative` times
+    // the magnitude of the largest term involved
+    fn divide(&self, other: &Polynomial<T>, relative: T) -> Result<(Polynomial<T>, Polynomial<T>), Error> {
+        let divisor = other.clone().trim();
+        if divisor.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        let mut remainder = self.clone().trim().coefficients;
+        if remainder.len() < divisor.coefficients.len() {
+            return Ok((Polynomial { coefficients: vec![T::zero()] }, Polynomial { coefficients: remainder }));
+        }
+
+        let mut scale = remainder.iter().fold(T::zero(), |scale, c| scale.max(c.abs()));
+        let steps = remainder.len() - divisor.coefficients.len() + 1;
+        let mut quotient = Vec::with_capacity(steps);
+        for i in 0..steps {
+            let factor = remainder[i] / divisor.coefficients[0];
+            for (j, coefficient) in divisor.coefficients.iter().enumerate() {
+                scale = scale.max((factor * *coefficient).abs());
+                remainder[i + j] = remainder[i + j] - factor * *coefficient;
+            }
+            quotient.push(factor);
+        }
+        remainder.drain(..steps);
+        for coefficient in remainder.iter_mut() {
+            if coefficient.abs() <= relative * scale {
+                *coefficient = T::zero();
+            }
+        }
+
+        Ok((Polynomial { coefficients: quotient }.trim(), Polynomial { coefficients: remainder }.trim()))
+    }
+
+    // Polynomial long division, returning the quotient and the remainder.
+    // Remainder coefficients that are only rounding errors become zero.
+    pub fn div_rem(&self, other: &Polynomial<T>) -> Result<(Polynomial<T>, Polynomial<T>), Error> {
+        let length = T::from(self.coefficients.len()).unwrap_or_else(T::one);
+        self.divide(other, T::epsilon() * length)
+    }
+
+    // Monic greatest common divisor by the Euclidean algorithm. The gcd of
+    // two zero polynomials is zero. Roots only agree up to rounding, so a
+    // remainder counts as zero within the square root of the precision.
+    pub fn gcd(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        let relative = T::epsilon().sqrt();
+        let mut a = self.clone().trim();
+        let mut b = other.clone().trim();
+        // Division only fails once `b` is zero
+        while let Ok((_, remainder)) = a.divide(&b, relative) {
+            a = b;
+            b = remainder;
+        }
+        if a.is_zero() {
+            return a;
+        }
+        let leading = a.coefficients[0];
+        let coefficients = a.coefficients.into_iter()
+            .map(|coefficient| coefficient / leading)
+            .collect();
+        Polynomial { coefficients }
+    }
+}
+
+impl<T> Polynomial<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // The polynomial of lowest degree passing through all points, found by
+    // solving the Vandermonde system for its coefficients. Points sharing an
+    // x value make the system dependent.
+    pub fn interpolate(points: &[(T, T)]) -> Result<Polynomial<T>, Error> {
+        let rows = points.iter()
+            .map(|&(x, y)| {
+                let mut row = vec![T::one(); points.len() + 1];
+                row[points.len()] = y;
+                for i in (0..points.len() - 1).rev() {
+                    row[i] = row[i + 1] * x;
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+        let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+        Ok(Polynomial { coefficients: solved.solution().collect() }.trim())
+    }
+
+    // Least-squares fit of a polynomial of the given degree by solving the
+    // normal equations. Fewer than `degree + 1` distinct x values leave the
+    // system dependent.
+    pub fn fit(points: &[(T, T)], degree: usize) -> Result<Fit<T>, Error> {
+        // Sums of x^p and y * x^p over all points
+        let mut x_sums = vec![T::zero(); 2 * degree + 1];
+        let mut xy_sums = vec![T::zero(); degree + 1];
+        for &(x, y) in points {
+            let mut x_power = T::one();
+            for p in 0..=2 * degree {
+                x_sums[p] = x_sums[p] + x_power;
+                if p <= degree {
+                    xy_sums[p] = xy_sums[p] + y * x_power;
+                }
+                x_power = x_power * x;
+            }
+        }
+
+        // Row j of the normal equations belongs to x^(degree - j)
+        let rows = (0..=degree)
+            .map(|j| {
+                let mut row: Vec<T> = (0..=degree).map(|k| x_sums[2 * degree - j - k]).collect();
+                row.push(xy_sums[degree - j]);
+                row
+            })
+            .collect::<Vec<_>>();
+        let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+        let polynomial = Polynomial { coefficients: solved.solution().collect() };
+
+        let residuals = points.iter()
+            .map(|&(x, y)| Ok(y - polynomial.eval(x)?))
+            .collect::<Result<Vec<T>, Error>>()?;
+        let sum_of_squares = residuals.iter().fold(T::zero(), |sum, r| sum + *r * *r);
+        let mean = points.iter().fold(T::zero(), |sum, &(_, y)| sum + y) / power(points.len());
+        let total = points.iter().fold(T::zero(), |sum, &(_, y)| sum + (y - mean) * (y - mean));
+        // Constant data is explained perfectly by any fit reproducing it
+        let r_squared = if total.is_zero() { T::one() } else { T::one() - sum_of_squares / total };
+
+        Ok(Fit { polynomial, residuals, sum_of_squares, r_squared })
+    }
+}
+
+// Result of a least-squares fit together with how well it matches the data
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fit<T> {
+    polynomial: Polynomial<T>,
+    residuals: Vec<T>,
+    sum_of_squares: T,
+    r_squared: T,
+}
+
+impl<T> Fit<T> {
+    pub fn polynomial(&self) -> &Polynomial<T> {
+        &self.polynomial
+    }
+
+    // Difference between the observed and the fitted value for every point
+    pub fn residuals(&self) -> &[T] {
+        &self.residuals
+    }
+
+    pub fn sum_of_squares(&self) -> &T {
+        &self.sum_of_squares
+    }
+
+    // Coefficient of determination, 1 for a perfect fit
+    pub fn r_squared(&self) -> &T {
+        &self.r_squared
+    }
+}
+
+impl<T: Num> Add for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Polynomial<T>) -> Polynomial<T> {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<T: Num> Sub for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: Polynomial<T>) -> Polynomial<T> {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<T: Num + Copy> Mul for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: Polynomial<T>) -> Polynomial<T> {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Polynomial { coefficients: vec![T::zero()] };
+        }
+        let mut coefficients = vec![T::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j] + *a * *b;
+            }
+        }
+        Polynomial { coefficients }.trim()
+    }
+}
+
+impl<T: Num + Copy> Polynomial<T> {
+    // The composition p(q(x)), expanded by Horner's scheme over polynomials
+    pub fn compose(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        let mut coefficients = self.coefficients.iter();
+        let mut result = match coefficients.next() {
+            Some(coefficient) => Polynomial { coefficients: vec![*coefficient] },
+            None => return Polynomial { coefficients: vec![T::zero()] },
+        };
+        for coefficient in coefficients {
+            result = result * other.clone() + Polynomial { coefficients: vec![*coefficient] };
+        }
+        result.trim()
+    }
+
+    // Monic polynomial (x - r1)(x - r2)...(x - rn) with the given roots,
+    // repeated ones for multiple roots, multiplied out one factor at a time
+    pub fn from_roots(roots: &[T]) -> Polynomial<T> {
+        let mut coefficients = vec![T::one()];
+        for &root in roots {
+            // Multiply by x, then subtract root times the previous polynomial
+            coefficients.push(T::zero());
+            for i in (1..coefficients.len()).rev() {
+                coefficients[i] = coefficients[i] - root * coefficients[i - 1];
+            }
+        }
+        Polynomial { coefficients }
+    }
+
+    // p(x + offset)
+    pub fn shift(&self, offset: T) -> Polynomial<T> {
+        self.compose(&Polynomial { coefficients: vec![T::one(), offset] })
+    }
+
+    // p(factor * x)
+    pub fn scale(&self, factor: T) -> Polynomial<T> {
+        let mut multiplier = T::one();
+        let mut coefficients = self.coefficients.clone();
+        for coefficient in coefficients.iter_mut().rev() {
+            *coefficient = *coefficient * multiplier;
+            multiplier = multiplier * factor;
+        }
+        Polynomial { coefficients }.trim()
+    }
+}
+
+impl<T: Real> Polynomial<T> {
+    // Taylor polynomial around `at` from the value and derivatives there,
+    // the sum of f^(k)(at) / k! (x - at)^k
+    pub fn from_derivatives(derivatives: &[T], at: T) -> Polynomial<T> {
+        let mut factorial = T::one();
+        let coefficients = derivatives.iter()
+            .enumerate()
+            .map(|(k, &derivative)| {
+                factorial = factorial * power(k.max(1));
+                derivative / factorial
+            })
+            .collect();
+        Polynomial::with_order(coefficients, Order::Ascending).shift(-at)
+    }
+
+    // Integrate term by term, `constant` being the new lowest coefficient.
+    // Integer coefficients would be truncated by the division.
+    pub fn antiderivative(&self, constant: T) -> Polynomial<T> {
+        let degree = self.coefficients.len();
+        let mut coefficients: Vec<T> = self.coefficients.iter()
+            .enumerate()
+            .map(|(i, coefficient)| *coefficient / power(degree - i))
+            .collect();
+        coefficients.push(constant);
+        Polynomial { coefficients }
+    }
+}
+
+// Scale every coefficient by a constant factor
+impl<T: Num + Copy> Mul<T> for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, factor: T) -> Polynomial<T> {
+        let coefficients = self.coefficients.into_iter()
+            .map(|coefficient| coefficient * factor)
+            .collect();
+        Polynomial { coefficients }.trim()
+    }
+}
+
+impl<T> Polynomial<T> {
+    // Coefficients in descending order
+    pub fn coefficients(&self) -> &Vec<T> {
+        &self.coefficients
+    }
+
+    // Takes the coefficients in the given order
+    pub fn with_order(mut coefficients: Vec<T>, order: Order) -> Polynomial<T> {
+        if order == Order::Ascending {
+            coefficients.reverse();
+        }
+        Polynomial { coefficients }
+    }
+
+    // Coefficients in the given order
+    pub fn coefficients_in(&self, order: Order) -> Vec<T> where T: Clone {
+        let mut coefficients = self.coefficients.clone();
+        if order == Order::Ascending {
+            coefficients.reverse();
+        }
+        coefficients
+    }
+}
+
+impl<T> Polynomial<T>
+    where T: Num + fmt::Display + fmt::Debug + core::marker::Copy {
+    // Horner's scheme over the coefficients, highest power first. Inherent,
+    // so that calls don't have to pick between `Function` and `Evaluate`.
+    pub fn eval(&self, x: T) -> Result<T, Error> {
+        Function::eval(self, x)
+    }
+}
+
 impl<T> Function<T> for Polynomial<T>
-    where T: Num + fmt::Display + fmt::Debug + std::marker::Copy {
-    fn coefficients<'a>(&'a self) -> &'a Vec<T> {
+    where T: Num + fmt::Display + fmt::Debug + core::marker::Copy {
+    fn coefficients(&self) -> &Vec<T> {
         &self.coefficients
     }
 }
 
+impl<T> Evaluate<T> for Polynomial<T>
+    where T: Num + fmt::Display + fmt::Debug + core::marker::Copy {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        Function::eval(self, x)
+    }
+
+    // Exact integral through the antiderivative
+    fn integrate(&self, a: T, b: T) -> Result<T, Error>
+        where T: Real {
+        let antiderivative = self.antiderivative(T::zero());
+        Ok(antiderivative.eval(b)? - antiderivative.eval(a)?)
+    }
+
+    // Exact: the terms of p(x + at) up to the degree, shifted back
+    fn taylor(&self, at: T, degree: usize) -> Result<Polynomial<T>, Error>
+        where T: Real {
+        let mut terms = self.shift(at).coefficients_in(Order::Ascending);
+        terms.truncate(degree + 1);
+        Ok(Polynomial::with_order(terms, Order::Ascending).shift(-at))
+    }
+}
+
+// Digits of a power as superscripts
+fn superscript(power: usize) -> String {
+    power.to_string().chars()
+        .map(|digit| match digit {
+            '0' => '⁰', '1' => '¹', '2' => '²', '3' => '³', '4' => '⁴',
+            '5' => '⁵', '6' => '⁶', '7' => '⁷', '8' => '⁸', _ => '⁹',
+        })
+        .collect()
+}
+
+// Algebraic notation such as "3x^2 - 2x + 1", which `from_str` reads back.
+// Zero terms are left out and coefficients of ±1 written as a bare x. The
+// alternate form `{:#}` uses superscripts and a proper minus sign,
+// "3x² − 2x + 1", and a precision applies to every coefficient.
 impl<T> fmt::Display for Polynomial<T>
-    where T: Num + fmt::Display + fmt::Debug {
+    where T: Num + Copy + fmt::Display {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.coefficients)
+        let (precision, unicode) = (f.precision(), f.alternate());
+        let text = |value: T| match precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => format!("{}", value),
+        };
+        let minus = if unicode { "−" } else { "-" };
+        let degree = self.coefficients.len().saturating_sub(1);
+        let mut first = true;
+        for (i, &coefficient) in self.coefficients.iter().enumerate() {
+            if coefficient.is_zero() {
+                continue;
+            }
+            let written = text(coefficient);
+            let (negative, magnitude) = match written.strip_prefix('-') {
+                Some(magnitude) => (true, magnitude),
+                None => (false, written.as_str()),
+            };
+            match (first, negative) {
+                (true, true) => write!(f, "{}", minus)?,
+                (true, false) => {},
+                (false, true) => write!(f, " {} ", minus)?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            let power = degree - i;
+            let unit = coefficient == T::one() || T::zero() - coefficient == T::one();
+            if !unit || power == 0 {
+                write!(f, "{}", magnitude)?;
+            }
+            match power {
+                0 => {},
+                1 => write!(f, "x")?,
+                _ if unicode => write!(f, "x{}", superscript(power))?,
+                _ => write!(f, "x^{}", power)?,
+            }
+        }
+        if first {
+            write!(f, "{}", text(T::zero()))?;
+        }
+        Ok(())
     }
 }
 
-// Macro to neatly instanciate a new polynomial
+// Macro to neatly instanciate a new polynomial, highest power first
 #[macro_export]
 macro_rules! polynomial {
     ($($x:expr),+) => {
-        {
-            let mut coefficients = Vec::new();
-            $(
-                coefficients.push($x);
-            )*
-            Polynomial::new(coefficients)
-        }
+        $crate::function::Polynomial::new($crate::__vec![$($x),+])
     };
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivative_and_antiderivative() {
+        let p = polynomial!(3.0, 2.0, 1.0);
+        assert_eq!(p.derivative(), polynomial!(6.0, 2.0));
+        assert_eq!(p.derivative().antiderivative(1.0), p);
+        assert_eq!(polynomial!(5.0).derivative(), polynomial!(0.0));
+    }
+
+    #[test]
+    fn add_and_subtract_differing_degrees() {
+        assert_eq!(polynomial!(1, 2, 3) + polynomial!(4, 5), polynomial!(1, 6, 8));
+        assert_eq!(polynomial!(4, 5) - polynomial!(1, 2, 3), polynomial!(-1, 2, 2));
+        assert_eq!(polynomial!(1, 2) - polynomial!(1, 2), polynomial!(0));
+        assert_eq!(polynomial!(1, 2, 3) - polynomial!(1, 0, 0), polynomial!(2, 3));
+    }
+
+    #[test]
+    fn multiply() {
+        // (x + 1)(x - 1) = x^2 - 1
+        assert_eq!(polynomial!(1, 1) * polynomial!(1, -1), polynomial!(1, 0, -1));
+        assert_eq!(polynomial!(1, 2, 3) * 2, polynomial!(2, 4, 6));
+        assert_eq!(polynomial!(1, 2, 3) * -1 + polynomial!(1, 2, 3), polynomial!(0));
+    }
+
+    #[test]
+    fn parse_from_str() {
+        let p: Polynomial<f64> = "3x^2 - 2x + 1".parse().unwrap();
+        assert_eq!(p, polynomial!(3.0, -2.0, 1.0));
+        let p: Polynomial<i32> = "-x^3 + 4 + x".parse().unwrap();
+        assert_eq!(p, polynomial!(-1, 0, 1, 4));
+        let p: Polynomial<i32> = "2x + 3x - 1 + 1".parse().unwrap();
+        assert_eq!(p, polynomial!(5, 0));
+        let p: Polynomial<f64> = "0.5*x^2".parse().unwrap();
+        assert_eq!(p, polynomial!(0.5, 0.0, 0.0));
+
+        assert!("".parse::<Polynomial<i32>>().is_err());
+        assert!("3y^2".parse::<Polynomial<i32>>().is_err());
+        assert!("x^".parse::<Polynomial<i32>>().is_err());
+        assert!("2 + - x".parse::<Polynomial<i32>>().is_err());
+
+        // Huge powers are refused before their coefficients are allocated
+        assert!(matches!("x^18446744073709551615".parse::<Polynomial<i32>>(), Err(Error::BuildError)));
+        assert!(matches!("x^1000000000".parse::<Polynomial<i32>>(), Err(Error::BuildError)));
+    }
+
+    #[test]
+    fn interpolate_points() {
+        let p = Polynomial::interpolate(&[(0.0, 1.0), (1.0, 3.0), (2.0, 7.0)]).unwrap();
+        for (actual, expected) in p.coefficients().iter().zip(&[1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!((p.eval(3.0).unwrap() - 13.0).abs() < 1e-12);
+
+        assert!(matches!(
+            Polynomial::interpolate(&[(1.0, 1.0), (1.0, 2.0)]),
+            Err(Error::SolveError(SolveError::DependentSolutionSet)) | Err(Error::SolveError(SolveError::EmptySolutionSet))
+        ));
+        assert!(matches!(
+            Polynomial::<f64>::interpolate(&[]),
+            Err(Error::SolveError(SolveError::TooSmall(0)))
+        ));
+    }
+
+    #[test]
+    fn least_squares_fit() {
+        // Exact line
+        let fit = Polynomial::fit(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)], 1).unwrap();
+        for (actual, expected) in fit.polynomial().coefficients().iter().zip(&[2.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!(fit.sum_of_squares().abs() < 1e-12);
+        assert!((fit.r_squared() - 1.0).abs() < 1e-12);
+
+        // Noisy samples: the best line through (0, 0), (1, 2), (2, 1) is
+        // y = 0.5x + 0.5 with residuals -0.5, 1, -0.5
+        let fit = Polynomial::fit(&[(0.0, 0.0), (1.0, 2.0), (2.0, 1.0)], 1).unwrap();
+        for (actual, expected) in fit.polynomial().coefficients().iter().zip(&[0.5, 0.5]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        for (actual, expected) in fit.residuals().iter().zip(&[-0.5, 1.0, -0.5]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!((fit.sum_of_squares() - 1.5).abs() < 1e-12);
+        assert!((fit.r_squared() - 0.25).abs() < 1e-12);
+
+        assert!(Polynomial::fit(&[(1.0, 1.0), (1.0, 2.0)], 1).is_err());
+    }
+
+    #[test]
+    fn evaluate_range() {
+        let p = polynomial!(1.0, 0.0, 0.0);
+        assert_eq!(p.eval_range(-1.0, 1.0, 5).unwrap(), vec![1.0, 0.25, 0.0, 0.25, 1.0]);
+        assert_eq!(p.eval_range(2.0, 3.0, 1).unwrap(), vec![4.0]);
+        assert!(p.eval_range(0.0, 1.0, 0).unwrap().is_empty());
+
+        let q = polynomial!(1i64, 1);
+        assert_eq!(q.eval_range(0, 4, 3).unwrap(), vec![1, 3, 5]);
+        assert!(q.eval_range(0, 4, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn coefficient_order() {
+        let ascending = Polynomial::with_order(vec![1, 2, 3], Order::Ascending);
+        let descending = Polynomial::with_order(vec![3, 2, 1], Order::Descending);
+        assert_eq!(ascending, descending);
+        assert_eq!(ascending.eval(2).unwrap(), 17);
+        assert_eq!(ascending.coefficients_in(Order::Ascending), vec![1, 2, 3]);
+        assert_eq!(ascending.coefficients_in(Order::Descending), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn compose_shift_and_scale() {
+        // p(x) = x^2 + 1, q(x) = 2x - 1: p(q(x)) = 4x^2 - 4x + 2
+        let p = polynomial!(1, 0, 1);
+        assert_eq!(p.compose(&polynomial!(2, -1)), polynomial!(4, -4, 2));
+        assert_eq!(p.compose(&polynomial!(3)), polynomial!(10));
+        // (x + 1)^2 + 1 = x^2 + 2x + 2
+        assert_eq!(p.shift(1), polynomial!(1, 2, 2));
+        // (3x)^2 + 1 = 9x^2 + 1
+        assert_eq!(p.scale(3), polynomial!(9, 0, 1));
+        assert_eq!(p.scale(0), polynomial!(1));
+    }
+
+    #[test]
+    fn numerical_integration() {
+        let p = polynomial!(3.0, 0.0, 0.0);
+        assert!((p.integrate(0.0, 2.0).unwrap() - 8.0).abs() < 1e-12);
+        assert!((p.trapezoid(0.0, 2.0, 100).unwrap() - 8.0).abs() < 1e-3);
+        assert!((p.simpson(0.0, 2.0, 3).unwrap() - 8.0).abs() < 1e-12);
+
+        // 1/(1 + x^2) integrates to arctan
+        let f = "1 / (1 + x^2)".parse::<crate::expr::Expr<f64>>().unwrap();
+        let quarter_pi = core::f64::consts::FRAC_PI_4;
+        assert!((f.trapezoid(0.0, 1.0, 1000).unwrap() - quarter_pi).abs() < 1e-6);
+        assert!((f.simpson(0.0, 1.0, 100).unwrap() - quarter_pi).abs() < 1e-9);
+        let (integral, error) = f.integrate_adaptive(0.0, 1.0, 1e-10).unwrap();
+        assert!((integral - quarter_pi).abs() < 1e-10);
+        assert!(error < 1e-10);
+        assert!((f.integrate(0.0, 1.0).unwrap() - quarter_pi).abs() < 1e-8);
+    }
+
+    #[test]
+    fn taylor_polynomials() {
+        // Tangent of x^3 at 1 is 3x - 2, and higher degrees give p back
+        let p = polynomial!(1.0, 0.0, 0.0, 0.0);
+        assert_eq!(p.taylor(1.0, 1).unwrap(), polynomial!(3.0, -2.0));
+        assert_eq!(p.taylor(1.0, 5).unwrap(), p);
+
+        // 1 + x + x^2 / 2 from central differences
+        let approximation = FnFunction::new(f64::exp).taylor(0.0, 2).unwrap();
+        for (a, b) in approximation.coefficients().iter().zip(&[0.5, 1.0, 1.0]) {
+            assert!((a - b).abs() < 1e-4);
+        }
+        assert_eq!(Polynomial::from_derivatives(&[2.0, 3.0], 1.0), polynomial!(3.0, -1.0));
+    }
+
+    #[test]
+    fn expand_roots() {
+        // (x - 1)(x + 2)(x - 3)
+        assert_eq!(Polynomial::from_roots(&[1, -2, 3]), polynomial!(1, -2, -5, 6));
+        assert_eq!(Polynomial::from_roots(&[2, 2]), polynomial!(1, -4, 4));
+        assert_eq!(Polynomial::<i32>::from_roots(&[]), polynomial!(1));
+        let roots = Polynomial::from_roots(&[-1.5, 0.5, 4.0]).real_roots();
+        assert!(roots.iter().zip(&[-1.5, 0.5, 4.0]).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    fn display() {
+        let p = polynomial!(3.0, -2.0, 1.0);
+        assert_eq!(p.to_string(), "3x^2 - 2x + 1");
+        assert_eq!(format!("{:#}", p), "3x² − 2x + 1");
+        assert_eq!(p.to_string().parse::<Polynomial<f64>>().unwrap(), p);
+        assert_eq!(polynomial!(-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1).to_string(), "-x^12 + 1");
+        assert_eq!(format!("{:#}", polynomial!(-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1)), "−x¹² + 1");
+        assert_eq!(polynomial!(0, 1, -1).to_string(), "x - 1");
+        assert_eq!(format!("{:.2}", polynomial!(0.5, -1.0 / 3.0)), "0.50x - 0.33");
+        assert_eq!(polynomial!(0).to_string(), "0");
+    }
+
+    #[test]
+    fn long_division() {
+        // x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+        let (quotient, remainder) = polynomial!(1.0, -2.0, 0.0, -4.0)
+            .div_rem(&polynomial!(1.0, -3.0)).unwrap();
+        assert_eq!(quotient, polynomial!(1.0, 1.0, 3.0));
+        assert_eq!(remainder, polynomial!(5.0));
+
+        let (quotient, remainder) = polynomial!(1.0, 2.0).div_rem(&polynomial!(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(quotient, polynomial!(0.0));
+        assert_eq!(remainder, polynomial!(1.0, 2.0));
+
+        assert!(matches!(polynomial!(1.0).div_rem(&polynomial!(0.0)), Err(Error::DivisionByZero)));
+
+        // Rounding leftovers of an exact division don't make up a remainder
+        let (_, remainder) = polynomial!(1.0, -0.8, 0.07).div_rem(&polynomial!(1.0, -0.1)).unwrap();
+        assert_eq!(remainder, polynomial!(0.0));
+    }
+
+    #[test]
+    fn greatest_common_divisor() {
+        // (x - 1)(x + 2) and 2(x - 1)(x - 3) share the factor x - 1
+        let a = polynomial!(1.0, 1.0, -2.0);
+        let b = polynomial!(2.0, -8.0, 6.0);
+        assert_eq!(a.gcd(&b), polynomial!(1.0, -1.0));
+        assert_eq!(polynomial!(0.0).gcd(&polynomial!(0.0)), polynomial!(0.0));
+
+        // (x - 0.1)(x - 0.7)(x - 2) and (x - 0.1)(x - 0.7)(x + 1) only share
+        // their factors up to rounding
+        let a = polynomial!(1.0, -2.8, 1.67, -0.14);
+        let b = polynomial!(1.0, 0.2, -0.73, 0.07);
+        let gcd = a.gcd(&b);
+        assert_eq!(gcd.coefficients().len(), 3);
+        for (actual, expected) in gcd.coefficients().iter().zip(&[1.0, -0.8, 0.07]) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn coefficient_functions() {
+        // Implementors of `Function` only provide the coefficients
+        struct Line(Vec<f64>);
+        impl fmt::Display for Line {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:?}", self.0)
+            }
+        }
+        impl Function<f64> for Line {
+            fn coefficients(&self) -> &Vec<f64> {
+                &self.0
+            }
+        }
+        assert_eq!(Line(vec![2.0, 1.0]).eval(3.0).unwrap(), 7.0);
+        let p = polynomial!(1.0, 0.0, -1.0);
+        assert_eq!(Function::eval(&p, 2.0).unwrap(), Evaluate::eval(&p, 2.0).unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, Equation, SolveError};
+use super::{power, Error, Evaluate};
+
+// Least-squares fit of y = c1 f1(x) + ... + cn fn(x) for any basis functions
+// fi, the model only having to be linear in its coefficients:
+//
+//     let (one, x) = (FnFunction::new(|_| 1.0), FnFunction::new(|x| x));
+//     let sin = FnFunction::new(f64::sin);
+//     let model = LinearModel::fit(&[&one, &x, &sin], &points)?;
+//
+// Each point gives a row f1(x) ... fn(x) | y of the design matrix, and the
+// overdetermined system is solved through its normal equations.
+pub struct LinearModel<'a, T> {
+    basis: &'a [&'a dyn Evaluate<T>],
+    coefficients: Vec<T>,
+    residuals: Vec<T>,
+    sum_of_squares: T,
+    r_squared: T,
+}
+
+impl<'a, T> LinearModel<'a, T> {
+    // One coefficient per basis function, in the same order
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    pub fn residuals(&self) -> &[T] {
+        &self.residuals
+    }
+
+    pub fn sum_of_squares(&self) -> &T {
+        &self.sum_of_squares
+    }
+
+    pub fn r_squared(&self) -> &T {
+        &self.r_squared
+    }
+}
+
+impl<'a, T> LinearModel<'a, T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Needs at least as many points as basis functions. Basis functions that
+    // are linearly dependent on the points leave the system dependent.
+    pub fn fit(basis: &'a [&'a dyn Evaluate<T>], points: &[(T, T)]) -> Result<LinearModel<'a, T>, Error> {
+        if basis.is_empty() {
+            return Err(SolveError::TooSmall(0).into());
+        }
+        if points.len() < basis.len() {
+            return Err(SolveError::UnfittingEquationAmount(points.len(), basis.len()).into());
+        }
+        let mut system = CoefficientMatrix::new(basis.len());
+        for &(x, y) in points {
+            let row = basis.iter()
+                .map(|f| f.eval(x))
+                .collect::<Result<Vec<T>, Error>>()?;
+            system = system.add_equation(Equation::new(row, y));
+        }
+        let coefficients = system.solve_rectangular()?;
+
+        let mut model = LinearModel { basis, coefficients, residuals: Vec::new(), sum_of_squares: T::zero(), r_squared: T::one() };
+        model.residuals = points.iter()
+            .map(|&(x, y)| Ok(y - model.eval(x)?))
+            .collect::<Result<Vec<T>, Error>>()?;
+        model.sum_of_squares = model.residuals.iter().fold(T::zero(), |sum, r| sum + *r * *r);
+        let mean = points.iter().fold(T::zero(), |sum, &(_, y)| sum + y) / power(points.len());
+        let total = points.iter().fold(T::zero(), |sum, &(_, y)| sum + (y - mean) * (y - mean));
+        // Constant data is explained perfectly by any fit reproducing it
+        if !total.is_zero() {
+            model.r_squared = T::one() - model.sum_of_squares / total;
+        }
+        Ok(model)
+    }
+}
+
+// The fitted model is a function itself, e.g. to plot or integrate it
+impl<'a, T> Evaluate<T> for LinearModel<'a, T>
+    where T: Real + fmt::Display {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        self.basis.iter()
+            .zip(&self.coefficients)
+            .try_fold(T::zero(), |sum, (f, &c)| Ok(sum + c * f.eval(x)?))
+    }
+}
+
+// Written as "2 * (x) + 0.5 * (sin(x))" through the basis functions'
+// own display
+impl<'a, T: fmt::Display> fmt::Display for LinearModel<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (basis, coefficient)) in self.basis.iter().zip(&self.coefficients).enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{} * ({})", coefficient, basis)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::FnFunction;
+    use crate::polynomial;
+
+    #[test]
+    fn fit_trigonometric_model() {
+        // y = 1 + 2x - 3 sin x, sampled exactly
+        let (one, x, sin) = (FnFunction::new(|_| 1.0), polynomial!(1.0, 0.0), FnFunction::new(f64::sin).named("sin"));
+        let basis: [&dyn Evaluate<f64>; 3] = [&one, &x, &sin];
+        let points: Vec<(f64, f64)> = (0..10)
+            .map(|i| i as f64 / 2.0)
+            .map(|x| (x, 1.0 + 2.0 * x - 3.0 * x.sin()))
+            .collect();
+        let model = LinearModel::fit(&basis, &points).unwrap();
+        for (a, b) in model.coefficients().iter().zip(&[1.0, 2.0, -3.0]) {
+            assert!((a - b).abs() < 1e-10);
+        }
+        assert!(*model.sum_of_squares() < 1e-18);
+        assert!((model.r_squared() - 1.0).abs() < 1e-12);
+        assert!((model.eval(1.0).unwrap() - (3.0 - 3.0 * 1.0f64.sin())).abs() < 1e-10);
+        assert!(model.to_string().ends_with(" * (sin(x))"));
+    }
+
+    #[test]
+    fn reject_too_few_points() {
+        let (one, x) = (FnFunction::new(|_| 1.0), FnFunction::new(|x| x));
+        let basis: [&dyn Evaluate<f64>; 2] = [&one, &x];
+        assert!(matches!(
+            LinearModel::fit(&basis, &[(0.0, 1.0)]),
+            Err(Error::SolveError(SolveError::UnfittingEquationAmount(1, 2)))
+        ));
+        // Both points share an x value, so the columns are dependent
+        assert!(LinearModel::fit(&basis, &[(1.0, 1.0), (1.0, 2.0)]).is_err());
+    }
+}
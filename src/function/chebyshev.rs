@@ -0,0 +1,151 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use super::{power, Error, Evaluate, Polynomial};
+
+// Sum c0 T0(t) + ... + cn Tn(t) of Chebyshev polynomials, with t running
+// from -1 to 1 as x runs over the interval [a, b]. Interpolating at the
+// Chebyshev nodes instead of equispaced points avoids Runge's phenomenon and
+// comes close to the best polynomial approximation of the same degree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chebyshev<T> {
+    coefficients: Vec<T>,
+    start: T,
+    end: T,
+}
+
+impl<T: Real> Chebyshev<T> {
+    // Approximation of the given degree to `f` on [start, end], which
+    // interpolates it at the degree + 1 roots of the next Chebyshev
+    // polynomial
+    pub fn fit<F>(f: &F, start: T, end: T, degree: usize) -> Result<Chebyshev<T>, Error>
+        where F: Evaluate<T> + ?Sized {
+        if start >= end {
+            return Err(Error::InvalidInterval);
+        }
+        let n = degree + 1;
+        let pi = T::from(core::f64::consts::PI).ok_or(Error::EvaluationError)?;
+        let two: T = power(2);
+        let (middle, radius) = ((start + end) / two, (end - start) / two);
+
+        // Angles of the nodes cos θk, where Tj(cos θ) = cos jθ
+        let angles: Vec<T> = (0..n).map(|k| pi * (power::<T>(2 * k + 1)) / (two * power(n))).collect();
+        let values = angles.iter()
+            .map(|&angle| f.eval(middle + radius * angle.cos()))
+            .collect::<Result<Vec<T>, Error>>()?;
+        let coefficients = (0..n)
+            .map(|j| {
+                let sum = angles.iter().zip(&values)
+                    .fold(T::zero(), |sum, (&angle, &value)| sum + value * (power::<T>(j) * angle).cos());
+                let weight = if j == 0 { T::one() } else { two };
+                weight * sum / power(n)
+            })
+            .collect();
+        Ok(Chebyshev { coefficients, start, end })
+    }
+
+    // Coefficients of T0 up to Tn
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    pub fn domain(&self) -> (T, T) {
+        (self.start, self.end)
+    }
+
+    // The same polynomial in the monomial basis, which is worse conditioned
+    // for higher degrees but evaluates with the usual tools
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        let two: T = power(2);
+        // t = (2x - a - b) / (b - a)
+        let scale = two / (self.end - self.start);
+        let t = Polynomial { coefficients: vec![scale, -(self.start + self.end) / (self.end - self.start)] };
+        let (mut previous, mut current) = (Polynomial { coefficients: vec![T::one()] }, t.clone());
+        let mut sum = previous.clone() * self.coefficients[0];
+        for (j, &coefficient) in self.coefficients.iter().enumerate().skip(1) {
+            if j > 1 {
+                // Tj = 2t Tj-1 - Tj-2
+                let next = t.clone() * current.clone() * two - previous;
+                previous = current;
+                current = next;
+            }
+            sum = sum + current.clone() * coefficient;
+        }
+        sum
+    }
+}
+
+impl<T> Evaluate<T> for Chebyshev<T>
+    where T: Real + fmt::Display {
+    // Clenshaw's recurrence, outside of the interval an error
+    fn eval(&self, x: T) -> Result<T, Error> {
+        if !(self.start..=self.end).contains(&x) {
+            return Err(Error::EvaluationError);
+        }
+        let two: T = power(2);
+        let t = (two * x - self.start - self.end) / (self.end - self.start);
+        let (mut b1, mut b2) = (T::zero(), T::zero());
+        for &coefficient in self.coefficients[1..].iter().rev() {
+            let b = two * t * b1 - b2 + coefficient;
+            b2 = b1;
+            b1 = b;
+        }
+        Ok(t * b1 - b2 + self.coefficients[0])
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Chebyshev<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Chebyshev approximation of degree {} on [{}, {}]",
+            self.coefficients.len() - 1, self.start, self.end)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial;
+
+    #[test]
+    fn reproduce_polynomial() {
+        // 2x^2 - 1 on [-1, 1] is T2 itself
+        let p = polynomial!(2.0, 0.0, -1.0);
+        let approximation = Chebyshev::fit(&p, -1.0, 1.0, 2).unwrap();
+        for (a, b) in approximation.coefficients().iter().zip(&[0.0, 0.0, 1.0]) {
+            assert!((a - b).abs() < 1e-14);
+        }
+
+        // On another interval the monomial form gives the cubic back
+        let p = polynomial!(1.0, -2.0, 0.0, 3.0);
+        let approximation = Chebyshev::fit(&p, 0.0, 4.0, 3).unwrap();
+        assert!((approximation.eval(2.5).unwrap() - p.eval(2.5).unwrap()).abs() < 1e-12);
+        for (a, b) in approximation.to_polynomial().coefficients().iter().zip(p.coefficients()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!(approximation.eval(5.0).is_err());
+        assert_eq!(approximation.to_string(), "Chebyshev approximation of degree 3 on [0, 4]");
+    }
+
+    #[test]
+    fn runge_function() {
+        // Equispaced interpolation of 1 / (1 + 25x^2) diverges towards the
+        // ends as the degree grows, while the Chebyshev error shrinks
+        let f = "1 / (1 + 25 x^2)".parse::<crate::expr::Expr<f64>>().unwrap();
+        let error = |g: &dyn Evaluate<f64>| (0..=200)
+            .map(|i| -1.0 + i as f64 / 100.0)
+            .map(|x| (g.eval(x).unwrap() - f.eval(x).unwrap()).abs())
+            .fold(0.0, f64::max);
+        let degree = 16;
+        let chebyshev = Chebyshev::fit(&f, -1.0, 1.0, degree).unwrap();
+        let points: Vec<(f64, f64)> = (0..=degree)
+            .map(|i| -1.0 + 2.0 * i as f64 / degree as f64)
+            .map(|x| (x, f.eval(x).unwrap()))
+            .collect();
+        let equispaced = Polynomial::interpolate(&points).unwrap();
+        assert!(error(&chebyshev) < 0.1);
+        assert!(error(&equispaced) > 1.0);
+        assert!(matches!(Chebyshev::fit(&f, 1.0, 1.0, 2), Err(Error::InvalidInterval)));
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+use num::Num;
+
+use super::{Error, Evaluate};
+
+// Any closure as an `Evaluate`, so that integration, Taylor polynomials and
+// sampling work with code the other function types can't express:
+//
+//     let sinc = FnFunction::new(|x: f64| x.sin() / x).named("sinc");
+//     let integral = sinc.integrate(1.0, 2.0)?;
+//
+// It is displayed by its name as "sinc(x)", by default "f(x)".
+#[derive(Clone)]
+pub struct FnFunction<F> {
+    f: F,
+    name: String,
+}
+
+impl<F> FnFunction<F> {
+    pub fn new(f: F) -> FnFunction<F> {
+        FnFunction { f, name: "f".to_string() }
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+}
+
+impl<T, F> Evaluate<T> for FnFunction<F>
+    where T: Num + Copy, F: Fn(T) -> T {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        Ok((self.f)(x))
+    }
+}
+
+impl<F> fmt::Display for FnFunction<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(x)", self.name)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closures_as_functions() {
+        let sinc = FnFunction::new(|x: f64| x.sin() / x).named("sinc");
+        assert_eq!(sinc.to_string(), "sinc(x)");
+        assert_eq!(sinc.eval(core::f64::consts::PI / 2.0).unwrap(), 2.0 / core::f64::consts::PI);
+        // Si(2) - Si(1)
+        assert!((sinc.integrate(1.0, 2.0).unwrap() - 0.659329906435512).abs() < 1e-8);
+
+        let offset = 3;
+        let shifted = FnFunction::new(move |x: i64| x + offset);
+        assert_eq!(shifted.eval_range(0, 4, 3).unwrap(), vec![3, 5, 7]);
+        assert_eq!(shifted.to_string(), "f(x)");
+    }
+}
@@ -0,0 +1,233 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use super::{power, Polynomial};
+
+// How a polynomial behaves around a point where its derivative vanishes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalKind {
+    Minimum,
+    Maximum,
+    // Stationary inflection point such as 0 for x^3, neither a minimum nor
+    // a maximum
+    Inflection,
+}
+
+impl fmt::Display for CriticalKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CriticalKind::Minimum => write!(f, "minimum"),
+            CriticalKind::Maximum => write!(f, "maximum"),
+            CriticalKind::Inflection => write!(f, "inflection point"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CriticalPoint<T> {
+    x: T,
+    y: T,
+    kind: CriticalKind,
+}
+
+impl<T: Copy> CriticalPoint<T> {
+    pub fn x(&self) -> T {
+        self.x
+    }
+
+    // Value of the polynomial at `x`
+    pub fn y(&self) -> T {
+        self.y
+    }
+
+    pub fn kind(&self) -> CriticalKind {
+        self.kind
+    }
+}
+
+impl<T: Real> Polynomial<T> {
+    // Horner's scheme, together with the same sum over the absolute values,
+    // which bounds the rounding error of the result
+    fn eval_with_bound(&self, x: T) -> (T, T) {
+        self.coefficients.iter().fold((T::zero(), T::zero()), |(sum, bound), &coefficient| {
+            (sum * x + coefficient, bound * x.abs() + coefficient.abs())
+        })
+    }
+
+    // Whether `value` is zero up to the rounding error of evaluating this
+    // polynomial, given the bound from `eval_with_bound`
+    fn negligible(&self, value: T, bound: T) -> bool {
+        value.abs() <= T::epsilon() * power::<T>(4 * self.coefficients.len()) * bound
+    }
+
+    // Order of the first derivative from `from` on that doesn't vanish at
+    // `x`, and whether it is positive there. `None` for constants.
+    fn leading_derivative(&self, x: T, from: usize) -> Option<(usize, bool)> {
+        let mut derivative = (0..from).fold(self.clone(), |p, _| p.derivative());
+        for order in from..self.coefficients.len() {
+            let (value, bound) = derivative.eval_with_bound(x);
+            if !derivative.negligible(value, bound) {
+                return Some((order, value > T::zero()));
+            }
+            derivative = derivative.derivative();
+        }
+        None
+    }
+
+    // Shrink the interval from `low` to `high`, over which the polynomial
+    // changes its sign, until no value lies between both ends
+    fn bisect(&self, mut low: T, mut high: T, low_negative: bool) -> T {
+        loop {
+            let middle = (low + high) / power(2);
+            if middle <= low || middle >= high {
+                return middle;
+            }
+            let (value, _) = self.eval_with_bound(middle);
+            if value.is_zero() {
+                return middle;
+            }
+            if value.is_sign_negative() == low_negative {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+    }
+
+    // Real roots in increasing order, each multiple root once. The roots of
+    // the derivative split the real line into pieces on which the
+    // polynomial is monotonic, so every piece holds at most one root, found
+    // by bisection. Roots of the derivative where the polynomial vanishes
+    // are multiple roots. The zero polynomial gives no roots.
+    pub fn real_roots(&self) -> Vec<T> {
+        let polynomial = self.clone().trim();
+        let c = &polynomial.coefficients;
+        let degree = c.len() - 1;
+        if degree == 0 {
+            return Vec::new();
+        }
+        if degree == 1 {
+            return vec![-c[1] / c[0]];
+        }
+
+        // Cauchy's bound: every root lies strictly between -limit and limit
+        let limit = T::one() + c[1..].iter().fold(T::zero(), |max, &coefficient| max.max((coefficient / c[0]).abs()));
+        let mut points = vec![-limit];
+        points.extend(polynomial.derivative().real_roots());
+        points.push(limit);
+        let values: Vec<(T, bool)> = points.iter()
+            .map(|&x| {
+                let (value, bound) = polynomial.eval_with_bound(x);
+                (value, polynomial.negligible(value, bound))
+            })
+            .collect();
+
+        let mut roots: Vec<T> = Vec::new();
+        for i in 1..points.len() {
+            let ((left, left_root), (right, right_root)) = (values[i - 1], values[i]);
+            if !left_root && !right_root && left.is_sign_negative() != right.is_sign_negative() {
+                roots.push(polynomial.bisect(points[i - 1], points[i], left.is_sign_negative()));
+            }
+            if right_root && i + 1 < points.len() {
+                roots.push(points[i]);
+            }
+        }
+        roots.dedup();
+        roots
+    }
+
+    fn classify(&self, x: T) -> CriticalKind {
+        match self.leading_derivative(x, 2) {
+            Some((order, true)) if order % 2 == 0 => CriticalKind::Minimum,
+            Some((order, false)) if order % 2 == 0 => CriticalKind::Maximum,
+            _ => CriticalKind::Inflection,
+        }
+    }
+
+    // Points where the derivative vanishes, in increasing order, classified
+    // by the first higher derivative that doesn't: of even order they are
+    // minima or maxima, of odd order inflection points. Constants have none.
+    pub fn critical_points(&self) -> Vec<CriticalPoint<T>> {
+        self.derivative().real_roots().into_iter()
+            .map(|x| CriticalPoint { x, y: self.eval_with_bound(x).0, kind: self.classify(x) })
+            .collect()
+    }
+
+    // Local minima and maxima on the closed interval from `a` to `b`, in
+    // increasing order: critical points inside it except inflection points,
+    // and both ends, which are extrema of the restricted polynomial. Empty
+    // for constants and unless a < b.
+    pub fn extrema_on(&self, a: T, b: T) -> Vec<CriticalPoint<T>> {
+        if a >= b {
+            return Vec::new();
+        }
+        let end = |x: T, left: bool| self.leading_derivative(x, 1).map(|(order, positive)| {
+            // Whether the polynomial grows when moving into the interval
+            let grows = if left { positive } else { positive == (order % 2 == 0) };
+            let kind = if grows { CriticalKind::Minimum } else { CriticalKind::Maximum };
+            CriticalPoint { x, y: self.eval_with_bound(x).0, kind }
+        });
+
+        let mut extrema: Vec<CriticalPoint<T>> = Vec::new();
+        extrema.extend(end(a, true));
+        extrema.extend(self.critical_points().into_iter()
+            .filter(|point| point.x > a && point.x < b && point.kind != CriticalKind::Inflection));
+        extrema.extend(end(b, false));
+        extrema
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn real_roots() {
+        // (x - 1)(x - 2)(x + 3)
+        assert!(close(&polynomial!(1.0, 0.0, -7.0, 6.0).real_roots(), &[-3.0, 1.0, 2.0]));
+        assert!(polynomial!(1.0, 0.0, 1.0).real_roots().is_empty());
+        // Multiple roots: (x^2 - 1)^2 and x^3
+        assert!(close(&polynomial!(1.0, 0.0, -2.0, 0.0, 1.0).real_roots(), &[-1.0, 1.0]));
+        assert!(close(&polynomial!(1.0, 0.0, 0.0, 0.0).real_roots(), &[0.0]));
+        assert!(close(&polynomial!(0.0, 2.0, -1.0).real_roots(), &[0.5]));
+        assert!(polynomial!(0.0).real_roots().is_empty());
+    }
+
+    #[test]
+    fn classify_critical_points() {
+        let points = polynomial!(1.0, 0.0, -3.0, 0.0).critical_points();
+        assert_eq!(points.len(), 2);
+        assert!((points[0].x() + 1.0).abs() < 1e-12 && (points[0].y() - 2.0).abs() < 1e-12);
+        assert_eq!(points[0].kind(), CriticalKind::Maximum);
+        assert_eq!(points[1].kind(), CriticalKind::Minimum);
+
+        let kinds = |p: Polynomial<f64>| -> Vec<CriticalKind> { p.critical_points().iter().map(CriticalPoint::kind).collect() };
+        assert_eq!(kinds(polynomial!(1.0, 0.0, 0.0, 0.0)), vec![CriticalKind::Inflection]);
+        assert_eq!(kinds(polynomial!(-1.0, 0.0, 0.0, 0.0, 0.0)), vec![CriticalKind::Maximum]);
+        assert!(kinds(polynomial!(2.0, 1.0)).is_empty());
+        assert_eq!(CriticalKind::Inflection.to_string(), "inflection point");
+    }
+
+    #[test]
+    fn extrema_on_interval() {
+        let p = polynomial!(1.0, 0.0, -3.0, 0.0);
+        let extrema: Vec<(f64, CriticalKind)> = p.extrema_on(0.0, 2.0).iter().map(|e| (e.x(), e.kind())).collect();
+        assert_eq!(extrema.len(), 3);
+        assert_eq!((extrema[0].0, extrema[0].1), (0.0, CriticalKind::Maximum));
+        assert!((extrema[1].0 - 1.0).abs() < 1e-12 && extrema[1].1 == CriticalKind::Minimum);
+        assert_eq!((extrema[2].0, extrema[2].1), (2.0, CriticalKind::Maximum));
+
+        // Ends at critical points: x^3 has a minimum at 0 on [0, 1]
+        let kinds: Vec<CriticalKind> = polynomial!(1.0, 0.0, 0.0, 0.0).extrema_on(0.0, 1.0).iter().map(CriticalPoint::kind).collect();
+        assert_eq!(kinds, vec![CriticalKind::Minimum, CriticalKind::Maximum]);
+        assert!(polynomial!(3.0).extrema_on(0.0, 1.0).is_empty());
+        assert!(p.extrema_on(1.0, 1.0).is_empty());
+    }
+}
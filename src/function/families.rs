@@ -0,0 +1,101 @@
+use alloc::vec;
+use num::Num;
+
+use super::{power, Polynomial};
+
+// Classical orthogonal polynomials by their three-term recurrences. Legendre
+// and Laguerre polynomials have fractional coefficients, so `T` needs exact
+// division such as for floats or `Ratio`; Hermite polynomials stay integer.
+
+impl<T: Num + Copy> Polynomial<T> {
+    // Build the polynomial of degree n from the first two and a step giving
+    // p(k + 1) from k, p(k) and p(k - 1)
+    fn recurrence(n: usize, first: Polynomial<T>, second: Polynomial<T>,
+        step: impl Fn(usize, &Polynomial<T>, &Polynomial<T>) -> Polynomial<T>) -> Polynomial<T> {
+        if n == 0 {
+            return first;
+        }
+        let (mut previous, mut current) = (first, second);
+        for k in 1..n {
+            let next = step(k, &current, &previous);
+            previous = current;
+            current = next;
+        }
+        current
+    }
+
+    fn x() -> Polynomial<T> {
+        Polynomial { coefficients: vec![T::one(), T::zero()] }
+    }
+
+    fn constant(value: T) -> Polynomial<T> {
+        Polynomial { coefficients: vec![value] }
+    }
+
+    // Pn, orthogonal on [-1, 1] and scaled to Pn(1) = 1:
+    // (k + 1) Pk+1 = (2k + 1) x Pk - k Pk-1
+    pub fn legendre(n: usize) -> Polynomial<T> {
+        Self::recurrence(n, Self::constant(T::one()), Self::x(), |k, current, previous| {
+            let k1: T = power(k + 1);
+            Self::x() * current.clone() * (power::<T>(2 * k + 1) / k1) - previous.clone() * (power::<T>(k) / k1)
+        })
+    }
+
+    // Physicists' Hn, orthogonal with respect to exp(-x^2):
+    // Hk+1 = 2x Hk - 2k Hk-1
+    pub fn hermite(n: usize) -> Polynomial<T> {
+        Self::recurrence(n, Self::constant(T::one()), Self::x() * power::<T>(2), |k, current, previous| {
+            Self::x() * current.clone() * power::<T>(2) - previous.clone() * power::<T>(2 * k)
+        })
+    }
+
+    // Ln, orthogonal on [0, ∞) with respect to exp(-x):
+    // (k + 1) Lk+1 = (2k + 1 - x) Lk - k Lk-1
+    pub fn laguerre(n: usize) -> Polynomial<T> {
+        let first = Self::constant(T::one());
+        let second = Self::constant(T::one()) - Self::x();
+        Self::recurrence(n, first, second, |k, current, previous| {
+            let k1: T = power(k + 1);
+            let factor = Self::constant(power(2 * k + 1)) - Self::x();
+            (factor * current.clone() - previous.clone() * power::<T>(k)) * (T::one() / k1)
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::Order;
+    use crate::polynomial;
+    use num::rational::Ratio;
+
+    #[test]
+    fn legendre() {
+        assert_eq!(Polynomial::<f64>::legendre(0), polynomial!(1.0));
+        assert_eq!(Polynomial::<f64>::legendre(2), polynomial!(1.5, 0.0, -0.5));
+        // P3 = (5x^3 - 3x) / 2
+        assert_eq!(Polynomial::<f64>::legendre(3), polynomial!(2.5, 0.0, -1.5, 0.0));
+        for n in 0..8 {
+            assert!((Polynomial::<f64>::legendre(n).eval(1.0).unwrap() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn hermite() {
+        assert_eq!(Polynomial::<i64>::hermite(1), polynomial!(2, 0));
+        // H4 = 16x^4 - 48x^2 + 12
+        assert_eq!(Polynomial::<i64>::hermite(4), polynomial!(16, 0, -48, 0, 12));
+    }
+
+    #[test]
+    fn laguerre() {
+        // L2 = (x^2 - 4x + 2) / 2, exactly with rationals
+        let r = |n, d| Ratio::new(n, d);
+        let exact = |coefficients| Polynomial::with_order(coefficients, Order::Descending);
+        assert_eq!(Polynomial::<Ratio<i64>>::laguerre(2), exact(vec![r(1, 2), r(-2, 1), r(1, 1)]));
+        // L3 = (-x^3 + 9x^2 - 18x + 6) / 6
+        assert_eq!(Polynomial::<Ratio<i64>>::laguerre(3), exact(vec![r(-1, 6), r(3, 2), r(-3, 1), r(1, 1)]));
+        assert_eq!(Polynomial::<f64>::laguerre(0), polynomial!(1.0));
+    }
+}
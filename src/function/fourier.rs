@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, Equation, SolveError};
+use super::{power, Error, Evaluate};
+
+// Truncated Fourier series a0 + Σ ak cos(k ω x) + bk sin(k ω x) with the
+// harmonics k = 1..n of the angular frequency ω, fitted to samples by least
+// squares. Unlike the discrete Fourier transform the samples don't need to
+// be evenly spaced or cover whole periods.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fourier<T> {
+    omega: T,
+    constant: T,
+    cosines: Vec<T>,
+    sines: Vec<T>,
+}
+
+impl<T: Copy> Fourier<T> {
+    pub fn omega(&self) -> T {
+        self.omega
+    }
+
+    // a0
+    pub fn constant(&self) -> T {
+        self.constant
+    }
+
+    // a1 up to an
+    pub fn cosines(&self) -> &[T] {
+        &self.cosines
+    }
+
+    // b1 up to bn
+    pub fn sines(&self) -> &[T] {
+        &self.sines
+    }
+}
+
+impl<T> Fourier<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Fit `harmonics` harmonics of `omega`, e.g. 2π / period, which needs at
+    // least 2 harmonics + 1 points. Samples too sparse for the highest
+    // harmonic leave the system dependent.
+    pub fn fit(points: &[(T, T)], harmonics: usize, omega: T) -> Result<Fourier<T>, Error> {
+        let unknowns = 2 * harmonics + 1;
+        if points.len() < unknowns {
+            return Err(SolveError::UnfittingEquationAmount(points.len(), unknowns).into());
+        }
+        let mut system = CoefficientMatrix::new(unknowns);
+        for &(x, y) in points {
+            let mut row = Vec::with_capacity(unknowns);
+            row.push(T::one());
+            for k in 1..=harmonics {
+                let (sin, cos) = (power::<T>(k) * omega * x).sin_cos();
+                row.push(cos);
+                row.push(sin);
+            }
+            system = system.add_equation(Equation::new(row, y));
+        }
+        let solution = system.solve_rectangular()?;
+        Ok(Fourier {
+            omega,
+            constant: solution[0],
+            cosines: solution[1..].iter().step_by(2).copied().collect(),
+            sines: solution[2..].iter().step_by(2).copied().collect(),
+        })
+    }
+}
+
+impl<T> Evaluate<T> for Fourier<T>
+    where T: Real + fmt::Display {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        Ok(self.cosines.iter()
+            .zip(&self.sines)
+            .enumerate()
+            .fold(self.constant, |sum, (i, (&a, &b))| {
+                let (sin, cos) = (power::<T>(i + 1) * self.omega * x).sin_cos();
+                sum + a * cos + b * sin
+            }))
+    }
+}
+
+// E.g. "1 + 2 cos(0.5x) - 0.5 sin(0.5x)", each harmonic written with its
+// frequency k ω
+impl<T: Real + fmt::Display> fmt::Display for Fourier<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.constant)?;
+        for (i, (&a, &b)) in self.cosines.iter().zip(&self.sines).enumerate() {
+            let frequency = power::<T>(i + 1) * self.omega;
+            for (name, value) in [("cos", a), ("sin", b)] {
+                let sign = if value < T::zero() { "-" } else { "+" };
+                write!(f, " {} {} {}({}x)", sign, value.abs(), name, frequency)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recover_coefficients() {
+        // Unevenly spaced samples of 1 + 2 cos 3x - 0.5 sin 3x + 4 sin 6x
+        let f = |x: f64| 1.0 + 2.0 * (3.0 * x).cos() - 0.5 * (3.0 * x).sin() + 4.0 * (6.0 * x).sin();
+        let points: Vec<(f64, f64)> = (0..40).map(|i| i as f64 * 0.037 + (i % 3) as f64 * 0.01).map(|x| (x, f(x))).collect();
+        let fourier = Fourier::fit(&points, 2, 3.0).unwrap();
+        assert!((fourier.constant() - 1.0).abs() < 1e-9);
+        for (a, b) in fourier.cosines().iter().zip(&[2.0, 0.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        for (a, b) in fourier.sines().iter().zip(&[-0.5, 4.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!((fourier.eval(0.123).unwrap() - f(0.123)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn display_and_too_few_points() {
+        let fourier = Fourier { omega: 0.5, constant: 1.0, cosines: vec![2.0], sines: vec![-0.5] };
+        assert_eq!(fourier.to_string(), "1 + 2 cos(0.5x) - 0.5 sin(0.5x)");
+        assert!(matches!(
+            Fourier::fit(&[(0.0, 1.0), (1.0, 2.0)], 1, 1.0),
+            Err(Error::SolveError(SolveError::UnfittingEquationAmount(2, 3)))
+        ));
+    }
+}
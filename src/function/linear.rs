@@ -0,0 +1,134 @@
+use alloc::{collections::BTreeMap, string::String, string::ToString, vec::Vec};
+use core::fmt;
+use num::Num;
+
+use crate::solver::Equation;
+use super::Error;
+
+// Linear combination a1 x1 + ... + an xn of named variables, an intermediate
+// between parsed user input and the rows of a coefficient matrix
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearForm<T> {
+    terms: Vec<(String, T)>,
+}
+
+impl<T> LinearForm<T> {
+    pub fn new() -> LinearForm<T> {
+        LinearForm { terms: Vec::new() }
+    }
+
+    // Names of the variables in the order they were first added
+    pub fn variables(&self) -> impl Iterator<Item = &str> + '_ {
+        self.terms.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl<T> Default for LinearForm<T> {
+    fn default() -> Self {
+        LinearForm::new()
+    }
+}
+
+impl<T: Num + Copy> LinearForm<T> {
+    // Add `coefficient` times `name`, summing up repeated variables
+    pub fn term(mut self, name: &str, coefficient: T) -> Self {
+        match self.terms.iter_mut().find(|(existing, _)| existing == name) {
+            Some((_, existing)) => *existing = *existing + coefficient,
+            None => self.terms.push((name.to_string(), coefficient)),
+        }
+        self
+    }
+
+    // Coefficient of `name`, zero if the variable doesn't occur
+    pub fn coefficient(&self, name: &str) -> T {
+        self.terms.iter()
+            .find(|(existing, _)| existing == name)
+            .map_or(T::zero(), |(_, coefficient)| *coefficient)
+    }
+
+    pub fn eval(&self, assignment: &BTreeMap<String, T>) -> Result<T, Error> {
+        self.terms.iter().try_fold(T::zero(), |sum, (name, coefficient)| {
+            match assignment.get(name) {
+                Some(value) => Ok(sum + *coefficient * *value),
+                None => Err(Error::UnknownVariable(name.clone())),
+            }
+        })
+    }
+
+    // Equation `self = result` with one coefficient per entry of
+    // `variables`, in that order
+    pub fn to_equation(&self, variables: &[&str], result: T) -> Result<Equation<T>, Error> {
+        if let Some(name) = self.variables().find(|name| !variables.contains(name)) {
+            return Err(Error::UnknownVariable(name.to_string()));
+        }
+        let coefficients = variables.iter().map(|name| self.coefficient(name)).collect();
+        Ok(Equation::new(coefficients, result))
+    }
+
+    // Split an equation into its left-hand side, naming the coefficients
+    // after `variables`, and its right-hand side
+    pub fn from_equation(equation: &Equation<T>, variables: &[&str]) -> Result<(LinearForm<T>, T), Error> {
+        if equation.coefficients().len() != variables.len() {
+            return Err(Error::BuildError);
+        }
+        let form = variables.iter()
+            .zip(equation.coefficients())
+            .fold(LinearForm::new(), |form, (name, coefficient)| form.term(name, *coefficient));
+        Ok((form, *equation.result()))
+    }
+}
+
+impl<T> fmt::Display for LinearForm<T>
+    where T: Num + Copy + PartialOrd + fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (name, coefficient)) in self.terms.iter().enumerate() {
+            let negative = *coefficient < T::zero();
+            let magnitude = if negative { T::zero() - *coefficient } else { *coefficient };
+            match (i, negative) {
+                (0, true) => write!(f, "-{} {}", magnitude, name)?,
+                (0, false) => write!(f, "{} {}", magnitude, name)?,
+                (_, true) => write!(f, " - {} {}", magnitude, name)?,
+                (_, false) => write!(f, " + {} {}", magnitude, name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn build_and_evaluate() {
+        let form = LinearForm::new().term("x", 2.0).term("y", -3.0).term("x", 1.0);
+        assert_eq!(form.variables().collect::<Vec<_>>(), vec!["x", "y"]);
+        assert_eq!(form.coefficient("x"), 3.0);
+        assert_eq!(form.coefficient("z"), 0.0);
+        assert_eq!(form.to_string(), "3 x - 3 y");
+
+        let mut assignment = BTreeMap::new();
+        assignment.insert("x".to_string(), 2.0);
+        assert!(matches!(form.eval(&assignment), Err(Error::UnknownVariable(name)) if name == "y"));
+        assignment.insert("y".to_string(), 1.0);
+        assert_eq!(form.eval(&assignment).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn convert_equations() {
+        let form = LinearForm::new().term("y", 4).term("x", 1);
+        let equation = form.to_equation(&["x", "y", "z"], 7).unwrap();
+        assert_eq!(equation, Equation::new(vec![1, 4, 0], 7));
+        assert!(matches!(form.to_equation(&["x"], 7), Err(Error::UnknownVariable(name)) if name == "y"));
+
+        let (back, result) = LinearForm::from_equation(&equation, &["x", "y", "z"]).unwrap();
+        assert_eq!(back.coefficient("y"), 4);
+        assert_eq!(result, 7);
+        assert!(LinearForm::from_equation(&equation, &["x"]).is_err());
+    }
+}
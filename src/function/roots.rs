@@ -0,0 +1,235 @@
+use num::traits::real::Real;
+
+use super::{power, Error, Evaluate};
+
+// Root finding for anything implementing `Evaluate`, such as parsed
+// expressions or closures:
+//
+//     let root = RootFinder::new().tolerance(1e-12).brent(&f, 0.0, 2.0)?;
+//
+// Bisection and Brent's method need a bracket [a, b] over which f changes
+// its sign and always converge; the secant method starts from two guesses
+// and converges faster but may diverge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RootFinder<T> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T: Real> RootFinder<T> {
+    pub fn new() -> RootFinder<T> {
+        RootFinder {
+            tolerance: T::epsilon() * power(4),
+            max_iterations: 200,
+        }
+    }
+
+    // Converged once the bracket, or for the secant method the last step,
+    // is within the tolerance relative to the size of the root
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    fn within(&self, step: T, x: T) -> bool {
+        step.abs() <= self.tolerance * x.abs().max(T::one())
+    }
+}
+
+impl<T: Real> Default for RootFinder<T> {
+    fn default() -> Self {
+        RootFinder::new()
+    }
+}
+
+// Values at both ends of a bracket, or the root if one of them is already
+fn bracket<T, F>(f: &F, a: T, b: T) -> Result<Result<(T, T), T>, Error>
+    where T: Real, F: Evaluate<T> + ?Sized {
+    let (fa, fb) = (f.eval(a)?, f.eval(b)?);
+    if fa.is_zero() {
+        return Ok(Err(a));
+    }
+    if fb.is_zero() {
+        return Ok(Err(b));
+    }
+    if (fa > T::zero()) == (fb > T::zero()) {
+        return Err(Error::NoSignChange);
+    }
+    Ok(Ok((fa, fb)))
+}
+
+impl<T: Real> RootFinder<T> {
+    // Halve the bracket until it is within the tolerance, gaining one bit
+    // per iteration
+    pub fn bisection<F>(&self, f: &F, mut a: T, mut b: T) -> Result<T, Error>
+        where F: Evaluate<T> + ?Sized {
+        let (mut fa, _) = match bracket(f, a, b)? {
+            Ok(values) => values,
+            Err(root) => return Ok(root),
+        };
+        for _ in 0..self.max_iterations {
+            let middle = (a + b) / power(2);
+            if self.within((b - a) / power(2), middle) || middle == a || middle == b {
+                return Ok(middle);
+            }
+            let value = f.eval(middle)?;
+            if value.is_zero() {
+                return Ok(middle);
+            }
+            if (value > T::zero()) == (fa > T::zero()) {
+                a = middle;
+                fa = value;
+            } else {
+                b = middle;
+            }
+        }
+        Err(Error::NoConvergence(self.max_iterations))
+    }
+
+    // Brent's method: inverse quadratic interpolation or secant steps where
+    // they stay inside the bracket and shrink it fast enough, bisection
+    // otherwise, so it converges superlinearly for smooth functions and
+    // never slower than bisection
+    pub fn brent<F>(&self, f: &F, mut a: T, mut b: T) -> Result<T, Error>
+        where F: Evaluate<T> + ?Sized {
+        let (mut fa, mut fb) = match bracket(f, a, b)? {
+            Ok(values) => values,
+            Err(root) => return Ok(root),
+        };
+        let two: T = power(2);
+        let (mut c, mut fc) = (a, fa);
+        let (mut d, mut e) = (b - a, b - a);
+        for _ in 0..self.max_iterations {
+            // c is the other end of the bracket around b
+            if (fb > T::zero()) == (fc > T::zero()) {
+                c = a;
+                fc = fa;
+                d = b - a;
+                e = d;
+            }
+            // b is the best estimate so far
+            if fc.abs() < fb.abs() {
+                a = b;
+                b = c;
+                c = a;
+                fa = fb;
+                fb = fc;
+                fc = fa;
+            }
+            let tolerance = self.tolerance * b.abs().max(T::one()) / two;
+            let middle = (c - b) / two;
+            if middle.abs() <= tolerance || fb.is_zero() {
+                return Ok(b);
+            }
+
+            if e.abs() >= tolerance && fa.abs() > fb.abs() {
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    // Secant step
+                    (two * middle * s, T::one() - s)
+                } else {
+                    // Inverse quadratic interpolation
+                    let (q, r) = (fa / fc, fb / fc);
+                    (s * (two * middle * q * (q - r) - (b - a) * (r - T::one())),
+                        (q - T::one()) * (r - T::one()) * (s - T::one()))
+                };
+                if p > T::zero() {
+                    q = -q;
+                } else {
+                    p = -p;
+                }
+                let three: T = power(3);
+                if two * p < (three * middle * q - (tolerance * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = middle;
+                    e = d;
+                }
+            } else {
+                d = middle;
+                e = d;
+            }
+
+            a = b;
+            fa = fb;
+            b = b + if d.abs() > tolerance { d } else if middle > T::zero() { tolerance } else { -tolerance };
+            fb = f.eval(b)?;
+        }
+        Err(Error::NoConvergence(self.max_iterations))
+    }
+
+    // Secant method from the guesses x0 and x1, which needn't bracket a root
+    pub fn secant<F>(&self, f: &F, mut x0: T, mut x1: T) -> Result<T, Error>
+        where F: Evaluate<T> + ?Sized {
+        let (mut f0, mut f1) = (f.eval(x0)?, f.eval(x1)?);
+        for _ in 0..self.max_iterations {
+            if f1.is_zero() {
+                return Ok(x1);
+            }
+            // A horizontal secant has no intersection to step to
+            if f1 == f0 {
+                break;
+            }
+            let x2 = x1 - f1 * (x1 - x0) / (f1 - f0);
+            if self.within(x2 - x1, x2) {
+                return Ok(x2);
+            }
+            x0 = x1;
+            f0 = f1;
+            x1 = x2;
+            f1 = f.eval(x1)?;
+        }
+        Err(Error::NoConvergence(self.max_iterations))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::FnFunction;
+
+    #[test]
+    fn find_square_root_of_two() {
+        let f = FnFunction::new(|x: f64| x * x - 2.0);
+        let finder = RootFinder::new();
+        let root = core::f64::consts::SQRT_2;
+        assert!((finder.bisection(&f, 0.0, 2.0).unwrap() - root).abs() < 1e-14);
+        assert!((finder.brent(&f, 0.0, 2.0).unwrap() - root).abs() < 1e-14);
+        assert!((finder.secant(&f, 1.0, 2.0).unwrap() - root).abs() < 1e-14);
+        assert!((f.root(2.0, 0.0).unwrap() - root).abs() < 1e-14);
+        assert_eq!(finder.brent(&f, -2.0, 0.0).map(|x| (x + root).abs() < 1e-14).ok(), Some(true));
+    }
+
+    #[test]
+    fn brent_beats_bisection() {
+        // Count evaluations through a cell
+        let count = core::cell::Cell::new(0);
+        let f = FnFunction::new(|x: f64| {
+            count.set(count.get() + 1);
+            x.cos() - x
+        });
+        let finder = RootFinder::new().tolerance(1e-12);
+        let brent = finder.brent(&f, 0.0, 1.0).unwrap();
+        let brent_evaluations = count.replace(0);
+        let bisection = finder.bisection(&f, 0.0, 1.0).unwrap();
+        assert!((brent - bisection).abs() < 1e-11);
+        assert!(brent_evaluations * 3 < count.get());
+    }
+
+    #[test]
+    fn failures() {
+        let f = "x^2 + 1".parse::<crate::expr::Expr<f64>>().unwrap();
+        assert!(matches!(RootFinder::new().brent(&f, -1.0, 1.0), Err(Error::NoSignChange)));
+        assert!(matches!(RootFinder::new().max_iterations(3).bisection(&FnFunction::new(|x: f64| x - 0.3), 0.0, 1.0), Err(Error::NoConvergence(3))));
+        // The secant of a constant is horizontal
+        assert!(matches!(RootFinder::new().secant(&FnFunction::new(|_: f64| 1.0), 0.0, 1.0), Err(Error::NoConvergence(_))));
+        assert_eq!(RootFinder::new().bisection(&FnFunction::new(|x: f64| x), 0.0, 1.0).unwrap(), 0.0);
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::Banded;
+use super::{power, Error, Evaluate};
+
+// Condition imposed on the ends of a spline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary<T> {
+    // Zero second derivative at both ends
+    Natural,
+    // Given first derivative at the start and the end
+    Clamped(T, T),
+}
+
+// Piecewise cubic interpolating a set of samples with continuous first and
+// second derivatives. Each piece is stored through the second derivatives
+// at its end points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spline<T> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    second_derivatives: Vec<T>,
+}
+
+impl<T> Spline<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Build a spline through `points`, which must be ordered by strictly
+    // increasing x values. NaN isn't ordered and is rejected as well.
+    pub fn new(points: &[(T, T)], boundary: Boundary<T>) -> Result<Spline<T>, Error> {
+        let increasing = |pair: &[(T, T)]| pair[0].0.partial_cmp(&pair[1].0) == Some(Ordering::Less);
+        if points.len() < 2 || !points.windows(2).all(increasing) {
+            return Err(Error::InvalidSamples);
+        }
+        let xs: Vec<T> = points.iter().map(|&(x, _)| x).collect();
+        let ys: Vec<T> = points.iter().map(|&(_, y)| y).collect();
+        let n = points.len() - 1;
+        let h: Vec<T> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let slope = |i: usize| (ys[i + 1] - ys[i]) / h[i];
+        let (two, six): (T, T) = (power(2), power(6));
+
+        // One row per second derivative, the end rows from the boundary
+        // condition and the interior rows from continuity of the slope. The
+        // system is tridiagonal, which the banded solver handles in O(n).
+        let mut below = vec![T::zero(); n];
+        let mut diagonal = vec![T::zero(); n + 1];
+        let mut above = vec![T::zero(); n];
+        let mut rhs = vec![T::zero(); n + 1];
+        match boundary {
+            Boundary::Natural => {
+                diagonal[0] = T::one();
+                diagonal[n] = T::one();
+            },
+            Boundary::Clamped(start, end) => {
+                diagonal[0] = two * h[0];
+                above[0] = h[0];
+                rhs[0] = six * (slope(0) - start);
+                below[n - 1] = h[n - 1];
+                diagonal[n] = two * h[n - 1];
+                rhs[n] = six * (end - slope(n - 1));
+            },
+        }
+        for i in 1..n {
+            below[i - 1] = h[i - 1];
+            diagonal[i] = two * (h[i - 1] + h[i]);
+            above[i] = h[i];
+            rhs[i] = six * (slope(i) - slope(i - 1));
+        }
+
+        let second_derivatives = Banded::tridiagonal(&below, &diagonal, &above)?.solve(&rhs)?;
+        Ok(Spline { xs, ys, second_derivatives })
+    }
+
+    // Interval the spline is defined on
+    pub fn domain(&self) -> (T, T) {
+        (self.xs[0], self.xs[self.xs.len() - 1])
+    }
+}
+
+impl<T> Evaluate<T> for Spline<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    fn eval(&self, x: T) -> Result<T, Error> {
+        let (start, end) = self.domain();
+        if !(start..=end).contains(&x) {
+            return Err(Error::EvaluationError);
+        }
+        // Index of the piece containing x, the last piece including its end
+        let i = self.xs[1..self.xs.len() - 1].partition_point(|&knot| knot <= x);
+
+        let h = self.xs[i + 1] - self.xs[i];
+        let (t, u) = (x - self.xs[i], self.xs[i + 1] - x);
+        let (m0, m1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+        let six: T = power(6);
+        Ok(m0 * u * u * u / (six * h) + m1 * t * t * t / (six * h)
+            + (self.ys[i] / h - m0 * h / six) * u
+            + (self.ys[i + 1] / h - m1 * h / six) * t)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spline<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cubic spline through {} points on [{}, {}]",
+            self.xs.len(), self.xs[0], self.xs[self.xs.len() - 1])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_spline() {
+        let spline = Spline::new(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)], Boundary::Natural).unwrap();
+        assert_eq!(spline.domain(), (0.0, 2.0));
+        assert!((spline.eval(1.0).unwrap() - 1.0).abs() < 1e-12);
+        assert!((spline.eval(0.5).unwrap() - 0.6875).abs() < 1e-12);
+        assert!((spline.eval(1.5).unwrap() - 0.6875).abs() < 1e-12);
+        assert!(spline.eval(2.5).is_err());
+    }
+
+    #[test]
+    fn clamped_spline_reproduces_cubic() {
+        let points: Vec<(f64, f64)> = (0..4).map(|x| (x as f64, (x * x * x) as f64)).collect();
+        let spline = Spline::new(&points, Boundary::Clamped(0.0, 27.0)).unwrap();
+        assert!((spline.eval(1.5).unwrap() - 3.375).abs() < 1e-12);
+        assert!((spline.eval(3.0).unwrap() - 27.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn many_samples() {
+        // Far too many for a dense solve
+        let points: Vec<(f64, f64)> = (0..=20_000).map(|i| (i as f64 * 1e-3, (i as f64 * 1e-3).sin())).collect();
+        let spline = Spline::new(&points, Boundary::Clamped(1.0, 20f64.cos())).unwrap();
+        assert!((spline.eval(10.0005).unwrap() - 10.0005f64.sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reject_invalid_samples() {
+        assert!(matches!(Spline::new(&[(0.0, 1.0)], Boundary::Natural), Err(Error::InvalidSamples)));
+        assert!(matches!(
+            Spline::new(&[(1.0, 1.0), (0.0, 2.0)], Boundary::Natural),
+            Err(Error::InvalidSamples)
+        ));
+        assert!(matches!(
+            Spline::new(&[(0.0, 1.0), (f64::NAN, 2.0), (2.0, 0.0)], Boundary::Natural),
+            Err(Error::InvalidSamples)
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use crate::solver::{axpy, dot, norm2, SolveError};
+
+mod bicgstab;
+mod gmres;
+mod precondition;
+mod reorder;
+mod sparse;
+
+pub use bicgstab::BiCgStab;
+pub use gmres::Gmres;
+pub use precondition::{Identity, Ilu0, Jacobi, Preconditioner};
+pub use sparse::Sparse;
+
+// Iterative solvers for large sparse systems, which only need products
+// with the matrix instead of eliminating it and so don't fill it in.
+// `ConjugateGradient` covers symmetric positive definite systems, `Gmres`
+// and `BiCgStab` general ones.
+
+#[derive(Debug)]
+pub enum IterativeError {
+    // A vector or matrix had this many entries instead of the expected
+    UnfittingDimension(usize, usize),
+    // An entry at (row, column) outside of the matrix
+    OutOfBounds(usize, usize),
+    // A preconditioner needs a nonzero diagonal entry in this row
+    ZeroPivot(usize),
+    // The iteration broke down at this step, e.g. because the matrix
+    // isn't symmetric positive definite for conjugate gradients
+    Breakdown(usize),
+    // No convergence within the given number of iterations
+    NoConvergence(usize),
+    // A direct solve failed
+    SolveError(SolveError),
+}
+
+impl From<SolveError> for IterativeError {
+    fn from(err: SolveError) -> Self {
+        IterativeError::SolveError(err)
+    }
+}
+
+impl fmt::Display for IterativeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IterativeError::UnfittingDimension(actual, expected) => {
+                write!(f, "Expected {} values but got {}", expected, actual)
+            },
+            IterativeError::OutOfBounds(row, column) => write!(f, "Entry ({}, {}) is outside of the matrix", row, column),
+            IterativeError::ZeroPivot(row) => write!(f, "Zero pivot in row {}", row),
+            IterativeError::Breakdown(iteration) => write!(f, "Breakdown in iteration {}", iteration),
+            IterativeError::NoConvergence(iterations) => write!(f, "No convergence after {} iterations", iterations),
+            IterativeError::SolveError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IterativeError {}
+
+// Result of an iterative solve
+#[derive(Clone, Debug, PartialEq)]
+pub struct Iteration<T> {
+    pub solution: Vec<T>,
+    pub iterations: usize,
+    // ‖b - A x‖ / ‖b‖ at the solution
+    pub residual: T,
+    // The relative residual after every iteration, for plotting the
+    // convergence or comparing preconditioners. GMRES reports the estimate
+    // it minimizes, which can differ from the true residual by rounding.
+    pub history: Vec<T>,
+}
+
+// Preconditioned conjugate gradients for symmetric positive definite
+// systems:
+//
+//     let a = Sparse::from_triplets(n, &entries)?;
+//     let x = ConjugateGradient::new().solve(&a, &b, &Ilu0::new(&a)?)?.solution;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConjugateGradient<T> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T: Real> ConjugateGradient<T> {
+    pub fn new() -> ConjugateGradient<T> {
+        ConjugateGradient {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+        }
+    }
+
+    // Converged once the residual relative to ‖b‖ falls below the tolerance
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn solve<P>(&self, a: &Sparse<T>, b: &[T], preconditioner: &P) -> Result<Iteration<T>, IterativeError>
+        where P: Preconditioner<T> {
+        let n = a.size();
+        if b.len() != n {
+            return Err(IterativeError::UnfittingDimension(b.len(), n));
+        }
+        let mut x = vec![T::zero(); n];
+        let mut history = Vec::new();
+        let scale = norm2(b);
+        if scale == T::zero() {
+            return Ok(Iteration { solution: x, iterations: 0, residual: T::zero(), history });
+        }
+
+        let mut r = b.to_vec();
+        let mut z = vec![T::zero(); n];
+        preconditioner.apply(&r, &mut z);
+        let mut p = z.clone();
+        let mut rz = dot(&r, &z);
+        let mut ap = vec![T::zero(); n];
+        for iteration in 1..=self.max_iterations {
+            a.apply_into(&p, &mut ap)?;
+            let curvature = dot(&p, &ap);
+            if curvature <= T::zero() || rz <= T::zero() {
+                return Err(IterativeError::Breakdown(iteration));
+            }
+            let alpha = rz / curvature;
+            axpy(alpha, &p, &mut x);
+            axpy(-alpha, &ap, &mut r);
+
+            let residual = norm2(&r) / scale;
+            history.push(residual);
+            if residual <= self.tolerance {
+                return Ok(Iteration { solution: x, iterations: iteration, residual, history });
+            }
+
+            preconditioner.apply(&r, &mut z);
+            let next = dot(&r, &z);
+            let beta = next / rz;
+            rz = next;
+            for (direction, &value) in p.iter_mut().zip(&z) {
+                *direction = value + beta * *direction;
+            }
+        }
+        Err(IterativeError::NoConvergence(self.max_iterations))
+    }
+}
+
+impl<T: Real> Default for ConjugateGradient<T> {
+    fn default() -> Self {
+        ConjugateGradient::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2D Poisson equation on a k × k grid with its unknowns scaled by up to
+    // 10², which makes it stiff
+    fn poisson(k: usize) -> Sparse<f64> {
+        let mut entries = Vec::new();
+        let index = |i: usize, j: usize| i * k + j;
+        let scale = |index: usize| 10f64.powi((index * 7 % 3) as i32);
+        for i in 0..k {
+            for j in 0..k {
+                let at = index(i, j);
+                entries.push((at, at, 4.0 * scale(at) * scale(at)));
+                for (di, dj) in [(1, 0), (0, 1)] {
+                    if i + di < k && j + dj < k {
+                        let next = index(i + di, j + dj);
+                        entries.push((at, next, -scale(at) * scale(next)));
+                        entries.push((next, at, -scale(at) * scale(next)));
+                    }
+                }
+            }
+        }
+        Sparse::from_triplets(k * k, &entries).unwrap()
+    }
+
+    #[test]
+    fn preconditioning_speeds_up() {
+        let a = poisson(12);
+        let x: Vec<f64> = (0..144).map(|i| (i as f64 * 0.37).sin()).collect();
+        let b = a.apply(&x).unwrap();
+        let cg = ConjugateGradient::new().tolerance(1e-12);
+
+        let plain = cg.solve(&a, &b, &Identity).unwrap();
+        let jacobi = cg.solve(&a, &b, &Jacobi::new(&a).unwrap()).unwrap();
+        let ilu = cg.solve(&a, &b, &Ilu0::new(&a).unwrap()).unwrap();
+        for result in [&plain, &jacobi, &ilu] {
+            assert!(result.residual <= 1e-12);
+            assert_eq!(result.history.len(), result.iterations);
+            for (value, expected) in result.solution.iter().zip(&x) {
+                assert!((value - expected).abs() < 1e-6);
+            }
+        }
+        assert!(jacobi.iterations < plain.iterations);
+        assert!(ilu.iterations < jacobi.iterations);
+
+        assert!(matches!(cg.max_iterations(2).solve(&a, &b, &Identity), Err(IterativeError::NoConvergence(2))));
+        let indefinite = Sparse::from_triplets(2, &[(0, 0, 1.0), (1, 1, -1.0)]).unwrap();
+        assert!(matches!(cg.solve(&indefinite, &[0.0, 1.0], &Identity), Err(IterativeError::Breakdown(1))));
+    }
+}
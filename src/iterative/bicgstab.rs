@@ -0,0 +1,142 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::{IterativeError, Iteration, Preconditioner, Sparse};
+use crate::solver::{axpy, dot, norm2};
+
+// BiCGSTAB for general nonsymmetric systems. Needs a fixed amount of
+// storage, unlike GMRES, but its residual may jump around on the way and
+// the iteration can break down, which is reported as `Breakdown`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiCgStab<T> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T: Real> BiCgStab<T> {
+    pub fn new() -> BiCgStab<T> {
+        BiCgStab {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+        }
+    }
+
+    // Converged once the residual relative to ‖b‖ falls below the tolerance
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn solve<P>(&self, a: &Sparse<T>, b: &[T], preconditioner: &P) -> Result<Iteration<T>, IterativeError>
+        where P: Preconditioner<T> {
+        let n = a.size();
+        if b.len() != n {
+            return Err(IterativeError::UnfittingDimension(b.len(), n));
+        }
+        let mut x = vec![T::zero(); n];
+        let mut history = Vec::new();
+        let size = norm2(b);
+        if size == T::zero() {
+            return Ok(Iteration { solution: x, iterations: 0, residual: T::zero(), history });
+        }
+
+        let mut r = b.to_vec();
+        let shadow = b.to_vec();
+        let (mut p, mut v) = (vec![T::zero(); n], vec![T::zero(); n]);
+        let (mut y, mut z, mut t) = (vec![T::zero(); n], vec![T::zero(); n], vec![T::zero(); n]);
+        let (mut rho, mut alpha, mut omega) = (T::one(), T::one(), T::one());
+        for iteration in 1..=self.max_iterations {
+            let next = dot(&shadow, &r);
+            if next == T::zero() || omega == T::zero() {
+                return Err(IterativeError::Breakdown(iteration));
+            }
+            let beta = (next / rho) * (alpha / omega);
+            rho = next;
+            for ((direction, &residual), &previous) in p.iter_mut().zip(&r).zip(&v) {
+                *direction = residual + beta * (*direction - omega * previous);
+            }
+
+            preconditioner.apply(&p, &mut y);
+            a.apply_into(&y, &mut v)?;
+            let projection = dot(&shadow, &v);
+            if projection == T::zero() {
+                return Err(IterativeError::Breakdown(iteration));
+            }
+            alpha = rho / projection;
+            // s, the residual after the first half step, is kept in r
+            axpy(-alpha, &v, &mut r);
+            axpy(alpha, &y, &mut x);
+            let residual = norm2(&r) / size;
+            if residual <= self.tolerance {
+                history.push(residual);
+                return Ok(Iteration { solution: x, iterations: iteration, residual, history });
+            }
+
+            preconditioner.apply(&r, &mut z);
+            a.apply_into(&z, &mut t)?;
+            let length = dot(&t, &t);
+            if length == T::zero() {
+                return Err(IterativeError::Breakdown(iteration));
+            }
+            omega = dot(&t, &r) / length;
+            axpy(omega, &z, &mut x);
+            axpy(-omega, &t, &mut r);
+            let residual = norm2(&r) / size;
+            history.push(residual);
+            if residual <= self.tolerance {
+                return Ok(Iteration { solution: x, iterations: iteration, residual, history });
+            }
+        }
+        Err(IterativeError::NoConvergence(self.max_iterations))
+    }
+}
+
+impl<T: Real> Default for BiCgStab<T> {
+    fn default() -> Self {
+        BiCgStab::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn nonsymmetric_system() {
+        // 1D convection-diffusion with upwinding
+        let n = 50;
+        let mut entries = Vec::new();
+        for i in 0..n {
+            entries.push((i, i, 12.0));
+            if i > 0 { entries.push((i, i - 1, -11.0)); }
+            if i + 1 < n { entries.push((i, i + 1, -1.0)); }
+        }
+        let a = Sparse::from_triplets(n, &entries).unwrap();
+        let x: Vec<f64> = (0..n).map(|i| 1.0 + (i as f64 * 0.3).sin()).collect();
+        let b = a.apply(&x).unwrap();
+
+        let solver = BiCgStab::new().tolerance(1e-10);
+        let plain = solver.solve(&a, &b, &Identity).unwrap();
+        let jacobi = solver.solve(&a, &b, &Jacobi::new(&a).unwrap()).unwrap();
+        let ilu = solver.solve(&a, &b, &Ilu0::new(&a).unwrap()).unwrap();
+        for result in [&plain, &jacobi, &ilu] {
+            assert!(result.residual <= 1e-10);
+            assert_eq!(result.history.len(), result.iterations);
+            assert_eq!(result.history.last(), Some(&result.residual));
+            for (value, expected) in result.solution.iter().zip(&x) {
+                assert!((value - expected).abs() < 1e-8);
+            }
+        }
+        // ILU(0) of a tridiagonal matrix is exact
+        assert_eq!(ilu.iterations, 1);
+
+        assert!(matches!(solver.max_iterations(1).solve(&a, &b, &Identity), Err(IterativeError::NoConvergence(1))));
+        assert!(matches!(solver.solve(&a, &[1.0], &Identity), Err(IterativeError::UnfittingDimension(1, 50))));
+    }
+}
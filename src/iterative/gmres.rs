@@ -0,0 +1,196 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::{IterativeError, Iteration, Preconditioner, Sparse};
+use crate::solver::{axpy, dot, norm2, scale};
+
+// Restarted GMRES for general nonsymmetric or indefinite systems. Every
+// cycle builds an orthonormal basis of up to `restart` Krylov vectors and
+// picks the x minimizing the residual over it, so the residual never
+// grows. Larger restarts converge in fewer iterations but store more
+// vectors. The preconditioner is applied from the right, so the reported
+// residuals are those of the original system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gmres<T> {
+    tolerance: T,
+    max_iterations: usize,
+    restart: usize,
+}
+
+impl<T: Real> Gmres<T> {
+    pub fn new() -> Gmres<T> {
+        Gmres {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+            restart: 30,
+        }
+    }
+
+    // Converged once the residual relative to ‖b‖ falls below the tolerance
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    // Total number of iterations over all cycles
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    // Krylov vectors per cycle, at least one
+    pub fn restart(mut self, restart: usize) -> Self {
+        self.restart = restart.max(1);
+        self
+    }
+
+    pub fn solve<P>(&self, a: &Sparse<T>, b: &[T], preconditioner: &P) -> Result<Iteration<T>, IterativeError>
+        where P: Preconditioner<T> {
+        let n = a.size();
+        if b.len() != n {
+            return Err(IterativeError::UnfittingDimension(b.len(), n));
+        }
+        let mut x = vec![T::zero(); n];
+        let mut history = Vec::new();
+        let size = norm2(b);
+        if size == T::zero() {
+            return Ok(Iteration { solution: x, iterations: 0, residual: T::zero(), history });
+        }
+
+        let m = self.restart;
+        let mut iterations = 0;
+        let mut r = vec![T::zero(); n];
+        let mut z = vec![T::zero(); n];
+        loop {
+            // Start every cycle from the true residual
+            a.apply_into(&x, &mut r)?;
+            for (value, &target) in r.iter_mut().zip(b) {
+                *value = target - *value;
+            }
+            let beta = norm2(&r);
+            let residual = beta / size;
+            if residual <= self.tolerance {
+                return Ok(Iteration { solution: x, iterations, residual, history });
+            }
+            if iterations >= self.max_iterations {
+                return Err(IterativeError::NoConvergence(self.max_iterations));
+            }
+
+            scale(beta.recip(), &mut r);
+            let mut basis = vec![r.clone()];
+            // Hessenberg matrix by columns, reduced to triangular form by
+            // Givens rotations as it grows
+            let mut h: Vec<Vec<T>> = Vec::with_capacity(m);
+            let mut rotations: Vec<(T, T)> = Vec::with_capacity(m);
+            let mut g = vec![beta];
+            while h.len() < m && iterations < self.max_iterations {
+                iterations += 1;
+                let j = h.len();
+                preconditioner.apply(&basis[j], &mut z);
+                let mut w = a.apply(&z)?;
+                let mut column = Vec::with_capacity(j + 2);
+                for vector in &basis {
+                    let projection = dot(&w, vector);
+                    axpy(-projection, vector, &mut w);
+                    column.push(projection);
+                }
+                let length = norm2(&w);
+                column.push(length);
+
+                for (i, &(c, s)) in rotations.iter().enumerate() {
+                    let (upper, lower) = (column[i], column[i + 1]);
+                    column[i] = c * upper + s * lower;
+                    column[i + 1] = c * lower - s * upper;
+                }
+                let denominator = column[j].hypot(column[j + 1]);
+                if denominator == T::zero() {
+                    return Err(IterativeError::Breakdown(iterations));
+                }
+                let (c, s) = (column[j] / denominator, column[j + 1] / denominator);
+                column[j] = denominator;
+                column.pop();
+                rotations.push((c, s));
+                g.push(-s * g[j]);
+                g[j] = c * g[j];
+                h.push(column);
+
+                let estimate = g[j + 1].abs() / size;
+                history.push(estimate);
+                // An exact solution within the basis ends the cycle early
+                if estimate <= self.tolerance || length == T::zero() {
+                    break;
+                }
+                scale(length.recip(), &mut w);
+                basis.push(w);
+            }
+
+            // Back substitution for the coefficients y of the basis vectors,
+            // then x ← x + M⁻¹ V y
+            let k = h.len();
+            let mut y = vec![T::zero(); k];
+            for i in (0..k).rev() {
+                let sum = (i + 1..k).fold(g[i], |sum, column| sum - h[column][i] * y[column]);
+                y[i] = sum / h[i][i];
+            }
+            let mut update = vec![T::zero(); n];
+            for (vector, &coefficient) in basis.iter().zip(&y) {
+                axpy(coefficient, vector, &mut update);
+            }
+            preconditioner.apply(&update, &mut z);
+            axpy(T::one(), &z, &mut x);
+        }
+    }
+}
+
+impl<T: Real> Default for Gmres<T> {
+    fn default() -> Self {
+        Gmres::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    // Upwind convection-diffusion on a k × k grid, which is nonsymmetric
+    fn convection(k: usize, speed: f64) -> Sparse<f64> {
+        let mut entries = Vec::new();
+        let index = |i: usize, j: usize| i * k + j;
+        for i in 0..k {
+            for j in 0..k {
+                let at = index(i, j);
+                entries.push((at, at, 4.0 + speed));
+                if i > 0 { entries.push((at, index(i - 1, j), -1.0 - speed)); }
+                if i + 1 < k { entries.push((at, index(i + 1, j), -1.0)); }
+                if j > 0 { entries.push((at, index(i, j - 1), -1.0)); }
+                if j + 1 < k { entries.push((at, index(i, j + 1), -1.0)); }
+            }
+        }
+        Sparse::from_triplets(k * k, &entries).unwrap()
+    }
+
+    #[test]
+    fn nonsymmetric_system() {
+        let a = convection(10, 20.0);
+        let x: Vec<f64> = (0..100).map(|i| (i as f64 * 0.61).cos()).collect();
+        let b = a.apply(&x).unwrap();
+
+        let full = Gmres::new().tolerance(1e-10).restart(100).solve(&a, &b, &Identity).unwrap();
+        let restarted = Gmres::new().tolerance(1e-10).restart(5).solve(&a, &b, &Identity).unwrap();
+        let ilu = Gmres::new().tolerance(1e-10).solve(&a, &b, &Ilu0::new(&a).unwrap()).unwrap();
+        for result in [&full, &restarted, &ilu] {
+            assert!(result.residual <= 1e-10);
+            assert_eq!(result.history.len(), result.iterations);
+            assert!(result.history.windows(2).all(|pair| pair[1] <= pair[0] * (1.0 + 1e-8)));
+            for (value, expected) in result.solution.iter().zip(&x) {
+                assert!((value - expected).abs() < 1e-8);
+            }
+        }
+        assert!(full.iterations <= restarted.iterations);
+        assert!(ilu.iterations < full.iterations);
+
+        let limited = Gmres::new().restart(3).max_iterations(4).solve(&a, &b, &Identity);
+        assert!(matches!(limited, Err(IterativeError::NoConvergence(4))));
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::{IterativeError, Sparse};
+
+// An approximation M of the system matrix A that is cheap to solve with.
+// The iterative solvers then work on M⁻¹ A, which has its eigenvalues
+// clustered closer together than A on stiff systems and so needs fewer
+// iterations. Implement this for problem-specific approximations, e.g. a
+// coarser discretization of the same operator.
+pub trait Preconditioner<T> {
+    // z ← M⁻¹ r
+    fn apply(&self, r: &[T], z: &mut [T]);
+}
+
+// M = I, i.e. no preconditioning
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Identity;
+
+impl<T: Copy> Preconditioner<T> for Identity {
+    fn apply(&self, r: &[T], z: &mut [T]) {
+        z.copy_from_slice(r);
+    }
+}
+
+// M = diag(A). Costs next to nothing and already helps when the rows of A
+// are on very different scales.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jacobi<T> {
+    inverse_diagonal: Vec<T>,
+}
+
+impl<T: Real> Jacobi<T> {
+    pub fn new(a: &Sparse<T>) -> Result<Self, IterativeError> {
+        let inverse_diagonal = (0..a.size())
+            .map(|i| match a.get(i, i) {
+                Some(value) if value != T::zero() => Ok(value.recip()),
+                _ => Err(IterativeError::ZeroPivot(i)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Jacobi { inverse_diagonal })
+    }
+}
+
+impl<T: Real> Preconditioner<T> for Jacobi<T> {
+    fn apply(&self, r: &[T], z: &mut [T]) {
+        for ((target, &value), &inverse) in z.iter_mut().zip(r).zip(&self.inverse_diagonal) {
+            *target = value * inverse;
+        }
+    }
+}
+
+// Incomplete LU factorization without fill-in: M = L U where L and U are
+// restricted to the nonzero pattern of A. Much closer to A than the
+// diagonal for matrices from discretized PDEs, at the cost of one
+// forward and one backward substitution per iteration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ilu0<T> {
+    // The strictly lower part holds L without its unit diagonal, the rest U
+    factors: Sparse<T>,
+}
+
+impl<T: Real> Ilu0<T> {
+    // Fails with `ZeroPivot` if a diagonal entry is missing or vanishes
+    // during the factorization
+    pub fn new(a: &Sparse<T>) -> Result<Self, IterativeError> {
+        let mut factors = a.clone();
+        let diagonal = factors.diagonal_positions()?;
+        let size = factors.size();
+        let (offsets, columns, values) = factors.parts_mut();
+        // Position of the entries of the current row by column
+        let mut position = vec![None; size];
+        for i in 0..size {
+            for p in offsets[i]..offsets[i + 1] {
+                position[columns[p]] = Some(p);
+            }
+            for p in offsets[i]..diagonal[i] {
+                let k = columns[p];
+                let pivot = values[diagonal[k]];
+                if pivot == T::zero() {
+                    return Err(IterativeError::ZeroPivot(k));
+                }
+                let factor = values[p] / pivot;
+                values[p] = factor;
+                for q in diagonal[k] + 1..offsets[k + 1] {
+                    if let Some(target) = position[columns[q]] {
+                        values[target] = values[target] - factor * values[q];
+                    }
+                }
+            }
+            if values[diagonal[i]] == T::zero() {
+                return Err(IterativeError::ZeroPivot(i));
+            }
+            for p in offsets[i]..offsets[i + 1] {
+                position[columns[p]] = None;
+            }
+        }
+        Ok(Ilu0 { factors })
+    }
+}
+
+impl<T: Real> Preconditioner<T> for Ilu0<T> {
+    fn apply(&self, r: &[T], z: &mut [T]) {
+        let size = self.factors.size();
+        // L y = r
+        for i in 0..size {
+            let (columns, values) = self.factors.row(i);
+            let split = columns.partition_point(|&column| column < i);
+            z[i] = columns[..split].iter().zip(values).fold(r[i], |sum, (&column, &value)| sum - value * z[column]);
+        }
+        // U z = y
+        for i in (0..size).rev() {
+            let (columns, values) = self.factors.row(i);
+            let split = columns.partition_point(|&column| column < i);
+            let sum = columns[split + 1..].iter().zip(&values[split + 1..])
+                .fold(z[i], |sum, (&column, &value)| sum - value * z[column]);
+            z[i] = sum / values[split];
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn exact_on_tridiagonal() {
+        // ILU(0) of a tridiagonal matrix is its complete LU factorization
+        let mut entries = Vec::new();
+        for i in 0..5 {
+            entries.push((i, i, 4.0));
+            if i > 0 {
+                entries.push((i, i - 1, -1.0));
+                entries.push((i - 1, i, -2.0));
+            }
+        }
+        let a = Sparse::from_triplets(5, &entries).unwrap();
+        let x = [1.0, -2.0, 3.0, 0.5, 2.0];
+        let b = a.apply(&x).unwrap();
+        let mut z = [0.0; 5];
+        Ilu0::new(&a).unwrap().apply(&b, &mut z);
+        for (value, expected) in z.iter().zip(&x) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        Jacobi::new(&a).unwrap().apply(&b, &mut z);
+        assert_eq!(z[0], b[0] / 4.0);
+        Identity.apply(&b, &mut z);
+        assert_eq!(z.to_vec(), b);
+
+        let missing = Sparse::from_triplets(2, &[(0, 0, 1.0), (1, 0, 1.0)]).unwrap();
+        assert!(matches!(Ilu0::new(&missing), Err(IterativeError::ZeroPivot(1))));
+        assert!(matches!(Jacobi::new(&missing), Err(IterativeError::ZeroPivot(1))));
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::{IterativeError, Sparse};
+use crate::solver::{Banded, ColumnPermutation};
+
+// Reverse Cuthill–McKee ordering: a breadth-first search from a node at the
+// rim of the graph of A, visiting neighbours by increasing degree, then
+// reversed. Numbering neighbours close together keeps the entries near
+// the diagonal, so banded elimination and ILU fill in far less.
+
+impl<T: Real> Sparse<T> {
+    // Neighbours of every unknown in the symmetrized pattern of A
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut neighbours = vec![Vec::new(); self.size()];
+        for row in 0..self.size() {
+            for &column in self.row(row).0 {
+                if column != row {
+                    neighbours[row].push(column);
+                    neighbours[column].push(row);
+                }
+            }
+        }
+        for list in &mut neighbours {
+            list.sort_unstable();
+            list.dedup();
+        }
+        neighbours
+    }
+
+    // Unknown i of the reordered system is unknown order[i] of this one
+    pub fn reverse_cuthill_mckee(&self) -> ColumnPermutation {
+        let neighbours = self.adjacency();
+        let degree = |node: usize| neighbours[node].len();
+        let mut visited = vec![false; self.size()];
+        let mut order = Vec::with_capacity(self.size());
+        // Each connected component is numbered on its own, starting from
+        // the unvisited node of lowest degree moved out to the rim
+        while let Some(start) = (0..self.size()).filter(|&node| !visited[node]).min_by_key(|&node| degree(node)) {
+            let start = peripheral(&neighbours, start);
+            let first = order.len();
+            visited[start] = true;
+            order.push(start);
+            let mut next = first;
+            while next < order.len() {
+                let node = order[next];
+                next += 1;
+                let mut unvisited: Vec<usize> = neighbours[node].iter().copied().filter(|&other| !visited[other]).collect();
+                unvisited.sort_by_key(|&other| degree(other));
+                for other in unvisited {
+                    visited[other] = true;
+                    order.push(other);
+                }
+            }
+        }
+        order.reverse();
+        ColumnPermutation::new(order).expect("every unknown numbered once")
+    }
+
+    // P A Pᵀ, i.e. the rows and columns reordered alike so that entry
+    // (i, j) of the result is entry (order[i], order[j]) of this matrix
+    pub fn permute(&self, permutation: &ColumnPermutation) -> Result<Sparse<T>, IterativeError> {
+        let order = permutation.order();
+        if order.len() != self.size() {
+            return Err(IterativeError::UnfittingDimension(order.len(), self.size()));
+        }
+        let position = permutation.inverse();
+        let position = position.order();
+        let mut entries = Vec::with_capacity(self.nonzeros());
+        for row in 0..self.size() {
+            let (columns, values) = self.row(row);
+            for (&column, &value) in columns.iter().zip(values) {
+                entries.push((position[row], position[column], value));
+            }
+        }
+        Sparse::from_triplets(self.size(), &entries)
+    }
+
+    // Number of diagonals below and above the main diagonal holding
+    // entries
+    pub fn bandwidths(&self) -> (usize, usize) {
+        (0..self.size()).fold((0, 0), |(lower, upper), row| {
+            let columns = self.row(row).0;
+            match (columns.first(), columns.last()) {
+                (Some(&first), Some(&last)) => (lower.max(row.saturating_sub(first)), upper.max(last.saturating_sub(row))),
+                _ => (lower, upper),
+            }
+        })
+    }
+
+    pub fn to_banded(&self) -> Banded<T> {
+        let (lower, upper) = self.bandwidths();
+        let mut banded = Banded::zeros(self.size(), lower, upper);
+        for row in 0..self.size() {
+            let (columns, values) = self.row(row);
+            for (&column, &value) in columns.iter().zip(values) {
+                banded.set(row, column, value).expect("entry inside the bandwidths");
+            }
+        }
+        banded
+    }
+
+    // Solve A x = b directly by banded elimination after reordering with
+    // reverse Cuthill–McKee. The solution is in the original order.
+    pub fn solve_banded(&self, b: &[T]) -> Result<Vec<T>, IterativeError> {
+        if b.len() != self.size() {
+            return Err(IterativeError::UnfittingDimension(b.len(), self.size()));
+        }
+        let permutation = self.reverse_cuthill_mckee();
+        let reordered = self.permute(&permutation)?;
+        let rhs: Vec<T> = permutation.order().iter().map(|&i| b[i]).collect();
+        let solution = reordered.to_banded().solve(&rhs)?;
+        Ok(permutation.unpermute(&solution))
+    }
+}
+
+// Node at the far end of a breadth-first search from `start`, repeated
+// while that increases the number of levels (George and Liu)
+fn peripheral(neighbours: &[Vec<usize>], mut start: usize) -> usize {
+    let mut depth = 0;
+    loop {
+        let (levels, last) = levels(neighbours, start);
+        if levels <= depth {
+            return start;
+        }
+        depth = levels;
+        let next = last.into_iter().min_by_key(|&node| neighbours[node].len()).unwrap_or(start);
+        if next == start {
+            return start;
+        }
+        start = next;
+    }
+}
+
+// Number of levels of a breadth-first search and the nodes of the last one
+fn levels(neighbours: &[Vec<usize>], start: usize) -> (usize, Vec<usize>) {
+    let mut level = vec![usize::MAX; neighbours.len()];
+    level[start] = 0;
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    let mut deepest = 0;
+    while let Some(node) = queue.pop_front() {
+        deepest = level[node];
+        for &other in &neighbours[node] {
+            if level[other] == usize::MAX {
+                level[other] = level[node] + 1;
+                queue.push_back(other);
+            }
+        }
+    }
+    let last = (0..neighbours.len()).filter(|&node| level[node] == deepest).collect();
+    (deepest + 1, last)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn narrow_band() {
+        // A path 0 - 1 - … - 19 with its nodes numbered randomly, plus a
+        // separate pair, has bandwidth one after reordering
+        let numbering: Vec<usize> = (0..20).map(|i| (i * 7 + 3) % 20).collect();
+        let mut entries = Vec::new();
+        for i in 0..20 {
+            entries.push((numbering[i], numbering[i], 4.0));
+            if i > 0 {
+                entries.push((numbering[i], numbering[i - 1], -1.0));
+                entries.push((numbering[i - 1], numbering[i], -2.0));
+            }
+        }
+        entries.extend_from_slice(&[(20, 20, 2.0), (21, 21, 3.0), (20, 21, 1.0), (21, 20, 1.0)]);
+        let a = Sparse::from_triplets(22, &entries).unwrap();
+        assert!(a.bandwidths().0 > 10);
+
+        let permutation = a.reverse_cuthill_mckee();
+        let reordered = a.permute(&permutation).unwrap();
+        assert_eq!(reordered.bandwidths(), (1, 1));
+        assert_eq!(reordered.nonzeros(), a.nonzeros());
+
+        let x: Vec<f64> = (0..22).map(|i| i as f64 - 5.0).collect();
+        let b = a.apply(&x).unwrap();
+        for (value, expected) in a.solve_banded(&b).unwrap().iter().zip(&x) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        let singular = Sparse::from_triplets(2, &[(0, 0, 1.0), (0, 1, 1.0)]).unwrap();
+        assert!(matches!(singular.solve_banded(&[1.0, 1.0]), Err(IterativeError::SolveError(SolveError::DependentSolutionSet))));
+    }
+}
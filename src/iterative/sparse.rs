@@ -0,0 +1,147 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::IterativeError;
+use crate::solver::Matrix;
+
+// Square matrix in compressed sparse row form: the column indices and
+// values of row i are at `offsets[i]..offsets[i + 1]`, sorted by column.
+// Products cost O(nonzeros) instead of O(n²), which is what makes the
+// iterative solvers pay off on large, mostly empty systems.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sparse<T> {
+    size: usize,
+    offsets: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Real> Sparse<T> {
+    // Matrix from (row, column, value) entries in any order. Duplicate
+    // entries are summed, as in the Matrix Market coordinate format.
+    pub fn from_triplets(size: usize, entries: &[(usize, usize, T)]) -> Result<Self, IterativeError> {
+        if let Some(&(row, column, _)) = entries.iter().find(|&&(row, column, _)| row >= size || column >= size) {
+            return Err(IterativeError::OutOfBounds(row, column));
+        }
+        let mut sorted = entries.to_vec();
+        sorted.sort_by_key(|&(row, column, _)| (row, column));
+
+        let mut offsets = vec![0; size + 1];
+        let mut columns = Vec::with_capacity(sorted.len());
+        let mut values: Vec<T> = Vec::with_capacity(sorted.len());
+        let mut last = None;
+        for (row, column, value) in sorted {
+            if last == Some((row, column)) {
+                let sum = values.last_mut().unwrap();
+                *sum = *sum + value;
+                continue;
+            }
+            last = Some((row, column));
+            offsets[row + 1] += 1;
+            columns.push(column);
+            values.push(value);
+        }
+        for row in 0..size {
+            offsets[row + 1] += offsets[row];
+        }
+        Ok(Sparse { size, offsets, columns, values })
+    }
+
+    // The nonzero entries of a square dense matrix
+    pub fn from_dense(matrix: &Matrix<T>) -> Result<Self, IterativeError> {
+        let size = matrix.rows();
+        if matrix.columns() != size {
+            return Err(IterativeError::UnfittingDimension(matrix.columns(), size));
+        }
+        let mut entries = Vec::new();
+        for row in 0..size {
+            for (column, &value) in matrix.row(row).unwrap().iter().enumerate() {
+                if value != T::zero() {
+                    entries.push((row, column, value));
+                }
+            }
+        }
+        Sparse::from_triplets(size, &entries)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Number of stored entries
+    pub fn nonzeros(&self) -> usize {
+        self.values.len()
+    }
+
+    // The entry at (row, column), zero if it isn't stored
+    pub fn get(&self, row: usize, column: usize) -> Option<T> {
+        if row >= self.size || column >= self.size {
+            return None;
+        }
+        let (columns, values) = self.row(row);
+        Some(columns.binary_search(&column).map_or(T::zero(), |position| values[position]))
+    }
+
+    // Column indices and values of the stored entries of a row
+    pub fn row(&self, row: usize) -> (&[usize], &[T]) {
+        let range = self.offsets[row]..self.offsets[row + 1];
+        (&self.columns[range.clone()], &self.values[range])
+    }
+
+    // A x
+    pub fn apply(&self, x: &[T]) -> Result<Vec<T>, IterativeError> {
+        let mut y = vec![T::zero(); self.size];
+        self.apply_into(x, &mut y)?;
+        Ok(y)
+    }
+
+    // y ← A x without allocating
+    pub fn apply_into(&self, x: &[T], y: &mut [T]) -> Result<(), IterativeError> {
+        if x.len() != self.size {
+            return Err(IterativeError::UnfittingDimension(x.len(), self.size));
+        }
+        for (row, target) in y.iter_mut().enumerate().take(self.size) {
+            let (columns, values) = self.row(row);
+            *target = columns.iter().zip(values).fold(T::zero(), |sum, (&column, &value)| sum + value * x[column]);
+        }
+        Ok(())
+    }
+
+    // Position of the diagonal entry of each row in `values`
+    pub(super) fn diagonal_positions(&self) -> Result<Vec<usize>, IterativeError> {
+        (0..self.size)
+            .map(|row| {
+                let (columns, _) = self.row(row);
+                columns.binary_search(&row)
+                    .map(|position| self.offsets[row] + position)
+                    .map_err(|_| IterativeError::ZeroPivot(row))
+            })
+            .collect()
+    }
+
+    pub(super) fn parts_mut(&mut self) -> (&[usize], &[usize], &mut [T]) {
+        (&self.offsets, &self.columns, &mut self.values)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::solver::Matrix;
+
+    #[test]
+    fn build_and_apply() {
+        let a = Sparse::from_triplets(3, &[(2, 0, 1.0), (0, 0, 4.0), (1, 1, 3.0), (0, 2, 1.0), (2, 0, 1.0)]).unwrap();
+        assert_eq!(a.nonzeros(), 4);
+        assert_eq!(a.get(2, 0), Some(2.0));
+        assert_eq!(a.get(1, 0), Some(0.0));
+        assert_eq!(a.get(3, 0), None);
+        assert_eq!(a.apply(&[1.0, 2.0, 3.0]).unwrap(), vec![7.0, 6.0, 2.0]);
+        assert!(matches!(a.apply(&[1.0]), Err(IterativeError::UnfittingDimension(1, 3))));
+        assert!(matches!(Sparse::from_triplets(2, &[(0, 2, 1.0)]), Err(IterativeError::OutOfBounds(0, 2))));
+
+        let dense = Matrix::from_rows(vec![vec![4.0, 0.0, 1.0], vec![0.0, 3.0, 0.0], vec![2.0, 0.0, 0.0]]).unwrap();
+        assert_eq!(Sparse::from_dense(&dense).unwrap(), a);
+    }
+}
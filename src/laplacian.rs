@@ -0,0 +1,228 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use crate::iterative::{IterativeError, Sparse};
+
+// Laplace's equation on a weighted graph: every free node holds the
+// weighted mean of its neighbours plus its source term, pinned nodes keep
+// their value:
+//
+//     let values = Laplacian::new(4)
+//         .edge(0, 1)
+//         .edge(1, 2)
+//         .edge(2, 3)
+//         .pin(0, 0.0)
+//         .pin(3, 3.0)
+//         .solve()?;
+//     // values == [0.0, 1.0, 2.0, 3.0]
+//
+// With pinned boundary values this interpolates harmonically between them
+// or smooths a mesh one coordinate at a time. With conductances as weights,
+// pinned potentials and injected currents as sources it solves resistor
+// networks.
+
+#[derive(Debug)]
+pub enum LaplacianError {
+    // An edge, pin or source refers to this node, which doesn't exist
+    NodeOutOfRange(usize),
+    // The edge at this index has no positive, finite weight
+    Weight(usize),
+    // This node is in a part of the graph without pinned nodes, so its
+    // value isn't determined
+    Unpinned(usize),
+    SolveError(IterativeError),
+}
+
+impl From<IterativeError> for LaplacianError {
+    fn from(err: IterativeError) -> Self {
+        LaplacianError::SolveError(err)
+    }
+}
+
+impl fmt::Display for LaplacianError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LaplacianError::NodeOutOfRange(node) => write!(f, "Node {} doesn't exist", node),
+            LaplacianError::Weight(index) => write!(f, "Edge {} needs a positive weight", index),
+            LaplacianError::Unpinned(node) => write!(f, "Node {} isn't connected to any pinned node", node),
+            LaplacianError::SolveError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LaplacianError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Laplacian<T> {
+    nodes: usize,
+    edges: Vec<(usize, usize, T)>,
+    pins: Vec<(usize, T)>,
+    sources: Vec<(usize, T)>,
+}
+
+impl<T: Real> Laplacian<T> {
+    pub fn new(nodes: usize) -> Laplacian<T> {
+        Laplacian { nodes, edges: Vec::new(), pins: Vec::new(), sources: Vec::new() }
+    }
+
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    pub fn edge(self, a: usize, b: usize) -> Self {
+        self.weighted_edge(a, b, T::one())
+    }
+
+    // Parallel edges add up their weights
+    pub fn weighted_edge(mut self, a: usize, b: usize, weight: T) -> Self {
+        self.edges.push((a, b, weight));
+        self
+    }
+
+    // Fix the value of a node, replacing an earlier pin
+    pub fn pin(mut self, node: usize, value: T) -> Self {
+        self.pins.retain(|&(pinned, _)| pinned != node);
+        self.pins.push((node, value));
+        self
+    }
+
+    // Net flow into a free node, e.g. an injected current. Sources at
+    // pinned nodes have no effect.
+    pub fn source(mut self, node: usize, amount: T) -> Self {
+        self.sources.push((node, amount));
+        self
+    }
+
+    fn check(&self) -> Result<(), LaplacianError> {
+        let nodes = self.edges.iter().flat_map(|&(a, b, _)| [a, b])
+            .chain(self.pins.iter().map(|&(node, _)| node))
+            .chain(self.sources.iter().map(|&(node, _)| node));
+        for node in nodes {
+            if node >= self.nodes {
+                return Err(LaplacianError::NodeOutOfRange(node));
+            }
+        }
+        match self.edges.iter().position(|&(_, _, weight)| !(weight > T::zero() && weight <= T::max_value())) {
+            Some(index) => Err(LaplacianError::Weight(index)),
+            None => Ok(()),
+        }
+    }
+
+    // The weighted Laplacian L = D - W of the whole graph, where W holds the
+    // edge weights and D their sums per node
+    pub fn matrix(&self) -> Result<Sparse<T>, LaplacianError> {
+        self.check()?;
+        let mut entries = Vec::with_capacity(4 * self.edges.len());
+        for &(a, b, weight) in self.edges.iter().filter(|&&(a, b, _)| a != b) {
+            entries.extend_from_slice(&[(a, a, weight), (b, b, weight), (a, b, -weight), (b, a, -weight)]);
+        }
+        Ok(Sparse::from_triplets(self.nodes, &entries)?)
+    }
+
+    // Value of every node, pinned or not
+    pub fn solve(&self) -> Result<Vec<T>, LaplacianError> {
+        self.check()?;
+        let mut values = vec![T::zero(); self.nodes];
+        let mut pinned = vec![false; self.nodes];
+        for &(node, value) in &self.pins {
+            values[node] = value;
+            pinned[node] = true;
+        }
+        self.check_pinned(&pinned)?;
+
+        // Number the free nodes and move the pinned values to the right
+        // hand side: L_ff x_f = s_f - L_fp x_p
+        let mut index = vec![None; self.nodes];
+        let free: Vec<usize> = (0..self.nodes).filter(|&node| !pinned[node]).collect();
+        for (i, &node) in free.iter().enumerate() {
+            index[node] = Some(i);
+        }
+        let mut rhs = vec![T::zero(); free.len()];
+        for &(node, amount) in &self.sources {
+            if let Some(i) = index[node] {
+                rhs[i] = rhs[i] + amount;
+            }
+        }
+        let mut entries = Vec::new();
+        for &(a, b, weight) in self.edges.iter().filter(|&&(a, b, _)| a != b) {
+            for &(from, to) in &[(a, b), (b, a)] {
+                if let Some(i) = index[from] {
+                    entries.push((i, i, weight));
+                    match index[to] {
+                        Some(j) => entries.push((i, j, -weight)),
+                        None => rhs[i] = rhs[i] + weight * values[to],
+                    }
+                }
+            }
+        }
+        if free.is_empty() {
+            return Ok(values);
+        }
+        let solution = Sparse::from_triplets(free.len(), &entries)?.solve_banded(&rhs)?;
+        for (&node, value) in free.iter().zip(solution) {
+            values[node] = value;
+        }
+        Ok(values)
+    }
+
+    // Every free node needs a path to a pinned one, or the reduced system
+    // is singular
+    fn check_pinned(&self, pinned: &[bool]) -> Result<(), LaplacianError> {
+        let mut neighbours = vec![Vec::new(); self.nodes];
+        for &(a, b, _) in &self.edges {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+        let mut reached = pinned.to_vec();
+        let mut stack: Vec<usize> = (0..self.nodes).filter(|&node| pinned[node]).collect();
+        while let Some(node) = stack.pop() {
+            for &other in &neighbours[node] {
+                if !reached[other] {
+                    reached[other] = true;
+                    stack.push(other);
+                }
+            }
+        }
+        match reached.iter().position(|&reached| !reached) {
+            Some(node) => Err(LaplacianError::Unpinned(node)),
+            None => Ok(()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, b) in actual.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn interpolate_and_networks() {
+        let path = Laplacian::new(4).edge(0, 1).edge(1, 2).edge(2, 3).pin(0, 0.0).pin(3, 3.0);
+        assert_close(&path.solve().unwrap(), &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(path.matrix().unwrap().get(1, 1), Some(2.0));
+
+        // Resistor network with conductances as weights: 1 Ω and 3 Ω in
+        // series divide 4 V into 1 V and 3 V, and 2 A into a 0.5 Ω
+        // resistor to ground give 1 V
+        let divider = Laplacian::new(3).weighted_edge(0, 1, 1.0).weighted_edge(1, 2, 1.0 / 3.0).pin(0, 4.0).pin(2, 0.0);
+        assert_close(&divider.solve().unwrap(), &[4.0, 3.0, 0.0]);
+        let injected = Laplacian::new(2).weighted_edge(0, 1, 2.0).pin(0, 0.0).source(1, 2.0);
+        assert_close(&injected.solve().unwrap(), &[0.0, 1.0]);
+
+        let floating = Laplacian::new(4).edge(0, 1).edge(2, 3).pin(0, 1.0);
+        assert!(matches!(floating.solve(), Err(LaplacianError::Unpinned(2))));
+        assert!(matches!(Laplacian::<f64>::new(2).edge(0, 2).solve(), Err(LaplacianError::NodeOutOfRange(2))));
+        assert!(matches!(Laplacian::new(2).weighted_edge(0, 1, -1.0).pin(0, 0.0).solve(), Err(LaplacianError::Weight(0))));
+        // Edges are counted from 0 like the nodes
+        assert_eq!(LaplacianError::Weight(0).to_string(), "Edge 0 needs a positive weight");
+    }
+}
@@ -0,0 +1,319 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use crate::solver::Matrix;
+
+// Linear programs over non-negative variables, solved by the two-phase
+// simplex method on a tableau:
+//
+//     let solution = Problem::maximize(vec![3.0, 5.0])
+//         .constraint(vec![1.0, 0.0], Relation::LessEqual, 4.0)
+//         .constraint(vec![3.0, 2.0], Relation::LessEqual, 18.0)
+//         .solve()?;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    LessEqual,
+    GreaterEqual,
+    Equal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LpError {
+    // A constraint has a different number of coefficients than the objective
+    UnfittingCoefficientAmount(usize, usize),
+    Infeasible,
+    Unbounded,
+}
+
+impl fmt::Display for LpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LpError::UnfittingCoefficientAmount(amount, variables) =>
+                write!(f, "Amount {} of coefficients does not fit {} variables", amount, variables),
+            LpError::Infeasible => write!(f, "The constraints can't be satisfied"),
+            LpError::Unbounded => write!(f, "The objective is unbounded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LpError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem<T> {
+    objective: Vec<T>,
+    maximize: bool,
+    constraints: Vec<(Vec<T>, Relation, T)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<T> {
+    values: Vec<T>,
+    objective: T,
+}
+
+impl<T> Solution<T> {
+    // Optimal value of each variable
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn objective(&self) -> &T {
+        &self.objective
+    }
+}
+
+impl<T: Real> Problem<T> {
+    pub fn maximize(objective: Vec<T>) -> Problem<T> {
+        Problem { objective, maximize: true, constraints: Vec::new() }
+    }
+
+    pub fn minimize(objective: Vec<T>) -> Problem<T> {
+        Problem { objective, maximize: false, constraints: Vec::new() }
+    }
+
+    pub fn constraint(mut self, coefficients: Vec<T>, relation: Relation, bound: T) -> Self {
+        self.constraints.push((coefficients, relation, bound));
+        self
+    }
+
+    pub fn solve(&self) -> Result<Solution<T>, LpError> {
+        let n = self.objective.len();
+        let m = self.constraints.len();
+        if let Some((coefficients, _, _)) = self.constraints.iter().find(|(c, _, _)| c.len() != n) {
+            return Err(LpError::UnfittingCoefficientAmount(coefficients.len(), n));
+        }
+
+        // Make every bound non-negative so that the slack or artificial
+        // variables form a feasible starting basis
+        let constraints: Vec<(Vec<T>, Relation, T)> = self.constraints.iter()
+            .map(|(coefficients, relation, bound)| {
+                if *bound >= T::zero() {
+                    return (coefficients.clone(), *relation, *bound);
+                }
+                let flipped = match relation {
+                    Relation::LessEqual => Relation::GreaterEqual,
+                    Relation::GreaterEqual => Relation::LessEqual,
+                    Relation::Equal => Relation::Equal,
+                };
+                (coefficients.iter().map(|c| -*c).collect(), flipped, -*bound)
+            })
+            .collect();
+
+        // Columns: variables, one slack per inequality, one artificial per
+        // >= or = constraint, right-hand side. Rows: constraints, the
+        // objective and the phase one objective.
+        let slacks = constraints.iter().filter(|(_, relation, _)| *relation != Relation::Equal).count();
+        let artificials = constraints.iter().filter(|(_, relation, _)| *relation != Relation::LessEqual).count();
+        let first_artificial = n + slacks;
+        let rhs = first_artificial + artificials;
+        let mut tableau = Tableau {
+            matrix: Matrix::zeros(m + 2, rhs + 1),
+            basis: vec![0; m],
+            epsilon: T::epsilon().sqrt(),
+        };
+
+        let (mut slack, mut artificial) = (n, first_artificial);
+        for (i, (coefficients, relation, bound)) in constraints.iter().enumerate() {
+            for (j, coefficient) in coefficients.iter().enumerate() {
+                tableau.matrix[(i, j)] = *coefficient;
+            }
+            tableau.matrix[(i, rhs)] = *bound;
+            match relation {
+                Relation::LessEqual => {
+                    tableau.matrix[(i, slack)] = T::one();
+                    tableau.basis[i] = slack;
+                    slack += 1;
+                },
+                Relation::GreaterEqual | Relation::Equal => {
+                    if *relation == Relation::GreaterEqual {
+                        tableau.matrix[(i, slack)] = -T::one();
+                        slack += 1;
+                    }
+                    tableau.matrix[(i, artificial)] = T::one();
+                    tableau.basis[i] = artificial;
+                    artificial += 1;
+                },
+            }
+        }
+
+        // The objective row holds -c for maximizing c x
+        for (j, coefficient) in self.objective.iter().enumerate() {
+            tableau.matrix[(m, j)] = if self.maximize { -*coefficient } else { *coefficient };
+        }
+
+        // Phase one: maximize minus the sum of the artificial variables
+        if artificials > 0 {
+            for j in first_artificial..rhs {
+                tableau.matrix[(m + 1, j)] = T::one();
+            }
+            for i in 0..m {
+                if tableau.basis[i] >= first_artificial {
+                    tableau.subtract_row(m + 1, i, T::one());
+                }
+            }
+            tableau.optimize(m + 1, rhs)?;
+            if tableau.matrix[(m + 1, rhs)] < -tableau.epsilon {
+                return Err(LpError::Infeasible);
+            }
+            // Pivot artificial variables left at zero out of the basis.
+            // Rows without another candidate are redundant.
+            for i in 0..m {
+                if tableau.basis[i] >= first_artificial {
+                    if let Some(j) = (0..first_artificial).find(|&j| tableau.matrix[(i, j)].abs() > tableau.epsilon) {
+                        tableau.pivot(i, j);
+                    }
+                }
+            }
+        }
+
+        // Phase two on the real objective, artificial columns excluded. The
+        // pivots of phase one already kept its row in terms of the basis.
+        tableau.optimize(m, first_artificial)?;
+
+        let mut values = vec![T::zero(); n];
+        for (i, &basic) in tableau.basis.iter().enumerate() {
+            if basic < n {
+                values[basic] = tableau.matrix[(i, rhs)];
+            }
+        }
+        let objective = values.iter()
+            .zip(&self.objective)
+            .fold(T::zero(), |sum, (value, coefficient)| sum + *value * *coefficient);
+        Ok(Solution { values, objective })
+    }
+}
+
+struct Tableau<T> {
+    matrix: Matrix<T>,
+    basis: Vec<usize>,
+    epsilon: T,
+}
+
+impl<T: Real> Tableau<T> {
+    fn constraints(&self) -> usize {
+        self.basis.len()
+    }
+
+    // row[target] -= factor * row[source]
+    fn subtract_row(&mut self, target: usize, source: usize, factor: T) {
+        for j in 0..self.matrix.columns() {
+            let value = self.matrix[(source, j)];
+            self.matrix[(target, j)] = self.matrix[(target, j)] - factor * value;
+        }
+    }
+
+    // Make column `column` basic in constraint row `row`
+    fn pivot(&mut self, row: usize, column: usize) {
+        let pivot = self.matrix[(row, column)];
+        for j in 0..self.matrix.columns() {
+            self.matrix[(row, j)] = self.matrix[(row, j)] / pivot;
+        }
+        for i in 0..self.matrix.rows() {
+            let factor = self.matrix[(i, column)];
+            if i != row && !factor.is_zero() {
+                self.subtract_row(i, row, factor);
+            }
+        }
+        self.basis[row] = column;
+    }
+
+    // Maximize along the objective in row `objective`, letting only the
+    // first `columns` columns enter the basis. Bland's rule of choosing the
+    // lowest eligible index prevents cycling.
+    fn optimize(&mut self, objective: usize, columns: usize) -> Result<(), LpError> {
+        let rhs = self.matrix.columns() - 1;
+        while let Some(entering) = (0..columns).find(|&j| self.matrix[(objective, j)] < -self.epsilon) {
+            let mut leaving: Option<(usize, T)> = None;
+            for i in 0..self.constraints() {
+                let coefficient = self.matrix[(i, entering)];
+                if coefficient <= self.epsilon {
+                    continue;
+                }
+                let ratio = self.matrix[(i, rhs)] / coefficient;
+                let better = match leaving {
+                    None => true,
+                    Some((best, best_ratio)) => ratio < best_ratio
+                        || (ratio == best_ratio && self.basis[i] < self.basis[best]),
+                };
+                if better {
+                    leaving = Some((i, ratio));
+                }
+            }
+            match leaving {
+                Some((row, _)) => self.pivot(row, entering),
+                None => return Err(LpError::Unbounded),
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(solution: &Solution<f64>, values: &[f64], objective: f64) {
+        for (actual, expected) in solution.values().iter().zip(values) {
+            assert!((actual - expected).abs() < 1e-9, "{:?}", solution);
+        }
+        assert!((solution.objective() - objective).abs() < 1e-9, "{:?}", solution);
+    }
+
+    #[test]
+    fn maximize_with_inequalities() {
+        let solution = Problem::maximize(vec![3.0, 5.0])
+            .constraint(vec![1.0, 0.0], Relation::LessEqual, 4.0)
+            .constraint(vec![0.0, 2.0], Relation::LessEqual, 12.0)
+            .constraint(vec![3.0, 2.0], Relation::LessEqual, 18.0)
+            .solve().unwrap();
+        assert_close(&solution, &[2.0, 6.0], 36.0);
+    }
+
+    #[test]
+    fn minimize_with_lower_bounds() {
+        let solution = Problem::minimize(vec![1.0, 1.0])
+            .constraint(vec![1.0, 2.0], Relation::GreaterEqual, 4.0)
+            .constraint(vec![3.0, 1.0], Relation::GreaterEqual, 6.0)
+            .solve().unwrap();
+        assert_close(&solution, &[1.6, 1.2], 2.8);
+
+        // A negative bound flips the relation: -x <= -2 is x >= 2
+        let solution = Problem::minimize(vec![1.0])
+            .constraint(vec![-1.0], Relation::LessEqual, -2.0)
+            .solve().unwrap();
+        assert_close(&solution, &[2.0], 2.0);
+    }
+
+    #[test]
+    fn equality_constraints() {
+        let solution = Problem::maximize(vec![1.0, 2.0])
+            .constraint(vec![1.0, 1.0], Relation::Equal, 3.0)
+            .constraint(vec![0.0, 1.0], Relation::LessEqual, 2.0)
+            .constraint(vec![2.0, 2.0], Relation::Equal, 6.0)
+            .solve().unwrap();
+        assert_close(&solution, &[1.0, 2.0], 5.0);
+    }
+
+    #[test]
+    fn report_failures() {
+        let infeasible = Problem::maximize(vec![1.0])
+            .constraint(vec![1.0], Relation::LessEqual, 1.0)
+            .constraint(vec![1.0], Relation::GreaterEqual, 2.0)
+            .solve();
+        assert_eq!(infeasible, Err(LpError::Infeasible));
+
+        let unbounded = Problem::maximize(vec![1.0, 0.0])
+            .constraint(vec![1.0, -1.0], Relation::LessEqual, 1.0)
+            .solve();
+        assert_eq!(unbounded, Err(LpError::Unbounded));
+
+        let unfitting = Problem::maximize(vec![1.0, 0.0])
+            .constraint(vec![1.0], Relation::LessEqual, 1.0)
+            .solve();
+        assert_eq!(unfitting, Err(LpError::UnfittingCoefficientAmount(1, 2)));
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, Matrix, SolveError};
+
+// Stationary distributions of finite Markov chains. Row i of the transition
+// matrix holds the probabilities of moving from state i to each state, so
+// the stationary distribution π solves π P = π with Σ πᵢ = 1:
+//
+//     let p = Matrix::from_rows(vec![vec![0.9, 0.1], vec![0.5, 0.5]])?;
+//     let pi = steady_state(&p)?; // [5/6, 1/6]
+
+#[derive(Debug)]
+pub enum MarkovError {
+    SolveError(SolveError),
+    // The transition matrix has this many rows and columns
+    NotSquare(usize, usize),
+    // The probability at this row and column is negative
+    NegativeProbability(usize, usize),
+    // The probabilities of this row don't sum to one
+    RowSum(usize),
+}
+
+impl From<SolveError> for MarkovError {
+    fn from(err: SolveError) -> Self {
+        MarkovError::SolveError(err)
+    }
+}
+
+impl fmt::Display for MarkovError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarkovError::SolveError(SolveError::DependentSolutionSet) =>
+                write!(f, "The chain has more than one stationary distribution"),
+            MarkovError::SolveError(err) => write!(f, "{}", err),
+            MarkovError::NotSquare(rows, columns) =>
+                write!(f, "Transition matrix has {} rows but {} columns", rows, columns),
+            MarkovError::NegativeProbability(row, column) =>
+                write!(f, "Negative probability in row {}, column {}", row + 1, column + 1),
+            MarkovError::RowSum(row) => write!(f, "Probabilities of row {} don't sum to 1", row + 1),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MarkovError {}
+
+// Check that `p` is a square, row-stochastic matrix. Row sums may be off by
+// √ε, enough for probabilities typed as rounded decimals.
+fn check_stochastic<T: Real>(p: &Matrix<T>) -> Result<(), MarkovError> {
+    if p.rows() != p.columns() {
+        return Err(MarkovError::NotSquare(p.rows(), p.columns()));
+    }
+    if p.rows() == 0 {
+        return Err(SolveError::TooSmall(0).into());
+    }
+    let tolerance = T::epsilon().sqrt();
+    for i in 0..p.rows() {
+        if let Some(j) = (0..p.columns()).find(|&j| p[(i, j)] < T::zero()) {
+            return Err(MarkovError::NegativeProbability(i, j));
+        }
+        let sum = (0..p.columns()).fold(T::zero(), |sum, j| sum + p[(i, j)]);
+        if (sum - T::one()).abs() > tolerance {
+            return Err(MarkovError::RowSum(i));
+        }
+    }
+    Ok(())
+}
+
+// Stationary distribution of the chain with transition matrix `p`. The
+// equations (Pᵀ - I) π = 0 are linearly dependent, so the last one is
+// replaced by the normalization Σ πᵢ = 1. Chains with several closed
+// classes have no unique stationary distribution and fail with
+// `DependentSolutionSet`.
+pub fn steady_state<T>(p: &Matrix<T>) -> Result<Vec<T>, MarkovError>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    check_stochastic(p)?;
+    let n = p.rows();
+    let rows: Vec<Vec<T>> = (0..n)
+        .map(|i| {
+            if i + 1 == n {
+                return vec![T::one(); n + 1];
+            }
+            let mut row: Vec<T> = (0..n).map(|j| p[(j, i)]).collect();
+            row[i] -= T::one();
+            row.push(T::zero());
+            row
+        })
+        .collect();
+    let solved = CoefficientMatrix::try_from(rows)?.validate()?.convert()?.solve()?;
+    Ok(solved.solution().collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stationary_distribution() {
+        let p = Matrix::from_rows(vec![vec![0.9, 0.1], vec![0.5, 0.5]]).unwrap();
+        let pi = steady_state(&p).unwrap();
+        assert!((pi[0] - 5.0 / 6.0).abs() < 1e-12 && (pi[1] - 1.0 / 6.0).abs() < 1e-12);
+
+        let p = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 0.0],
+            vec![0.5, 0.0, 0.5],
+            vec![0.0, 1.0, 0.0],
+        ]).unwrap();
+        let pi = steady_state(&p).unwrap();
+        for (actual, expected) in pi.iter().zip(&[0.25, 0.5, 0.25]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_chains() {
+        let not_square = Matrix::from_rows(vec![vec![0.5, 0.5]]).unwrap();
+        assert!(matches!(steady_state(&not_square), Err(MarkovError::NotSquare(1, 2))));
+        let negative = Matrix::from_rows(vec![vec![1.5, -0.5], vec![0.5, 0.5]]).unwrap();
+        assert!(matches!(steady_state(&negative), Err(MarkovError::NegativeProbability(0, 1))));
+        let sum = Matrix::from_rows(vec![vec![0.5, 0.4], vec![0.5, 0.5]]).unwrap();
+        assert!(matches!(steady_state(&sum), Err(MarkovError::RowSum(0))));
+
+        // Two absorbing states
+        let reducible = Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        assert!(matches!(
+            steady_state(&reducible),
+            Err(MarkovError::SolveError(SolveError::DependentSolutionSet))
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::difference::{DifferenceError, Differences};
+use crate::solver::{CoefficientMatrix, SolveError};
+
+mod least_squares;
+
+pub use least_squares::{LeastSquares, LevenbergMarquardt};
+
+// Newton's method for systems of nonlinear equations F(x) = 0. Every
+// iteration solves the linear system J(x) d = -F(x) for the correction d
+// with the coefficient matrix solver.
+
+#[derive(Debug)]
+pub enum NewtonError {
+    // F or the Jacobian returned the wrong number of values
+    UnfittingDimension(usize, usize),
+    // The Jacobian became singular
+    SolveError(SolveError),
+    // No convergence within the given number of iterations
+    NoConvergence(usize),
+}
+
+impl From<SolveError> for NewtonError {
+    fn from(err: SolveError) -> Self {
+        NewtonError::SolveError(err)
+    }
+}
+
+impl From<DifferenceError> for NewtonError {
+    fn from(err: DifferenceError) -> Self {
+        match err {
+            DifferenceError::UnfittingDimension(actual, expected) => NewtonError::UnfittingDimension(actual, expected),
+        }
+    }
+}
+
+impl fmt::Display for NewtonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NewtonError::UnfittingDimension(actual, expected) => {
+                write!(f, "Expected {} values but got {}", expected, actual)
+            },
+            NewtonError::SolveError(err) => write!(f, "Failed to solve for the correction: {}", err),
+            NewtonError::NoConvergence(iterations) => write!(f, "No convergence after {} iterations", iterations),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NewtonError {}
+
+// Settings of the iteration:
+//
+//     let root = Newton::new().tolerance(1e-12).solve(|x| vec![x[0] * x[0] - 2.0], vec![1.0])?;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Newton<T> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T: Real> Newton<T> {
+    pub fn new() -> Newton<T> {
+        Newton {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 50,
+        }
+    }
+
+    // Converged once the largest residual or the largest correction,
+    // relative to the size of x, falls below the tolerance
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+impl<T: Real> Default for Newton<T> {
+    fn default() -> Self {
+        Newton::new()
+    }
+}
+
+fn max_abs<T: Real>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |max, value| max.max(value.abs()))
+}
+
+impl<T> Newton<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Solve with a forward difference approximation of the Jacobian
+    pub fn solve<F>(&self, f: F, initial: Vec<T>) -> Result<Vec<T>, NewtonError>
+        where F: Fn(&[T]) -> Vec<T> {
+        let differences = Differences::forward();
+        let jacobian = |x: &[T], fx: &[T]| Ok(differences.jacobian_with(&f, x, fx)?);
+        self.iterate(&f, jacobian, initial)
+    }
+
+    // Solve with a Jacobian given as one row of partial derivatives per
+    // equation
+    pub fn solve_with_jacobian<F, J>(&self, f: F, jacobian: J, initial: Vec<T>) -> Result<Vec<T>, NewtonError>
+        where F: Fn(&[T]) -> Vec<T>, J: Fn(&[T]) -> Vec<Vec<T>> {
+        self.iterate(&f, |x: &[T], _: &[T]| Ok(jacobian(x)), initial)
+    }
+
+    fn iterate<F, J>(&self, f: &F, jacobian: J, mut x: Vec<T>) -> Result<Vec<T>, NewtonError>
+        where F: Fn(&[T]) -> Vec<T>, J: Fn(&[T], &[T]) -> Result<Vec<Vec<T>>, NewtonError> {
+        let n = x.len();
+        for _ in 0..self.max_iterations {
+            let fx = f(&x);
+            if fx.len() != n {
+                return Err(NewtonError::UnfittingDimension(fx.len(), n));
+            }
+            if max_abs(&fx) <= self.tolerance {
+                return Ok(x);
+            }
+
+            let mut rows = jacobian(&x, &fx)?;
+            if rows.len() != n {
+                return Err(NewtonError::UnfittingDimension(rows.len(), n));
+            }
+            for (row, value) in rows.iter_mut().zip(&fx) {
+                if row.len() != n {
+                    return Err(NewtonError::UnfittingDimension(row.len(), n));
+                }
+                row.push(-*value);
+            }
+            let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+
+            let correction: Vec<T> = solved.solution().collect();
+            for (value, delta) in x.iter_mut().zip(&correction) {
+                *value = *value + *delta;
+            }
+            if max_abs(&correction) <= self.tolerance * (T::one() + max_abs(&x)) {
+                return Ok(x);
+            }
+        }
+        Err(NewtonError::NoConvergence(self.max_iterations))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Circle of radius 2 intersected with the line y = x
+    fn circle(x: &[f64]) -> Vec<f64> {
+        vec![x[0] * x[0] + x[1] * x[1] - 4.0, x[0] - x[1]]
+    }
+
+    #[test]
+    fn numeric_jacobian() {
+        let root = Newton::new().solve(circle, vec![1.0, 0.5]).unwrap();
+        let expected = 2.0f64.sqrt();
+        assert!((root[0] - expected).abs() < 1e-10);
+        assert!((root[1] - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn supplied_jacobian() {
+        let jacobian = |x: &[f64]| vec![vec![2.0 * x[0], 2.0 * x[1]], vec![1.0, -1.0]];
+        let root = Newton::new().solve_with_jacobian(circle, jacobian, vec![-1.0, -3.0]).unwrap();
+        let expected = -(2.0f64.sqrt());
+        assert!((root[0] - expected).abs() < 1e-10);
+        assert!((root[1] - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn report_failures() {
+        // x^2 + 1 has no real root
+        let result = Newton::new().max_iterations(20).solve(|x: &[f64]| vec![x[0] * x[0] + 1.0], vec![0.5]);
+        assert!(matches!(result, Err(NewtonError::NoConvergence(20)) | Err(NewtonError::SolveError(_))));
+
+        // Singular Jacobian at the start
+        let result = Newton::new().solve(circle, vec![0.0, 0.0]);
+        assert!(matches!(result, Err(NewtonError::SolveError(_))));
+
+        let result = Newton::new().solve(|_: &[f64]| vec![1.0], vec![0.0, 0.0]);
+        assert!(matches!(result, Err(NewtonError::UnfittingDimension(1, 2))));
+    }
+}
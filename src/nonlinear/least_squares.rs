@@ -0,0 +1,226 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::difference::Differences;
+use crate::solver::CoefficientMatrix;
+use super::{max_abs, NewtonError};
+
+// Levenberg–Marquardt for nonlinear least squares, minimizing the sum of
+// squared residuals r(p) over the parameters p. Each iteration solves the
+// damped normal equations (JᵀJ + λ diag JᵀJ) d = -Jᵀr, which move like
+// Gauss–Newton for small λ and like gradient descent for large λ. λ shrinks
+// after steps that lower the sum and grows after those that don't.
+//
+//     // y = a exp(bx)
+//     let fit = LevenbergMarquardt::new().fit(|x, p| p[0] * (p[1] * x).exp(), &points, vec![1.0, 0.1])?;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevenbergMarquardt<T> {
+    tolerance: T,
+    max_iterations: usize,
+    damping: T,
+}
+
+// Parameters at the minimum together with its residuals
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeastSquares<T> {
+    parameters: Vec<T>,
+    residuals: Vec<T>,
+    sum_of_squares: T,
+    iterations: usize,
+}
+
+impl<T: Copy> LeastSquares<T> {
+    pub fn parameters(&self) -> &[T] {
+        &self.parameters
+    }
+
+    pub fn residuals(&self) -> &[T] {
+        &self.residuals
+    }
+
+    pub fn sum_of_squares(&self) -> T {
+        self.sum_of_squares
+    }
+
+    // Including the rejected steps
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+impl<T: Real> LevenbergMarquardt<T> {
+    pub fn new() -> LevenbergMarquardt<T> {
+        LevenbergMarquardt {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 200,
+            damping: T::from(1e-3).unwrap_or_else(T::epsilon),
+        }
+    }
+
+    // Converged once a step, relative to the size of the parameters, falls
+    // below the tolerance or the gradient of the sum of squares vanishes
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    // Initial λ, larger for starting points far from the minimum
+    pub fn damping(mut self, damping: T) -> Self {
+        self.damping = damping;
+        self
+    }
+}
+
+impl<T: Real> Default for LevenbergMarquardt<T> {
+    fn default() -> Self {
+        LevenbergMarquardt::new()
+    }
+}
+
+fn sum_of_squares<T: Real>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |sum, &value| sum + value * value)
+}
+
+impl<T> LevenbergMarquardt<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Fit the model y = f(x, p) to the points, starting from the initial
+    // parameters. It takes at least as many points as parameters.
+    pub fn fit<F>(&self, model: F, points: &[(T, T)], initial: Vec<T>) -> Result<LeastSquares<T>, NewtonError>
+        where F: Fn(T, &[T]) -> T {
+        if points.len() < initial.len() {
+            return Err(NewtonError::UnfittingDimension(points.len(), initial.len()));
+        }
+        self.minimize(|p: &[T]| points.iter().map(|&(x, y)| model(x, p) - y).collect(), initial)
+    }
+
+    // Minimize the sum of squares of any residuals, with forward difference
+    // approximations of their Jacobian
+    pub fn minimize<R>(&self, residuals: R, mut p: Vec<T>) -> Result<LeastSquares<T>, NewtonError>
+        where R: Fn(&[T]) -> Vec<T> {
+        let n = p.len();
+        let differences = Differences::forward();
+        let mut r = residuals(&p);
+        let mut cost = sum_of_squares(&r);
+        let mut lambda = self.damping;
+        let ten: T = T::from(10).unwrap_or_else(T::one);
+
+        let mut iterations = 0;
+        while iterations < self.max_iterations {
+            let jacobian = differences.jacobian_with(&residuals, &p, &r)?;
+            let column = |j: usize| jacobian.iter().map(move |row| row[j]);
+            let normal: Vec<Vec<T>> = (0..n)
+                .map(|i| (0..n).map(|j| column(i).zip(column(j)).fold(T::zero(), |sum, (a, b)| sum + a * b)).collect())
+                .collect();
+            let gradient: Vec<T> = (0..n)
+                .map(|i| column(i).zip(&r).fold(T::zero(), |sum, (a, &b)| sum + a * b))
+                .collect();
+            if max_abs(&gradient) <= self.tolerance * self.tolerance * (T::one() + cost) {
+                break;
+            }
+
+            // Retry with more damping until the sum of squares goes down
+            loop {
+                iterations += 1;
+                let rows: Vec<Vec<T>> = normal.iter()
+                    .zip(&gradient)
+                    .enumerate()
+                    .map(|(i, (row, &g))| {
+                        let mut row = row.clone();
+                        row[i] = row[i] + lambda * row[i];
+                        row.push(-g);
+                        row
+                    })
+                    .collect();
+                let step: Vec<T> = CoefficientMatrix::try_from(rows)?.convert()?.solve()?.solution().collect();
+                let trial: Vec<T> = p.iter().zip(&step).map(|(&a, &b)| a + b).collect();
+                let trial_residuals = residuals(&trial);
+                if trial_residuals.len() != r.len() {
+                    return Err(NewtonError::UnfittingDimension(trial_residuals.len(), r.len()));
+                }
+                let trial_cost = sum_of_squares(&trial_residuals);
+                let converged = max_abs(&step) <= self.tolerance * (T::one() + max_abs(&p));
+                if trial_cost <= cost {
+                    p = trial;
+                    r = trial_residuals;
+                    cost = trial_cost;
+                    lambda = lambda / ten;
+                    if converged {
+                        return Ok(LeastSquares { parameters: p, residuals: r, sum_of_squares: cost, iterations });
+                    }
+                    break;
+                }
+                // Damping has shrunk the step to nothing at the minimum
+                if converged {
+                    return Ok(LeastSquares { parameters: p, residuals: r, sum_of_squares: cost, iterations });
+                }
+                lambda = lambda * ten;
+                if iterations >= self.max_iterations {
+                    return Err(NewtonError::NoConvergence(self.max_iterations));
+                }
+            }
+        }
+        if iterations >= self.max_iterations {
+            return Err(NewtonError::NoConvergence(self.max_iterations));
+        }
+        Ok(LeastSquares { parameters: p, residuals: r, sum_of_squares: cost, iterations })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_exponential() {
+        // Exact samples of y = 2 exp(-0.7x)
+        let points: Vec<(f64, f64)> = (0..10).map(|i| i as f64 / 2.0).map(|x| (x, 2.0 * (-0.7 * x).exp())).collect();
+        let fit = LevenbergMarquardt::new()
+            .fit(|x, p| p[0] * (p[1] * x).exp(), &points, vec![1.0, 0.0])
+            .unwrap();
+        assert!((fit.parameters()[0] - 2.0).abs() < 1e-7);
+        assert!((fit.parameters()[1] + 0.7).abs() < 1e-7);
+        assert!(fit.sum_of_squares() < 1e-14);
+        assert_eq!(fit.residuals().len(), 10);
+    }
+
+    #[test]
+    fn fit_noisy_gaussian() {
+        // Peak of height 3 at 1.5 with width 0.8, perturbed by a fixed pattern
+        let noise = [0.01, -0.02, 0.015, 0.0, -0.01, 0.02, -0.015, 0.005, 0.01, -0.005];
+        let gaussian = |x: f64, p: &[f64]| p[0] * (-(x - p[1]).powi(2) / (2.0 * p[2] * p[2])).exp();
+        let points: Vec<(f64, f64)> = (0..30)
+            .map(|i| i as f64 / 10.0)
+            .map(|x| (x, gaussian(x, &[3.0, 1.5, 0.8]) + noise[(x * 10.0) as usize % 10]))
+            .collect();
+        let fit = LevenbergMarquardt::new().fit(gaussian, &points, vec![1.0, 1.0, 1.0]).unwrap();
+        for (a, b) in fit.parameters().iter().zip(&[3.0, 1.5, 0.8]) {
+            assert!((a - b).abs() < 0.05);
+        }
+        // Better than the generating parameters on the noisy data
+        let generating: f64 = points.iter().map(|&(x, y)| (gaussian(x, &[3.0, 1.5, 0.8]) - y).powi(2)).sum();
+        assert!(fit.sum_of_squares() <= generating);
+    }
+
+    #[test]
+    fn report_failures() {
+        let line = |x: f64, p: &[f64]| p[0] + p[1] * x;
+        assert!(matches!(
+            LevenbergMarquardt::new().fit(line, &[(0.0, 1.0)], vec![0.0, 0.0]),
+            Err(NewtonError::UnfittingDimension(1, 2))
+        ));
+        let points: Vec<(f64, f64)> = (0..20).map(|i| (i as f64, (i as f64).sin())).collect();
+        assert!(matches!(
+            LevenbergMarquardt::new().max_iterations(2).fit(|x, p| p[0] * (p[1] * x).sin(), &points, vec![0.1, 3.0]),
+            Err(NewtonError::NoConvergence(2))
+        ));
+    }
+}
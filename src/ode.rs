@@ -0,0 +1,171 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::function::{Error, Evaluate};
+use crate::solver::{CoefficientMatrix, SolveError};
+
+// Fixed-step integration of initial value problems y' = f(x, y), y(x0) = y0
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Euler,
+    RungeKutta4,
+}
+
+// Sampled solution with one x and one y value per step, including the
+// initial value
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory<T> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+}
+
+impl<T> Trajectory<T> {
+    pub fn xs(&self) -> &[T] {
+        &self.xs
+    }
+
+    pub fn ys(&self) -> &[T] {
+        &self.ys
+    }
+}
+
+// Integrate from `x0` to `end` in `steps` equal steps
+pub fn integrate<T, F>(method: Method, mut f: F, (x0, y0): (T, T), end: T, steps: usize) -> Trajectory<T>
+    where T: Real, F: FnMut(T, T) -> T {
+    let mut xs = Vec::with_capacity(steps + 1);
+    let mut ys = Vec::with_capacity(steps + 1);
+    xs.push(x0);
+    ys.push(y0);
+    if steps == 0 {
+        return Trajectory { xs, ys };
+    }
+
+    let count = T::from(steps).unwrap_or_else(T::one);
+    let h = (end - x0) / count;
+    let two = T::one() + T::one();
+    let six = two + two + two;
+    let (mut x, mut y) = (x0, y0);
+    for i in 1..=steps {
+        y = match method {
+            Method::Euler => y + h * f(x, y),
+            Method::RungeKutta4 => {
+                let k1 = f(x, y);
+                let k2 = f(x + h / two, y + h / two * k1);
+                let k3 = f(x + h / two, y + h / two * k2);
+                let k4 = f(x + h, y + h * k3);
+                y + h / six * (k1 + two * k2 + two * k3 + k4)
+            },
+        };
+        // Computing x from the step count avoids accumulating rounding errors
+        x = x0 + h * T::from(i).unwrap_or_else(T::one);
+        xs.push(x);
+        ys.push(y);
+    }
+    Trajectory { xs, ys }
+}
+
+// Integrate y' = f(x) for any `Evaluate` f, which yields its antiderivative
+// through (x0, y0)
+pub fn integrate_function<T, F>(method: Method, f: &F, start: (T, T), end: T, steps: usize) -> Result<Trajectory<T>, Error>
+    where T: Real, F: Evaluate<T> {
+    let mut error = None;
+    let trajectory = integrate(method, |x, _| f.eval(x).unwrap_or_else(|err| {
+        error.get_or_insert(err);
+        T::zero()
+    }), start, end, steps);
+    match error {
+        Some(err) => Err(err),
+        None => Ok(trajectory),
+    }
+}
+
+// Implicit Euler for the linear system y' = A y, which stays stable for
+// stiff systems at step sizes where the explicit methods blow up. Every
+// step solves (I - h A) y_next = y. Returns the state after each step,
+// starting with `y0`.
+pub fn implicit_euler<T>(a: &[Vec<T>], y0: Vec<T>, h: T, steps: usize) -> Result<Vec<Vec<T>>, SolveError>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    let n = y0.len();
+    if a.len() != n {
+        return Err(SolveError::UnfittingEquationAmount(a.len(), n));
+    }
+    if let Some(row) = a.iter().find(|row| row.len() != n) {
+        return Err(SolveError::UnfittingCoefficientAmount(row.len(), n));
+    }
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(y0);
+    for _ in 0..steps {
+        let y = &states[states.len() - 1];
+        let rows = (0..n)
+            .map(|i| {
+                let mut row = vec![T::zero(); n + 1];
+                for (j, (entry, coefficient)) in row.iter_mut().zip(&a[i]).enumerate() {
+                    let identity = if i == j { T::one() } else { T::zero() };
+                    *entry = identity - h * *coefficient;
+                }
+                row[n] = y[i];
+                row
+            })
+            .collect::<Vec<_>>();
+        let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+        states.push(solved.solution().collect());
+    }
+    Ok(states)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial;
+
+    #[test]
+    fn exponential_growth() {
+        // y' = y, y(0) = 1 has the solution e^x
+        let euler = integrate(Method::Euler, |_, y| y, (0.0, 1.0), 1.0, 1000);
+        let rk4 = integrate(Method::RungeKutta4, |_, y| y, (0.0, 1.0), 1.0, 10);
+        let e = 1.0f64.exp();
+        assert_eq!(euler.xs().len(), 1001);
+        assert_eq!(*rk4.xs().last().unwrap(), 1.0);
+        assert!((euler.ys().last().unwrap() - e).abs() < 2e-3);
+        assert!((rk4.ys().last().unwrap() - e).abs() < 3e-6);
+
+        let empty = integrate(Method::Euler, |_, y| y, (0.0, 1.0), 1.0, 0);
+        assert_eq!(empty.ys(), &[1.0]);
+    }
+
+    #[test]
+    fn integrate_polynomial() {
+        // y' = 3x^2 gives y = x^3 + y0, exact for RK4
+        let trajectory = integrate_function(Method::RungeKutta4, &polynomial!(3.0, 0.0, 0.0), (0.0, 1.0), 2.0, 4).unwrap();
+        for (x, y) in trajectory.xs().iter().zip(trajectory.ys()) {
+            assert!((y - (x * x * x + 1.0)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn stiff_decay() {
+        // y' = -1000 y with h = 0.01: explicit Euler oscillates and grows,
+        // implicit Euler decays monotonically
+        let explicit = integrate(Method::Euler, |_, y| -1000.0 * y, (0.0, 1.0), 0.1, 10);
+        assert!(explicit.ys().last().unwrap().abs() > 1.0);
+
+        let states = implicit_euler(&[vec![-1000.0]], vec![1.0], 0.01, 10).unwrap();
+        assert_eq!(states.len(), 11);
+        assert!(states.windows(2).all(|pair| 0.0 < pair[1][0] && pair[1][0] < pair[0][0]));
+
+        // The oscillation y1' = y2, y2' = -y1 is damped by implicit Euler
+        let states = implicit_euler(&[vec![0.0, 1.0], vec![-1.0, 0.0]], vec![1.0, 0.0], 0.1, 5).unwrap();
+        let norm = |y: &Vec<f64>| (y[0] * y[0] + y[1] * y[1]).sqrt();
+        assert!(norm(&states[5]) < 1.0);
+
+        assert!(matches!(
+            implicit_euler(&[vec![1.0, 2.0]], vec![1.0], 0.1, 1),
+            Err(SolveError::UnfittingCoefficientAmount(2, 1))
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::eigen::{eigenvalues, eigenvector, EigenError};
+use crate::solver::{dot, SolveError};
+
+// Principal component analysis of observations given as rows of a data
+// matrix, one column per variable:
+//
+//     let pca = pca(&observations)?;
+//     // pca.components()[0] is the direction of largest variance
+//     let scores = pca.project(&observations[0]);
+//
+// The components are the unit eigenvectors of the sample covariance matrix,
+// ordered by their eigenvalues, the variances along them.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pca<T> {
+    mean: Vec<T>,
+    components: Vec<Vec<T>>,
+    variances: Vec<T>,
+    explained: Vec<T>,
+}
+
+impl<T: Real> Pca<T> {
+    // Mean of each variable, which the data is centered on
+    pub fn mean(&self) -> &[T] {
+        &self.mean
+    }
+
+    // Unit directions, by descending variance
+    pub fn components(&self) -> &[Vec<T>] {
+        &self.components
+    }
+
+    // Variance of the data along each component
+    pub fn variances(&self) -> &[T] {
+        &self.variances
+    }
+
+    // Share of the total variance along each component, summing to one
+    pub fn explained_variance_ratios(&self) -> &[T] {
+        &self.explained
+    }
+
+    // Coordinates of an observation along the components
+    pub fn project(&self, observation: &[T]) -> Vec<T> {
+        let centered: Vec<T> = observation.iter().zip(&self.mean).map(|(&x, &mean)| x - mean).collect();
+        self.components.iter().map(|component| dot(component, &centered)).collect()
+    }
+}
+
+// Principal components of at least two observations. Eigenvectors for
+// repeated variances are found one at a time, each shifting the found
+// directions above the largest variance so that the next one differs.
+pub fn pca<T>(data: &[Vec<T>]) -> Result<Pca<T>, EigenError>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    let n = data.first().map_or(0, |row| row.len());
+    if n == 0 {
+        return Err(SolveError::TooSmall(0).into());
+    }
+    if let Some(row) = data.iter().find(|row| row.len() != n) {
+        return Err(SolveError::UnfittingCoefficientAmount(row.len(), n).into());
+    }
+    if data.len() < 2 {
+        return Err(SolveError::UnfittingEquationAmount(data.len(), 2).into());
+    }
+
+    let count = T::from(data.len()).unwrap_or_else(T::one);
+    let mean: Vec<T> = (0..n)
+        .map(|j| data.iter().fold(T::zero(), |sum, row| sum + row[j]) / count)
+        .collect();
+    let mut covariance = vec![vec![T::zero(); n]; n];
+    for row in data {
+        for i in 0..n {
+            for j in 0..n {
+                covariance[i][j] = covariance[i][j] + (row[i] - mean[i]) * (row[j] - mean[j]);
+            }
+        }
+    }
+    for value in covariance.iter_mut().flatten() {
+        *value = *value / (count - T::one());
+    }
+
+    // Symmetric, so the eigenvalues are real and non-negative up to rounding
+    let variances: Vec<T> = eigenvalues(&covariance)?
+        .into_iter()
+        .map(|value| value.re.max(T::zero()))
+        .collect();
+    let total = variances.iter().fold(T::zero(), |sum, &value| sum + value);
+    let shift = total + T::one();
+    let mut deflated = covariance;
+    let mut components: Vec<Vec<T>> = Vec::with_capacity(n);
+    for &variance in &variances {
+        let mut component = eigenvector(&deflated, variance)?;
+        // Remove what rounding left of the earlier components
+        for earlier in &components {
+            let overlap = dot(&component, earlier);
+            for (x, &e) in component.iter_mut().zip(earlier) {
+                *x -= overlap * e;
+            }
+        }
+        let length = dot(&component, &component).sqrt();
+        for x in component.iter_mut() {
+            *x = *x / length;
+        }
+        for i in 0..n {
+            for j in 0..n {
+                deflated[i][j] = deflated[i][j] + shift * component[i] * component[j];
+            }
+        }
+        components.push(component);
+    }
+
+    let explained = variances.iter()
+        .map(|&variance| if total.is_zero() { T::zero() } else { variance / total })
+        .collect();
+    Ok(Pca { mean, components, variances, explained })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elongated_cloud() {
+        // Points along the diagonal y = x with a small spread across it
+        let data: Vec<Vec<f64>> = (0..10)
+            .map(|i| {
+                // Mirrored around the middle, so uncorrelated with t
+                let (t, across) = (i as f64 - 4.5, [0.1, -0.1, 0.1, -0.1, 0.0][i.min(9 - i)]);
+                vec![1.0 + t + across, 2.0 + t - across]
+            })
+            .collect();
+        let pca = pca(&data).unwrap();
+        assert!((pca.mean()[0] - 1.0).abs() < 1e-12 && (pca.mean()[1] - 2.0).abs() < 1e-12);
+        let diagonal = 0.5f64.sqrt();
+        assert!((pca.components()[0][0].abs() - diagonal).abs() < 1e-9);
+        assert!((pca.components()[0][0] - pca.components()[0][1]).abs() < 1e-9);
+        assert!(dot(&pca.components()[0], &pca.components()[1]).abs() < 1e-9);
+        let ratios = pca.explained_variance_ratios();
+        assert!(ratios[0] > 0.99);
+        assert!((ratios[0] + ratios[1] - 1.0).abs() < 1e-12);
+
+        // The mean projects to the origin
+        for score in pca.project(&[1.0, 2.0]) {
+            assert!(score.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn repeated_variances() {
+        // Square corners have the same variance in every direction
+        let data = vec![vec![1.0, 1.0, 0.0], vec![-1.0, 1.0, 0.0], vec![1.0, -1.0, 0.0], vec![-1.0, -1.0, 0.0]];
+        let pca = pca(&data).unwrap();
+        let components = pca.components();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot(&components[i], &components[j]) - expected).abs() < 1e-9);
+            }
+        }
+        assert!((pca.variances()[0] - 4.0 / 3.0).abs() < 1e-9);
+        assert!(pca.variances()[2].abs() < 1e-9);
+        assert!(components[2][2].abs() > 1.0 - 1e-9);
+    }
+
+    #[test]
+    fn reject_invalid_data() {
+        assert!(matches!(
+            pca(&[vec![1.0, 2.0]]),
+            Err(EigenError::SolveError(SolveError::UnfittingEquationAmount(1, 2)))
+        ));
+        assert!(matches!(
+            pca(&[vec![1.0, 2.0], vec![1.0]]),
+            Err(EigenError::SolveError(SolveError::UnfittingCoefficientAmount(1, 2)))
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, SolveError};
+
+// Quadratic programs with equality constraints, minimizing ½xᵀQx - cᵀx
+// subject to Ax = b:
+//
+//     // closest point to (1, 2) on the line x + y = 1
+//     let solution = Problem::minimize(vec![vec![1.0, 0.0], vec![0.0, 1.0]], vec![1.0, 2.0])
+//         .constraint(vec![1.0, 1.0], 1.0)
+//         .solve()?;
+//
+// The optimality (KKT) conditions Qx + Aᵀλ = c and Ax = b form one linear
+// system in x and the Lagrange multipliers λ,
+//
+//     | Q  Aᵀ | | x |   | c |
+//     | A  0  | | λ | = | b |
+//
+// solved by elimination. It has a unique solution if A has independent rows
+// and Q is positive definite on the null space of A, which then makes x the
+// minimum. Otherwise the system is singular, or for indefinite Q the
+// solution is a saddle point.
+
+#[derive(Debug)]
+pub enum QpError {
+    // Q, c or a constraint don't fit the number of variables
+    UnfittingCoefficientAmount(usize, usize),
+    SolveError(SolveError),
+}
+
+impl From<SolveError> for QpError {
+    fn from(err: SolveError) -> Self {
+        QpError::SolveError(err)
+    }
+}
+
+impl fmt::Display for QpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QpError::UnfittingCoefficientAmount(amount, variables) =>
+                write!(f, "Amount {} of coefficients does not fit {} variables", amount, variables),
+            QpError::SolveError(err) => write!(f, "Failed to solve the KKT system: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QpError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem<T> {
+    quadratic: Vec<Vec<T>>,
+    linear: Vec<T>,
+    constraints: Vec<(Vec<T>, T)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<T> {
+    values: Vec<T>,
+    multipliers: Vec<T>,
+    objective: T,
+}
+
+impl<T> Solution<T> {
+    // Optimal value of each variable
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    // Lagrange multiplier of each constraint, the rate at which the minimum
+    // falls as its bound grows
+    pub fn multipliers(&self) -> &[T] {
+        &self.multipliers
+    }
+
+    pub fn objective(&self) -> &T {
+        &self.objective
+    }
+}
+
+impl<T> Problem<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Q as rows of a symmetric n × n matrix and c with n entries
+    pub fn minimize(quadratic: Vec<Vec<T>>, linear: Vec<T>) -> Problem<T> {
+        Problem { quadratic, linear, constraints: Vec::new() }
+    }
+
+    // Row aᵀx = b of the constraints
+    pub fn constraint(mut self, coefficients: Vec<T>, bound: T) -> Self {
+        self.constraints.push((coefficients, bound));
+        self
+    }
+
+    pub fn solve(&self) -> Result<Solution<T>, QpError> {
+        let n = self.linear.len();
+        if self.quadratic.len() != n {
+            return Err(QpError::UnfittingCoefficientAmount(self.quadratic.len(), n));
+        }
+        if let Some(row) = self.quadratic.iter().find(|row| row.len() != n) {
+            return Err(QpError::UnfittingCoefficientAmount(row.len(), n));
+        }
+        if let Some((coefficients, _)) = self.constraints.iter().find(|(c, _)| c.len() != n) {
+            return Err(QpError::UnfittingCoefficientAmount(coefficients.len(), n));
+        }
+
+        let mut rows: Vec<Vec<T>> = Vec::with_capacity(n + self.constraints.len());
+        for (i, (row, &c)) in self.quadratic.iter().zip(&self.linear).enumerate() {
+            rows.push(row.iter().copied()
+                .chain(self.constraints.iter().map(|(a, _)| a[i]))
+                .chain(Some(c))
+                .collect());
+        }
+        for (a, b) in &self.constraints {
+            rows.push(a.iter().copied()
+                .chain(self.constraints.iter().map(|_| T::zero()))
+                .chain(Some(*b))
+                .collect());
+        }
+        let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+        let mut values: Vec<T> = solved.solution().collect();
+        let multipliers = values.split_off(n);
+
+        let two = T::one() + T::one();
+        let objective = self.quadratic.iter().zip(&values).zip(&self.linear)
+            .fold(T::zero(), |sum, ((row, &x), &c)| {
+                let qx = row.iter().zip(&values).fold(T::zero(), |sum, (&q, &y)| sum + q * y);
+                sum + x * qx / two - c * x
+            });
+        Ok(Solution { values, multipliers, objective })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(n: usize) -> Vec<Vec<f64>> {
+        (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect()
+    }
+
+    #[test]
+    fn project_onto_line() {
+        // ½|x|² - (1, 2)ᵀx is least at the point closest to (1, 2)
+        let solution = Problem::minimize(identity(2), vec![1.0, 2.0])
+            .constraint(vec![1.0, 1.0], 1.0)
+            .solve()
+            .unwrap();
+        for (a, b) in solution.values().iter().zip(&[0.0, 1.0]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!((solution.multipliers()[0] - 1.0).abs() < 1e-12);
+        assert!((solution.objective() + 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn unconstrained_and_multiple_constraints() {
+        // Without constraints it solves Qx = c
+        let q = vec![vec![2.0, 1.0], vec![1.0, 3.0]];
+        let solution = Problem::minimize(q, vec![3.0, 4.0]).solve().unwrap();
+        for (a, b) in solution.values().iter().zip(&[1.0, 1.0]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // Smallest vector in R³ with x1 + x2 + x3 = 3 and x1 - x3 = 0
+        let solution = Problem::minimize(identity(3), vec![0.0; 3])
+            .constraint(vec![1.0, 1.0, 1.0], 3.0)
+            .constraint(vec![1.0, 0.0, -1.0], 0.0)
+            .solve()
+            .unwrap();
+        for (a, b) in solution.values().iter().zip(&[1.0, 1.0, 1.0]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn report_failures() {
+        let result = Problem::minimize(identity(2), vec![0.0; 2]).constraint(vec![1.0], 1.0).solve();
+        assert!(matches!(result, Err(QpError::UnfittingCoefficientAmount(1, 2))));
+        // The same constraint twice makes the system singular
+        let result = Problem::minimize(identity(2), vec![0.0; 2])
+            .constraint(vec![1.0, 1.0], 1.0)
+            .constraint(vec![2.0, 2.0], 2.0)
+            .solve();
+        assert!(matches!(result, Err(QpError::SolveError(_))));
+    }
+}
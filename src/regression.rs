@@ -0,0 +1,157 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use crate::solver::{dot, CoefficientMatrix, SolveError};
+
+// Multiple linear regression y = b0 + b1 x1 + ... + bk xk by least squares.
+// Each observation is a row holding the predictors and the target in the
+// column given to `fit`:
+//
+//     // columns: area, rooms, price
+//     let fit = Regression::fit(&observations, 2)?;
+//     // fit.coefficients() == [intercept, per area, per room]
+//
+// The normal equations XᵀX b = Xᵀy are factorized once, for the
+// coefficients and for the diagonal of (XᵀX)⁻¹ behind the standard errors.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression<T> {
+    coefficients: Vec<T>,
+    standard_errors: Vec<T>,
+    residuals: Vec<T>,
+    r_squared: T,
+}
+
+impl<T> Regression<T> {
+    // Intercept followed by one coefficient per predictor, in the order of
+    // the columns
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    // Standard error of each coefficient, estimating the variance of the
+    // noise from the residuals
+    pub fn standard_errors(&self) -> &[T] {
+        &self.standard_errors
+    }
+
+    // Observed minus fitted target of each observation
+    pub fn residuals(&self) -> &[T] {
+        &self.residuals
+    }
+
+    // Coefficient of determination, 1 for a perfect fit
+    pub fn r_squared(&self) -> &T {
+        &self.r_squared
+    }
+}
+
+impl<T> Regression<T>
+    where T: Real + SubAssign + fmt::Display + fmt::Debug {
+    // Regress column `target` of the observations on all other columns. It
+    // takes more observations than coefficients so that the noise can be
+    // estimated, and predictors that aren't linearly dependent.
+    pub fn fit(observations: &[Vec<T>], target: usize) -> Result<Regression<T>, SolveError> {
+        let columns = observations.first().map_or(0, |row| row.len());
+        if let Some(row) = observations.iter().find(|row| row.len() != columns) {
+            return Err(SolveError::UnfittingCoefficientAmount(row.len(), columns));
+        }
+        if target >= columns {
+            return Err(SolveError::IndexOutOfBounds(target, columns));
+        }
+        // Intercept and one coefficient per predictor
+        let parameters = columns;
+        if observations.len() <= parameters {
+            return Err(SolveError::UnfittingEquationAmount(observations.len(), parameters + 1));
+        }
+
+        let design: Vec<Vec<T>> = observations.iter()
+            .map(|row| Some(T::one()).into_iter()
+                .chain(row.iter().enumerate().filter(|&(j, _)| j != target).map(|(_, &x)| x))
+                .collect())
+            .collect();
+        let targets: Vec<T> = observations.iter().map(|row| row[target]).collect();
+        let column = |j: usize| design.iter().map(move |row| row[j]);
+
+        let normal: Vec<Vec<T>> = (0..parameters)
+            .map(|i| {
+                let mut row: Vec<T> = (0..parameters)
+                    .map(|j| column(i).zip(column(j)).fold(T::zero(), |sum, (a, b)| sum + a * b))
+                    .collect();
+                row.push(column(i).zip(&targets).fold(T::zero(), |sum, (a, &y)| sum + a * y));
+                row
+            })
+            .collect();
+        let system = CoefficientMatrix::try_from(normal)?.validate()?;
+        let lu = system.lu()?;
+        let coefficients = lu.solve(&system.results().collect::<Vec<T>>())?;
+
+        let residuals: Vec<T> = design.iter()
+            .zip(&targets)
+            .map(|(row, &y)| y - dot(row, &coefficients))
+            .collect();
+        let sum_of_squares = dot(&residuals, &residuals);
+        let mean = targets.iter().fold(T::zero(), |sum, &y| sum + y) / count(targets.len());
+        let total = targets.iter().fold(T::zero(), |sum, &y| sum + (y - mean) * (y - mean));
+        // Constant targets are explained perfectly by any fit reproducing them
+        let r_squared = if total.is_zero() { T::one() } else { T::one() - sum_of_squares / total };
+
+        let variance = sum_of_squares / count(observations.len() - parameters);
+        let mut unit = vec![T::zero(); parameters];
+        let mut standard_errors = Vec::with_capacity(parameters);
+        for j in 0..parameters {
+            unit[j] = T::one();
+            standard_errors.push((variance * lu.solve(&unit)?[j]).max(T::zero()).sqrt());
+            unit[j] = T::zero();
+        }
+
+        Ok(Regression { coefficients, standard_errors, residuals, r_squared })
+    }
+}
+
+fn count<T: Real>(n: usize) -> T {
+    T::from(n).unwrap_or_else(T::max_value)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_plane() {
+        // y = 1 + 2 a - b with the target in the middle column
+        let observations: Vec<Vec<f64>> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (2.0, 3.0), (1.0, 1.0)].iter()
+            .map(|&(a, b)| vec![a, 1.0 + 2.0 * a - b, b])
+            .collect();
+        let fit = Regression::fit(&observations, 1).unwrap();
+        for (actual, expected) in fit.coefficients().iter().zip(&[1.0, 2.0, -1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!((fit.r_squared() - 1.0).abs() < 1e-12);
+        assert!(fit.residuals().iter().chain(fit.standard_errors()).all(|value| value.abs() < 1e-6));
+    }
+
+    #[test]
+    fn simple_regression_statistics() {
+        let observations = vec![vec![1.0, 1.0], vec![2.0, 3.0], vec![3.0, 2.0], vec![4.0, 5.0], vec![5.0, 4.0]];
+        let fit = Regression::fit(&observations, 1).unwrap();
+        // Slope Sxy / Sxx = 8 / 10, intercept 3 - 0.8 * 3
+        assert!((fit.coefficients()[0] - 0.6).abs() < 1e-12 && (fit.coefficients()[1] - 0.8).abs() < 1e-12);
+        // Residual sum of squares 3.6 over 3 degrees of freedom
+        let variance: f64 = 3.6 / 3.0;
+        assert!((fit.standard_errors()[1] - (variance / 10.0).sqrt()).abs() < 1e-12);
+        assert!((fit.standard_errors()[0] - (variance * (1.0 / 5.0 + 9.0 / 10.0)).sqrt()).abs() < 1e-12);
+        assert!((fit.r_squared() - 0.64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reject_invalid_data() {
+        assert!(matches!(Regression::fit(&[vec![1.0, 2.0], vec![1.0]], 0), Err(SolveError::UnfittingCoefficientAmount(1, 2))));
+        assert!(matches!(Regression::fit(&[vec![1.0, 2.0]], 2), Err(SolveError::IndexOutOfBounds(2, 2))));
+        assert!(matches!(Regression::fit(&[vec![1.0, 2.0], vec![2.0, 3.0]], 1), Err(SolveError::UnfittingEquationAmount(2, 3))));
+    }
+}
@@ -1,20 +1,137 @@
 
+use alloc::{collections::BTreeSet, vec, vec::Vec};
 use num::{Num, Zero};
 use num::traits::real::Real;
-use std::ops::SubAssign;
-use std::fmt;
+use core::ops::{Index, IndexMut, SubAssign};
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod affine;
+mod analyze;
+mod banded;
+mod bareiss;
+mod blocked;
+mod builder;
+mod cancel;
+mod collect;
+mod compensated;
+mod cramer;
+#[cfg(feature = "cross-check")]
+mod cross_check;
+mod dependency;
+mod describe;
+mod diophantine;
+mod equilibrate;
+mod fixed;
+mod format;
+mod fraction;
+mod gauss_jordan;
+mod geometry;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod iter;
+mod json;
+mod ldlt;
+mod lu;
+mod matrix;
+mod memory;
+mod min_norm;
+mod mixed;
+mod modular;
+mod mtx;
+mod named;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod norm;
+mod ops;
+mod orthogonal;
+mod partial;
+mod permute;
+mod pinv;
+mod progress;
+mod reuse;
+mod ridge;
+mod sensitivity;
+mod shape;
+mod small;
+mod snapshot;
+mod stepwise;
+mod stream;
+mod timing;
+mod tls;
+mod trace;
+mod uncertainty;
+mod units;
+mod update;
+mod vector;
 
 mod error {
-	use std::fmt;
-	use std::error;
+	use alloc::{string::String, vec::Vec};
+	use core::fmt;
 
 	#[derive(Debug)]
 	pub enum SolveError {
 		TooSmall(usize),
+		TooLarge(usize, usize),
 		UnfittingEquationAmount(usize, usize),
 		UnfittingCoefficientAmount(usize, usize),
 		DependentSolutionSet,
 		EmptySolutionSet,
+		IncompatibleDimensions((usize, usize), (usize, usize)),
+		Cancelled,
+		IndexOutOfBounds(usize, usize),
+		NotValidated,
+		DuplicateName(String),
+		// Every equation whose number of coefficients differs from the size,
+		// as its index and number of coefficients, and the size
+		UnfittingRows(Vec<(usize, usize)>, usize),
+		InvalidPermutation(usize),
+		// Row and column of a NaN or infinity, the results being column
+		// `size`, in the input or produced by overflow during elimination
+		NonFiniteInput(usize, usize),
+		NonFiniteResult(usize, usize),
+		// Cells created by `set` or `set_rhs` that haven't been given a
+		// value, as row and column, the results being column `size`
+		UnenteredCells(Vec<(usize, usize)>),
+		// Fixed-point elimination left the range of `Fixed` while
+		// eliminating the given row with the pivot in the given column
+		FixedPointOverflow(usize, usize),
+		// Integer elimination left the range of the type while eliminating
+		// the given row with the pivot in the given column
+		Overflow(usize, usize),
+		// A sampling method was asked for no samples at all
+		NoSamples,
+	}
+
+	impl SolveError {
+		// Stable identifier of the variant for frontends, which shouldn't
+		// match on the Display text
+		pub fn code(&self) -> &'static str {
+			match self {
+				SolveError::TooSmall(_) => "TooSmall",
+				SolveError::TooLarge(..) => "TooLarge",
+				SolveError::UnfittingEquationAmount(..) => "UnfittingEquationAmount",
+				SolveError::UnfittingCoefficientAmount(..) => "UnfittingCoefficientAmount",
+				SolveError::DependentSolutionSet => "DependentSolutionSet",
+				SolveError::EmptySolutionSet => "EmptySolutionSet",
+				SolveError::IncompatibleDimensions(..) => "IncompatibleDimensions",
+				SolveError::Cancelled => "Cancelled",
+				SolveError::IndexOutOfBounds(..) => "IndexOutOfBounds",
+				SolveError::NotValidated => "NotValidated",
+				SolveError::DuplicateName(_) => "DuplicateName",
+				SolveError::UnfittingRows(..) => "UnfittingRows",
+				SolveError::InvalidPermutation(_) => "InvalidPermutation",
+				SolveError::NonFiniteInput(..) => "NonFiniteInput",
+				SolveError::NonFiniteResult(..) => "NonFiniteResult",
+				SolveError::UnenteredCells(_) => "UnenteredCells",
+				SolveError::FixedPointOverflow(..) => "FixedPointOverflow",
+				SolveError::Overflow(..) => "Overflow",
+				SolveError::NoSamples => "NoSamples",
+			}
+		}
 	}
 
 	impl fmt::Display for SolveError {
@@ -22,6 +139,8 @@ mod error {
 			match self {
 				SolveError::TooSmall(size) =>
 					write!(f, "Matrix size of {} is too small", size),				
+				SolveError::TooLarge(size, max) =>
+					write!(f, "Matrix size of {} is too large, at most {} is supported", size, max),
 				SolveError::UnfittingEquationAmount(amount, size) =>
 					write!(f, "Amount {} of equations does not fit in matrix of size {}", amount, size),
 				SolveError::UnfittingCoefficientAmount(amount, size) =>
@@ -30,18 +149,102 @@ mod error {
 					write!(f, "The system of equations is dependent"),
 				SolveError::EmptySolutionSet =>
 					write!(f, "The system of equations has no solution"),
+				SolveError::IncompatibleDimensions((a, b), (c, d)) =>
+					write!(f, "Dimensions {}x{} and {}x{} are incompatible", a, b, c, d),
+				SolveError::Cancelled =>
+					write!(f, "The solve was cancelled"),
+				SolveError::IndexOutOfBounds(index, len) =>
+					write!(f, "Index {} is out of bounds for {} equations", index, len),
+				SolveError::NotValidated =>
+					write!(f, "The matrix is not a valid square system"),
+				SolveError::DuplicateName(name) =>
+					write!(f, "The name {} is given to more than one unknown", name),
+				SolveError::UnfittingRows(rows, size) => {
+					write!(f, "Equations with a number of coefficients other than {}:", size)?;
+					for (index, (row, amount)) in rows.iter().enumerate() {
+						let separator = if index == 0 { "" } else { "," };
+						write!(f, "{} {} ({})", separator, row, amount)?;
+					}
+					Ok(())
+				},
+				SolveError::InvalidPermutation(index) =>
+					write!(f, "Index {} is repeated or out of range in the permutation", index),
+				SolveError::NonFiniteInput(row, column) =>
+					write!(f, "The value in row {} and column {} is not a finite number", row, column),
+				SolveError::NonFiniteResult(row, column) =>
+					write!(f, "Elimination produced a value that is not a finite number in row {} and column {}", row, column),
+				SolveError::UnenteredCells(cells) => {
+					write!(f, "Cells without a value:")?;
+					for (index, (row, column)) in cells.iter().enumerate() {
+						let separator = if index == 0 { "" } else { "," };
+						write!(f, "{} ({}, {})", separator, row, column)?;
+					}
+					Ok(())
+				},
+				SolveError::FixedPointOverflow(row, column) =>
+					write!(f, "Fixed-point overflow eliminating row {} with the pivot in column {}, fewer fraction bits leave more room for the integer part", row, column),
+				SolveError::Overflow(row, column) =>
+					write!(f, "Integer overflow eliminating row {} with the pivot in column {}, a big integer type such as BigInt avoids it", row, column),
+				SolveError::NoSamples =>
+					write!(f, "At least one sample is needed"),
 			}
 		}
 	}
 
-	impl error::Error for SolveError {}
+	#[cfg(feature = "std")]
+	impl std::error::Error for SolveError {}
 }
 
-use error::SolveError;
-
-type Result<T> = std::result::Result<CoefficientMatrix<T>, SolveError>;
+pub use affine::fit_affine;
+pub use analyze::Issue;
+pub use banded::Banded;
+pub use error::SolveError;
+pub use builder::{Builder, Solved, Triangular, Validated};
+pub use cancel::CancelToken;
+pub use cramer::Cramer;
+#[cfg(feature = "cross-check")]
+pub use cross_check::CrossCheck;
+pub use dependency::Dependency;
+#[cfg(all(feature = "wasm", not(target_os = "wasi")))]
+pub(crate) use describe::variable_name;
+pub use diophantine::IntegerSolution;
+pub use equilibrate::Scaling;
+pub use fixed::{Fixed, Fixed32};
+pub use format::{EquationFormat, MatrixFormat};
+pub use fraction::Fraction;
+pub use gauss_jordan::Method;
+pub use geometry::{Intersection, Line, Plane};
+#[cfg(feature = "gpu")]
+pub use gpu::GpuContext;
+pub use iter::Column;
+pub use json::SystemData;
+pub use lu::Lu;
+pub use matrix::Matrix;
+pub use modular::ModInt;
+pub use mtx::MtxError;
+pub use named::NamedSystem;
+pub use orthogonal::orthonormalize;
+pub use permute::ColumnPermutation;
+pub use progress::Progress;
+pub use reuse::InputLayout;
+pub use sensitivity::Sensitivity;
+pub use shape::{Shape, Strategy};
+pub use small::{SmallSystem, System2, System3, System4};
+pub use snapshot::{Snapshot, SnapshotError};
+pub use stepwise::Elimination;
+pub use stream::EquationStream;
+pub use timing::Timings;
+use stepwise::Cursor;
+pub use trace::{Observer, ParseTraceError, Step, Trace};
+pub use uncertainty::UncertainSolution;
+pub use units::{Unit, UnitError, UnitSystem};
+pub use update::UpdatedLu;
+pub use vector::{axpy, dot, norm1, norm2, norm_inf, scale};
+
+type Result<T> = core::result::Result<CoefficientMatrix<T>, SolveError>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Equation<T> {
 	coefficients: Vec<T>,
 	result: T,
@@ -51,13 +254,6 @@ impl<T: Num + Copy> Equation<T>
 where
 	T: Num + Copy
 {
-	pub fn new(coefficients: Vec<T>, result: T) -> Equation<T> {
-		Equation {
-			coefficients,
-			result,
-		}
-	}
-
 	fn get(&self, idx: usize) -> T {
 		*self.coefficients.get(idx).unwrap()
 	}
@@ -76,140 +272,349 @@ where
 } 
 
 impl<T> Equation<T> {
+	pub fn new(coefficients: Vec<T>, result: T) -> Equation<T> {
+		Equation {
+			coefficients,
+			result,
+		}
+	}
+
+	pub fn coefficients(&self) -> &[T] {
+		&self.coefficients
+	}
+
+	pub fn result(&self) -> &T {
+		&self.result
+	}
+
 	fn len(&self) -> usize {
 		self.coefficients.len()
 	}
 }
 
+impl<T: Num + Copy> CoefficientMatrix<T> {
+	// Substitute a known value for unknown `index`, moving its terms to the
+	// right-hand sides. The result has one unknown less, the later ones
+	// moving up by one, and the same equations, so a square system becomes
+	// overdetermined; see `solve_rectangular`, or drop an equation.
+	pub fn fix_variable(&self, index: usize, value: T) -> core::result::Result<CoefficientMatrix<T>, SolveError> {
+		if index >= self.size {
+			return Err(SolveError::IndexOutOfBounds(index, self.size));
+		}
+		let matrix = self.matrix.iter()
+			.map(|equation| {
+				let mut coefficients = equation.coefficients.clone();
+				let result = if index < coefficients.len() {
+					equation.result - coefficients.remove(index) * value
+				} else {
+					equation.result
+				};
+				Equation::new(coefficients, result)
+			})
+			.collect();
+		let unentered = self.unentered.iter()
+			.filter(|&&(_, column)| column != Some(index))
+			.map(|&(row, column)| (row, column.map(|column| if column > index { column - 1 } else { column })))
+			.collect();
+		Ok(CoefficientMatrix { size: self.size - 1, matrix, unentered })
+	}
+}
+
+impl<T: Num + Copy> Equation<T> {
+	// Equation of `size` coefficients given as (column, coefficient) terms,
+	// the others being zero. Terms for the same column are added up, which
+	// suits stencils, e.g. [(i - 1, -1.0), (i, 2.0), (i + 1, -1.0)].
+	pub fn from_terms(size: usize, terms: &[(usize, T)], result: T) -> core::result::Result<Equation<T>, SolveError> {
+		let mut coefficients = vec![T::zero(); size];
+		for &(column, value) in terms {
+			let coefficient = coefficients.get_mut(column).ok_or(SolveError::IndexOutOfBounds(column, size))?;
+			*coefficient = *coefficient + value;
+		}
+		Ok(Equation::new(coefficients, result))
+	}
+}
+
 impl<T> fmt::Display for Equation<T>
 where
-	T: Num + Copy + fmt::Display + fmt::Debug
+	T: Num + Copy + fmt::Display
 {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{:?} = {}", self.coefficients, self.result)
+		fmt::Display::fmt(&self.format(), f)
 	}
-
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CoefficientMatrix<T> {
 	size: usize,
 	matrix: Vec<Equation<T>>,
+	// Cells `set` and `set_rhs` created without a value yet, as row and
+	// column with `None` for the right-hand side
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeSet::is_empty"))]
+	unentered: BTreeSet<(usize, Option<usize>)>,
 }
 
 
-impl<T> CoefficientMatrix<T>
-where
-	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
-{
+impl<T> CoefficientMatrix<T> {
 	pub fn new(size: usize) -> Self {
 		CoefficientMatrix {
 			size,
 			matrix: Vec::with_capacity(size),
+			unentered: BTreeSet::new(),
 		}
 	}
 
+	// Number of unknowns
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
 	pub fn add_equation(mut self, equation: Equation<T>) -> Self {
 		self.matrix.push(equation);
 		self
 	}
 
+	// Like `add_equation` for a matrix held in place
+	pub fn push_equation(&mut self, equation: Equation<T>) {
+		self.matrix.push(equation);
+	}
+
+	// Replace the equation at `index`
+	pub fn set_equation(&mut self, index: usize, equation: Equation<T>) -> core::result::Result<(), SolveError> {
+		let len = self.matrix.len();
+		let slot = self.matrix.get_mut(index).ok_or(SolveError::IndexOutOfBounds(index, len))?;
+		*slot = equation;
+		self.unentered.retain(|&(row, _)| row != index);
+		Ok(())
+	}
+
+	// Remove and return the equation at `index`, shifting later ones up
+	pub fn remove_equation(&mut self, index: usize) -> core::result::Result<Equation<T>, SolveError> {
+		if index >= self.matrix.len() {
+			return Err(SolveError::IndexOutOfBounds(index, self.matrix.len()));
+		}
+		self.unentered = self.unentered.iter()
+			.filter(|&&(row, _)| row != index)
+			.map(|&(row, column)| (if row > index { row - 1 } else { row }, column))
+			.collect();
+		Ok(self.matrix.remove(index))
+	}
+
+	// The steps after `validate` rely on its checks. Rather than
+	// indexing out of bounds on a matrix that skipped it, they fail.
+	fn ensure_validated(&self) -> core::result::Result<(), SolveError> {
+		let square = self.size > 0
+			&& self.unentered.is_empty()
+			&& self.matrix.len() == self.size
+			&& self.matrix.iter().all(|equation| equation.len() == self.size);
+		if square {
+			Ok(())
+		} else {
+			Err(SolveError::NotValidated)
+		}
+	}
+
+	// Fail with every equation that doesn't have `size` coefficients
+	fn check_rows(&self) -> core::result::Result<(), SolveError> {
+		let unfitting: alloc::vec::Vec<(usize, usize)> = self.matrix.iter()
+			.map(Equation::len)
+			.enumerate()
+			.filter(|&(_, amount)| amount != self.size)
+			.collect();
+		if unfitting.is_empty() {
+			Ok(())
+		} else {
+			Err(SolveError::UnfittingRows(unfitting, self.size))
+		}
+	}
+
 	pub fn validate(self) -> Result<T> {
 		if self.size < 1 {
 			return Err(SolveError::TooSmall(self.size));
 		}
+		if !self.unentered.is_empty() {
+			let mut cells: Vec<_> = self.unentered.iter()
+				.map(|&(row, column)| (row, column.unwrap_or(self.size)))
+				.collect();
+			cells.sort_unstable();
+			return Err(SolveError::UnenteredCells(cells));
+		}
 
 		if self.matrix.len() == self.size {
-			let mut unfitting_amount = None;
-			for equation in self.matrix.iter() {
-				if equation.len() != self.size {
-					unfitting_amount = Some(equation.len());
-				}
-			}
-			match unfitting_amount {
-				Some(amount) => Err(SolveError::UnfittingCoefficientAmount(amount, self.size)),
-				None => Ok(self),
-			}
+			self.check_rows()?;
+			Ok(self)
  		} else {
 			Err(SolveError::UnfittingEquationAmount(self.matrix.len(), self.size))
 		}
 	}
+}
 
-	// Convert the matrix to upper triangular form
-	pub fn convert(mut self) -> Result<T> {
-		// at this point self needs to be validated == have a size of more than 0
-		for a in 0..self.size-1 {
-			let mut pivot = self.matrix[a].get(a);
-			
-			// Search for and set a better pivot in case there is one
-			for i in a+1..self.size {
-				if self.matrix[i].get(a).abs() > pivot.abs() {
-					self.matrix.swap(i, a);
-					pivot = self.matrix[a].get(a);
-				}
-			}
+impl<T: Clone> CoefficientMatrix<T> {
+	// The right-hand side of every equation. Once the matrix is solved these
+	// are the values of the unknowns.
+	pub fn results(&self) -> impl Iterator<Item = T> + '_ {
+		self.matrix.iter().map(|equation| equation.result.clone())
+	}
 
-			for b in a+1..self.size {
-				let ratio = self.matrix[b].get(a) / pivot;
-				for c in a..self.size {
-					let eliminator = self.matrix[a].get(c) * ratio;
-					*self.matrix[b].get_mut(c) -= eliminator;
-				}
-				let eliminator = self.matrix[a].get_result() * ratio;
-				*self.matrix[b].get_result_mut() -= eliminator;
-			}
+	// Coefficient in the given row and column, if it exists and has been
+	// entered
+	pub fn get(&self, row: usize, column: usize) -> Option<T> {
+		if self.unentered.contains(&(row, Some(column))) {
+			return None;
 		}
+		self.matrix.get(row)?.coefficients.get(column).cloned()
+	}
 
-		Ok(self)
+	// Right-hand side of the given row, if it exists and has been entered
+	pub fn rhs(&self, row: usize) -> Option<T> {
+		if self.unentered.contains(&(row, None)) {
+			return None;
+		}
+		self.matrix.get(row).map(|equation| equation.result.clone())
 	}
+}
 
-	pub fn solve(mut self) -> Result<T> {
-		for i in (0..self.size).rev() {
-			let divisor = self.matrix[i].get(i);
+impl<T: Zero + Clone> CoefficientMatrix<T> {
+	// Change the number of unknowns, padding every equation with zero
+	// coefficients or dropping the trailing ones. The equations themselves
+	// are kept.
+	pub fn resize(&mut self, size: usize) {
+		self.size = size;
+		self.unentered.retain(|&(_, column)| column.is_none_or(|column| column < size));
+		for equation in self.matrix.iter_mut() {
+			equation.coefficients.resize(size, T::zero());
+		}
+	}
 
-			if divisor.is_zero() {
-				if self.matrix[i].get_result().is_zero() {
-					return Err(SolveError::DependentSolutionSet);
-				} else {
-					return Err(SolveError::EmptySolutionSet);
-				}
-			}
+	// Equation `row` for editing single cells. Missing rows up to the size,
+	// or one past the last equation, are created and short equations are
+	// padded, their new cells being zero until entered.
+	fn cell_row(&mut self, row: usize) -> core::result::Result<&mut Equation<T>, SolveError> {
+		let limit = self.matrix.len().max(self.size) + 1;
+		if row >= limit {
+			return Err(SolveError::IndexOutOfBounds(row, limit));
+		}
+		while self.matrix.len() <= row {
+			let index = self.matrix.len();
+			self.unentered.extend((0..self.size).map(|column| (index, Some(column))));
+			self.unentered.insert((index, None));
+			self.matrix.push(Equation { coefficients: alloc::vec![T::zero(); self.size], result: T::zero() });
+		}
+		let equation = &mut self.matrix[row];
+		let length = equation.coefficients.len();
+		if length < self.size {
+			self.unentered.extend((length..self.size).map(|column| (row, Some(column))));
+			equation.coefficients.resize(self.size, T::zero());
+		}
+		Ok(equation)
+	}
 
-			// Divide each value in the current row with the row's leading coefficient
-			for j in 0..self.size {
-				let quotient = self.matrix[i].get(j) / divisor;
-				*self.matrix[i].get_mut(j) = quotient;
-			}
-			let result_quotient = self.matrix[i].get_result() / divisor;
-			*self.matrix[i].get_result_mut() = result_quotient;
-
-			// Eliminate all coefficients in the current row's leading coefficient's column
-			for j in (0..i).rev() {
-				let factor = self.matrix[j].get(i);
-				for k in 0..self.size {
-					let eliminator = self.matrix[i].get(k) * factor;
-					*self.matrix[j].get_mut(k) -= eliminator;
-				}
-				let result_eliminator = self.matrix[i].get_result() * factor;
-				*self.matrix[j].get_result_mut() -= result_eliminator;
-			}
+	// Set a single coefficient, e.g. from a spreadsheet-like editor filling
+	// in cells in any order. Whether every cell has been entered is only
+	// checked by `validate`.
+	pub fn set(&mut self, row: usize, column: usize, value: T) -> core::result::Result<(), SolveError> {
+		if column >= self.size {
+			return Err(SolveError::IndexOutOfBounds(column, self.size));
 		}
+		self.cell_row(row)?.coefficients[column] = value;
+		self.unentered.remove(&(row, Some(column)));
+		Ok(())
+	}
 
-		Ok(self)
+	// Set the right-hand side of a single equation, see `set`
+	pub fn set_rhs(&mut self, row: usize, value: T) -> core::result::Result<(), SolveError> {
+		self.cell_row(row)?.result = value;
+		self.unentered.remove(&(row, None));
+		Ok(())
+	}
+
+	// Append `count` unknowns with zero coefficients in every equation,
+	// returning the index of the first one
+	pub fn add_unknowns(&mut self, count: usize) -> usize {
+		let first = self.size;
+		self.resize(first + count);
+		first
+	}
+
+	// Append an unknown with one coefficient per equation, e.g. a slack
+	// variable, returning its index
+	pub fn add_unknown(&mut self, column: &[T]) -> core::result::Result<usize, SolveError> {
+		if column.len() != self.matrix.len() {
+			return Err(SolveError::IncompatibleDimensions((column.len(), 1), (self.matrix.len(), 1)));
+		}
+		let index = self.add_unknowns(1);
+		for (equation, value) in self.matrix.iter_mut().zip(column) {
+			equation.coefficients[index] = value.clone();
+		}
+		Ok(index)
 	}
 }
 
-impl<T> fmt::Display for CoefficientMatrix<T>
-where T: Num + fmt::Display + fmt::Debug + Copy {
-	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		for equation in self.matrix.iter() {
-			write!(f, "{}\n", equation)?;
+// Coefficients indexed by (row, column). Panics if either is out of bounds;
+// use `get` for checked access.
+impl<T> Index<(usize, usize)> for CoefficientMatrix<T> {
+	type Output = T;
+
+	fn index(&self, (row, column): (usize, usize)) -> &T {
+		&self.matrix[row].coefficients[column]
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for CoefficientMatrix<T> {
+	fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+		&mut self.matrix[row].coefficients[column]
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
+{
+
+	// Convert the matrix to upper triangular form
+	pub fn convert(self) -> Result<T> {
+		self.convert_observed(&mut ())
+	}
+
+	// Like `convert`, reporting each row operation to the observer
+	pub fn convert_observed<O: Observer<T>>(mut self, observer: &mut O) -> Result<T> {
+		self.ensure_validated()?;
+		self.check_finite()?;
+		let mut cursor = Cursor::start(self.size);
+		while cursor.pivots_done(self.size) < self.size - 1 {
+			cursor = self.advance(cursor, observer)?;
 		}
-		Ok(())
+
+		Ok(self)
+	}
+
+	pub fn solve(self) -> Result<T> {
+		self.solve_observed(&mut ())
+	}
+
+	// Like `solve`, reporting each row operation to the observer
+	pub fn solve_observed<O: Observer<T>>(mut self, observer: &mut O) -> Result<T> {
+		self.ensure_validated()?;
+		let mut cursor = Cursor::Scale { row: self.size - 1 };
+		while cursor != Cursor::Done {
+			cursor = self.advance(cursor, observer)?;
+		}
+
+		Ok(self)
 	}
 }
 
 
+// 3×3 system whose elimination swaps rows, shared by the tests of the
+// solver modules
+#[cfg(test)]
+fn swapping_system() -> CoefficientMatrix<f64> {
+	CoefficientMatrix::new(3)
+		.add_equation(Equation::new(vec![1.0, 2.0, -1.0], 2.0))
+		.add_equation(Equation::new(vec![4.0, 1.0,  2.0], 12.0))
+		.add_equation(Equation::new(vec![-2.0, 5.0, 1.0], 4.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +666,54 @@ mod tests {
             .expect("{err}");
     }
     #[test]
+    fn equation_from_terms() {
+        let equation = Equation::from_terms(4, &[(1, -1.0), (2, 2.0), (3, -1.0), (2, 0.5)], 1.0).unwrap();
+        assert_eq!(equation, Equation::new(vec![0.0, -1.0, 2.5, -1.0], 1.0));
+        assert!(matches!(Equation::from_terms(2, &[(2, 1.0)], 0.0), Err(SolveError::IndexOutOfBounds(2, 2))));
+    }
+
+    #[test]
+    fn append_unknowns() {
+        let mut matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 4.0))
+            .add_equation(Equation::new(vec![1.0, -1.0], 0.0));
+        assert_eq!(matrix.add_unknown(&[1.0, 0.0]).unwrap(), 2);
+        assert_eq!(matrix.add_unknowns(2), 3);
+        assert_eq!(matrix.size(), 5);
+        assert_eq!(matrix.rows().next().unwrap().coefficients(), &[1.0, 1.0, 1.0, 0.0, 0.0]);
+        assert!(matches!(matrix.add_unknown(&[1.0]), Err(SolveError::IncompatibleDimensions((1, 1), (2, 1)))));
+        assert_eq!(matrix.size(), 5);
+    }
+
+    #[test]
+    fn substitute_known_value() {
+        // x + y + z = 6, x - y = -1, 2x + z = 5 with z = 3
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 1.0, 1.0], 6.0))
+            .add_equation(Equation::new(vec![1.0, -1.0, 0.0], -1.0))
+            .add_equation(Equation::new(vec![2.0, 0.0, 1.0], 5.0));
+        let reduced = matrix.fix_variable(2, 3.0).unwrap();
+        assert_eq!(reduced, CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, -1.0], -1.0))
+            .add_equation(Equation::new(vec![2.0, 0.0], 2.0)));
+        let solution = reduced.solve_rectangular().unwrap();
+        assert!((solution[0] - 1.0).abs() < 1e-12 && (solution[1] - 2.0).abs() < 1e-12);
+        assert!(matches!(matrix.fix_variable(3, 0.0), Err(SolveError::IndexOutOfBounds(3, 3))));
+    }
+
+    #[test]
+    fn all_unfitting_rows_reported() {
+        let err = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![8.0], 2.0))
+            .add_equation(Equation::new(vec![0.0, 4.5, 1.0], 1.5))
+            .add_equation(Equation::new(vec![3.0, 0.0, 1.0, 2.0], 5.0))
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Equations with a number of coefficients other than 3: 0 (1), 2 (4)");
+        assert!(matches!(err, SolveError::UnfittingRows(rows, 3) if rows == vec![(0, 1), (2, 4)]));
+    }
+    #[test]
     #[should_panic]
     fn matrix_too_long() {
         let _ = CoefficientMatrix::new(2)
@@ -279,6 +732,119 @@ mod tests {
             .expect("{err}");
     }
     #[test]
+    fn read_solved_values() {
+        let mut solved = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5))
+            .validate().unwrap()
+            .solve().unwrap();
+        assert_eq!(solved[(0, 0)], 1.0);
+        assert_eq!(solved.get(1, 1), Some(1.0));
+        assert_eq!(solved.get(2, 0), None);
+        assert_eq!(solved.rhs(0), Some(0.5));
+        solved[(0, 1)] = 3.0;
+        assert_eq!(solved.get(0, 1), Some(3.0));
+    }
+    #[test]
+    fn error_codes() {
+        assert_eq!(SolveError::TooSmall(0).code(), "TooSmall");
+        assert_eq!(SolveError::TooLarge(5, 4).to_string(), "Matrix size of 5 is too large, at most 4 is supported");
+        assert_eq!(SolveError::IndexOutOfBounds(3, 2).code(), "IndexOutOfBounds");
+        let err = CoefficientMatrix::<f64>::new(2).validate().unwrap_err();
+        assert_eq!(err.code(), "UnfittingEquationAmount");
+    }
+    #[test]
+    fn unvalidated_input_fails() {
+        let short = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0], 2.0))
+            .add_equation(Equation::new(vec![0.0, 4.5], 1.5));
+        assert!(matches!(short.clone().convert(), Err(SolveError::NotValidated)));
+        assert!(matches!(short.solve(), Err(SolveError::NotValidated)));
+        assert!(matches!(CoefficientMatrix::<f64>::new(0).convert(), Err(SolveError::NotValidated)));
+    }
+
+    #[test]
+    fn solve_single_precision() {
+        let solution: Vec<f32> = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0f32, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0f32,  3.0], 2.0))
+            .validate().unwrap()
+            .convert().unwrap()
+            .solve().unwrap()
+            .solution()
+            .collect();
+        assert!((solution[0] - 0.5).abs() < 1e-6);
+        assert!((solution[1] - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn edit_equations() {
+        let mut mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0));
+        mat.set_equation(1, Equation::new(vec![1.0, 1.0], 1.0)).unwrap();
+        assert_eq!(mat.rhs(1), Some(1.0));
+        assert_eq!(mat.remove_equation(0).unwrap(), Equation::new(vec![8.0, -6.0], 2.0));
+        assert_eq!(mat.get(0, 0), Some(1.0));
+        assert!(matches!(mat.remove_equation(2), Err(SolveError::IndexOutOfBounds(2, 2))));
+        assert!(matches!(
+            mat.set_equation(5, Equation::new(vec![1.0, 1.0], 1.0)),
+            Err(SolveError::IndexOutOfBounds(5, 2))
+        ));
+    }
+    #[test]
+    fn resize_unknowns() {
+        let mut mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5));
+        mat.resize(3);
+        assert_eq!(mat.size(), 3);
+        assert_eq!(mat.rows().next().unwrap().coefficients(), &[8.0, -6.0, 0.0]);
+        mat.resize(1);
+        assert_eq!(mat.rows().nth(1).unwrap().coefficients(), &[0.0]);
+        assert_eq!(mat.rhs(1), Some(1.5));
+    }
+    #[test]
+    fn set_cells() {
+        // Filled in column by column from an empty system
+        let mut mat = CoefficientMatrix::new(2);
+        mat.set(1, 1, 4.5).unwrap();
+        assert_eq!(mat.get(0, 0), None);
+        assert_eq!(mat.rhs(1), None);
+        assert!(matches!(
+            mat.clone().validate(),
+            Err(SolveError::UnenteredCells(cells)) if cells == vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]
+        ));
+        mat.set(0, 0, 8.0).unwrap();
+        mat.set(0, 1, -6.0).unwrap();
+        mat.set_rhs(0, 2.0).unwrap();
+        mat.set_rhs(1, 1.5).unwrap();
+        // Unentered cells aren't taken for zeros, not even by `convert`
+        assert!(matches!(mat.clone().convert(), Err(SolveError::NotValidated)));
+        assert!(matches!(mat.clone().validate(), Err(SolveError::UnenteredCells(cells)) if cells == vec![(1, 0)]));
+        mat.set(1, 0, 0.0).unwrap();
+        let solution: Vec<f64> = mat.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        assert_eq!(solution, vec![0.5, 1.0 / 3.0]);
+
+        // Short equations are padded, rows can't be skipped past the size
+        let mut mat = CoefficientMatrix::new(3).add_equation(Equation::new(vec![1.0], 1.0));
+        mat.set(0, 2, 2.0).unwrap();
+        assert_eq!(mat.rows().next().unwrap().coefficients(), &[1.0, 0.0, 2.0]);
+        assert_eq!(mat.get(0, 1), None);
+        assert!(matches!(mat.set(0, 3, 1.0), Err(SolveError::IndexOutOfBounds(3, 3))));
+        assert!(matches!(mat.set_rhs(4, 1.0), Err(SolveError::IndexOutOfBounds(4, 4))));
+    }
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5));
+        let json = serde_json::to_string(&mat).unwrap();
+        assert_eq!(serde_json::from_str::<CoefficientMatrix<f64>>(&json).unwrap(), mat);
+    }
+    #[test]
     fn matrix_valid() {
         let _ = CoefficientMatrix::new(2)
             .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
@@ -0,0 +1,90 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+// Affine map x ↦ A x + t taking each `from` point as close as possible to
+// its `to` point in the least-squares sense, as the homogeneous
+// (N + 1) x (N + 1) matrix [[A, t], [0, 1]]. Every output coordinate is a
+// separate least-squares problem over the rows [x, 1], and all of them
+// share the normal equations, which are factorized once. It takes at least
+// N + 1 points that don't lie on a common hyperplane, otherwise the
+// normal equations are singular.
+pub fn fit_affine<T, const N: usize>(pairs: &[([T; N], [T; N])]) -> Result<Matrix<T>, SolveError>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	if pairs.len() < N + 1 {
+		return Err(SolveError::UnfittingEquationAmount(pairs.len(), N + 1));
+	}
+	let design: Vec<Vec<T>> = pairs.iter()
+		.map(|(from, _)| from.iter().copied().chain(Some(T::one())).collect())
+		.collect();
+	let column = |j: usize| design.iter().map(move |row| row[j]);
+
+	let normal: Vec<Vec<T>> = (0..=N)
+		.map(|i| {
+			let mut row: Vec<T> = (0..=N)
+				.map(|j| column(i).zip(column(j)).fold(T::zero(), |sum, (a, b)| sum + a * b))
+				.collect();
+			row.push(T::zero());
+			row
+		})
+		.collect();
+	let lu = CoefficientMatrix::try_from(normal)?.validate()?.lu()?;
+
+	let mut transform = Matrix::identity(N + 1);
+	let mut rhs = vec![T::zero(); N + 1];
+	for output in 0..N {
+		for (j, value) in rhs.iter_mut().enumerate() {
+			*value = column(j).zip(pairs).fold(T::zero(), |sum, (x, (_, to))| sum + x * to[output]);
+		}
+		for (j, value) in lu.solve(&rhs)?.into_iter().enumerate() {
+			transform[(output, j)] = value;
+		}
+	}
+	Ok(transform)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn fit_exact_transform() {
+        // Rotation by 90 degrees, scaling by 2 and translation by (1, -1)
+        let map = |[x, y]: [f64; 2]| [-2.0 * y + 1.0, 2.0 * x - 1.0];
+        let pairs: Vec<_> = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [3.0, 2.0]].iter()
+            .map(|&p| (p, map(p)))
+            .collect();
+        let transform = fit_affine(&pairs).unwrap();
+        let expected = [[0.0, -2.0, 1.0], [2.0, 0.0, -1.0], [0.0, 0.0, 1.0]];
+        for (i, row) in expected.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                assert!((transform[(i, j)] - value).abs() < 1e-12, "{}", transform);
+            }
+        }
+    }
+
+    #[test]
+    fn fit_noisy_translation() {
+        let pairs = [
+            ([0.0, 0.0, 0.0], [1.1, 0.0, 0.0]),
+            ([1.0, 0.0, 0.0], [1.9, 0.0, 0.0]),
+            ([0.0, 1.0, 0.0], [1.0, 1.0, 0.0]),
+            ([0.0, 0.0, 1.0], [1.0, 0.0, 1.0]),
+            ([1.0, 1.0, 1.0], [2.0, 1.0, 1.0]),
+        ];
+        let transform = fit_affine(&pairs).unwrap();
+        assert!((transform[(0, 3)] - 1.0).abs() < 0.1 && (transform[(0, 0)] - 1.0).abs() < 0.2);
+        assert!((transform[(1, 1)] - 1.0).abs() < 1e-12 && transform[(1, 3)].abs() < 1e-12);
+
+        assert!(matches!(fit_affine(&pairs[..3]), Err(SolveError::UnfittingEquationAmount(3, 4))));
+        let collinear = [([0.0, 0.0], [0.0, 0.0]), ([1.0, 1.0], [1.0, 1.0]), ([2.0, 2.0], [2.0, 2.0])];
+        assert!(fit_affine(&collinear).is_err());
+    }
+}
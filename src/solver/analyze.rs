@@ -0,0 +1,139 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use super::CoefficientMatrix;
+
+// Problem in the equations found by `analyze` without eliminating, with
+// the indices of the equations involved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+	// All coefficients and the result are zero, 0 = 0
+	ZeroRow(usize),
+	// All coefficients are zero, but the result isn't
+	ContradictoryRow(usize),
+	// The second equation is a multiple of the first
+	Duplicate(usize, usize),
+	// The coefficients of the second equation are a multiple of those of
+	// the first, but the result isn't the same multiple
+	Contradictory(usize, usize),
+}
+
+impl Issue {
+	// Whether the system certainly has no solution, as opposed to being
+	// dependent
+	pub fn is_contradiction(&self) -> bool {
+		matches!(self, Issue::ContradictoryRow(_) | Issue::Contradictory(..))
+	}
+}
+
+impl fmt::Display for Issue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Issue::ZeroRow(row) =>
+				write!(f, "Equation {} has only zero coefficients and says nothing about the unknowns", row),
+			Issue::ContradictoryRow(row) =>
+				write!(f, "Equation {} has only zero coefficients but a nonzero result", row),
+			Issue::Duplicate(first, second) =>
+				write!(f, "Equation {} is a multiple of equation {}", second, first),
+			Issue::Contradictory(first, second) =>
+				write!(f, "Equation {} contradicts equation {}: the coefficients are a multiple, the result isn't", second, first),
+		}
+	}
+}
+
+// Index and magnitude of the largest coefficient
+fn largest<T: Real>(coefficients: &[T]) -> (usize, T) {
+	coefficients.iter()
+		.enumerate()
+		.fold((0, T::zero()), |(index, max), (i, value)| {
+			if value.abs() > max { (i, value.abs()) } else { (index, max) }
+		})
+}
+
+impl<T: Real> CoefficientMatrix<T> {
+	// Look for zero equations and for pairs of equations with proportional
+	// coefficients before solving, which are the usual reasons for a
+	// `DependentSolutionSet` or `EmptySolutionSet` with systems entered by
+	// hand. Dependencies between more than two equations aren't found. An
+	// empty list doesn't mean that the system is solvable.
+	pub fn analyze(&self) -> Vec<Issue> {
+		let tolerance = T::epsilon() * T::from(self.size.max(1)).unwrap_or_else(T::one);
+		let mut issues = Vec::new();
+		// Equations already reported, which aren't compared again
+		let mut reported = vec![false; self.matrix.len()];
+
+		for (i, equation) in self.matrix.iter().enumerate() {
+			let (pivot, max) = largest(&equation.coefficients);
+			if max.is_zero() {
+				issues.push(if equation.result.is_zero() { Issue::ZeroRow(i) } else { Issue::ContradictoryRow(i) });
+				reported[i] = true;
+				continue;
+			}
+			if reported[i] {
+				continue;
+			}
+			for (j, other) in self.matrix.iter().enumerate().skip(i + 1) {
+				if reported[j] || other.coefficients.len() != equation.coefficients.len() {
+					continue;
+				}
+				let factor = other.coefficients[pivot] / equation.coefficients[pivot];
+				let scale = max * factor.abs();
+				let proportional = !factor.is_zero() && equation.coefficients.iter()
+					.zip(&other.coefficients)
+					.all(|(&a, &b)| (b - factor * a).abs() <= tolerance * scale);
+				if !proportional {
+					continue;
+				}
+				let expected = factor * equation.result;
+				let scale = expected.abs().max(other.result.abs());
+				issues.push(if (other.result - expected).abs() <= tolerance * scale {
+					Issue::Duplicate(i, j)
+				} else {
+					Issue::Contradictory(i, j)
+				});
+				reported[j] = true;
+			}
+		}
+		issues
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn find_issues() {
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 2.0, -1.0], 3.0))
+            .add_equation(Equation::new(vec![0.0, 0.0, 0.0], 0.0))
+            .add_equation(Equation::new(vec![-0.5, -1.0, 0.5], -1.5))
+            .add_equation(Equation::new(vec![2.0, 4.0, -2.0], 5.0))
+            .add_equation(Equation::new(vec![0.0, 0.0, 0.0], 1.0))
+            .add_equation(Equation::new(vec![1.0, 1.0, 1.0], 1.0));
+        let issues = matrix.analyze();
+        assert_eq!(issues, vec![
+            Issue::Duplicate(0, 2),
+            Issue::Contradictory(0, 3),
+            Issue::ZeroRow(1),
+            Issue::ContradictoryRow(4),
+        ]);
+        assert!(!issues[0].is_contradiction());
+        assert!(issues[1].is_contradiction());
+        assert_eq!(issues[0].to_string(), "Equation 2 is a multiple of equation 0");
+    }
+
+    #[test]
+    fn rounding_tolerated() {
+        let matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![0.1 * 3.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![0.3, 1.0], 2.0));
+        assert_eq!(matrix.analyze(), vec![Issue::Duplicate(0, 1)]);
+        let matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![0.3, 1.0], 2.0))
+            .add_equation(Equation::new(vec![0.3, 1.0 + 1e-9], 2.0));
+        assert!(matrix.analyze().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::SolveError;
+
+// Square matrix that is zero except for `lower` diagonals below and `upper`
+// diagonals above the main diagonal, as arising from finite differences.
+// Storage and elimination are O(n (lower + upper)) instead of O(n²) and
+// O(n³). Partial pivoting may fill in `lower` more diagonals above, so each
+// row keeps room for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Banded<T> {
+	size: usize,
+	lower: usize,
+	upper: usize,
+	// Row i holds the columns i - lower up to i + lower + upper
+	data: Vec<T>,
+}
+
+impl<T: Real> Banded<T> {
+	pub fn zeros(size: usize, lower: usize, upper: usize) -> Self {
+		Banded {
+			size,
+			lower,
+			upper,
+			data: vec![T::zero(); size * (2 * lower + upper + 1)],
+		}
+	}
+
+	// Tridiagonal matrix with the given diagonals, `below` and `above`
+	// being one shorter than `diagonal`
+	pub fn tridiagonal(below: &[T], diagonal: &[T], above: &[T]) -> Result<Self, SolveError> {
+		let size = diagonal.len();
+		if below.len() + 1 != size.max(1) || above.len() + 1 != size.max(1) {
+			return Err(SolveError::UnfittingCoefficientAmount(below.len().max(above.len()), size.saturating_sub(1)));
+		}
+		let mut matrix = Banded::zeros(size, 1, 1);
+		for (i, &value) in diagonal.iter().enumerate() {
+			matrix.set(i, i, value)?;
+		}
+		for (i, (&below, &above)) in below.iter().zip(above).enumerate() {
+			matrix.set(i + 1, i, below)?;
+			matrix.set(i, i + 1, above)?;
+		}
+		Ok(matrix)
+	}
+
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
+	pub fn bandwidths(&self) -> (usize, usize) {
+		(self.lower, self.upper)
+	}
+
+	fn width(&self) -> usize {
+		2 * self.lower + self.upper + 1
+	}
+
+	fn position(&self, row: usize, column: usize) -> Option<usize> {
+		let offset = (column + self.lower).checked_sub(row)?;
+		if row < self.size && column < self.size && offset < self.width() {
+			Some(row * self.width() + offset)
+		} else {
+			None
+		}
+	}
+
+	// Entry at the given position, zero outside the band
+	pub fn get(&self, row: usize, column: usize) -> T {
+		self.position(row, column).map_or(T::zero(), |index| self.data[index])
+	}
+
+	// Fails for positions outside the band
+	pub fn set(&mut self, row: usize, column: usize, value: T) -> Result<(), SolveError> {
+		if row >= self.size || column > row + self.upper || row > column + self.lower {
+			return Err(SolveError::IndexOutOfBounds(row * self.size + column, self.size * self.size));
+		}
+		let index = self.position(row, column).expect("position inside the band");
+		self.data[index] = value;
+		Ok(())
+	}
+
+	// Solve self * x = b by banded Gaussian elimination with partial
+	// pivoting
+	pub fn solve(&self, b: &[T]) -> Result<Vec<T>, SolveError> {
+		let n = self.size;
+		if b.len() != n {
+			return Err(SolveError::IncompatibleDimensions((n, n), (b.len(), 1)));
+		}
+		if n == 0 {
+			return Err(SolveError::TooSmall(0));
+		}
+		let mut a = self.clone();
+		let mut x = b.to_vec();
+		// Furthest column right of the diagonal after fill-in
+		let reach = self.lower + self.upper;
+		for k in 0..n {
+			let last = (k + self.lower).min(n - 1);
+			let end = (k + reach).min(n - 1);
+			let pivot = (k..=last)
+				.max_by(|&i, &j| a.get(i, k).abs().partial_cmp(&a.get(j, k).abs()).unwrap_or(core::cmp::Ordering::Equal))
+				.unwrap_or(k);
+			if a.get(pivot, k).is_zero() {
+				return Err(SolveError::DependentSolutionSet);
+			}
+			if pivot != k {
+				for j in k..=end {
+					let (p, q) = (a.position(k, j).unwrap(), a.position(pivot, j).unwrap());
+					a.data.swap(p, q);
+				}
+				x.swap(k, pivot);
+			}
+			for i in k + 1..=last {
+				let ratio = a.get(i, k) / a.get(k, k);
+				if ratio.is_zero() {
+					continue;
+				}
+				for j in k..=end {
+					let index = a.position(i, j).unwrap();
+					a.data[index] = a.data[index] - ratio * a.get(k, j);
+				}
+				x[i] = x[i] - ratio * x[k];
+			}
+		}
+		for i in (0..n).rev() {
+			let end = (i + reach).min(n - 1);
+			let sum = (i + 1..=end).fold(x[i], |sum, j| sum - a.get(i, j) * x[j]);
+			x[i] = sum / a.get(i, i);
+		}
+		Ok(x)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn solve_tridiagonal() {
+        // -u'' = 2 on four interior points with zero boundaries, h = 1
+        let matrix = Banded::tridiagonal(&[-1.0; 3], &[2.0; 4], &[-1.0; 3]).unwrap();
+        let solution = matrix.solve(&[2.0; 4]).unwrap();
+        for (actual, expected) in solution.iter().zip(&[4.0, 6.0, 6.0, 4.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn pivot_within_band() {
+        // Needs a row swap in the first column
+        let mut matrix = Banded::zeros(3, 1, 1);
+        for &(i, j, value) in &[(0, 0, 0.0), (0, 1, 1.0), (1, 0, 2.0), (1, 1, 1.0), (1, 2, 1.0), (2, 1, 1.0), (2, 2, 3.0)] {
+            matrix.set(i, j, value).unwrap();
+        }
+        let solution = matrix.solve(&[1.0, 4.0, 7.0]).unwrap();
+        for (actual, expected) in solution.iter().zip(&[0.5, 1.0, 2.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert_eq!(matrix.get(0, 2), 0.0);
+        assert!(matches!(matrix.set(0, 2, 1.0), Err(SolveError::IndexOutOfBounds(..))));
+        assert!(matches!(Banded::<f64>::zeros(2, 1, 0).solve(&[1.0, 1.0]), Err(SolveError::DependentSolutionSet)));
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use num::rational::Ratio;
+use num::{CheckedDiv, CheckedMul, CheckedSub, Integer};
+
+use super::{CoefficientMatrix, Equation, Result, SolveError};
+
+// Fraction-free elimination after Bareiss for integer matrices such as
+// `CoefficientMatrix<i64>` or `CoefficientMatrix<BigInt>`. Every division
+// performed is exact, so all intermediate values stay integers and the
+// determinant and solutions are computed without rounding. Intermediate
+// values still grow, so every product and difference is checked and fails
+// with `SolveError::Overflow` rather than wrapping around for fixed-width
+// integers.
+
+// Augmented rows in row echelon form together with the rank of the
+// coefficients and whether an odd number of rows was swapped
+struct Echelon<T> {
+	rows: Vec<Vec<T>>,
+	rank: usize,
+	odd_swaps: bool,
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Integer + Clone + CheckedMul + CheckedSub
+{
+	fn bareiss_echelon(self) -> core::result::Result<Echelon<T>, SolveError> {
+		let size = self.size;
+		let mut rows: Vec<Vec<T>> = self.matrix.into_iter()
+			.map(|equation| {
+				let mut row = equation.coefficients;
+				row.push(equation.result);
+				row
+			})
+			.collect();
+
+		let mut previous = T::one();
+		let mut rank = 0;
+		let mut odd_swaps = false;
+		for column in 0..size {
+			let pivot_row = match (rank..size).find(|&row| !rows[row][column].is_zero()) {
+				Some(row) => row,
+				None => continue,
+			};
+			if pivot_row != rank {
+				rows.swap(pivot_row, rank);
+				odd_swaps = !odd_swaps;
+			}
+
+			let pivot = rows[rank][column].clone();
+			let pivot_row = rows[rank].clone();
+			for (index, row) in rows[rank+1..].iter_mut().enumerate() {
+				let factor = row[column].clone();
+				for (value, above) in row.iter_mut().zip(pivot_row.iter()).skip(column + 1) {
+					let product = pivot.checked_mul(value)
+						.zip(factor.checked_mul(above))
+						.and_then(|(a, b)| a.checked_sub(&b))
+						.ok_or(SolveError::Overflow(rank + 1 + index, column))?;
+					*value = product / previous.clone();
+				}
+				row[column] = T::zero();
+			}
+			previous = pivot;
+			rank += 1;
+		}
+
+		Ok(Echelon {
+			rows,
+			rank,
+			odd_swaps,
+		})
+	}
+
+	// Convert a validated matrix to fraction-free upper triangular form. The
+	// last diagonal entry is the determinant, up to the sign of the row swaps.
+	pub fn bareiss(self) -> Result<T> {
+		self.ensure_validated()?;
+		let size = self.size;
+		let echelon = self.bareiss_echelon()?;
+		if echelon.rank < size {
+			// The remaining rows have no coefficients left, so any nonzero
+			// result makes the system inconsistent
+			let consistent = echelon.rows[echelon.rank..].iter().all(|row| row[size].is_zero());
+			return Err(if consistent {
+				SolveError::DependentSolutionSet
+			} else {
+				SolveError::EmptySolutionSet
+			});
+		}
+
+		let matrix = echelon.rows.into_iter()
+			.map(|mut row| {
+				let result = row.pop().unwrap();
+				Equation::new(row, result)
+			})
+			.collect();
+		Ok(CoefficientMatrix {
+			size,
+			matrix,
+			unentered: BTreeSet::new(),
+		})
+	}
+
+	// Exact determinant of the coefficients of a validated matrix
+	pub fn determinant_exact(&self) -> core::result::Result<T, SolveError> {
+		let size = self.size;
+		let echelon = self.clone().validate()?.bareiss_echelon()?;
+		if echelon.rank < size {
+			return Ok(T::zero());
+		}
+
+		let determinant = echelon.rows[size - 1][size - 1].clone();
+		Ok(if echelon.odd_swaps {
+			T::zero() - determinant
+		} else {
+			determinant
+		})
+	}
+
+	// Exact rational solution of a validated matrix
+	pub fn solve_exact(&self) -> core::result::Result<Vec<Ratio<T>>, SolveError>
+	where
+		Ratio<T>: CheckedMul + CheckedSub + CheckedDiv
+	{
+		let triangular = self.clone().validate()?.bareiss()?;
+		let size = triangular.size;
+		let mut solution = vec![Ratio::from_integer(T::zero()); size];
+		for row in (0..size).rev() {
+			let equation = &triangular.matrix[row];
+			let overflow = || SolveError::Overflow(row, row);
+			let mut sum = Ratio::from_integer(equation.result.clone());
+			for (coefficient, value) in equation.coefficients.iter().zip(solution.iter()).skip(row + 1) {
+				let term = Ratio::from_integer(coefficient.clone()).checked_mul(value).ok_or_else(overflow)?;
+				sum = sum.checked_sub(&term).ok_or_else(overflow)?;
+			}
+			solution[row] = sum.checked_div(&Ratio::from_integer(equation.coefficients[row].clone())).ok_or_else(overflow)?;
+		}
+		Ok(solution)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use num::rational::Ratio;
+
+    fn system() -> CoefficientMatrix<i64> {
+        CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![2, 1, -1], 8))
+            .add_equation(Equation::new(vec![-3, -1, 2], -11))
+            .add_equation(Equation::new(vec![-2, 1, 2], -3))
+    }
+
+    #[test]
+    fn exact_determinant() {
+        assert_eq!(system().determinant_exact().unwrap(), -1);
+        let singular = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1, 2], 3))
+            .add_equation(Equation::new(vec![2, 4], 6));
+        assert_eq!(singular.determinant_exact().unwrap(), 0);
+    }
+
+    #[test]
+    fn exact_solution() {
+        let solution = system().solve_exact().unwrap();
+        assert_eq!(solution, vec![Ratio::from_integer(2), Ratio::from_integer(3), Ratio::from_integer(-1)]);
+
+        let halves = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1, 1], 1))
+            .add_equation(Equation::new(vec![1, -1], 0))
+            .solve_exact().unwrap();
+        assert_eq!(halves, vec![Ratio::new(1, 2), Ratio::new(1, 2)]);
+    }
+
+    #[test]
+    fn singular_systems() {
+        let inconsistent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1, 2], 3))
+            .add_equation(Equation::new(vec![2, 4], 7));
+        assert!(matches!(inconsistent.solve_exact(), Err(SolveError::EmptySolutionSet)));
+    }
+
+    #[test]
+    fn report_overflow() {
+        // The first elimination leaves entries around 2^40, whose products in
+        // the second one exceed i64
+        let big = 1 << 20;
+        let system = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![big, 1, 1], 1i64))
+            .add_equation(Equation::new(vec![1, big, 1], 1))
+            .add_equation(Equation::new(vec![1, 1, big], 1));
+        assert!(matches!(system.solve_exact(), Err(SolveError::Overflow(2, 1))));
+        assert!(matches!(system.determinant_exact(), Err(SolveError::Overflow(2, 1))));
+        assert_eq!(SolveError::Overflow(2, 1).code(), "Overflow");
+
+        // The same system fits into i128
+        let system = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![big as i128, 1, 1], 1))
+            .add_equation(Equation::new(vec![1, big as i128, 1], 1))
+            .add_equation(Equation::new(vec![1, 1, big as i128], 1));
+        assert_eq!(system.solve_exact().unwrap()[0], Ratio::new(1, big as i128 + 2));
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Result, SolveError};
+
+// Dense augmented matrix, size rows of size + 1 values stored contiguously
+struct Augmented<T> {
+	size: usize,
+	data: Vec<T>,
+}
+
+impl<T: Copy> Augmented<T> {
+	fn width(&self) -> usize {
+		self.size + 1
+	}
+
+	fn get(&self, row: usize, column: usize) -> T {
+		self.data[row * self.width() + column]
+	}
+
+	fn swap_rows(&mut self, a: usize, b: usize) {
+		let width = self.width();
+		for column in 0..width {
+			self.data.swap(a * width + column, b * width + column);
+		}
+	}
+
+	// Row `source` and the row `target` below it, mutably
+	fn pair(&mut self, source: usize, target: usize) -> (&[T], &mut [T]) {
+		let width = self.width();
+		let (upper, lower) = self.data.split_at_mut(target * width);
+		(&upper[source * width..(source + 1) * width], &mut lower[..width])
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve like `convert` followed by `solve`, but factorize the matrix in
+	// panels of `block` columns. The updates of the trailing matrix then
+	// run over tiles of `block` columns which stay in cache, which pays off
+	// for systems of several hundred unknowns and more. The pivots are the
+	// same as `convert`'s. Nothing is observed.
+	pub fn solve_blocked(self, block: usize) -> Result<T> {
+		self.ensure_validated()?;
+		let size = self.size;
+		let block = block.max(1);
+		let mut data = Vec::with_capacity(size * (size + 1));
+		for equation in &self.matrix {
+			data.extend_from_slice(&equation.coefficients);
+			data.push(equation.result);
+		}
+		let mut a = Augmented { size, data };
+
+		for start in (0..size).step_by(block) {
+			let end = (start + block).min(size);
+			a.factorize_panel(start, end);
+			a.update_trailing(start, end, block);
+		}
+
+		// Back substitution, leaving the solution in the last column
+		let mut solution = a.data.iter().skip(size).step_by(size + 1).copied().collect::<Vec<T>>();
+		for row in (0..size).rev() {
+			let divisor = a.get(row, row);
+			let mut value = solution[row];
+			for (column, x) in solution.iter().enumerate().skip(row + 1) {
+				value -= a.get(row, column) * *x;
+			}
+			if divisor.is_zero() {
+				return Err(if value.is_zero() {
+					SolveError::DependentSolutionSet
+				} else {
+					SolveError::EmptySolutionSet
+				});
+			}
+			solution[row] = value / divisor;
+		}
+
+		let mut solved = self;
+		solved.set_solved(solution);
+		Ok(solved)
+	}
+
+	// Overwrite a square matrix with the identity and the given solution,
+	// the form `solve` leaves it in
+	pub(super) fn set_solved(&mut self, solution: Vec<T>) {
+		for (row, (equation, value)) in self.matrix.iter_mut().zip(solution).enumerate() {
+			for (column, coefficient) in equation.coefficients.iter_mut().enumerate() {
+				*coefficient = if row == column { T::one() } else { T::zero() };
+			}
+			equation.result = value;
+		}
+	}
+}
+
+impl<T: Real + SubAssign> Augmented<T> {
+	// Eliminate the columns start..end below the diagonal, updating only
+	// the panel itself. The multipliers are kept in place of the zeros.
+	fn factorize_panel(&mut self, start: usize, end: usize) {
+		let size = self.size;
+		for column in start..end {
+			// The first row with the largest magnitude, as in `convert`
+			let mut pivot_row = column;
+			for row in column + 1..size {
+				if self.get(row, column).abs() > self.get(pivot_row, column).abs() {
+					pivot_row = row;
+				}
+			}
+			if pivot_row != column {
+				self.swap_rows(pivot_row, column);
+			}
+			let pivot = self.get(column, column);
+			if pivot.is_zero() {
+				continue;
+			}
+			for row in column + 1..size {
+				let (source, target) = self.pair(column, row);
+				let ratio = target[column] / pivot;
+				target[column] = ratio;
+				for c in column + 1..end {
+					target[c] -= source[c] * ratio;
+				}
+			}
+		}
+	}
+
+	// Apply the panel's eliminations to the columns right of it, including
+	// the right-hand side, one tile of columns at a time
+	fn update_trailing(&mut self, start: usize, end: usize, block: usize) {
+		let (size, width) = (self.size, self.width());
+		for tile in (end..width).step_by(block) {
+			let tile_end = (tile + block).min(width);
+			for column in start..end {
+				if self.get(column, column).is_zero() {
+					continue;
+				}
+				for row in column + 1..size {
+					let (source, target) = self.pair(column, row);
+					let ratio = target[column];
+					for c in tile..tile_end {
+						target[c] -= source[c] * ratio;
+					}
+				}
+			}
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn blocked_matches_scalar() {
+        // Diagonally dominant with some structure, solved in blocks that
+        // don't divide the size
+        let size = 23;
+        let mut mat = CoefficientMatrix::new(size);
+        for i in 0..size {
+            let coefficients = (0..size)
+                .map(|j| if i == j { 30.0 } else { ((i * 7 + j * 3) % 11) as f64 - 5.0 })
+                .collect();
+            mat = mat.add_equation(Equation::new(coefficients, i as f64));
+        }
+        let expected: Vec<f64> = mat.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        for &block in &[1, 4, 8, 64] {
+            let solved = mat.clone().solve_blocked(block).unwrap();
+            for (x, y) in solved.solution().zip(&expected) {
+                assert!((x - y).abs() < 1e-12);
+            }
+            assert_eq!(solved.rows().nth(1).unwrap().coefficients()[1], 1.0);
+        }
+    }
+
+    #[test]
+    fn blocked_pivots_and_errors() {
+        let swapped = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![0.0, 2.0], 4.0))
+            .add_equation(Equation::new(vec![3.0, 1.0], 5.0));
+        assert_eq!(swapped.solve_blocked(1).unwrap().solution().collect::<Vec<_>>(), vec![1.0, 2.0]);
+
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 6.0));
+        assert!(matches!(dependent.solve_blocked(2), Err(SolveError::DependentSolutionSet)));
+        let empty = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 7.0));
+        assert!(matches!(empty.solve_blocked(2), Err(SolveError::EmptySolutionSet)));
+        assert!(matches!(CoefficientMatrix::<f64>::new(2).solve_blocked(2), Err(SolveError::NotValidated)));
+    }
+}
@@ -0,0 +1,128 @@
+use num::traits::real::Real;
+use num::{Num, Zero};
+use core::fmt;
+use core::ops::{Deref, SubAssign};
+
+use super::{CoefficientMatrix, Equation, Observer, SolveError};
+
+// Typestates for processing a system. Each state only offers the step that
+// may follow it, so within this chain calling `convert` on an unvalidated
+// matrix or `solve` on one that isn't triangular is a compile error:
+//
+//     let solved = CoefficientMatrix::build(2)
+//         .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+//         .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+//         .validate()?
+//         .convert()?
+//         .solve()?;
+//
+// Every state dereferences to the underlying `CoefficientMatrix` for reading.
+// The methods of `CoefficientMatrix` itself stay available and check the
+// order at run time instead, `convert` and `solve` failing with
+// `NotValidated` on a matrix that isn't a valid square system, so the
+// states document the order rather than enforce it for every caller.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Builder<T>(CoefficientMatrix<T>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T>(CoefficientMatrix<T>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangular<T>(CoefficientMatrix<T>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solved<T>(CoefficientMatrix<T>);
+
+impl<T> CoefficientMatrix<T> {
+	pub fn build(size: usize) -> Builder<T> {
+		Builder(CoefficientMatrix::new(size))
+	}
+}
+
+impl<T> Builder<T> {
+	pub fn add_equation(self, equation: Equation<T>) -> Self {
+		Builder(self.0.add_equation(equation))
+	}
+
+	pub fn validate(self) -> Result<Validated<T>, SolveError> {
+		self.0.validate().map(Validated)
+	}
+}
+
+impl<T> Validated<T>
+where
+	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
+{
+	pub fn convert(self) -> Result<Triangular<T>, SolveError> {
+		self.0.convert().map(Triangular)
+	}
+
+	pub fn convert_observed<O: Observer<T>>(self, observer: &mut O) -> Result<Triangular<T>, SolveError> {
+		self.0.convert_observed(observer).map(Triangular)
+	}
+}
+
+impl<T> Triangular<T>
+where
+	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
+{
+	pub fn solve(self) -> Result<Solved<T>, SolveError> {
+		self.0.solve().map(Solved)
+	}
+
+	pub fn solve_observed<O: Observer<T>>(self, observer: &mut O) -> Result<Solved<T>, SolveError> {
+		self.0.solve_observed(observer).map(Solved)
+	}
+}
+
+macro_rules! matrix_state {
+	($($state:ident),*) => {$(
+		impl<T> $state<T> {
+			pub fn into_inner(self) -> CoefficientMatrix<T> {
+				self.0
+			}
+		}
+
+		impl<T> Deref for $state<T> {
+			type Target = CoefficientMatrix<T>;
+
+			fn deref(&self) -> &CoefficientMatrix<T> {
+				&self.0
+			}
+		}
+	)*};
+}
+
+matrix_state!(Builder, Validated, Triangular, Solved);
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn build_and_solve() {
+        let solved = CoefficientMatrix::build(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .validate().unwrap()
+            .convert().unwrap()
+            .solve().unwrap();
+        let expected = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .validate().unwrap()
+            .convert().unwrap()
+            .solve().unwrap();
+        assert_eq!(solved.into_inner(), expected);
+    }
+
+    #[test]
+    fn build_rejects_invalid() {
+        let result = CoefficientMatrix::<f64>::build(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .validate();
+        assert!(matches!(result, Err(SolveError::UnfittingEquationAmount(1, 2))));
+    }
+}
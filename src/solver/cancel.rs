@@ -0,0 +1,22 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Shared flag for abandoning a solve in progress. Clones refer to the same
+// flag, so one clone can be handed to an `Elimination` while another stays
+// with whoever decides to cancel.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+	pub fn new() -> Self {
+		CancelToken::default()
+	}
+
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+}
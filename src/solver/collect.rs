@@ -0,0 +1,94 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::convert::TryFrom;
+use core::iter::FromIterator;
+
+use super::{CoefficientMatrix, Equation, SolveError};
+
+// Build a validated matrix from augmented rows, the last element of each row
+// being its right-hand side
+impl<T> TryFrom<Vec<Vec<T>>> for CoefficientMatrix<T> {
+	type Error = SolveError;
+
+	fn try_from(rows: Vec<Vec<T>>) -> Result<Self, SolveError> {
+		let size = rows.len();
+		let mut matrix = CoefficientMatrix::new(size);
+		for mut row in rows {
+			let result = match row.pop() {
+				Some(result) => result,
+				None => return Err(SolveError::UnfittingCoefficientAmount(0, size)),
+			};
+			matrix = matrix.add_equation(Equation::new(row, result));
+		}
+		matrix.validate()
+	}
+}
+
+// Collect equations into a matrix with as many unknowns as the first
+// equation has coefficients. Like `add_equation` this doesn't validate;
+// collect into a `Result` for that:
+//
+//     let matrix: CoefficientMatrix<f64> = equations.into_iter().collect();
+//     let validated: Result<CoefficientMatrix<f64>, SolveError> = equations.into_iter().collect();
+impl<T> FromIterator<Equation<T>> for CoefficientMatrix<T> {
+	fn from_iter<I: IntoIterator<Item = Equation<T>>>(iter: I) -> Self {
+		let matrix: Vec<Equation<T>> = iter.into_iter().collect();
+		CoefficientMatrix {
+			size: matrix.first().map_or(0, |equation| equation.coefficients.len()),
+			matrix,
+			unentered: BTreeSet::new(),
+		}
+	}
+}
+
+impl<T> FromIterator<Equation<T>> for Result<CoefficientMatrix<T>, SolveError> {
+	fn from_iter<I: IntoIterator<Item = Equation<T>>>(iter: I) -> Self {
+		iter.into_iter().collect::<CoefficientMatrix<T>>().validate()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::convert::TryFrom;
+
+    fn expected() -> CoefficientMatrix<f64> {
+        CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+    }
+
+    #[test]
+    fn try_from_rows() {
+        let mat = CoefficientMatrix::try_from(vec![vec![8.0, -6.0, 2.0], vec![2.0, 3.0, 2.0]]).unwrap();
+        assert_eq!(mat, expected());
+        let short = CoefficientMatrix::try_from(vec![vec![8.0, 2.0], vec![2.0, 3.0, 2.0]]);
+        assert!(matches!(short, Err(SolveError::UnfittingRows(rows, 2)) if rows == vec![(0, 1)]));
+    }
+
+    #[test]
+    fn collect_equations() {
+        let mat: CoefficientMatrix<f64> = vec![
+            Equation::new(vec![8.0, -6.0], 2.0),
+            Equation::new(vec![2.0,  3.0], 2.0),
+        ].into_iter().collect();
+        assert_eq!(mat, expected());
+        assert!(mat.validate().is_ok());
+
+        let ragged: CoefficientMatrix<f64> = vec![
+            Equation::new(vec![8.0, -6.0], 2.0),
+            Equation::new(vec![2.0], 2.0),
+        ].into_iter().collect();
+        assert_eq!(ragged.size(), 2);
+        assert!(matches!(ragged.validate(), Err(SolveError::UnfittingRows(rows, 2)) if rows == vec![(1, 1)]));
+        assert_eq!(std::iter::empty().collect::<CoefficientMatrix<f64>>().size(), 0);
+
+        let validated: std::result::Result<CoefficientMatrix<f64>, SolveError> = expected().rows().cloned().collect();
+        assert_eq!(validated.unwrap(), expected());
+        let ragged: std::result::Result<CoefficientMatrix<f64>, SolveError> = vec![
+            Equation::new(vec![8.0, -6.0], 2.0),
+            Equation::new(vec![2.0], 2.0),
+        ].into_iter().collect();
+        assert!(matches!(ragged, Err(SolveError::UnfittingRows(rows, 2)) if rows == vec![(1, 1)]));
+    }
+}
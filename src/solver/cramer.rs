@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, SolveError};
+
+// Largest system `solve_cramer` accepts. Cofactor expansion takes n!
+// products, which stops being cheap soon after.
+const CRAMER_MAX_SIZE: usize = 4;
+
+// The determinants of Cramer's rule, x_i = det(A_i) / det(A), where A_i is
+// A with column i replaced by the right-hand side
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cramer<T> {
+	determinant: T,
+	replaced: Vec<T>,
+}
+
+impl<T: Real> Cramer<T> {
+	pub fn determinant(&self) -> T {
+		self.determinant
+	}
+
+	// det(A_i) for every unknown i
+	pub fn replaced(&self) -> &[T] {
+		&self.replaced
+	}
+
+	pub fn solution(&self) -> Vec<T> {
+		self.replaced.iter().map(|&value| value / self.determinant).collect()
+	}
+}
+
+// Laplace expansion along the first row of the columns in `columns`
+fn cofactor_determinant<T: Real>(rows: &[&[T]], columns: &mut Vec<usize>) -> T {
+	let row = rows.len() - columns.len();
+	if columns.len() == 1 {
+		return rows[row][columns[0]];
+	}
+	let mut sum = T::zero();
+	for position in 0..columns.len() {
+		let column = columns.remove(position);
+		let minor = rows[row][column] * cofactor_determinant(rows, columns);
+		sum = if position % 2 == 0 { sum + minor } else { sum - minor };
+		columns.insert(position, column);
+	}
+	sum
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Cramer's rule for validated systems of up to `CRAMER_MAX_SIZE`
+	// unknowns, mainly to show the method. Singular systems are classified
+	// by elimination since vanishing determinants alone can't tell dependent
+	// from inconsistent systems.
+	pub fn cramer(&self) -> Result<Cramer<T>, SolveError> {
+		self.ensure_validated()?;
+		let size = self.size;
+		if size > CRAMER_MAX_SIZE {
+			return Err(SolveError::TooLarge(size, CRAMER_MAX_SIZE));
+		}
+
+		let mut rows: Vec<Vec<T>> = self.matrix.iter().map(|equation| equation.coefficients.clone()).collect();
+		let determinant = {
+			let view: Vec<&[T]> = rows.iter().map(Vec::as_slice).collect();
+			cofactor_determinant(&view, &mut (0..size).collect())
+		};
+		if determinant.is_zero() {
+			self.clone().convert()?.solve()?;
+			// Rounding in elimination may still find a solution
+			return Err(SolveError::DependentSolutionSet);
+		}
+
+		let replaced = (0..size)
+			.map(|column| {
+				for (row, equation) in rows.iter_mut().zip(&self.matrix) {
+					row[column] = equation.result;
+				}
+				let view: Vec<&[T]> = rows.iter().map(Vec::as_slice).collect();
+				let value = cofactor_determinant(&view, &mut (0..size).collect());
+				for (row, equation) in rows.iter_mut().zip(&self.matrix) {
+					row[column] = equation.coefficients[column];
+				}
+				value
+			})
+			.collect();
+		Ok(Cramer { determinant, replaced })
+	}
+
+	pub fn solve_cramer(&self) -> Result<Vec<T>, SolveError> {
+		self.cramer().map(|cramer| cramer.solution())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn cramers_rule() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0));
+        let cramer = mat.cramer().unwrap();
+        assert_eq!(cramer.determinant(), 36.0);
+        assert_eq!(cramer.replaced(), &[18.0, 12.0]);
+        assert_eq!(cramer.solution(), vec![0.5, 1.0 / 3.0]);
+
+        let mat = swapping_system();
+        assert_eq!(mat.cramer().unwrap().determinant(), -47.0);
+        let expected: Vec<f64> = mat.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        for (x, y) in mat.solve_cramer().unwrap().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cramer_limits() {
+        let mut large = CoefficientMatrix::new(5);
+        for i in 0..5 {
+            large = large.add_equation(Equation::new((0..5).map(|j| if i == j { 1.0 } else { 0.0 }).collect(), 1.0));
+        }
+        assert!(matches!(large.solve_cramer(), Err(SolveError::TooLarge(5, 4))));
+
+        let empty = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 7.0));
+        assert!(matches!(empty.solve_cramer(), Err(SolveError::EmptySolutionSet)));
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 6.0));
+        assert!(matches!(dependent.solve_cramer(), Err(SolveError::DependentSolutionSet)));
+    }
+}
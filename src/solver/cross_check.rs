@@ -0,0 +1,139 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, SolveError};
+
+// Solution of `convert` and `solve` next to one of an independent, naive
+// Gauss-Jordan reduction with complete pivoting that shares no code with
+// the elimination, for tools that want a second opinion on a result
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossCheck<T> {
+	solution: Vec<T>,
+	// `None` if the reference found the system singular
+	reference: Option<Vec<T>>,
+	discrepancies: Vec<usize>,
+}
+
+impl<T> CrossCheck<T> {
+	pub fn solution(&self) -> &[T] {
+		&self.solution
+	}
+
+	pub fn reference(&self) -> Option<&[T]> {
+		self.reference.as_deref()
+	}
+
+	// Unknowns on which both solutions differ by more than the tolerance
+	pub fn discrepancies(&self) -> &[usize] {
+		&self.discrepancies
+	}
+
+	pub fn is_consistent(&self) -> bool {
+		self.discrepancies.is_empty()
+	}
+}
+
+// Reduce [A | b] to [I | x], always pivoting on the largest remaining entry
+fn reference_solve<T: Real>(mut rows: Vec<Vec<T>>) -> Option<Vec<T>> {
+	let n = rows.len();
+	// Unknown belonging to each column after column swaps
+	let mut order: Vec<usize> = (0..n).collect();
+	for k in 0..n {
+		let mut pivot = (k, k);
+		for (i, row) in rows.iter().enumerate().skip(k) {
+			for (j, value) in row.iter().enumerate().take(n).skip(k) {
+				if value.abs() > rows[pivot.0][pivot.1].abs() {
+					pivot = (i, j);
+				}
+			}
+		}
+		if rows[pivot.0][pivot.1].is_zero() {
+			return None;
+		}
+		rows.swap(k, pivot.0);
+		for row in rows.iter_mut() {
+			row.swap(k, pivot.1);
+		}
+		order.swap(k, pivot.1);
+
+		let divisor = rows[k][k];
+		for value in rows[k].iter_mut() {
+			*value = *value / divisor;
+		}
+		let pivot_row = rows[k].clone();
+		for (i, row) in rows.iter_mut().enumerate() {
+			if i == k {
+				continue;
+			}
+			let factor = row[k];
+			for (value, &above) in row.iter_mut().zip(&pivot_row) {
+				*value = *value - factor * above;
+			}
+		}
+	}
+	let mut solution = vec![T::zero(); n];
+	for (row, &unknown) in rows.iter().zip(&order) {
+		solution[unknown] = row[n];
+	}
+	Some(solution)
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve a validated matrix and compare the solution against the
+	// reference. Unknowns count as discrepant if they differ by more than
+	// `tolerance` times the larger magnitude, or than `tolerance` near zero.
+	pub fn cross_check(&self, tolerance: T) -> Result<CrossCheck<T>, SolveError> {
+		self.ensure_validated()?;
+		let solution: Vec<T> = self.clone().convert()?.solve()?.solution().collect();
+		let rows = self.matrix.iter()
+			.map(|equation| {
+				let mut row = equation.coefficients.clone();
+				row.push(equation.result);
+				row
+			})
+			.collect();
+		let reference = reference_solve(rows);
+		let discrepancies = match &reference {
+			Some(reference) => solution.iter()
+				.zip(reference)
+				.enumerate()
+				.filter(|&(_, (&a, &b))| (a - b).abs() > tolerance * a.abs().max(b.abs()).max(T::one()))
+				.map(|(index, _)| index)
+				.collect(),
+			None => (0..solution.len()).collect(),
+		};
+		Ok(CrossCheck { solution, reference, discrepancies })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn agree_with_reference() {
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![0.0, 2.0, -1.0], 2.0))
+            .add_equation(Equation::new(vec![4.0, 1.0,  2.0], 12.0))
+            .add_equation(Equation::new(vec![-2.0, 5.0, 9.0], 4.0))
+            .validate().unwrap();
+        let check = matrix.cross_check(1e-12).unwrap();
+        assert!(check.is_consistent(), "{:?}", check);
+        assert_eq!(check.reference().unwrap().len(), 3);
+        assert!(matches!(CoefficientMatrix::<f64>::new(2).cross_check(1e-12), Err(SolveError::NotValidated)));
+    }
+
+    #[test]
+    fn reference_with_column_swaps() {
+        // The largest entry of the first pivot step is in the last column
+        let rows = vec![vec![1.0, 0.0, 5.0, 6.0], vec![0.0, 2.0, 0.0, 4.0], vec![1.0, 1.0, 0.0, 3.0]];
+        assert_eq!(super::reference_solve(rows), Some(vec![1.0, 2.0, 1.0]));
+        assert_eq!(super::reference_solve(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]]), None);
+    }
+}
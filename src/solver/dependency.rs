@@ -0,0 +1,164 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num::traits::real::Real;
+
+use super::CoefficientMatrix;
+
+// Linear dependency between equations, explaining a `DependentSolutionSet`
+// or `EmptySolutionSet`: the coefficients of equation `row` are the sum of
+// those of the equations in `combination`, each times its factor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency<T> {
+	row: usize,
+	combination: Vec<(usize, T)>,
+	consistent: bool,
+}
+
+impl<T> Dependency<T> {
+	pub fn row(&self) -> usize {
+		self.row
+	}
+
+	// The other equations involved and their factors, by index
+	pub fn combination(&self) -> &[(usize, T)] {
+		&self.combination
+	}
+
+	// Whether the results follow the same combination, so that the equation
+	// is redundant rather than contradictory
+	pub fn is_consistent(&self) -> bool {
+		self.consistent
+	}
+}
+
+impl<T> fmt::Display for Dependency<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Equation {} is ", self.row)?;
+		match self.combination.as_slice() {
+			[] => write!(f, "zero")?,
+			[(other, _)] => write!(f, "a multiple of equation {}", other)?,
+			combination => {
+				write!(f, "a linear combination of equations ")?;
+				for (index, (other, _)) in combination.iter().enumerate() {
+					let separator = match index {
+						0 => "",
+						_ if index + 1 == combination.len() => " and ",
+						_ => ", ",
+					};
+					write!(f, "{}{}", separator, other)?;
+				}
+			},
+		}
+		if !self.consistent {
+			write!(f, ", but its result isn't")?;
+		}
+		Ok(())
+	}
+}
+
+impl<T: Real> CoefficientMatrix<T> {
+	// Find equations whose coefficients depend linearly on each other, after
+	// a solve failed with `DependentSolutionSet` or `EmptySolutionSet`.
+	// Elimination runs on the coefficients together with an identity matrix
+	// recording how every row was combined; a row eliminated to zero gives
+	// the dependency. `None` if the coefficients are independent or the
+	// equations don't all have `size` coefficients.
+	pub fn dependency(&self) -> Option<Dependency<T>> {
+		let size = self.size;
+		let count = self.matrix.len();
+		if self.matrix.iter().any(|equation| equation.coefficients.len() != size) {
+			return None;
+		}
+		let max = self.matrix.iter()
+			.flat_map(|equation| equation.coefficients.iter())
+			.fold(T::zero(), |max, value| max.max(value.abs()));
+		let tolerance = T::epsilon() * T::from(size.max(count)).unwrap_or_else(T::one) * max;
+
+		// Coefficients followed by the combination of original rows
+		let mut rows: Vec<Vec<T>> = self.matrix.iter()
+			.enumerate()
+			.map(|(i, equation)| {
+				let mut row = equation.coefficients.clone();
+				row.extend((0..count).map(|j| if i == j { T::one() } else { T::zero() }));
+				row
+			})
+			.collect();
+		let mut pivoted = vec![false; count];
+		for column in 0..size {
+			let pivot = (0..count)
+				.filter(|&i| !pivoted[i])
+				.max_by(|&a, &b| rows[a][column].abs().partial_cmp(&rows[b][column].abs()).unwrap_or(core::cmp::Ordering::Equal));
+			let pivot = match pivot {
+				Some(pivot) if rows[pivot][column].abs() > tolerance => pivot,
+				_ => continue,
+			};
+			pivoted[pivot] = true;
+			let pivot_row = rows[pivot].clone();
+			for (i, row) in rows.iter_mut().enumerate() {
+				if pivoted[i] {
+					continue;
+				}
+				let factor = row[column] / pivot_row[column];
+				for (value, &above) in row.iter_mut().zip(&pivot_row) {
+					*value = *value - factor * above;
+				}
+			}
+		}
+
+		let combination = &rows[pivoted.iter().position(|&done| !done)?][size..];
+		let largest = combination.iter().fold(T::zero(), |max, value| max.max(value.abs()));
+		let row = combination.iter().rposition(|value| value.abs() > T::epsilon() * largest)?;
+		// Solve combination · A = 0 for equation `row`
+		let scale = -combination[row];
+		let combination: Vec<(usize, T)> = combination.iter()
+			.enumerate()
+			.filter(|&(i, value)| i != row && value.abs() > T::epsilon() * largest)
+			.map(|(i, &value)| (i, value / scale))
+			.collect();
+
+		let expected = combination.iter().fold(T::zero(), |sum, &(i, factor)| sum + factor * self.matrix[i].result);
+		let result = self.matrix[row].result;
+		let magnitude = expected.abs().max(result.abs()).max(T::one());
+		let consistent = (result - expected).abs() <= T::epsilon().sqrt() * magnitude;
+		Some(Dependency { row, combination, consistent })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn combination_of_two() {
+        // The third equation is the first plus twice the second
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 2.0, -1.0], 3.0))
+            .add_equation(Equation::new(vec![0.0, 1.0, 4.0], 1.0))
+            .add_equation(Equation::new(vec![1.0, 4.0, 7.0], 5.0))
+            .validate().unwrap();
+        assert!(matches!(matrix.clone().convert().and_then(CoefficientMatrix::solve), Err(SolveError::DependentSolutionSet)));
+        let dependency = matrix.dependency().unwrap();
+        assert_eq!(dependency.row(), 2);
+        let factors: Vec<(usize, f64)> = dependency.combination().to_vec();
+        assert_eq!(factors.len(), 2);
+        assert!((factors[0].1 - 1.0).abs() < 1e-12 && (factors[1].1 - 2.0).abs() < 1e-12);
+        assert!(dependency.is_consistent());
+        assert_eq!(dependency.to_string(), "Equation 2 is a linear combination of equations 0 and 1");
+    }
+
+    #[test]
+    fn contradiction_and_independence() {
+        let matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![2.0, 4.0], 2.0))
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0));
+        let dependency = matrix.dependency().unwrap();
+        assert!(!dependency.is_consistent());
+        assert_eq!(dependency.to_string(), "Equation 1 is a multiple of equation 0, but its result isn't");
+
+        let matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![2.0, 4.0], 2.0))
+            .add_equation(Equation::new(vec![1.0, 3.0], 3.0));
+        assert_eq!(matrix.dependency(), None);
+    }
+}
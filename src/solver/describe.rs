@@ -0,0 +1,142 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use num::traits::real::Real;
+use core::fmt;
+
+use super::{CoefficientMatrix, Equation};
+
+// Name of the unknown at `index` out of `count` named together. Up to
+// three use the familiar x, y, z; more are all numbered so that a screen
+// reader announces them as "x 1", "x 2", ... rather than mixing both. The
+// count includes unknowns past the size of the system, e.g. from rows
+// longer than `size`.
+pub(crate) fn variable_name(index: usize, count: usize) -> String {
+	const LETTERS: [&str; 3] = ["x", "y", "z"];
+	if count <= LETTERS.len() && index < LETTERS.len() {
+		LETTERS[index].to_string()
+	} else {
+		format!("x {}", index + 1)
+	}
+}
+
+// Spoken form of a single number, e.g. "negative 2"
+fn number<T: Real + fmt::Display>(value: T) -> String {
+	if value < T::zero() {
+		format!("negative {}", -value)
+	} else {
+		format!("{}", value)
+	}
+}
+
+impl<T> Equation<T>
+where
+	T: Real + fmt::Display
+{
+	// Verbalize the equation, e.g. "8 x minus 6 y equals 2", naming `count`
+	// unknowns or as many as it has coefficients
+	fn describe(&self, count: usize) -> String {
+		let count = count.max(self.coefficients.len());
+		let mut terms = String::new();
+		for (idx, coefficient) in self.coefficients.iter().enumerate() {
+			if coefficient.is_zero() {
+				continue;
+			}
+
+			let magnitude = coefficient.abs();
+			let negative = *coefficient < T::zero();
+			if terms.is_empty() {
+				if negative {
+					terms.push_str("negative ");
+				}
+			} else if negative {
+				terms.push_str(" minus ");
+			} else {
+				terms.push_str(" plus ");
+			}
+
+			if !magnitude.is_one() {
+				terms.push_str(&format!("{} ", magnitude));
+			}
+			terms.push_str(&variable_name(idx, count));
+		}
+
+		if terms.is_empty() {
+			terms.push('0');
+		}
+
+		format!("{} equals {}", terms, number(self.result))
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + fmt::Display
+{
+	// Screen-reader-friendly sentence form of the system, e.g.
+	// "Equation 1: 8 x minus 6 y equals 2; Equation 2: 2 x plus 3 y equals 2."
+	// Describing a solved matrix reads out its solution.
+	pub fn describe(&self) -> String {
+		let count = self.matrix.iter().map(Equation::len).fold(self.size, usize::max);
+		let equations: Vec<String> = self.matrix.iter()
+			.enumerate()
+			.map(|(idx, equation)| format!("Equation {}: {}", idx + 1, equation.describe(count)))
+			.collect();
+		format!("{}.", equations.join("; "))
+	}
+
+	// Sentence form of the right-hand sides of a solved matrix, e.g.
+	// "The solution is x equals 0.5 and y equals 1."
+	pub fn describe_solution(&self) -> String {
+		let count = self.matrix.len().max(self.size);
+		let values: Vec<String> = self.matrix.iter()
+			.enumerate()
+			.map(|(idx, equation)| format!("{} equals {}", variable_name(idx, count), number(equation.result)))
+			.collect();
+		match values.split_last() {
+			None => "There is no solution to describe.".to_string(),
+			Some((last, [])) => format!("The solution is {}.", last),
+			Some((last, rest)) => format!("The solution is {} and {}.", rest.join(", "), last),
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn describe_system() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![-1.0, 0.0], -2.5));
+        assert_eq!(
+            mat.describe(),
+            "Equation 1: 8 x minus 6 y equals 2; Equation 2: negative x equals negative 2.5."
+        );
+    }
+
+    #[test]
+    fn describe_solved_system() {
+        let solved = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 4.5))
+            .validate().unwrap()
+            .solve().unwrap();
+        assert_eq!(solved.describe_solution(), "The solution is x equals 1 and y equals 1.");
+    }
+
+    #[test]
+    fn number_unknowns_past_the_letters() {
+        let long = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0, 3.0, 4.0], 1.0));
+        assert_eq!(long.describe(), "Equation 1: x 1 plus 2 x 2 plus 3 x 3 plus 4 x 4 equals 1.");
+
+        let results = (1..=4).fold(CoefficientMatrix::new(2), |mat, result| {
+            mat.add_equation(Equation::new(vec![1.0, 0.0], result as f64))
+        });
+        assert_eq!(
+            results.describe_solution(),
+            "The solution is x 1 equals 1, x 2 equals 2, x 3 equals 3 and x 4 equals 4."
+        );
+    }
+}
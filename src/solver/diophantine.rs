@@ -0,0 +1,190 @@
+use alloc::{vec, vec::Vec};
+use num::Integer;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+// Integer solutions of A x = b for any m x n integer matrix A. Unimodular
+// column operations bring A into its Hermite normal form H = A U, which is
+// lower triangular, so H y = b is solved by forward substitution and every
+// integer solution is x = U y. The columns of U past the rank of A span the
+// integer kernel.
+
+// All integer solutions as `particular` plus any integer combination of the
+// `kernel` vectors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerSolution<T> {
+	particular: Vec<T>,
+	kernel: Vec<Vec<T>>,
+}
+
+impl<T> IntegerSolution<T> {
+	pub fn particular(&self) -> &[T] {
+		&self.particular
+	}
+
+	// Basis of the integer solutions of A x = 0, empty if the particular
+	// solution is unique
+	pub fn kernel(&self) -> &[Vec<T>] {
+		&self.kernel
+	}
+}
+
+// Replace columns `a` and `b` of every row by the combinations
+// (a, b) * [[s, u], [t, v]]
+fn combine<T: Integer + Clone>(rows: &mut [Vec<T>], (a, b): (usize, usize), [s, t, u, v]: [&T; 4]) {
+	for row in rows.iter_mut() {
+		let (x, y) = (row[a].clone(), row[b].clone());
+		row[a] = x.clone() * s.clone() + y.clone() * t.clone();
+		row[b] = x * u.clone() + y * v.clone();
+	}
+}
+
+impl<T: Integer + Clone> Matrix<T> {
+	// Integer solutions of self * x = results, or `None` if there are only
+	// rational ones
+	pub fn solve_integer(&self, results: &[T]) -> Result<Option<IntegerSolution<T>>, SolveError> {
+		let (m, n) = (self.rows(), self.columns());
+		if results.len() != m {
+			return Err(SolveError::IncompatibleDimensions((m, n), (results.len(), 1)));
+		}
+
+		let mut h: Vec<Vec<T>> = (0..m).map(|i| self.row(i).unwrap().to_vec()).collect();
+		let mut u: Vec<Vec<T>> = (0..n)
+			.map(|i| (0..n).map(|j| if i == j { T::one() } else { T::zero() }).collect())
+			.collect();
+
+		// Column of the pivot of each row, if it has one
+		let mut pivots = vec![None; m];
+		let mut rank = 0;
+		for (i, pivot) in pivots.iter_mut().enumerate() {
+			if rank == n {
+				break;
+			}
+			for j in rank + 1..n {
+				if h[i][j].is_zero() {
+					continue;
+				}
+				// With g = s a + t b the 2x2 transformation has determinant 1
+				let (a, b) = (h[i][rank].clone(), h[i][j].clone());
+				let gcd = a.extended_gcd(&b);
+				let u_factor = T::zero() - b / gcd.gcd.clone();
+				let v_factor = a / gcd.gcd;
+				let factors = [&gcd.x, &gcd.y, &u_factor, &v_factor];
+				combine(&mut h, (rank, j), factors);
+				combine(&mut u, (rank, j), factors);
+			}
+			if h[i][rank].is_zero() {
+				continue;
+			}
+			if h[i][rank] < T::zero() {
+				for row in h.iter_mut().chain(u.iter_mut()) {
+					row[rank] = T::zero() - row[rank].clone();
+				}
+			}
+			// Reduce the entries left of the pivot to keep the numbers small
+			for j in 0..rank {
+				let quotient = h[i][j].div_floor(&h[i][rank]);
+				if !quotient.is_zero() {
+					let minus = T::zero() - quotient;
+					combine(&mut h, (j, rank), [&T::one(), &minus, &T::zero(), &T::one()]);
+					combine(&mut u, (j, rank), [&T::one(), &minus, &T::zero(), &T::one()]);
+				}
+			}
+			*pivot = Some(rank);
+			rank += 1;
+		}
+
+		// Forward substitution for y, leaving the free entries at zero
+		let mut y = vec![T::zero(); n];
+		for (i, pivot) in pivots.iter().enumerate() {
+			let mut rest = results[i].clone();
+			for (entry, value) in h[i].iter().zip(&y) {
+				rest = rest - entry.clone() * value.clone();
+			}
+			match pivot {
+				Some(column) => {
+					let (quotient, remainder) = rest.div_rem(&h[i][*column]);
+					if !remainder.is_zero() {
+						return Ok(None);
+					}
+					y[*column] = quotient;
+				},
+				None if !rest.is_zero() => return Ok(None),
+				None => (),
+			}
+		}
+
+		let particular = u.iter()
+			.map(|row| row.iter().zip(&y).fold(T::zero(), |sum, (a, b)| sum + a.clone() * b.clone()))
+			.collect();
+		let kernel = (rank..n)
+			.map(|column| u.iter().map(|row| row[column].clone()).collect())
+			.collect();
+		Ok(Some(IntegerSolution { particular, kernel }))
+	}
+}
+
+impl<T: Integer + Clone> CoefficientMatrix<T> {
+	// Integer solutions of a validated matrix
+	pub fn solve_integer(&self) -> Result<Option<IntegerSolution<T>>, SolveError> {
+		let (coefficients, results) = self.clone().validate()?.into_parts();
+		coefficients.solve_integer(&results)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn check(a: &Matrix<i64>, b: &[i64], solution: &IntegerSolution<i64>) {
+        assert_eq!(a.apply(solution.particular()).unwrap(), b);
+        for vector in solution.kernel() {
+            assert_eq!(a.apply(vector).unwrap(), vec![0; b.len()]);
+        }
+    }
+
+    #[test]
+    fn underdetermined() {
+        // 6x + 10y + 15z = 7 has solutions since gcd(6, 10, 15) = 1
+        let a = Matrix::from_rows(vec![vec![6, 10, 15]]).unwrap();
+        let solution = a.solve_integer(&[7]).unwrap().unwrap();
+        check(&a, &[7], &solution);
+        assert_eq!(solution.kernel().len(), 2);
+
+        // 4x + 6y = 3 has rational solutions only
+        let a = Matrix::from_rows(vec![vec![4, 6]]).unwrap();
+        assert_eq!(a.solve_integer(&[3]).unwrap(), None);
+        let solution = a.solve_integer(&[10]).unwrap().unwrap();
+        check(&a, &[10], &solution);
+        assert_eq!(solution.kernel(), &[vec![-3, 2]]);
+    }
+
+    #[test]
+    fn overdetermined() {
+        let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        let solution = a.solve_integer(&[5, 11, 17]).unwrap().unwrap();
+        assert_eq!(solution.particular(), &[1, 2]);
+        assert!(solution.kernel().is_empty());
+        assert_eq!(a.solve_integer(&[5, 11, 18]).unwrap(), None);
+        assert!(matches!(a.solve_integer(&[1]), Err(SolveError::IncompatibleDimensions((3, 2), (1, 1)))));
+    }
+
+    #[test]
+    fn square_systems() {
+        // Unique rational solution (1/2, 1/2)
+        let halves = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1, 1], 1))
+            .add_equation(Equation::new(vec![1, -1], 0));
+        assert_eq!(halves.solve_integer().unwrap(), None);
+
+        let dependent = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![2, 4, -2], 6))
+            .add_equation(Equation::new(vec![1, 2, -1], 3))
+            .add_equation(Equation::new(vec![0, 3, 3], 9));
+        let solution = dependent.solve_integer().unwrap().unwrap();
+        let (a, b) = dependent.into_parts();
+        check(&a, &b, &solution);
+        assert_eq!(solution.kernel().len(), 1);
+    }
+}
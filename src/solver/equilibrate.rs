@@ -0,0 +1,119 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use super::CoefficientMatrix;
+
+// Row and column factors applied by `equilibrate`. The scaled system is
+// R A C y = R b with diagonal R and C, so the original unknowns are x = C y.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scaling<T> {
+	rows: Vec<T>,
+	columns: Vec<T>,
+}
+
+impl<T: Real> Scaling<T> {
+	pub fn rows(&self) -> &[T] {
+		&self.rows
+	}
+
+	pub fn columns(&self) -> &[T] {
+		&self.columns
+	}
+
+	// Map the solution of the scaled system back to the original unknowns
+	pub fn unscale<I: IntoIterator<Item = T>>(&self, solution: I) -> Vec<T> {
+		solution.into_iter()
+			.zip(&self.columns)
+			.map(|(value, factor)| value * *factor)
+			.collect()
+	}
+}
+
+// Power of two closest to 1 / max, which scales without rounding errors.
+// Zero rows or columns are left alone.
+fn factor<T: Real>(max: T) -> T {
+	if max.is_zero() || max > T::max_value() {
+		return T::one();
+	}
+	let two = T::one() + T::one();
+	two.powf(-max.log2().round())
+}
+
+impl<T: Real> CoefficientMatrix<T> {
+	// Scale every equation so that its largest coefficient is about one.
+	// Badly scaled systems, e.g. with coefficients spanning many orders of
+	// magnitude, then pick better pivots.
+	pub fn equilibrate_rows(self) -> (CoefficientMatrix<T>, Scaling<T>) {
+		self.scale(false)
+	}
+
+	// Like `equilibrate_rows`, followed by scaling every column so that its
+	// largest coefficient is about one. Solutions of the result have to be
+	// mapped back with `Scaling::unscale`.
+	pub fn equilibrate(self) -> (CoefficientMatrix<T>, Scaling<T>) {
+		self.scale(true)
+	}
+
+	fn scale(mut self, columns: bool) -> (CoefficientMatrix<T>, Scaling<T>) {
+		let mut scaling = Scaling {
+			rows: Vec::with_capacity(self.matrix.len()),
+			columns: vec![T::one(); self.size],
+		};
+		for equation in self.matrix.iter_mut() {
+			let max = equation.coefficients.iter().fold(T::zero(), |max, value| max.max(value.abs()));
+			let factor = factor(max);
+			for value in equation.coefficients.iter_mut() {
+				*value = *value * factor;
+			}
+			equation.result = equation.result * factor;
+			scaling.rows.push(factor);
+		}
+
+		if columns {
+			for (column, scale) in scaling.columns.iter_mut().enumerate() {
+				let max = self.matrix.iter()
+					.filter_map(|equation| equation.coefficients.get(column))
+					.fold(T::zero(), |max, value| max.max(value.abs()));
+				*scale = factor(max);
+				for value in self.matrix.iter_mut().filter_map(|equation| equation.coefficients.get_mut(column)) {
+					*value = *value * *scale;
+				}
+			}
+		}
+		(self, scaling)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn equilibrate_badly_scaled_system() {
+        // Coefficients spanning fifteen orders of magnitude, x = y = 1
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1e6, 1e-3], 1e6 + 1e-3))
+            .add_equation(Equation::new(vec![1e-9, 3e3], 1e-9 + 3e3));
+        let (scaled, scaling) = mat.equilibrate();
+        for equation in scaled.rows() {
+            let max = equation.coefficients().iter().fold(0.0f64, |max, value| max.max(value.abs()));
+            assert!((0.5..=2.0).contains(&max), "{:?}", equation);
+        }
+        let solved = scaled.validate().unwrap().convert().unwrap().solve().unwrap();
+        let solution = scaling.unscale(solved.solution());
+        assert!(solution.iter().all(|x| (x - 1.0).abs() < 1e-12), "{:?}", solution);
+    }
+
+    #[test]
+    fn rows_only() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![4.0, 8.0], 12.0))
+            .add_equation(Equation::new(vec![0.0, 0.0], 0.0));
+        let (scaled, scaling) = mat.equilibrate_rows();
+        assert_eq!(scaling.rows(), &[0.125, 1.0]);
+        assert_eq!(scaling.columns(), &[1.0, 1.0]);
+        assert_eq!(scaled.rhs(0), Some(1.5));
+        assert_eq!(scaled.get(0, 1), Some(1.0));
+    }
+}
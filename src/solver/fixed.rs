@@ -0,0 +1,643 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::num::ParseIntError;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+use num::traits::real::Real;
+use num::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, ToPrimitive, Zero};
+
+use super::stepwise::{Arithmetic, Cursor};
+use super::CoefficientMatrix;
+
+// Binary fixed-point number with BITS fractional bits, stored as an i64
+// holding value * 2^BITS. Arithmetic is integer arithmetic, so solving the
+// same system gives bit-identical results on every platform, which floats
+// don't guarantee:
+//
+//     let x = Fixed::<32>::from(3) / Fixed::from(4); // exactly 0.75
+//
+// Products and quotients are rounded to the nearest representable value.
+// BITS must be below 63. The operators panic on overflow in every build
+// rather than wrapping, so the generic solvers do as well; the `Checked*`
+// traits return `None` instead, and `solve_checked` uses them to fail with
+// `SolveError::FixedPointOverflow`. The transcendental functions `Real` requires
+// (`exp`, `sin`, `powf`, ...) go through f64 and aren't covered by that
+// guarantee; the solvers only use arithmetic, comparisons, `abs` and `sqrt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<const BITS: u32> {
+	raw: i64,
+}
+
+// 32 integer and 32 fractional bits
+pub type Fixed32 = Fixed<32>;
+
+impl<const BITS: u32> Fixed<BITS> {
+	const ONE: i64 = 1 << BITS;
+
+	pub fn from_raw(raw: i64) -> Self {
+		Fixed { raw }
+	}
+
+	// value * 2^BITS, the representation compared for bit-identical results
+	pub fn raw(&self) -> i64 {
+		self.raw
+	}
+
+	// `None` if the integer doesn't fit with BITS fractional bits
+	pub fn from_int(value: i64) -> Option<Self> {
+		value.checked_mul(Self::ONE).map(Fixed::from_raw)
+	}
+
+	// Nearest representable value, `None` for NaN, infinity or values out of
+	// range. Exact for f64 values with at most BITS fractional bits.
+	pub fn from_f64(value: f64) -> Option<Self> {
+		let scaled = (value * Self::ONE as f64).round();
+		// i64::MAX as f64 rounds up to 2^63, which is out of range
+		if scaled >= -(i64::MAX as f64) && scaled < i64::MAX as f64 {
+			Some(Fixed::from_raw(scaled as i64))
+		} else {
+			None
+		}
+	}
+
+	pub fn to_f64(&self) -> f64 {
+		self.raw as f64 / Self::ONE as f64
+	}
+
+	fn narrow(value: i128) -> Option<Self> {
+		i64::try_from(value).ok().map(Fixed::from_raw)
+	}
+
+	// Divide by 2^BITS, rounding halves up
+	fn rescale(value: i128) -> i128 {
+		if BITS == 0 {
+			value
+		} else {
+			(value + (1 << (BITS - 1))) >> BITS
+		}
+	}
+
+	fn through_f64(self, f: impl Fn(f64) -> f64) -> Self {
+		Self::from_f64(f(self.to_f64())).expect("result isn't representable as a fixed-point number")
+	}
+}
+
+impl<const BITS: u32> From<i32> for Fixed<BITS> {
+	fn from(value: i32) -> Self {
+		Self::from_int(i64::from(value)).expect("attempt to convert with overflow")
+	}
+}
+
+impl<const BITS: u32> Add for Fixed<BITS> {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		self.checked_add(&other).expect("attempt to add with overflow")
+	}
+}
+
+impl<const BITS: u32> Sub for Fixed<BITS> {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self.checked_sub(&other).expect("attempt to subtract with overflow")
+	}
+}
+
+impl<const BITS: u32> Neg for Fixed<BITS> {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Fixed::from_raw(self.raw.checked_neg().expect("attempt to negate with overflow"))
+	}
+}
+
+impl<const BITS: u32> Mul for Fixed<BITS> {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		self.checked_mul(&other).expect("attempt to multiply with overflow")
+	}
+}
+
+impl<const BITS: u32> Div for Fixed<BITS> {
+	type Output = Self;
+
+	fn div(self, other: Self) -> Self {
+		assert!(other.raw != 0, "attempt to divide by zero");
+		self.checked_div(&other).expect("attempt to divide with overflow")
+	}
+}
+
+impl<const BITS: u32> CheckedAdd for Fixed<BITS> {
+	fn checked_add(&self, other: &Self) -> Option<Self> {
+		self.raw.checked_add(other.raw).map(Fixed::from_raw)
+	}
+}
+
+impl<const BITS: u32> CheckedSub for Fixed<BITS> {
+	fn checked_sub(&self, other: &Self) -> Option<Self> {
+		self.raw.checked_sub(other.raw).map(Fixed::from_raw)
+	}
+}
+
+impl<const BITS: u32> CheckedMul for Fixed<BITS> {
+	fn checked_mul(&self, other: &Self) -> Option<Self> {
+		Self::narrow(Self::rescale(i128::from(self.raw) * i128::from(other.raw)))
+	}
+}
+
+impl<const BITS: u32> CheckedDiv for Fixed<BITS> {
+	// Rounds to nearest, halves away from zero. `None` for a zero divisor.
+	fn checked_div(&self, other: &Self) -> Option<Self> {
+		if other.raw == 0 {
+			return None;
+		}
+		let numerator = i128::from(self.raw) << BITS;
+		let denominator = i128::from(other.raw);
+		let half = denominator.abs() / 2;
+		let rounded = if (numerator < 0) == (denominator < 0) {
+			(numerator.abs() + half) / denominator.abs()
+		} else {
+			-((numerator.abs() + half) / denominator.abs())
+		};
+		Self::narrow(rounded)
+	}
+}
+
+impl<const BITS: u32> Rem for Fixed<BITS> {
+	type Output = Self;
+
+	fn rem(self, other: Self) -> Self {
+		Fixed::from_raw(self.raw % other.raw)
+	}
+}
+
+impl<const BITS: u32> AddAssign for Fixed<BITS> {
+	fn add_assign(&mut self, other: Self) {
+		*self = *self + other;
+	}
+}
+
+impl<const BITS: u32> SubAssign for Fixed<BITS> {
+	fn sub_assign(&mut self, other: Self) {
+		*self = *self - other;
+	}
+}
+
+impl<const BITS: u32> Zero for Fixed<BITS> {
+	fn zero() -> Self {
+		Fixed::from_raw(0)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.raw == 0
+	}
+}
+
+impl<const BITS: u32> One for Fixed<BITS> {
+	fn one() -> Self {
+		Fixed::from_raw(Self::ONE)
+	}
+}
+
+// Parsing the character alone gives the error for an invalid digit
+fn digit(c: char, radix: u32) -> Result<u32, ParseIntError> {
+	match c.to_digit(radix) {
+		Some(digit) => Ok(digit),
+		None => u8::from_str_radix(c.encode_utf8(&mut [0; 4]), radix).map(u32::from),
+	}
+}
+
+impl<const BITS: u32> Num for Fixed<BITS> {
+	type FromStrRadixErr = ParseIntError;
+
+	// Digits with an optional sign and point, e.g. "-1.25". The fraction is
+	// rounded to the nearest representable value; digits beyond the first
+	// 64 bits of precision are checked but ignored.
+	fn from_str_radix(text: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+		let (negative, unsigned) = match text.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, text.strip_prefix('+').unwrap_or(text)),
+		};
+		let (integer, fraction) = match unsigned.find('.') {
+			Some(point) => (&unsigned[..point], &unsigned[point + 1..]),
+			None => (unsigned, ""),
+		};
+		let integer = if integer.is_empty() && !fraction.is_empty() {
+			0
+		} else {
+			// Reject a second sign such as in "--1"
+			u64::from_str_radix(integer, radix)?;
+			i64::from_str_radix(integer, radix)?
+		};
+
+		let (mut numerator, mut denominator) = (0u128, 1u128);
+		for c in fraction.chars() {
+			let digit = digit(c, radix)?;
+			if denominator * u128::from(radix) <= u128::from(u64::MAX) {
+				numerator = numerator * u128::from(radix) + u128::from(digit);
+				denominator *= u128::from(radix);
+			}
+		}
+		let fraction = ((numerator << BITS) + denominator / 2) / denominator;
+
+		let raw = integer.checked_mul(Self::ONE)
+			.and_then(|raw| raw.checked_add(fraction as i64))
+			.map(|raw| if negative { -raw } else { raw });
+		// An overflowing integer part gives the error i64 parsing would
+		match raw {
+			Some(raw) => Ok(Fixed::from_raw(raw)),
+			None => "9223372036854775808".parse::<i64>().map(Fixed::from_raw),
+		}
+	}
+}
+
+impl<const BITS: u32> ToPrimitive for Fixed<BITS> {
+	// Integer conversions truncate towards zero like those of floats
+	fn to_i64(&self) -> Option<i64> {
+		Some(self.trunc().raw >> BITS)
+	}
+
+	fn to_u64(&self) -> Option<u64> {
+		u64::try_from(self.trunc().raw >> BITS).ok()
+	}
+
+	fn to_f64(&self) -> Option<f64> {
+		Some(Fixed::to_f64(self))
+	}
+}
+
+impl<const BITS: u32> num::NumCast for Fixed<BITS> {
+	fn from<N: ToPrimitive>(value: N) -> Option<Self> {
+		match value.to_i64() {
+			Some(integer) if value.to_f64() == Some(integer as f64) => Self::from_int(integer),
+			_ => value.to_f64().and_then(Self::from_f64),
+		}
+	}
+}
+
+impl<const BITS: u32> Real for Fixed<BITS> {
+	// Symmetric around zero, so that `abs` and negation never overflow
+	fn min_value() -> Self {
+		Fixed::from_raw(-i64::MAX)
+	}
+
+	fn min_positive_value() -> Self {
+		Fixed::from_raw(1)
+	}
+
+	fn epsilon() -> Self {
+		Fixed::from_raw(1)
+	}
+
+	fn max_value() -> Self {
+		Fixed::from_raw(i64::MAX)
+	}
+
+	fn floor(self) -> Self {
+		Fixed::from_raw(self.raw & !(Self::ONE - 1))
+	}
+
+	fn ceil(self) -> Self {
+		-(-self).floor()
+	}
+
+	fn round(self) -> Self {
+		if self.raw < 0 {
+			-(-self).round()
+		} else {
+			(self + Self::from_raw(Self::ONE / 2)).floor()
+		}
+	}
+
+	fn trunc(self) -> Self {
+		if self.raw < 0 {
+			self.ceil()
+		} else {
+			self.floor()
+		}
+	}
+
+	fn fract(self) -> Self {
+		self - self.trunc()
+	}
+
+	fn abs(self) -> Self {
+		if self.raw < 0 {
+			-self
+		} else {
+			self
+		}
+	}
+
+	fn signum(self) -> Self {
+		match self.raw.cmp(&0) {
+			Ordering::Less => -Self::one(),
+			Ordering::Equal => Self::zero(),
+			Ordering::Greater => Self::one(),
+		}
+	}
+
+	fn is_sign_positive(self) -> bool {
+		self.raw >= 0
+	}
+
+	fn is_sign_negative(self) -> bool {
+		self.raw < 0
+	}
+
+	// Rounded once, after the addition
+	fn mul_add(self, a: Self, b: Self) -> Self {
+		let product = i128::from(self.raw) * i128::from(a.raw);
+		Self::narrow(Self::rescale(product + (i128::from(b.raw) << BITS))).expect("attempt to multiply with overflow")
+	}
+
+	fn recip(self) -> Self {
+		Self::one() / self
+	}
+
+	fn powi(self, exponent: i32) -> Self {
+		let (mut base, mut remaining, mut result) = (self, exponent.unsigned_abs(), Self::one());
+		while remaining > 0 {
+			if remaining & 1 == 1 {
+				result = result * base;
+			}
+			remaining >>= 1;
+			if remaining > 0 {
+				base = base * base;
+			}
+		}
+		if exponent < 0 {
+			result.recip()
+		} else {
+			result
+		}
+	}
+
+	fn powf(self, exponent: Self) -> Self {
+		self.through_f64(|x| x.powf(exponent.to_f64()))
+	}
+
+	// Integer square root of value * 2^(2 BITS), rounded down
+	fn sqrt(self) -> Self {
+		assert!(self.raw >= 0, "attempt to take the square root of a negative number");
+		let target = (self.raw as u128) << BITS;
+		let mut root = (target as f64).sqrt() as u128;
+		while root * root > target {
+			root -= 1;
+		}
+		while (root + 1) * (root + 1) <= target {
+			root += 1;
+		}
+		Fixed::from_raw(root as i64)
+	}
+
+	fn exp(self) -> Self {
+		self.through_f64(f64::exp)
+	}
+
+	fn exp2(self) -> Self {
+		self.through_f64(f64::exp2)
+	}
+
+	fn ln(self) -> Self {
+		self.through_f64(f64::ln)
+	}
+
+	fn log(self, base: Self) -> Self {
+		self.through_f64(|x| x.log(base.to_f64()))
+	}
+
+	fn log2(self) -> Self {
+		self.through_f64(f64::log2)
+	}
+
+	fn log10(self) -> Self {
+		self.through_f64(f64::log10)
+	}
+
+	fn to_degrees(self) -> Self {
+		self * Self::from_f64(180.0 / core::f64::consts::PI).expect("too few integer bits for 180 / π")
+	}
+
+	fn to_radians(self) -> Self {
+		self * Self::from_f64(core::f64::consts::PI / 180.0).expect("too few integer bits for π / 180")
+	}
+
+	fn max(self, other: Self) -> Self {
+		Ord::max(self, other)
+	}
+
+	fn min(self, other: Self) -> Self {
+		Ord::min(self, other)
+	}
+
+	fn abs_sub(self, other: Self) -> Self {
+		if self <= other {
+			Self::zero()
+		} else {
+			self - other
+		}
+	}
+
+	fn cbrt(self) -> Self {
+		self.through_f64(f64::cbrt)
+	}
+
+	fn hypot(self, other: Self) -> Self {
+		(self * self + other * other).sqrt()
+	}
+
+	fn sin(self) -> Self {
+		self.through_f64(f64::sin)
+	}
+
+	fn cos(self) -> Self {
+		self.through_f64(f64::cos)
+	}
+
+	fn tan(self) -> Self {
+		self.through_f64(f64::tan)
+	}
+
+	fn asin(self) -> Self {
+		self.through_f64(f64::asin)
+	}
+
+	fn acos(self) -> Self {
+		self.through_f64(f64::acos)
+	}
+
+	fn atan(self) -> Self {
+		self.through_f64(f64::atan)
+	}
+
+	fn atan2(self, other: Self) -> Self {
+		self.through_f64(|y| y.atan2(other.to_f64()))
+	}
+
+	fn sin_cos(self) -> (Self, Self) {
+		(self.sin(), self.cos())
+	}
+
+	fn exp_m1(self) -> Self {
+		self.through_f64(f64::exp_m1)
+	}
+
+	fn ln_1p(self) -> Self {
+		self.through_f64(f64::ln_1p)
+	}
+
+	fn sinh(self) -> Self {
+		self.through_f64(f64::sinh)
+	}
+
+	fn cosh(self) -> Self {
+		self.through_f64(f64::cosh)
+	}
+
+	fn tanh(self) -> Self {
+		self.through_f64(f64::tanh)
+	}
+
+	fn asinh(self) -> Self {
+		self.through_f64(f64::asinh)
+	}
+
+	fn acosh(self) -> Self {
+		self.through_f64(f64::acosh)
+	}
+
+	fn atanh(self) -> Self {
+		self.through_f64(f64::atanh)
+	}
+}
+
+// Shortest decimal that reads back as the same f64, or as many digits as
+// the precision asks for
+impl<const BITS: u32> fmt::Display for Fixed<BITS> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&self.to_f64(), f)
+	}
+}
+
+// Checked arithmetic for `solve_checked`. Fixed-point values are never
+// NaN or infinite, and their magnitudes are compared without `abs`, which
+// overflows for the most negative value.
+struct Checked;
+
+impl<const BITS: u32> Arithmetic<Fixed<BITS>> for Checked {
+	fn exceeds(a: Fixed<BITS>, b: Fixed<BITS>) -> bool {
+		a.raw.unsigned_abs() > b.raw.unsigned_abs()
+	}
+
+	fn divide(a: Fixed<BITS>, b: Fixed<BITS>) -> Option<Fixed<BITS>> {
+		a.checked_div(&b)
+	}
+
+	fn subtract_product(value: Fixed<BITS>, other: Fixed<BITS>, factor: Fixed<BITS>) -> Option<Fixed<BITS>> {
+		other.checked_mul(&factor).and_then(|product| value.checked_sub(&product))
+	}
+
+	fn usable(_: Fixed<BITS>) -> bool {
+		true
+	}
+}
+
+impl<const BITS: u32> CoefficientMatrix<Fixed<BITS>> {
+	// `convert` followed by `solve` with checked arithmetic, giving the same
+	// bits when nothing overflows and `SolveError::FixedPointOverflow` with the row
+	// being changed and the pivot column when something does
+	pub fn solve_checked(mut self) -> super::Result<Fixed<BITS>> {
+		self.ensure_validated()?;
+		let mut cursor = Cursor::start(self.size);
+		while cursor != Cursor::Done {
+			cursor = self.advance_with::<Checked, _>(cursor, &mut ())?;
+		}
+		Ok(self)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::convert::TryFrom;
+    use num::traits::real::Real;
+    use num::{CheckedDiv, CheckedMul, Num};
+
+    type F = Fixed32;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (F::from(3), F::from(4));
+        assert_eq!((a / b).to_f64(), 0.75);
+        assert_eq!((a * b).raw(), 12 << 32);
+        assert_eq!((b - a - a).to_f64(), -2.0);
+        assert_eq!((F::from(1) / F::from(3)).raw(), 1_431_655_765);
+        assert_eq!((F::from(-1) / F::from(3)).raw(), -1_431_655_765);
+        assert_eq!(F::from(2).sqrt().to_f64(), (2.0f64 * (1u64 << 32) as f64 * (1u64 << 32) as f64).sqrt().floor() / (1u64 << 32) as f64);
+        assert_eq!(F::from(-3).powi(-2), F::from(1) / F::from(9));
+
+        let x = F::from_f64(-2.5).unwrap();
+        assert_eq!((x.floor(), x.ceil(), x.round(), x.trunc()), (F::from(-3), F::from(-2), F::from(-3), F::from(-2)));
+        assert_eq!(x.fract().to_f64(), -0.5);
+        assert_eq!(F::from_f64(f64::NAN), None);
+        assert_eq!(F::from_f64(1e10), None);
+        assert_eq!(<F as num::NumCast>::from(7u8), Some(F::from(7)));
+    }
+
+    #[test]
+    #[should_panic(expected = "multiply with overflow")]
+    fn overflow_panics() {
+        let _ = F::from(1 << 20) * F::from(1 << 20);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(F::from_str_radix("-1.25", 10).unwrap().to_f64(), -1.25);
+        assert_eq!(F::from_str_radix(".5", 10).unwrap().to_string(), "0.5");
+        assert_eq!(F::from_str_radix("10.1", 2).unwrap().to_f64(), 2.5);
+        assert_eq!(F::from_str_radix("0.1", 10).unwrap(), F::from(1) / F::from(10));
+        for invalid in &["1.2.3", "--1", "1.+5", "", "x", "99999999999"] {
+            assert!(F::from_str_radix(invalid, 10).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn solve_deterministically() {
+        let f = |rows: Vec<Vec<i32>>| -> Vec<Vec<F>> {
+            rows.into_iter().map(|row| row.into_iter().map(F::from).collect()).collect()
+        };
+        let system = CoefficientMatrix::try_from(f(vec![vec![8, -6, 2], vec![2, 3, 2]])).unwrap()
+            .validate().unwrap()
+            .convert().unwrap()
+            .solve().unwrap();
+        let solution: Vec<F> = system.solution().collect();
+        assert_eq!(solution[0], F::from_f64(0.5).unwrap());
+        // 1/3 rounded to 32 fractional bits, the same on every platform
+        assert_eq!(solution[1].raw(), 1_431_655_765);
+
+        let singular = CoefficientMatrix::try_from(f(vec![vec![1, 2, 3], vec![2, 4, 6]])).unwrap()
+            .validate().unwrap();
+        assert!(matches!(singular.convert().and_then(CoefficientMatrix::solve), Err(SolveError::DependentSolutionSet)));
+    }
+
+    #[test]
+    fn solve_checked() {
+        let f = |rows: Vec<Vec<i32>>| -> CoefficientMatrix<F> {
+            let rows: Vec<Vec<F>> = rows.into_iter().map(|row| row.into_iter().map(F::from).collect()).collect();
+            CoefficientMatrix::try_from(rows).unwrap().validate().unwrap()
+        };
+        let system = f(vec![vec![1, 2, -1, 2], vec![4, 1, 2, 12], vec![-2, 5, 1, 4]]);
+        assert_eq!(system.clone().solve_checked().unwrap(), system.convert().unwrap().solve().unwrap());
+        assert!(matches!(f(vec![vec![1, 2, 3], vec![2, 4, 5]]).solve_checked(), Err(SolveError::EmptySolutionSet)));
+
+        // -2·10⁹ - 2·10⁹ doesn't fit into 32 integer bits
+        let large = f(vec![vec![1, 2_000_000_000, 0], vec![1, -2_000_000_000, 0]]);
+        assert!(matches!(large.solve_checked(), Err(SolveError::FixedPointOverflow(1, 0))));
+        assert_eq!(F::from(1 << 20).checked_mul(&F::from(1 << 20)), None);
+        assert_eq!(F::from(1).checked_div(&F::zero()), None);
+    }
+}
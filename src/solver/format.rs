@@ -0,0 +1,227 @@
+use alloc::{format, vec, string::String, string::ToString, vec::Vec};
+use core::fmt;
+use core::iter;
+
+use num::Num;
+
+use super::{CoefficientMatrix, Equation};
+
+// Aligned, fixed-width rendering of a matrix. Every column is padded to its
+// widest entry and the results are optionally set apart by a separator:
+//
+//     8  -6 | 2
+//     0 4.5 | 1.5
+pub struct MatrixFormat<'a, T> {
+	matrix: &'a CoefficientMatrix<T>,
+	precision: Option<usize>,
+	separator: Option<&'a str>,
+}
+
+impl<'a, T> MatrixFormat<'a, T> {
+	// Number of decimal places to print. Without it the precision of the
+	// formatter (`{:.3}`) is used, falling back to the shortest representation.
+	pub fn precision(mut self, precision: usize) -> Self {
+		self.precision = Some(precision);
+		self
+	}
+
+	// Separator between coefficients and results, `None` to omit it
+	pub fn separator(mut self, separator: Option<&'a str>) -> Self {
+		self.separator = separator;
+		self
+	}
+}
+
+impl<T> CoefficientMatrix<T> {
+	pub fn format(&self) -> MatrixFormat<'_, T> {
+		MatrixFormat {
+			matrix: self,
+			precision: None,
+			separator: Some("|"),
+		}
+	}
+}
+
+impl<'a, T: fmt::Display> fmt::Display for MatrixFormat<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let precision = self.precision.or_else(|| f.precision());
+		let cell = |value: &T| match precision {
+			Some(precision) => format!("{:.*}", precision, value),
+			None => format!("{}", value),
+		};
+
+		// Each row holds the formatted coefficients followed by the result
+		let rows: Vec<Vec<String>> = self.matrix.matrix.iter()
+			.map(|equation| equation.coefficients.iter()
+				.chain(iter::once(&equation.result))
+				.map(cell)
+				.collect())
+			.collect();
+
+		let columns = rows.iter().map(|row| row.len() - 1).max().unwrap_or(0);
+		let mut widths = vec![0; columns];
+		let mut result_width = 0;
+		for row in rows.iter() {
+			let (result, coefficients) = row.split_last().unwrap();
+			for (width, coefficient) in widths.iter_mut().zip(coefficients) {
+				*width = (*width).max(coefficient.chars().count());
+			}
+			result_width = result_width.max(result.chars().count());
+		}
+
+		for row in rows.iter() {
+			let (result, coefficients) = row.split_last().unwrap();
+			let cells: Vec<String> = widths.iter()
+				.enumerate()
+				.map(|(idx, width)| {
+					let coefficient = coefficients.get(idx).map(|c| c.as_str()).unwrap_or("");
+					format!("{:>width$}", coefficient, width = width)
+				})
+				.collect();
+			let separator = match self.separator {
+				Some(separator) => format!(" {} ", separator),
+				None => " ".to_string(),
+			};
+			writeln!(f, "{}{}{:>width$}", cells.join(" "), separator, result, width = result_width)?;
+		}
+		Ok(())
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for CoefficientMatrix<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&self.format(), f)
+	}
+}
+
+// Algebraic rendering of an equation such as "8x1 - 6x2 = 2", leaving out
+// zero terms and coefficients of ±1. The unicode form, also chosen by the
+// alternate flag (`{:#}`), uses subscripts and a proper minus sign:
+// "8x₁ − 6x₂ = 2".
+pub struct EquationFormat<'a, T> {
+	equation: &'a Equation<T>,
+	names: &'a [&'a str],
+	precision: Option<usize>,
+	unicode: bool,
+}
+
+impl<'a, T> EquationFormat<'a, T> {
+	// Names of the unknowns in order. Unknowns without one keep the
+	// numbered default.
+	pub fn names(mut self, names: &'a [&'a str]) -> Self {
+		self.names = names;
+		self
+	}
+
+	// Number of decimal places to print, see `MatrixFormat::precision`
+	pub fn precision(mut self, precision: usize) -> Self {
+		self.precision = Some(precision);
+		self
+	}
+
+	pub fn unicode(mut self, unicode: bool) -> Self {
+		self.unicode = unicode;
+		self
+	}
+}
+
+impl<T> Equation<T> {
+	pub fn format(&self) -> EquationFormat<'_, T> {
+		EquationFormat {
+			equation: self,
+			names: &[],
+			precision: None,
+			unicode: false,
+		}
+	}
+}
+
+// Digits of an index as subscripts
+fn subscript(index: usize) -> String {
+	index.to_string().chars()
+		.map(|digit| char::from_u32('₀' as u32 + digit.to_digit(10).unwrap_or(0)).unwrap_or('₀'))
+		.collect()
+}
+
+impl<'a, T: Num + Copy + fmt::Display> fmt::Display for EquationFormat<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let precision = self.precision.or_else(|| f.precision());
+		let unicode = self.unicode || f.alternate();
+		let text = |value: T| match precision {
+			Some(precision) => format!("{:.*}", precision, value),
+			None => format!("{}", value),
+		};
+		let minus = if unicode { "−" } else { "-" };
+		let sign = |written: &str| match written.strip_prefix('-') {
+			Some(magnitude) => (true, magnitude.to_string()),
+			None => (false, written.to_string()),
+		};
+
+		let mut first = true;
+		for (idx, &coefficient) in self.equation.coefficients.iter().enumerate() {
+			if coefficient.is_zero() {
+				continue;
+			}
+			let (negative, magnitude) = sign(&text(coefficient));
+			match (first, negative) {
+				(true, true) => write!(f, "{}", minus)?,
+				(true, false) => {},
+				(false, true) => write!(f, " {} ", minus)?,
+				(false, false) => write!(f, " + ")?,
+			}
+			first = false;
+
+			if coefficient != T::one() && T::zero() - coefficient != T::one() {
+				write!(f, "{}", magnitude)?;
+			}
+			match self.names.get(idx) {
+				Some(name) => write!(f, "{}", name)?,
+				None if unicode => write!(f, "x{}", subscript(idx + 1))?,
+				None => write!(f, "x{}", idx + 1)?,
+			}
+		}
+		if first {
+			write!(f, "{}", text(T::zero()))?;
+		}
+
+		let (negative, magnitude) = sign(&text(self.equation.result));
+		write!(f, " = {}{}", if negative { minus } else { "" }, magnitude)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn system() -> CoefficientMatrix<f64> {
+        CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.5], 12.0))
+    }
+
+    #[test]
+    fn aligned_columns() {
+        assert_eq!(system().to_string(), "8  -6 |  2\n2 3.5 | 12\n");
+    }
+
+    #[test]
+    fn algebraic_equations() {
+        let equation = Equation::new(vec![8.0, -6.0, 0.0, 1.0], -2.0);
+        assert_eq!(equation.to_string(), "8x1 - 6x2 + x4 = -2");
+        assert_eq!(format!("{:#}", equation), "8x₁ − 6x₂ + x₄ = −2");
+        assert_eq!(equation.format().names(&["a", "b"]).precision(1).to_string(), "8.0a - 6.0b + x4 = -2.0");
+        assert_eq!(Equation::new(vec![-1, 0], 3).to_string(), "-x1 = 3");
+        assert_eq!(Equation::new(vec![0, 0], 3).format().unicode(true).to_string(), "0 = 3");
+        assert_eq!(format!("{:#}", Equation::new(vec![1; 12], 0)).split(' ').nth(22), Some("x₁₂"));
+    }
+
+    #[test]
+    fn precision_and_separator() {
+        assert_eq!(format!("{:.1}", system()), "8.0 -6.0 |  2.0\n2.0  3.5 | 12.0\n");
+        assert_eq!(
+            system().format().precision(0).separator(None).to_string(),
+            "8 -6  2\n2  4 12\n"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::{format, string::String};
+use num::traits::real::Real;
+use core::fmt;
+
+use super::describe::variable_name;
+use super::CoefficientMatrix;
+
+// Largest denominator still considered a "simple" fraction
+const MAX_DENOMINATOR: u64 = 10_000;
+
+// A fraction approximating a floating point value, used to display solutions
+// like `x = 1/3` instead of `x = 0.3333333333333333`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fraction {
+	numerator: i64,
+	denominator: u64,
+}
+
+impl Fraction {
+	// Find the simplest fraction within `tolerance` of `value` by expanding
+	// it as a continued fraction. Returns `None` if there is no such fraction
+	// with a denominator of at most `max_denominator`.
+	pub fn approximate(value: f64, tolerance: f64, max_denominator: u64) -> Option<Self> {
+		if !value.is_finite() {
+			return None;
+		}
+
+		let magnitude = value.abs();
+		let mut x = magnitude;
+		// Convergents h/k of the continued fraction, starting from 1/0 and 0/1
+		let (mut h, mut h_prev) = (1.0, 0.0);
+		let (mut k, mut k_prev) = (0.0, 1.0);
+		for _ in 0..64 {
+			let a = x.floor();
+			let next_h = a * h + h_prev;
+			let next_k = a * k + k_prev;
+			if next_k > max_denominator as f64 || next_h > i64::MAX as f64 {
+				return None;
+			}
+			h_prev = h;
+			k_prev = k;
+			h = next_h;
+			k = next_k;
+
+			let fraction = x - a;
+			if (h / k - magnitude).abs() <= tolerance || fraction == 0.0 {
+				let numerator = if value < 0.0 { -(h as i64) } else { h as i64 };
+				return Some(Fraction {
+					numerator,
+					denominator: k as u64,
+				});
+			}
+			x = 1.0 / fraction;
+		}
+		None
+	}
+
+	pub fn numerator(&self) -> i64 {
+		self.numerator
+	}
+
+	pub fn denominator(&self) -> u64 {
+		self.denominator
+	}
+}
+
+impl fmt::Display for Fraction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.denominator == 1 {
+			write!(f, "{}", self.numerator)
+		} else {
+			write!(f, "{}/{}", self.numerator, self.denominator)
+		}
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + fmt::Display
+{
+	// Render the results of a solved matrix one per line, e.g. `x = 1/3`.
+	// Values without a simple fraction within `tolerance` are printed as is.
+	pub fn solution_fractions(&self, tolerance: f64) -> String {
+		let mut output = String::new();
+		let count = self.matrix.len().max(self.size);
+		for (idx, equation) in self.matrix.iter().enumerate() {
+			let name = variable_name(idx, count).replace(' ', "");
+			let fraction = equation.result.to_f64()
+				.and_then(|value| Fraction::approximate(value, tolerance, MAX_DENOMINATOR));
+			match fraction {
+				Some(fraction) => output.push_str(&format!("{} = {}\n", name, fraction)),
+				None => output.push_str(&format!("{} = {}\n", name, equation.result)),
+			}
+		}
+		output
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::Fraction;
+
+    #[test]
+    fn approximate_fractions() {
+        let third = Fraction::approximate(1.0 / 3.0, 1e-9, 10_000).unwrap();
+        assert_eq!((third.numerator(), third.denominator()), (1, 3));
+        assert_eq!(Fraction::approximate(-2.5, 1e-9, 10_000).unwrap().to_string(), "-5/2");
+        assert_eq!(Fraction::approximate(4.0, 1e-9, 10_000).unwrap().to_string(), "4");
+        assert_eq!(Fraction::approximate(std::f64::consts::PI, 1e-12, 10_000), None);
+    }
+
+    #[test]
+    fn solution_as_fractions() {
+        let solved = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .validate().unwrap()
+            .convert().unwrap()
+            .solve().unwrap();
+        assert_eq!(solved.solution_fractions(1e-9), "x = 1/2\ny = 1/3\n");
+    }
+
+    #[test]
+    fn more_results_than_unknowns() {
+        let mat = [0.5, 0.25, 2.0, -1.5].iter().fold(CoefficientMatrix::new(2), |mat, &result| {
+            mat.add_equation(Equation::new(vec![1.0, 0.0], result))
+        });
+        assert_eq!(mat.solution_fractions(1e-9), "x1 = 1/2\nx2 = 1/4\nx3 = 2\nx4 = -3/2\n");
+    }
+}
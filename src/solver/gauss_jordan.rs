@@ -0,0 +1,125 @@
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Observer, Result, SolveError, Step};
+
+// How `solve` reduces the matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+	// `convert` to upper triangular form, then back substitute
+	#[default]
+	Substitution,
+	// Reduce each column above and below its pivot in a single pass, the
+	// way it's usually taught
+	GaussJordan,
+	// Like `Substitution`, with compensated summation, see
+	// `solve_compensated`
+	Compensated,
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve a validated matrix with the given method
+	pub fn solve_with<O: Observer<T>>(self, method: Method, observer: &mut O) -> Result<T> {
+		match method {
+			Method::Substitution => self.convert_observed(observer)?.solve_observed(observer),
+			Method::GaussJordan => self.solve_gauss_jordan_observed(observer),
+			Method::Compensated => self.solve_compensated_observed(observer),
+		}
+	}
+
+	pub fn solve_gauss_jordan(self) -> Result<T> {
+		self.solve_gauss_jordan_observed(&mut ())
+	}
+
+	// Reduce to reduced row echelon form column by column: swap the
+	// largest candidate into place, scale the pivot row to a leading one
+	// and eliminate the column from every other row
+	pub fn solve_gauss_jordan_observed<O: Observer<T>>(mut self, observer: &mut O) -> Result<T> {
+		self.ensure_validated()?;
+		let size = self.size;
+		for column in 0..size {
+			let mut pivot_row = column;
+			for row in column + 1..size {
+				if self.matrix[row].get(column).abs() > self.matrix[pivot_row].get(column).abs() {
+					pivot_row = row;
+				}
+			}
+			if pivot_row != column {
+				self.matrix.swap(column, pivot_row);
+				observer.record(Step::Swap(column, pivot_row));
+			}
+
+			let pivot = self.matrix[column].get(column);
+			observer.record(Step::Pivot(column, pivot));
+			if pivot.is_zero() {
+				return Err(if (column..size).all(|row| self.matrix[row].get_result().is_zero()) {
+					SolveError::DependentSolutionSet
+				} else {
+					SolveError::EmptySolutionSet
+				});
+			}
+			observer.record(Step::Scale(column, pivot));
+			for value in self.matrix[column].coefficients.iter_mut() {
+				*value = *value / pivot;
+			}
+			*self.matrix[column].get_result_mut() = self.matrix[column].get_result() / pivot;
+
+			let pivot_equation = self.matrix[column].clone();
+			for (row, equation) in self.matrix.iter_mut().enumerate() {
+				let factor = equation.get(column);
+				if row == column || factor.is_zero() {
+					continue;
+				}
+				observer.record(Step::Eliminate(row, column, factor));
+				for (value, &above) in equation.coefficients.iter_mut().zip(&pivot_equation.coefficients) {
+					*value -= above * factor;
+				}
+				*equation.get_result_mut() -= pivot_equation.result * factor;
+			}
+		}
+		Ok(self)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn reduce_in_one_pass() {
+        let mut trace = Trace::new();
+        let solved = swapping_system().solve_with(Method::GaussJordan, &mut trace).unwrap();
+        let expected = swapping_system().solve_with(Method::Substitution, &mut ()).unwrap();
+        for (x, y) in solved.solution().zip(expected.solution()) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        for row in 0..3 {
+            for column in 0..3 {
+                assert!((solved[(row, column)] - if row == column { 1.0 } else { 0.0 }).abs() < 1e-12);
+            }
+        }
+
+        // Each column is pivoted, scaled and then cleared above and below
+        assert_eq!(trace.steps()[0], Step::Swap(0, 1));
+        assert_eq!(trace.steps()[1], Step::Pivot(0, 4.0));
+        assert_eq!(trace.steps()[2], Step::Scale(0, 4.0));
+        assert!(trace.steps().iter().any(|step| matches!(step, Step::Eliminate(0, 2, _))));
+    }
+
+    #[test]
+    fn singular_systems() {
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 6.0));
+        assert!(matches!(dependent.solve_gauss_jordan(), Err(SolveError::DependentSolutionSet)));
+        let empty = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 7.0));
+        assert!(matches!(empty.solve_gauss_jordan(), Err(SolveError::EmptySolutionSet)));
+    }
+}
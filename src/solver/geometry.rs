@@ -0,0 +1,102 @@
+use num::traits::real::Real;
+
+use super::{SmallSystem, SolveError};
+
+// Intersections of lines in the plane and of three planes in space, set up
+// as 2x2 and 3x3 `SmallSystem`s. Like those, a singular system is only
+// detected for exactly parallel inputs, nearly parallel ones intersect far
+// away.
+
+// Line a x + b y = c
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<T> {
+	normal: [T; 2],
+	offset: T,
+}
+
+// Plane n · x = d
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane<T> {
+	normal: [T; 3],
+	offset: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intersection<T, const N: usize> {
+	Point([T; N]),
+	// No common point
+	Parallel,
+	// Infinitely many common points
+	Coincident,
+}
+
+impl<T: Real> Line<T> {
+	pub fn new(a: T, b: T, c: T) -> Self {
+		Line { normal: [a, b], offset: c }
+	}
+
+	// Line through two points, which must differ
+	pub fn through(p: [T; 2], q: [T; 2]) -> Self {
+		let normal = [q[1] - p[1], p[0] - q[0]];
+		Line { normal, offset: normal[0] * p[0] + normal[1] * p[1] }
+	}
+
+	pub fn intersect(&self, other: &Line<T>) -> Intersection<T, 2> {
+		intersection(SmallSystem::new([self.normal, other.normal], [self.offset, other.offset]).solve())
+	}
+}
+
+impl<T: Real> Plane<T> {
+	pub fn new(normal: [T; 3], offset: T) -> Self {
+		Plane { normal, offset }
+	}
+
+	// Plane through three points, which must not lie on one line
+	pub fn through(p: [T; 3], q: [T; 3], r: [T; 3]) -> Self {
+		let (u, v) = ([q[0] - p[0], q[1] - p[1], q[2] - p[2]], [r[0] - p[0], r[1] - p[1], r[2] - p[2]]);
+		let normal = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+		Plane { normal, offset: normal[0] * p[0] + normal[1] * p[1] + normal[2] * p[2] }
+	}
+
+	pub fn intersect(&self, second: &Plane<T>, third: &Plane<T>) -> Intersection<T, 3> {
+		intersection(SmallSystem::new(
+			[self.normal, second.normal, third.normal],
+			[self.offset, second.offset, third.offset],
+		).solve())
+	}
+}
+
+fn intersection<T, const N: usize>(solved: Result<[T; N], SolveError>) -> Intersection<T, N> {
+	match solved {
+		Ok(point) => Intersection::Point(point),
+		Err(SolveError::DependentSolutionSet) => Intersection::Coincident,
+		Err(_) => Intersection::Parallel,
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn intersect_lines() {
+        let diagonal = Line::through([0.0, 0.0], [2.0, 2.0]);
+        assert_eq!(diagonal.intersect(&Line::new(1.0, 0.0, 1.0)), Intersection::Point([1.0, 1.0]));
+        assert_eq!(diagonal.intersect(&Line::through([0.0, 1.0], [1.0, 2.0])), Intersection::Parallel);
+        assert_eq!(diagonal.intersect(&Line::new(-3.0, 3.0, 0.0)), Intersection::Coincident);
+    }
+
+    #[test]
+    fn intersect_planes() {
+        let x = Plane::new([1.0, 0.0, 0.0], 1.0);
+        let y = Plane::through([0.0, 2.0, 0.0], [1.0, 2.0, 0.0], [0.0, 2.0, 1.0]);
+        let z = Plane::new([0.0, 0.0, 2.0], 6.0);
+        assert_eq!(x.intersect(&y, &z), Intersection::Point([1.0, 2.0, 3.0]));
+        assert_eq!(x.intersect(&Plane::new([2.0, 0.0, 0.0], 4.0), &z), Intersection::Parallel);
+        // Three planes through the z axis
+        let diagonal = Plane::new([1.0, -1.0, 0.0], 0.0);
+        let through_axis = Plane::new([1.0, 0.0, 0.0], 0.0);
+        assert_eq!(through_axis.intersect(&Plane::new([0.0, 1.0, 0.0], 0.0), &diagonal), Intersection::Coincident);
+    }
+}
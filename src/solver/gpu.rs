@@ -0,0 +1,448 @@
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use wgpu::util::DeviceExt;
+
+use super::{CoefficientMatrix, Lu, Result};
+
+// Kernels on the augmented matrix of `n` rows of n + 1 values, one dispatch
+// per column `k`. WebGPU has no f64, so they work in single precision and
+// `solve_gpu` refines the result on the CPU.
+const SHADER: &str = r#"
+struct Step {
+    k: u32,
+    n: u32,
+}
+
+@group(0) @binding(0) var<storage, read_write> a: array<f32>;
+@group(0) @binding(1) var<storage, read_write> pivots: array<u32>;
+@group(0) @binding(2) var<storage, read_write> x: array<f32>;
+@group(0) @binding(3) var<uniform> step: Step;
+
+const GROUP: u32 = 256u;
+
+var<workgroup> best_value: array<f32, GROUP>;
+var<workgroup> best_row: array<u32, GROUP>;
+
+// Find the first row with the largest magnitude in column k, as `convert`
+// does, and swap it with row k including the multipliers left of k. Run as
+// a single workgroup.
+@compute @workgroup_size(256)
+fn pivot(@builtin(local_invocation_index) id: u32) {
+    let k = step.k;
+    let n = step.n;
+    let width = n + 1u;
+    var value = -1.0;
+    var row = k;
+    for (var r = k + id; r < n; r += GROUP) {
+        let candidate = abs(a[r * width + k]);
+        if (candidate > value) {
+            value = candidate;
+            row = r;
+        }
+    }
+    best_value[id] = value;
+    best_row[id] = row;
+    workgroupBarrier();
+    for (var stride = GROUP / 2u; stride > 0u; stride /= 2u) {
+        if (id < stride) {
+            let other = best_value[id + stride];
+            if (other > best_value[id] || (other == best_value[id] && best_row[id + stride] < best_row[id])) {
+                best_value[id] = other;
+                best_row[id] = best_row[id + stride];
+            }
+        }
+        workgroupBarrier();
+    }
+    let p = workgroupUniformLoad(&best_row[0]);
+    if (id == 0u) {
+        pivots[k] = p;
+    }
+    if (p == k) {
+        return;
+    }
+    for (var c = id; c < width; c += GROUP) {
+        let value = a[k * width + c];
+        a[k * width + c] = a[p * width + c];
+        a[p * width + c] = value;
+    }
+}
+
+// Replace column k below the pivot with the multipliers
+@compute @workgroup_size(64)
+fn multipliers(@builtin(global_invocation_id) id: vec3<u32>) {
+    let k = step.k;
+    let n = step.n;
+    let width = n + 1u;
+    let row = k + 1u + id.x;
+    let pivot = a[k * width + k];
+    if (row >= n || pivot == 0.0) {
+        return;
+    }
+    a[row * width + k] = a[row * width + k] / pivot;
+}
+
+// Subtract the multiples of row k from the rows below it, right of column k
+@compute @workgroup_size(16, 16)
+fn eliminate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let k = step.k;
+    let n = step.n;
+    let width = n + 1u;
+    let row = k + 1u + id.y;
+    let column = k + 1u + id.x;
+    if (row >= n || column >= width || a[k * width + k] == 0.0) {
+        return;
+    }
+    a[row * width + column] -= a[k * width + column] * a[row * width + k];
+}
+
+// Back substitution for unknown k: store it in x and subtract its terms
+// from the right-hand sides of the rows above
+@compute @workgroup_size(64)
+fn substitute(@builtin(global_invocation_id) id: vec3<u32>) {
+    let k = step.k;
+    let n = step.n;
+    let width = n + 1u;
+    let row = id.x;
+    if (row > k) {
+        return;
+    }
+    let value = a[k * width + n] / a[k * width + k];
+    if (row == k) {
+        x[k] = value;
+    } else {
+        a[row * width + n] -= a[row * width + k] * value;
+    }
+}
+"#;
+
+// Size of the `Step` uniform, each column's at a dynamic offset
+const STEP_SIZE: u64 = 8;
+
+// Device and compute pipelines of the WebGPU backend. Creating them takes a
+// while, so keep the context around for many solves.
+pub struct GpuContext {
+	device: wgpu::Device,
+	queue: wgpu::Queue,
+	layout: wgpu::BindGroupLayout,
+	pivot: wgpu::ComputePipeline,
+	multipliers: wgpu::ComputePipeline,
+	eliminate: wgpu::ComputePipeline,
+	substitute: wgpu::ComputePipeline,
+}
+
+impl GpuContext {
+	// None if there is no adapter, e.g. in browsers without WebGPU
+	pub async fn new() -> Option<GpuContext> {
+		let instance = wgpu::Instance::default();
+		let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
+			power_preference: wgpu::PowerPreference::HighPerformance,
+			..Default::default()
+		}).await?;
+		// Ask for what the adapter offers, the defaults limit storage
+		// buffers to systems of about 5800 unknowns
+		let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
+			label: Some("mat-solve"),
+			required_limits: adapter.limits(),
+			..Default::default()
+		}, None).await.ok()?;
+
+		let storage = |binding| wgpu::BindGroupLayoutEntry {
+			binding,
+			visibility: wgpu::ShaderStages::COMPUTE,
+			ty: wgpu::BindingType::Buffer {
+				ty: wgpu::BufferBindingType::Storage { read_only: false },
+				has_dynamic_offset: false,
+				min_binding_size: None,
+			},
+			count: None,
+		};
+		let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			label: None,
+			entries: &[storage(0), storage(1), storage(2), wgpu::BindGroupLayoutEntry {
+				binding: 3,
+				visibility: wgpu::ShaderStages::COMPUTE,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Uniform,
+					has_dynamic_offset: true,
+					min_binding_size: wgpu::BufferSize::new(STEP_SIZE),
+				},
+				count: None,
+			}],
+		});
+		let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: None,
+			bind_group_layouts: &[&layout],
+			push_constant_ranges: &[],
+		});
+		let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+			label: None,
+			source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+		});
+		let pipeline = |entry_point| device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+			label: Some(entry_point),
+			layout: Some(&pipeline_layout),
+			module: &module,
+			entry_point: Some(entry_point),
+			compilation_options: Default::default(),
+			cache: None,
+		});
+
+		Some(GpuContext {
+			pivot: pipeline("pivot"),
+			multipliers: pipeline("multipliers"),
+			eliminate: pipeline("eliminate"),
+			substitute: pipeline("substitute"),
+			device,
+			queue,
+			layout,
+		})
+	}
+
+	// Whether the augmented matrix of a system of this many unknowns fits
+	// into one storage buffer
+	pub fn fits(&self, size: usize) -> bool {
+		let limits = self.device.limits();
+		let bytes = (size as u64).saturating_mul(size as u64 + 1).saturating_mul(4);
+		bytes <= u64::from(limits.max_storage_buffer_binding_size) && bytes <= limits.max_buffer_size
+	}
+
+	// Eliminate and substitute the augmented matrix, returning the matrix
+	// with the factors in place, the pivot rows and the solution. None if
+	// reading back the results fails.
+	async fn run(&self, size: usize, augmented: &[f32]) -> Option<(Vec<f32>, Vec<u32>, Vec<f32>)> {
+		let (n, width) = (size as u32, size as u32 + 1);
+		let alignment = u64::from(self.device.limits().min_uniform_buffer_offset_alignment);
+		let mut steps = Vec::with_capacity(size * alignment as usize);
+		for k in 0..n {
+			steps.extend(k.to_le_bytes().iter().chain(&n.to_le_bytes()));
+			steps.resize((k as u64 + 1) as usize * alignment as usize, 0);
+		}
+
+		let storage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC;
+		let a = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+			label: Some("augmented"),
+			contents: &to_bytes(augmented),
+			usage: storage,
+		});
+		let pivots = self.device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some("pivots"),
+			size: size as u64 * 4,
+			usage: storage,
+			mapped_at_creation: false,
+		});
+		let x = self.device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some("solution"),
+			size: size as u64 * 4,
+			usage: storage,
+			mapped_at_creation: false,
+		});
+		let step = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+			label: Some("steps"),
+			contents: &steps,
+			usage: wgpu::BufferUsages::UNIFORM,
+		});
+		let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+			label: None,
+			layout: &self.layout,
+			entries: &[
+				wgpu::BindGroupEntry { binding: 0, resource: a.as_entire_binding() },
+				wgpu::BindGroupEntry { binding: 1, resource: pivots.as_entire_binding() },
+				wgpu::BindGroupEntry { binding: 2, resource: x.as_entire_binding() },
+				wgpu::BindGroupEntry {
+					binding: 3,
+					resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+						buffer: &step,
+						offset: 0,
+						size: wgpu::BufferSize::new(STEP_SIZE),
+					}),
+				},
+			],
+		});
+
+		let mut encoder = self.device.create_command_encoder(&Default::default());
+		{
+			let mut pass = encoder.begin_compute_pass(&Default::default());
+			for k in 0..n {
+				pass.set_bind_group(0, &bind_group, &[k * alignment as u32]);
+				let below = n - k - 1;
+				pass.set_pipeline(&self.pivot);
+				pass.dispatch_workgroups(1, 1, 1);
+				if below > 0 {
+					pass.set_pipeline(&self.multipliers);
+					pass.dispatch_workgroups(groups(below, 64), 1, 1);
+					pass.set_pipeline(&self.eliminate);
+					pass.dispatch_workgroups(groups(width - k - 1, 16), groups(below, 16), 1);
+				}
+			}
+			pass.set_pipeline(&self.substitute);
+			for k in (0..n).rev() {
+				pass.set_bind_group(0, &bind_group, &[k * alignment as u32]);
+				pass.dispatch_workgroups(groups(k + 1, 64), 1, 1);
+			}
+		}
+
+		let sources = [&a, &pivots, &x];
+		let staging: Vec<wgpu::Buffer> = sources.iter().map(|source| {
+			let target = self.device.create_buffer(&wgpu::BufferDescriptor {
+				label: None,
+				size: source.size(),
+				usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+				mapped_at_creation: false,
+			});
+			encoder.copy_buffer_to_buffer(source, 0, &target, 0, source.size());
+			target
+		}).collect();
+		self.queue.submit(Some(encoder.finish()));
+
+		let mut receivers = Vec::with_capacity(staging.len());
+		for target in &staging {
+			let (sender, receiver) = futures_channel::oneshot::channel();
+			target.slice(..).map_async(wgpu::MapMode::Read, move |mapped| {
+				let _ = sender.send(mapped);
+			});
+			receivers.push(receiver);
+		}
+		// Browsers map the buffers on their own, natively they are only
+		// mapped while polling
+		self.device.poll(wgpu::Maintain::Wait);
+		for receiver in receivers {
+			receiver.await.ok()?.ok()?;
+		}
+
+		let mut words = staging.iter().map(|target| {
+			let words = target.slice(..).get_mapped_range()
+				.chunks_exact(4)
+				.map(|bytes| bytes.try_into().unwrap())
+				.collect::<Vec<[u8; 4]>>();
+			target.unmap();
+			words
+		});
+		let matrix = words.next()?.into_iter().map(f32::from_le_bytes).collect();
+		let pivots = words.next()?.into_iter().map(u32::from_le_bytes).collect();
+		let solution = words.next()?.into_iter().map(f32::from_le_bytes).collect();
+		Some((matrix, pivots, solution))
+	}
+}
+
+fn groups(invocations: u32, size: u32) -> u32 {
+	invocations.div_ceil(size)
+}
+
+fn to_bytes(values: &[f32]) -> Vec<u8> {
+	values.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+impl CoefficientMatrix<f64> {
+	// Solve like `solve_mixed`, but run the single precision elimination
+	// and back substitution as WebGPU compute shaders. The GPU only pays off
+	// for systems of a thousand unknowns and more. Without a context, for
+	// systems too large for its buffers, if the GPU fails or if its factors
+	// are singular or don't refine to f64 accuracy within `max_iterations`
+	// rounds, the system is solved on the CPU instead, which also reports
+	// why a singular system has no unique solution.
+	pub async fn solve_gpu(self, context: Option<&GpuContext>, max_iterations: usize) -> Result<f64> {
+		self.ensure_validated()?;
+		let size = self.size;
+		let context = match context {
+			Some(context) if context.fits(size) => context,
+			_ => return self.convert()?.solve(),
+		};
+		let mut augmented = Vec::with_capacity(size * (size + 1));
+		for equation in &self.matrix {
+			augmented.extend(equation.coefficients.iter().map(|&value| value as f32));
+			augmented.push(equation.result as f32);
+		}
+
+		let (factors, pivots, solution) = match context.run(size, &augmented).await {
+			Some(results) => results,
+			None => return self.convert()?.solve(),
+		};
+		let width = size + 1;
+		if (0..size).any(|k| factors[k * width + k] == 0.0) {
+			return self.convert()?.solve();
+		}
+		let pivots: Vec<usize> = pivots.into_iter().map(|pivot| pivot as usize).collect();
+		let lu = Lu::from_packed(&factors, width, &pivots);
+		let solution = solution.into_iter().map(f64::from).collect();
+		match self.refine(&lu, solution, max_iterations) {
+			Some(solution) => {
+				let mut solved = self;
+				solved.set_solved(solution);
+				Ok(solved)
+			},
+			None => self.convert()?.solve(),
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn system(size: usize) -> CoefficientMatrix<f64> {
+        let mut mat = CoefficientMatrix::new(size);
+        for i in 0..size {
+            // Diagonally dominant with pairs of rows swapped, which forces
+            // row swaps
+            let dominant = if i ^ 1 < size { i ^ 1 } else { i };
+            let coefficients = (0..size)
+                .map(|j| if j == dominant { 10.0 } else { 1.0 / (i + j + 1) as f64 })
+                .collect();
+            mat = mat.add_equation(Equation::new(coefficients, (i as f64).sqrt()));
+        }
+        mat.validate().unwrap()
+    }
+
+    fn expected(mat: &CoefficientMatrix<f64>) -> Vec<f64> {
+        mat.clone().convert().unwrap().solve().unwrap().solution().collect()
+    }
+
+    #[test]
+    fn solve_on_gpu_or_fall_back() {
+        // Runs the kernels only where an adapter is available
+        let context = pollster::block_on(GpuContext::new());
+        for size in [1, 2, 17, 300] {
+            let mat = system(size);
+            let expected = expected(&mat);
+            let solved = pollster::block_on(mat.solve_gpu(context.as_ref(), 10)).unwrap();
+            for (x, y) in solved.solution().zip(&expected) {
+                assert!((x - y).abs() < 1e-12);
+            }
+            assert_eq!(solved.get(0, 0), Some(1.0));
+        }
+    }
+
+    #[test]
+    fn report_singular_systems_from_cpu() {
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 6.0))
+            .validate().unwrap();
+        let context = pollster::block_on(GpuContext::new());
+        assert!(matches!(
+            pollster::block_on(dependent.solve_gpu(context.as_ref(), 10)),
+            Err(SolveError::DependentSolutionSet),
+        ));
+        assert!(matches!(
+            pollster::block_on(CoefficientMatrix::new(2).solve_gpu(None, 10)),
+            Err(SolveError::NotValidated),
+        ));
+    }
+
+    #[test]
+    fn unpack_factors() {
+        // Rows swapped 0 <-> 1 for the first column, multipliers in place
+        let packed = [
+            4.0, 2.0, 0.0,
+            0.5, 1.0, 0.0,
+        ];
+        let lu = Lu::from_packed(&packed, 3, &[1, 1]);
+        assert_eq!(lu.permutation(), &[1, 0]);
+        assert_eq!(lu.l()[(1, 0)], 0.5);
+        assert_eq!(lu.u()[(1, 0)], 0.0);
+        assert_eq!(lu.determinant(), -4.0);
+        // 2 x + 2 y = 4, 4 x + 2 y = 6
+        assert_eq!(lu.solve(&[4.0, 6.0]).unwrap(), vec![1.0, 1.0]);
+    }
+}
@@ -0,0 +1,87 @@
+use core::iter::FusedIterator;
+
+use super::{CoefficientMatrix, Equation};
+
+// Iterator over the coefficients of a single column, top to bottom. It
+// ends at the first equation too short to have the column, so that the
+// n-th value always belongs to the n-th equation.
+#[derive(Debug, Clone)]
+pub struct Column<'a, T> {
+	rows: core::slice::Iter<'a, Equation<T>>,
+	column: usize,
+}
+
+impl<'a, T: Clone> Iterator for Column<'a, T> {
+	type Item = T;
+
+	fn next(&mut self) -> Option<T> {
+		let value = self.rows.next()?.coefficients.get(self.column).cloned();
+		if value.is_none() {
+			self.rows = [].iter();
+		}
+		value
+	}
+}
+
+impl<'a, T: Clone> FusedIterator for Column<'a, T> {}
+
+impl<T> CoefficientMatrix<T> {
+	pub fn rows(&self) -> impl ExactSizeIterator<Item = &Equation<T>> {
+		self.matrix.iter()
+	}
+
+	// One iterator per unknown over its coefficients in every equation
+	pub fn columns(&self) -> impl ExactSizeIterator<Item = Column<'_, T>> {
+		let rows = &self.matrix;
+		(0..self.size).map(move |column| Column {
+			rows: rows.iter(),
+			column,
+		})
+	}
+}
+
+impl<T: Clone> CoefficientMatrix<T> {
+	// Values of the unknowns of a solved matrix, e.g.
+	// `matrix.solution().collect::<Vec<_>>()`
+	pub fn solution(&self) -> impl ExactSizeIterator<Item = T> + '_ {
+		self.matrix.iter().map(|equation| equation.result.clone())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn iterate_rows_and_columns() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5));
+        let rows: Vec<&[f64]> = mat.rows().map(|equation| equation.coefficients()).collect();
+        assert_eq!(rows, vec![&[8.0, -6.0][..], &[0.0, 4.5][..]]);
+        let columns: Vec<Vec<f64>> = mat.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec![vec![8.0, 0.0], vec![-6.0, 4.5]]);
+
+        // A short row ends the column instead of being skipped
+        let ragged = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0], 1.5))
+            .add_equation(Equation::new(vec![1.0, 3.0], 1.0));
+        let mut column = ragged.columns().nth(1).unwrap();
+        assert_eq!(column.by_ref().collect::<Vec<_>>(), vec![-6.0]);
+        assert_eq!(column.next(), None);
+    }
+
+    #[test]
+    fn collect_solution() {
+        let solution = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5))
+            .validate().unwrap()
+            .solve().unwrap()
+            .solution()
+            .collect::<Vec<_>>();
+        assert_eq!(solution, vec![0.5, 1.0 / 3.0]);
+    }
+}
@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CoefficientMatrix, Equation, SolveError};
+
+// Exchange format for systems that are stored or shared, e.g. as JSON:
+//
+//     { "size": 2, "rows": [[8, -6], [2, 3]], "rhs": [2, 2] }
+//
+// `rows` holds the coefficients of each equation and `rhs` its right-hand
+// side. Unlike the serialized `CoefficientMatrix` this layout is documented
+// and stays stable.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemData<T> {
+	pub size: usize,
+	pub rows: Vec<Vec<T>>,
+	pub rhs: Vec<T>,
+}
+
+impl<T> From<CoefficientMatrix<T>> for SystemData<T> {
+	fn from(matrix: CoefficientMatrix<T>) -> Self {
+		let (rows, rhs) = matrix.matrix.into_iter()
+			.map(|equation| (equation.coefficients, equation.result))
+			.unzip();
+		SystemData {
+			size: matrix.size,
+			rows,
+			rhs,
+		}
+	}
+}
+
+// The system as entered, which needn't be complete yet. Only the number of
+// right-hand sides has to match the rows.
+impl<T> TryFrom<SystemData<T>> for CoefficientMatrix<T> {
+	type Error = SolveError;
+
+	fn try_from(data: SystemData<T>) -> Result<Self, SolveError> {
+		if data.rhs.len() != data.rows.len() {
+			return Err(SolveError::UnfittingEquationAmount(data.rhs.len(), data.rows.len()));
+		}
+		Ok(data.rows.into_iter()
+			.zip(data.rhs)
+			.fold(CoefficientMatrix::new(data.size), |matrix, (row, result)| {
+				matrix.add_equation(Equation::new(row, result))
+			}))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn system_data_round_trip() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0));
+        let data = SystemData::from(mat.clone());
+        assert_eq!(data.rows, vec![vec![8.0, -6.0], vec![2.0, 3.0]]);
+        assert_eq!(data.rhs, vec![2.0, 2.0]);
+        assert_eq!(CoefficientMatrix::try_from(data).unwrap(), mat);
+
+        let unfitting = SystemData { size: 2, rows: vec![vec![1.0, 2.0]], rhs: vec![] };
+        assert!(matches!(CoefficientMatrix::try_from(unfitting), Err(SolveError::UnfittingEquationAmount(0, 1))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_schema() {
+        let json = r#"{"size":2,"rows":[[8.0,-6.0],[2.0,3.0]],"rhs":[2.0,2.0]}"#;
+        let data: SystemData<f64> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&data).unwrap(), json);
+    }
+}
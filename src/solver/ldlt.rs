@@ -0,0 +1,138 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, SolveError};
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Whether the coefficients are square and a_ij and a_ji differ by at
+	// most `tolerance`
+	pub fn is_symmetric(&self, tolerance: T) -> bool {
+		if self.ensure_validated().is_err() {
+			return false;
+		}
+		(0..self.size).all(|i| (0..i).all(|j| {
+			(self.matrix[i].coefficients[j] - self.matrix[j].coefficients[i]).abs() <= tolerance
+		}))
+	}
+
+	// Solve a symmetric system through A = L D Lᵀ, which needs about half
+	// the operations of `convert` and `solve`. Only the lower triangle is
+	// read. As there is no pivoting, a vanishing pivot, which can happen
+	// for indefinite or singular matrices, falls back to general
+	// elimination.
+	pub fn solve_symmetric(&self) -> Result<Vec<T>, SolveError> {
+		self.ensure_validated()?;
+		match self.ldlt() {
+			Some((lower, diagonal)) => Ok(self.ldlt_substitute(&lower, &diagonal)),
+			None => Ok(self.clone().convert()?.solve()?.solution().collect()),
+		}
+	}
+
+	// Take the symmetric path if the coefficients are symmetric within
+	// `tolerance`, general elimination otherwise
+	pub fn solve_auto(&self, tolerance: T) -> Result<Vec<T>, SolveError> {
+		if self.is_symmetric(tolerance) {
+			self.solve_symmetric()
+		} else {
+			Ok(self.clone().validate()?.convert()?.solve()?.solution().collect())
+		}
+	}
+
+	// Rows of the unit lower triangular L, without the diagonal, and D
+	fn ldlt(&self) -> Option<(Vec<Vec<T>>, Vec<T>)> {
+		let n = self.size;
+		let scale = self.matrix.iter()
+			.flat_map(|equation| equation.coefficients.iter())
+			.fold(T::zero(), |max, value| max.max(value.abs()));
+		let threshold = scale * T::epsilon() * T::from(n).unwrap_or_else(T::one);
+
+		let mut lower: Vec<Vec<T>> = (0..n).map(|i| vec![T::zero(); i]).collect();
+		let mut diagonal = vec![T::zero(); n];
+		for j in 0..n {
+			let mut d = self.matrix[j].coefficients[j];
+			for k in 0..j {
+				d -= lower[j][k] * lower[j][k] * diagonal[k];
+			}
+			if d.abs() <= threshold {
+				return None;
+			}
+			diagonal[j] = d;
+			for i in j + 1..n {
+				let mut value = self.matrix[i].coefficients[j];
+				for k in 0..j {
+					value -= lower[i][k] * lower[j][k] * diagonal[k];
+				}
+				lower[i][j] = value / d;
+			}
+		}
+		Some((lower, diagonal))
+	}
+
+	// Solve L D Lᵀ x = b by forward substitution, scaling and back
+	// substitution
+	fn ldlt_substitute(&self, lower: &[Vec<T>], diagonal: &[T]) -> Vec<T> {
+		let n = self.size;
+		let mut x: Vec<T> = self.matrix.iter().map(|equation| equation.result).collect();
+		for i in 0..n {
+			for k in 0..i {
+				let value = lower[i][k] * x[k];
+				x[i] -= value;
+			}
+		}
+		for (value, d) in x.iter_mut().zip(diagonal) {
+			*value = *value / *d;
+		}
+		for i in (0..n).rev() {
+			for k in i + 1..n {
+				let value = lower[k][i] * x[k];
+				x[i] -= value;
+			}
+		}
+		x
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn stiffness() -> CoefficientMatrix<f64> {
+        CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![ 2.0, -1.0,  0.0], 1.0))
+            .add_equation(Equation::new(vec![-1.0,  2.0, -1.0], 0.0))
+            .add_equation(Equation::new(vec![ 0.0, -1.0,  2.0], 1.0))
+    }
+
+    #[test]
+    fn symmetric_solve() {
+        let mat = stiffness();
+        assert!(mat.is_symmetric(0.0));
+        let solution = mat.solve_symmetric().unwrap();
+        assert!(solution.iter().all(|x| (x - 1.0).abs() < 1e-12), "{:?}", solution);
+        assert_eq!(mat.solve_auto(1e-12).unwrap(), solution);
+
+        // Indefinite with a zero leading entry falls back to elimination
+        let indefinite = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![0.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![1.0, 0.0], 3.0));
+        assert_eq!(indefinite.solve_symmetric().unwrap(), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn detect_symmetry() {
+        let skewed = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.001, 1.0], 3.0));
+        assert!(!skewed.is_symmetric(1e-6));
+        assert!(skewed.is_symmetric(1e-2));
+        let solution = skewed.solve_auto(1e-6).unwrap();
+        assert!((solution[0] * 2.001 + solution[1] - 3.0).abs() < 1e-12);
+        assert!(!CoefficientMatrix::<f64>::new(2).is_symmetric(1.0));
+    }
+}
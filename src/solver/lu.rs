@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Matrix, Observer, SolveError, Step};
+
+// Factors of P A = L U as computed by `convert`: the permutation of the
+// row swaps, the unit lower triangular L of the elimination multipliers and
+// the upper triangular U left in the converted matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu<T> {
+	// Row i of P A is row permutation[i] of A
+	permutation: Vec<usize>,
+	lower: Matrix<T>,
+	upper: Matrix<T>,
+	odd_swaps: bool,
+}
+
+// Collects the swaps and multipliers while converting
+struct Recorder<T> {
+	permutation: Vec<usize>,
+	lower: Matrix<T>,
+	odd_swaps: bool,
+}
+
+impl<T: Real> Observer<T> for Recorder<T> {
+	fn record(&mut self, step: Step<T>) {
+		match step {
+			Step::Swap(a, b) => {
+				self.permutation.swap(a, b);
+				// Multipliers of earlier columns move with their rows
+				for column in 0..a.min(b) {
+					let value = self.lower[(a, column)];
+					self.lower[(a, column)] = self.lower[(b, column)];
+					self.lower[(b, column)] = value;
+				}
+				self.odd_swaps = !self.odd_swaps;
+			},
+			Step::Eliminate(target, source, ratio) => self.lower[(target, source)] = ratio,
+			Step::Pivot(..) | Step::Scale(..) => (),
+		}
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Factorize the coefficients of a validated matrix. The right-hand
+	// sides are ignored.
+	pub fn lu(&self) -> Result<Lu<T>, SolveError> {
+		self.clone().convert_factorized(&mut ()).map(|(_, lu)| lu)
+	}
+
+	// Solve like `convert` followed by `solve`, also returning the factors
+	// so that later right-hand sides can be solved with `Lu::solve`
+	pub fn solve_factorized(self) -> Result<(CoefficientMatrix<T>, Lu<T>), SolveError> {
+		self.solve_factorized_observed(&mut ())
+	}
+
+	pub fn solve_factorized_observed<O: Observer<T>>(self, observer: &mut O) -> Result<(CoefficientMatrix<T>, Lu<T>), SolveError> {
+		let (converted, lu) = self.convert_factorized(observer)?;
+		Ok((converted.solve_observed(observer)?, lu))
+	}
+
+	fn convert_factorized<O: Observer<T>>(self, observer: &mut O) -> Result<(CoefficientMatrix<T>, Lu<T>), SolveError> {
+		self.ensure_validated()?;
+		let size = self.size;
+		let mut recorder = Recorder {
+			permutation: (0..size).collect(),
+			lower: Matrix::identity(size),
+			odd_swaps: false,
+		};
+		let converted = self.convert_observed(&mut (&mut recorder, observer))?;
+		let (upper, _) = converted.clone().into_parts();
+		let lu = Lu {
+			permutation: recorder.permutation,
+			lower: recorder.lower,
+			upper,
+			odd_swaps: recorder.odd_swaps,
+		};
+		Ok((converted, lu))
+	}
+}
+
+impl<T: Real> Lu<T> {
+	// Factors left in place by an elimination that swaps whole rows,
+	// multipliers included, and keeps the multipliers in place of the
+	// zeros: `rows` of `width` values each, of which the first `pivots.len()`
+	// columns hold L and U, and the row swapped with row k for each k
+	#[cfg(feature = "gpu")]
+	pub(super) fn from_packed(rows: &[T], width: usize, pivots: &[usize]) -> Lu<T> {
+		let size = pivots.len();
+		let mut permutation: Vec<usize> = (0..size).collect();
+		let mut odd_swaps = false;
+		for (k, &pivot) in pivots.iter().enumerate() {
+			if pivot != k {
+				permutation.swap(k, pivot);
+				odd_swaps = !odd_swaps;
+			}
+		}
+		let mut lower = Matrix::identity(size);
+		let mut upper = Matrix::zeros(size, size);
+		for row in 0..size {
+			for column in 0..size {
+				let value = rows[row * width + column];
+				if column < row {
+					lower[(row, column)] = value;
+				} else {
+					upper[(row, column)] = value;
+				}
+			}
+		}
+		Lu { permutation, lower, upper, odd_swaps }
+	}
+
+	pub fn permutation(&self) -> &[usize] {
+		&self.permutation
+	}
+
+	// Permutation matrix P
+	pub fn p(&self) -> Matrix<T> {
+		let size = self.permutation.len();
+		let mut p = Matrix::zeros(size, size);
+		for (row, &column) in self.permutation.iter().enumerate() {
+			p[(row, column)] = T::one();
+		}
+		p
+	}
+
+	pub fn l(&self) -> &Matrix<T> {
+		&self.lower
+	}
+
+	pub fn u(&self) -> &Matrix<T> {
+		&self.upper
+	}
+
+	// Determinant of A with the sign of the row swaps
+	pub fn determinant(&self) -> T {
+		let size = self.permutation.len();
+		let product = (0..size).fold(T::one(), |product, i| product * self.upper[(i, i)]);
+		if self.odd_swaps {
+			-product
+		} else {
+			product
+		}
+	}
+
+	// Solve A x = b with the factors, e.g. for many right-hand sides
+	pub fn solve(&self, b: &[T]) -> Result<Vec<T>, SolveError> {
+		let size = self.permutation.len();
+		if b.len() != size {
+			return Err(SolveError::IncompatibleDimensions((size, size), (b.len(), 1)));
+		}
+		let mut x: Vec<T> = self.permutation.iter().map(|&row| b[row]).collect();
+		for i in 0..size {
+			for k in 0..i {
+				x[i] = x[i] - self.lower[(i, k)] * x[k];
+			}
+		}
+		for i in (0..size).rev() {
+			for k in i + 1..size {
+				x[i] = x[i] - self.upper[(i, k)] * x[k];
+			}
+			let pivot = self.upper[(i, i)];
+			if pivot.is_zero() {
+				return Err(SolveError::DependentSolutionSet);
+			}
+			x[i] = x[i] / pivot;
+		}
+		Ok(x)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn factors_reproduce_matrix() {
+        let mat = swapping_system();
+        let lu = mat.lu().unwrap();
+        let (a, b) = mat.clone().into_parts();
+        let pa = lu.p().product(&a).unwrap();
+        let product = lu.l().product(lu.u()).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((pa[(i, j)] - product[(i, j)]).abs() < 1e-12);
+                if j > i {
+                    assert_eq!(lu.l()[(i, j)], 0.0);
+                }
+                if j < i {
+                    assert!(lu.u()[(i, j)].abs() < 1e-12);
+                }
+            }
+        }
+
+        // 1 (1 - 10) - 2 (4 + 4) - 1 (20 + 2)
+        assert!((lu.determinant() - -47.0).abs() < 1e-12);
+        let expected: Vec<f64> = mat.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        for (x, y) in lu.solve(&b).unwrap().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        assert!(lu.solve(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn solve_and_keep_factors() {
+        let mut trace = Trace::new();
+        let (solved, lu) = swapping_system().validate().unwrap().solve_factorized_observed(&mut trace).unwrap();
+        let expected: Vec<f64> = swapping_system().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        assert_eq!(solved.solution().collect::<Vec<_>>(), expected);
+        assert_eq!(lu, swapping_system().lu().unwrap());
+        assert!(!trace.steps().is_empty());
+
+        // A new right-hand side needs only the substitutions
+        let x = lu.solve(&[1.0, 0.0, 0.0]).unwrap();
+        let (a, _) = swapping_system().into_parts();
+        let b = a.apply(&x).unwrap();
+        assert!((b[0] - 1.0).abs() < 1e-12 && b[1].abs() < 1e-12 && b[2].abs() < 1e-12);
+    }
+}
@@ -0,0 +1,223 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+use core::ops::{Index, IndexMut};
+use num::Num;
+
+use super::{CoefficientMatrix, Equation, SolveError};
+
+// Dense rows x columns matrix stored row by row. Unlike `CoefficientMatrix`
+// it has no right-hand side and needn't be square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+	rows: usize,
+	columns: usize,
+	data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+	// Build from rows of equal length
+	pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Matrix<T>, SolveError> {
+		let columns = rows.first().map_or(0, |row| row.len());
+		if let Some(row) = rows.iter().find(|row| row.len() != columns) {
+			return Err(SolveError::UnfittingCoefficientAmount(row.len(), columns));
+		}
+		Ok(Matrix {
+			rows: rows.len(),
+			columns,
+			data: rows.into_iter().flatten().collect(),
+		})
+	}
+
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	pub fn columns(&self) -> usize {
+		self.columns
+	}
+
+	pub fn row(&self, row: usize) -> Option<&[T]> {
+		if row < self.rows {
+			Some(&self.data[row * self.columns..(row + 1) * self.columns])
+		} else {
+			None
+		}
+	}
+
+	pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+		if column < self.columns {
+			self.row(row).map(|row| &row[column])
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: Num + Copy> Matrix<T> {
+	pub fn zeros(rows: usize, columns: usize) -> Matrix<T> {
+		Matrix { rows, columns, data: vec![T::zero(); rows * columns] }
+	}
+
+	pub fn identity(size: usize) -> Matrix<T> {
+		let mut matrix = Matrix::zeros(size, size);
+		for i in 0..size {
+			matrix[(i, i)] = T::one();
+		}
+		matrix
+	}
+
+	pub fn transpose(&self) -> Matrix<T> {
+		let mut transposed = Matrix::zeros(self.columns, self.rows);
+		for i in 0..self.rows {
+			for j in 0..self.columns {
+				transposed[(j, i)] = self[(i, j)];
+			}
+		}
+		transposed
+	}
+
+	// Entry-wise sum of two matrices of the same dimensions
+	pub fn sum(&self, other: &Matrix<T>) -> Result<Matrix<T>, SolveError> {
+		if (self.rows, self.columns) != (other.rows, other.columns) {
+			return Err(self.incompatible(other));
+		}
+		let data = self.data.iter().zip(&other.data).map(|(a, b)| *a + *b).collect();
+		Ok(Matrix { rows: self.rows, columns: self.columns, data })
+	}
+
+	// Matrix product self * other
+	pub fn product(&self, other: &Matrix<T>) -> Result<Matrix<T>, SolveError> {
+		if self.columns != other.rows {
+			return Err(self.incompatible(other));
+		}
+		let mut product = Matrix::zeros(self.rows, other.columns);
+		for i in 0..self.rows {
+			for k in 0..self.columns {
+				let factor = self[(i, k)];
+				for j in 0..other.columns {
+					product[(i, j)] = product[(i, j)] + factor * other[(k, j)];
+				}
+			}
+		}
+		Ok(product)
+	}
+
+	// Matrix-vector product self * vector
+	pub fn apply(&self, vector: &[T]) -> Result<Vec<T>, SolveError> {
+		if self.columns != vector.len() {
+			return Err(SolveError::IncompatibleDimensions((self.rows, self.columns), (vector.len(), 1)));
+		}
+		Ok(self.data.chunks(self.columns.max(1))
+			.take(self.rows)
+			.map(|row| row.iter().zip(vector).fold(T::zero(), |sum, (a, b)| sum + *a * *b))
+			.collect())
+	}
+
+	fn incompatible(&self, other: &Matrix<T>) -> SolveError {
+		SolveError::IncompatibleDimensions((self.rows, self.columns), (other.rows, other.columns))
+	}
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+	type Output = T;
+
+	fn index(&self, (row, column): (usize, usize)) -> &T {
+		assert!(row < self.rows && column < self.columns, "index ({}, {}) out of bounds", row, column);
+		&self.data[row * self.columns + column]
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+	fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+		assert!(row < self.rows && column < self.columns, "index ({}, {}) out of bounds", row, column);
+		&mut self.data[row * self.columns + column]
+	}
+}
+
+impl<T> CoefficientMatrix<T> {
+	// Split into the coefficients and the right-hand sides
+	pub fn into_parts(self) -> (Matrix<T>, Vec<T>) {
+		let mut data = Vec::with_capacity(self.size * self.matrix.len());
+		let mut results = Vec::with_capacity(self.matrix.len());
+		let columns = self.matrix.first().map_or(0, |equation| equation.coefficients.len());
+		for equation in self.matrix {
+			data.extend(equation.coefficients);
+			results.push(equation.result);
+		}
+		(Matrix { rows: results.len(), columns, data }, results)
+	}
+
+	// Validated system of a square coefficient matrix and its right-hand
+	// sides
+	pub fn from_parts(coefficients: Matrix<T>, results: Vec<T>) -> super::Result<T> {
+		if coefficients.rows != coefficients.columns {
+			return Err(SolveError::UnfittingCoefficientAmount(coefficients.columns, coefficients.rows));
+		}
+		if results.len() != coefficients.rows {
+			return Err(SolveError::UnfittingEquationAmount(results.len(), coefficients.rows));
+		}
+		let mut matrix = CoefficientMatrix::new(coefficients.rows);
+		let mut data = coefficients.data.into_iter();
+		for result in results {
+			let row = data.by_ref().take(coefficients.columns).collect();
+			matrix = matrix.add_equation(Equation::new(row, result));
+		}
+		matrix.validate()
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let cells: Vec<String> = self.data.iter().map(|value| format!("{}", value)).collect();
+		let width = cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0);
+		for row in cells.chunks(self.columns.max(1)) {
+			let row: Vec<String> = row.iter().map(|cell| format!("{:>width$}", cell, width = width)).collect();
+			writeln!(f, "{}", row.join(" "))?;
+		}
+		Ok(())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn matrix(rows: Vec<Vec<i32>>) -> Matrix<i32> {
+        Matrix::from_rows(rows).unwrap()
+    }
+
+    #[test]
+    fn algebra() {
+        let a = matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = matrix(vec![vec![1, 0], vec![0, 1], vec![1, 1]]);
+        assert_eq!(a.product(&b).unwrap(), matrix(vec![vec![4, 5], vec![10, 11]]));
+        assert_eq!(a.transpose(), matrix(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(a.sum(&a).unwrap(), matrix(vec![vec![2, 4, 6], vec![8, 10, 12]]));
+        assert_eq!(a.apply(&[1, 1, 1]).unwrap(), vec![6, 15]);
+        assert_eq!(Matrix::identity(3).product(&b).unwrap(), b);
+        assert_eq!(a.to_string(), "1 2 3\n4 5 6\n");
+
+        assert!(matches!(a.product(&a), Err(SolveError::IncompatibleDimensions((2, 3), (2, 3)))));
+        assert!(matches!(a.sum(&b), Err(SolveError::IncompatibleDimensions((2, 3), (3, 2)))));
+        assert!(matches!(a.apply(&[1]), Err(SolveError::IncompatibleDimensions((2, 3), (1, 1)))));
+        assert!(matches!(
+            Matrix::from_rows(vec![vec![1, 2], vec![3]]),
+            Err(SolveError::UnfittingCoefficientAmount(1, 2))
+        ));
+    }
+
+    #[test]
+    fn coefficient_matrix_parts() {
+        let system = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8, -6], 2))
+            .add_equation(Equation::new(vec![2, 3], 2));
+        let (coefficients, results) = system.clone().into_parts();
+        assert_eq!(coefficients, matrix(vec![vec![8, -6], vec![2, 3]]));
+        assert_eq!(results, vec![2, 2]);
+        assert_eq!(CoefficientMatrix::from_parts(coefficients, results).unwrap(), system);
+
+        assert!(CoefficientMatrix::from_parts(matrix(vec![vec![1, 2]]), vec![1]).is_err());
+        assert!(CoefficientMatrix::from_parts(Matrix::<i32>::identity(2), vec![1]).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt;
+use core::mem::{size_of, size_of_val};
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Elimination, Equation, Lu, Matrix, Trace, UpdatedLu};
+
+// Bytes held on the heap, so that callers with little memory, like a wasm
+// module in a browser tab, can refuse a system before allocation fails.
+// Spare capacity is counted where the allocation is known.
+
+impl<T> CoefficientMatrix<T> {
+	pub fn heap_bytes(&self) -> usize {
+		self.matrix.capacity() * size_of::<Equation<T>>()
+			+ self.matrix.iter()
+				.map(|equation| equation.coefficients.capacity() * size_of::<T>())
+				.sum::<usize>()
+	}
+
+	// Bytes a system of `size` equations will hold once filled in
+	pub fn estimated_bytes(size: usize) -> usize {
+		size.saturating_mul(size_of::<Equation<T>>())
+			.saturating_add(size.saturating_mul(size).saturating_mul(size_of::<T>()))
+	}
+}
+
+impl<T> Matrix<T> {
+	pub fn heap_bytes(&self) -> usize {
+		self.rows() * self.columns() * size_of::<T>()
+	}
+}
+
+impl<T> Trace<T> {
+	pub fn heap_bytes(&self) -> usize {
+		size_of_val(self.steps())
+	}
+}
+
+impl<T> Lu<T>
+where
+	T: Real
+{
+	pub fn heap_bytes(&self) -> usize {
+		size_of_val(self.permutation()) + self.l().heap_bytes() + self.u().heap_bytes()
+	}
+}
+
+impl<T> UpdatedLu<T>
+where
+	T: Real
+{
+	pub fn heap_bytes(&self) -> usize {
+		self.lu().heap_bytes() + self.update_count() * (3 * self.lu().permutation().len() + 1) * size_of::<T>()
+	}
+}
+
+impl<T> Elimination<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	pub fn heap_bytes(&self) -> usize {
+		self.matrix().heap_bytes()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::mem::size_of;
+
+    #[test]
+    fn count_heap_bytes() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0));
+        let bytes = mat.heap_bytes();
+        assert!(bytes >= 2 * size_of::<Equation<f64>>() + 4 * size_of::<f64>());
+        assert!(CoefficientMatrix::<f64>::estimated_bytes(2) <= bytes);
+        assert_eq!(CoefficientMatrix::<f64>::estimated_bytes(usize::MAX), usize::MAX);
+
+        let lu = mat.lu().unwrap();
+        assert_eq!(lu.heap_bytes(), 2 * size_of::<usize>() + 8 * size_of::<f64>());
+        assert_eq!(Trace::<f64>::new().heap_bytes(), 0);
+    }
+}
@@ -0,0 +1,87 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{dot, CoefficientMatrix, SolveError};
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solution of least Euclidean norm for a system with fewer equations than
+	// unknowns, which `validate` rejects. With x = Aᵀy the system becomes
+	// A Aᵀ y = b, which is square and regular as long as the equations are
+	// independent.
+	pub fn solve_min_norm(&self) -> Result<Vec<T>, SolveError> {
+		let (size, equations) = (self.size, self.matrix.len());
+		if size < 1 {
+			return Err(SolveError::TooSmall(size));
+		}
+		if equations < 1 || equations > size {
+			return Err(SolveError::UnfittingEquationAmount(equations, size));
+		}
+		if let Some(equation) = self.matrix.iter().find(|equation| equation.len() != size) {
+			return Err(SolveError::UnfittingCoefficientAmount(equation.len(), size));
+		}
+
+		let rows = self.matrix.iter()
+			.map(|equation| {
+				let mut row: Vec<T> = self.matrix.iter()
+					.map(|other| dot(&equation.coefficients, &other.coefficients))
+					.collect();
+				row.push(equation.result);
+				row
+			})
+			.collect::<Vec<_>>();
+		let solved = CoefficientMatrix::try_from(rows)?.convert()?.solve()?;
+
+		let mut solution = vec![T::zero(); size];
+		for (equation, y) in self.matrix.iter().zip(solved.solution()) {
+			for (value, coefficient) in solution.iter_mut().zip(&equation.coefficients) {
+				*value = *value + *coefficient * y;
+			}
+		}
+		Ok(solution)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn min_norm_solution() {
+        // x + y + z = 3 is closest to the origin at (1, 1, 1)
+        let plane = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 1.0, 1.0], 3.0));
+        assert!(plane.clone().validate().is_err());
+        let solution = plane.solve_min_norm().unwrap();
+        assert!(solution.iter().all(|x| (x - 1.0).abs() < 1e-12));
+
+        // Two planes meeting in the line (t, 1, 2 - t), closest at t = 1
+        let line = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 0.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![0.0, 2.0, 0.0], 2.0));
+        let solution = line.solve_min_norm().unwrap();
+        for (actual, expected) in solution.iter().zip(&[1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12, "{:?}", solution);
+        }
+    }
+
+    #[test]
+    fn invalid_systems() {
+        let dependent = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 2.0, 2.0], 6.0));
+        assert!(matches!(dependent.solve_min_norm(), Err(SolveError::DependentSolutionSet)));
+
+        let empty = CoefficientMatrix::<f64>::new(2);
+        assert!(matches!(empty.solve_min_norm(), Err(SolveError::UnfittingEquationAmount(0, 2))));
+
+        let short = CoefficientMatrix::new(2).add_equation(Equation::new(vec![1.0], 1.0));
+        assert!(matches!(short.solve_min_norm(), Err(SolveError::UnfittingCoefficientAmount(1, 2))));
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+use super::{axpy, dot, norm_inf, CoefficientMatrix, Equation, Lu, Result};
+
+impl CoefficientMatrix<f64> {
+	// Solve like `convert` followed by `solve`, but factorize in single
+	// precision and recover double precision by iterative refinement: each
+	// round computes the residual b - A x in f64 and corrects x by the
+	// single precision solution for it. If the corrections don't shrink
+	// below f64 accuracy within `max_iterations` rounds, which happens for
+	// systems too ill-conditioned for f32, it falls back to f64 elimination.
+	pub fn solve_mixed(self, max_iterations: usize) -> Result<f64> {
+		self.ensure_validated()?;
+		let single = self.matrix.iter()
+			.map(|equation| Equation::new(
+				equation.coefficients.iter().map(|&value| value as f32).collect(),
+				equation.result as f32,
+			))
+			.fold(CoefficientMatrix::new(self.size), CoefficientMatrix::add_equation);
+
+		let lu = match single.lu() {
+			Ok(lu) => lu,
+			Err(_) => return self.convert()?.solve(),
+		};
+		let solution = match lu.solve(&single.results().collect::<Vec<f32>>()) {
+			Ok(solution) => solution.into_iter().map(f64::from).collect(),
+			Err(_) => return self.convert()?.solve(),
+		};
+		match self.refine(&lu, solution, max_iterations) {
+			Some(solution) => {
+				let mut solved = self;
+				solved.set_solved(solution);
+				Ok(solved)
+			},
+			None => self.convert()?.solve(),
+		}
+	}
+
+	// Improve a solution obtained from single precision factors of the
+	// coefficients by iterative refinement. None if it doesn't reach f64
+	// accuracy within `max_iterations` rounds.
+	pub(super) fn refine(&self, lu: &Lu<f32>, mut solution: Vec<f64>, max_iterations: usize) -> Option<Vec<f64>> {
+		for _ in 0..max_iterations {
+			let residual: Vec<f32> = self.matrix.iter()
+				.map(|equation| (equation.result - dot(&equation.coefficients, &solution)) as f32)
+				.collect();
+			let correction: Vec<f64> = lu.solve(&residual).ok()?.into_iter().map(f64::from).collect();
+			axpy(1.0, &correction, &mut solution);
+			let largest = (norm_inf(&correction), norm_inf(&solution));
+			if !largest.0.is_finite() {
+				return None;
+			}
+			if largest.0 <= largest.1 * f64::EPSILON {
+				return Some(solution);
+			}
+		}
+		None
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn refine_to_double_precision() {
+        let size = 12;
+        let mut mat = CoefficientMatrix::new(size);
+        for i in 0..size {
+            let coefficients = (0..size)
+                .map(|j| if i == j { 20.0 } else { 1.0 / (i + j + 1) as f64 })
+                .collect();
+            mat = mat.add_equation(Equation::new(coefficients, (i as f64).sqrt()));
+        }
+        let expected: Vec<f64> = mat.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        let solved = mat.solve_mixed(10).unwrap();
+        for (x, y) in solved.solution().zip(&expected) {
+            // Far beyond what f32 alone could reach
+            assert!((x - y).abs() < 1e-13);
+        }
+        assert_eq!(solved.get(0, 0), Some(1.0));
+    }
+
+    #[test]
+    fn fall_back_to_double_precision() {
+        // Singular in f32 since 1 + 1e-10 rounds to 1, but not in f64
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![1.0, 1.0 + 1e-10], 2.0 + 1e-10));
+        let solution: Vec<f64> = mat.solve_mixed(10).unwrap().solution().collect();
+        assert!((solution[0] - 1.0).abs() < 1e-5 && (solution[1] - 1.0).abs() < 1e-5);
+
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 6.0));
+        assert!(matches!(dependent.solve_mixed(10), Err(SolveError::DependentSolutionSet)));
+    }
+}
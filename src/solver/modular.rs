@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use num::{Num, One, Zero};
+
+use super::{CoefficientMatrix, SolveError};
+
+// Residue modulo the prime P, so that systems can be solved over the finite
+// field Z/pZ:
+//
+//     let x = ModInt::<7>::new(3) / ModInt::new(5); // 2, since 2 * 5 = 10 = 3 mod 7
+//
+// P must be prime for division to be defined, which isn't checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt<const P: u64> {
+	value: u64,
+}
+
+impl<const P: u64> ModInt<P> {
+	pub fn new(value: u64) -> Self {
+		ModInt { value: value % P }
+	}
+
+	// Representative in 0..P
+	pub fn value(&self) -> u64 {
+		self.value
+	}
+
+	pub fn pow(self, mut exponent: u64) -> Self {
+		let (mut base, mut result) = (self, Self::one());
+		while exponent > 0 {
+			if exponent & 1 == 1 {
+				result = result * base;
+			}
+			base = base * base;
+			exponent >>= 1;
+		}
+		result
+	}
+
+	// Multiplicative inverse by Fermat's little theorem, `None` for zero
+	pub fn inverse(self) -> Option<Self> {
+		if self.is_zero() {
+			None
+		} else {
+			Some(self.pow(P - 2))
+		}
+	}
+}
+
+impl<const P: u64> From<i64> for ModInt<P> {
+	fn from(value: i64) -> Self {
+		ModInt { value: i128::from(value).rem_euclid(i128::from(P)) as u64 }
+	}
+}
+
+impl<const P: u64> Add for ModInt<P> {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		ModInt { value: ((u128::from(self.value) + u128::from(other.value)) % u128::from(P)) as u64 }
+	}
+}
+
+impl<const P: u64> Sub for ModInt<P> {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self + -other
+	}
+}
+
+impl<const P: u64> Neg for ModInt<P> {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		ModInt { value: (P - self.value) % P }
+	}
+}
+
+impl<const P: u64> Mul for ModInt<P> {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		ModInt { value: (u128::from(self.value) * u128::from(other.value) % u128::from(P)) as u64 }
+	}
+}
+
+impl<const P: u64> Div for ModInt<P> {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn div(self, other: Self) -> Self {
+		self * other.inverse().expect("attempt to divide by zero")
+	}
+}
+
+// Every nonzero element divides every other one, leaving no remainder
+impl<const P: u64> Rem for ModInt<P> {
+	type Output = Self;
+
+	fn rem(self, other: Self) -> Self {
+		assert!(!other.is_zero(), "attempt to calculate the remainder with a divisor of zero");
+		Self::zero()
+	}
+}
+
+impl<const P: u64> Zero for ModInt<P> {
+	fn zero() -> Self {
+		ModInt { value: 0 }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.value == 0
+	}
+}
+
+impl<const P: u64> One for ModInt<P> {
+	fn one() -> Self {
+		ModInt::new(1)
+	}
+}
+
+impl<const P: u64> Num for ModInt<P> {
+	type FromStrRadixErr = core::num::ParseIntError;
+
+	// Negative numbers are taken to their residue
+	fn from_str_radix(text: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+		i64::from_str_radix(text, radix).map(ModInt::from)
+	}
+}
+
+impl<const P: u64> fmt::Display for ModInt<P> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.value)
+	}
+}
+
+impl<T: Num + Copy> CoefficientMatrix<T> {
+	// Gauss-Jordan elimination for exact fields such as `ModInt` or
+	// `Ratio`. Without magnitudes to compare, the first nonzero entry of
+	// each column is taken as pivot.
+	pub fn solve_field(&self) -> Result<Vec<T>, SolveError> {
+		let size = self.size;
+		let mut rows: Vec<Vec<T>> = self.clone().validate()?.matrix.into_iter()
+			.map(|equation| {
+				let mut row = equation.coefficients;
+				row.push(equation.result);
+				row
+			})
+			.collect();
+
+		let mut rank = 0;
+		for column in 0..size {
+			let pivot_row = match (rank..size).find(|&row| !rows[row][column].is_zero()) {
+				Some(row) => row,
+				None => continue,
+			};
+			rows.swap(pivot_row, rank);
+			let pivot = rows[rank][column];
+			for value in rows[rank].iter_mut() {
+				*value = *value / pivot;
+			}
+			let pivot_row = rows[rank].clone();
+			for (i, row) in rows.iter_mut().enumerate() {
+				let factor = row[column];
+				if i == rank || factor.is_zero() {
+					continue;
+				}
+				for (value, above) in row.iter_mut().zip(&pivot_row) {
+					*value = *value - factor * *above;
+				}
+			}
+			rank += 1;
+		}
+
+		if rank < size {
+			let consistent = rows[rank..].iter().all(|row| row[size].is_zero());
+			return Err(if consistent {
+				SolveError::DependentSolutionSet
+			} else {
+				SolveError::EmptySolutionSet
+			});
+		}
+		Ok(rows.into_iter().map(|row| row[size]).collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::convert::TryFrom;
+
+    type F7 = ModInt<7>;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (F7::new(3), F7::new(5));
+        assert_eq!(a + b, F7::new(1));
+        assert_eq!(a - b, F7::new(5));
+        assert_eq!(a * b, F7::new(1));
+        assert_eq!(a / b, F7::new(2));
+        assert_eq!(F7::from(-1), F7::new(6));
+        assert_eq!(F7::new(0).inverse(), None);
+        assert_eq!("-2".parse::<i64>().map(F7::from).unwrap().value(), 5);
+        assert_eq!(<F7 as num::Num>::from_str_radix("10", 10).unwrap().to_string(), "3");
+
+        // Products near the modulus don't overflow
+        let large = ModInt::<18_446_744_073_709_551_557>::new(u64::MAX - 100);
+        assert_eq!(large * large.inverse().unwrap(), ModInt::new(1));
+    }
+
+    #[test]
+    fn solve_mod_prime() {
+        let f = |rows: Vec<Vec<i64>>| -> Vec<Vec<F7>> {
+            rows.into_iter().map(|row| row.into_iter().map(F7::from).collect()).collect()
+        };
+        // 2x + y = 3, x + 3y = 4 (mod 7) has x = 1, y = 1. The first column
+        // starts with a small nonzero pivot that must not be skipped.
+        let system = CoefficientMatrix::try_from(f(vec![vec![2, 1, 3], vec![1, 3, 4]])).unwrap();
+        assert_eq!(system.solve_field().unwrap(), vec![F7::new(1), F7::new(1)]);
+
+        // 7 vanishes mod 7, which needs a row swap
+        let system = CoefficientMatrix::try_from(f(vec![vec![7, 1, 2], vec![1, 1, 3]])).unwrap();
+        assert_eq!(system.solve_field().unwrap(), vec![F7::new(1), F7::new(2)]);
+
+        // x + y = 1, 8x + 8y = 2 is inconsistent as 8 = 1 mod 7
+        let system = CoefficientMatrix::try_from(f(vec![vec![1, 1, 1], vec![8, 8, 2]])).unwrap();
+        assert!(matches!(system.solve_field(), Err(SolveError::EmptySolutionSet)));
+    }
+}
@@ -0,0 +1,297 @@
+use alloc::{collections::BTreeSet, format, vec, string::String, string::ToString, vec::Vec};
+use num::Num;
+use core::fmt;
+use core::str::FromStr;
+
+use super::{CoefficientMatrix, Equation};
+
+// Reading and writing of the Matrix Market exchange format
+// (https://math.nist.gov/MatrixMarket/formats.html). A system is stored as
+// its augmented n x (n + 1) matrix, the last column holding the results.
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MtxError {
+	MissingHeader,
+	UnsupportedFormat(String),
+	InvalidSize(usize),
+	InvalidEntry(usize),
+	UnfittingEntryAmount(usize, usize),
+	NotAugmented(usize, usize),
+	// A coordinate matrix without entries in the given row, counted from 1
+	EmptyRow(usize),
+}
+
+impl fmt::Display for MtxError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			MtxError::MissingHeader =>
+				write!(f, "Missing %%MatrixMarket header"),
+			MtxError::UnsupportedFormat(header) =>
+				write!(f, "Unsupported Matrix Market format '{}'", header),
+			MtxError::InvalidSize(line) =>
+				write!(f, "Invalid size line at line {}", line),
+			MtxError::InvalidEntry(line) =>
+				write!(f, "Invalid entry at line {}", line),
+			MtxError::UnfittingEntryAmount(amount, expected) =>
+				write!(f, "Found {} entries but expected {}", amount, expected),
+			MtxError::NotAugmented(rows, columns) =>
+				write!(f, "A {} x {} matrix is not an augmented system", rows, columns),
+			MtxError::EmptyRow(row) =>
+				write!(f, "Row {} has no entries", row),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MtxError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Layout {
+	Array,
+	Coordinate,
+}
+
+// Augmented matrices are never square, so only the `general` symmetry applies
+fn parse_header(line: &str) -> Result<Layout, MtxError> {
+	let fields: Vec<String> = line.split_whitespace().map(|field| field.to_lowercase()).collect();
+	if fields.first().map(|field| field.as_str()) != Some("%%matrixmarket") {
+		return Err(MtxError::MissingHeader);
+	}
+
+	let unsupported = || MtxError::UnsupportedFormat(line.trim().to_string());
+	if fields.len() != 5 || fields[1] != "matrix" {
+		return Err(unsupported());
+	}
+	let layout = match fields[2].as_str() {
+		"array" => Layout::Array,
+		"coordinate" => Layout::Coordinate,
+		_ => return Err(unsupported()),
+	};
+	match (fields[3].as_str(), fields[4].as_str()) {
+		("real", "general") | ("integer", "general") => Ok(layout),
+		_ => Err(unsupported()),
+	}
+}
+
+fn parse_usize(field: Option<&str>, line: usize) -> Result<usize, MtxError> {
+	field.and_then(|field| field.parse().ok()).ok_or(MtxError::InvalidSize(line))
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Num + Copy + FromStr
+{
+	// Read an augmented system from Matrix Market text. Both the dense
+	// `array` and the sparse `coordinate` layouts are accepted.
+	pub fn from_matrix_market(input: &str) -> Result<Self, MtxError> {
+		let mut lines = input.lines().enumerate();
+		let layout = match lines.next() {
+			Some((_, header)) => parse_header(header)?,
+			None => return Err(MtxError::MissingHeader),
+		};
+
+		// Skip comments and blank lines
+		let mut lines = lines
+			.map(|(idx, line)| (idx + 1, line.trim()))
+			.filter(|(_, line)| !line.is_empty() && !line.starts_with('%'));
+
+		let (size_line, size) = lines.next().ok_or(MtxError::InvalidSize(1))?;
+		let mut fields = size.split_whitespace();
+		let rows = parse_usize(fields.next(), size_line)?;
+		let columns = parse_usize(fields.next(), size_line)?;
+		if rows.checked_add(1) != Some(columns) {
+			return Err(MtxError::NotAugmented(rows, columns));
+		}
+		let length = rows.checked_mul(columns).ok_or(MtxError::InvalidSize(size_line))?;
+
+		// The size line is untrusted, so the dense matrix is only allocated
+		// once the entries that were actually read vouch for it
+		let values = match layout {
+			Layout::Array => {
+				// Array entries are listed column by column
+				let entries = lines
+					.map(|(line, entry)| entry.parse::<T>().map_err(|_| MtxError::InvalidEntry(line)))
+					.collect::<Result<Vec<T>, MtxError>>()?;
+				if entries.len() != length {
+					return Err(MtxError::UnfittingEntryAmount(entries.len(), length));
+				}
+				let mut values = vec![T::zero(); length];
+				for (idx, value) in entries.into_iter().enumerate() {
+					let (row, column) = (idx % rows, idx / rows);
+					values[row * columns + column] = value;
+				}
+				values
+			},
+			Layout::Coordinate => {
+				let expected = parse_usize(fields.next(), size_line)?;
+				let mut entries = Vec::new();
+				let mut seen = BTreeSet::new();
+				for (line, entry) in lines {
+					let mut fields = entry.split_whitespace();
+					let row = parse_usize(fields.next(), line).map_err(|_| MtxError::InvalidEntry(line))?;
+					let column = parse_usize(fields.next(), line).map_err(|_| MtxError::InvalidEntry(line))?;
+					let value = fields.next()
+						.and_then(|field| field.parse::<T>().ok())
+						.ok_or(MtxError::InvalidEntry(line))?;
+					// Out of range, or a second value for the same coordinates
+					if row < 1 || row > rows || column < 1 || column > columns || !seen.insert((row, column)) {
+						return Err(MtxError::InvalidEntry(line));
+					}
+					entries.push((row - 1, column - 1, value));
+				}
+				if entries.len() != expected {
+					return Err(MtxError::UnfittingEntryAmount(entries.len(), expected));
+				}
+				// An equation without a single stored entry can't be part of
+				// a usable system. Requiring one per row bounds the matrix by
+				// the input instead of by the size line, so the count is
+				// checked before anything of the size of a row is allocated.
+				if entries.len() < rows {
+					return Err(MtxError::InvalidSize(size_line));
+				}
+				let mut filled = vec![false; rows];
+				for &(row, _, _) in &entries {
+					filled[row] = true;
+				}
+				if let Some(row) = filled.iter().position(|&filled| !filled) {
+					return Err(MtxError::EmptyRow(row + 1));
+				}
+				let mut values = vec![T::zero(); length];
+				for (row, column, value) in entries {
+					values[row * columns + column] = value;
+				}
+				values
+			},
+		};
+
+		let matrix = values.chunks(columns)
+			.map(|row| Equation::new(row[..rows].to_vec(), row[rows]))
+			.collect();
+		Ok(CoefficientMatrix {
+			size: rows,
+			matrix,
+			unentered: BTreeSet::new(),
+		})
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Num + Copy + fmt::Display
+{
+	fn augmented_size(&self) -> (usize, usize) {
+		(self.matrix.len(), self.size + 1)
+	}
+
+	// Value of the augmented system, the result being column `size`.
+	// Unvalidated rows count as padded with zeros or cut to `size`.
+	fn augmented(&self, equation: &Equation<T>, column: usize) -> T {
+		if column == self.size {
+			equation.result
+		} else {
+			equation.coefficients.get(column).copied().unwrap_or_else(T::zero)
+		}
+	}
+
+	// Write the augmented system as a dense Matrix Market `array`
+	pub fn to_matrix_market(&self) -> String {
+		let (rows, columns) = self.augmented_size();
+		let mut output = format!("%%MatrixMarket matrix array real general\n{} {}\n", rows, columns);
+		for column in 0..columns {
+			for equation in self.matrix.iter() {
+				output.push_str(&format!("{}\n", self.augmented(equation, column)));
+			}
+		}
+		output
+	}
+
+	// Write the nonzero entries of the augmented system as a sparse Matrix
+	// Market `coordinate` matrix
+	pub fn to_matrix_market_coordinate(&self) -> String {
+		let (rows, columns) = self.augmented_size();
+		let mut entries = String::new();
+		let mut amount = 0;
+		for (row, equation) in self.matrix.iter().enumerate() {
+			for column in 0..columns {
+				let value = self.augmented(equation, column);
+				if !value.is_zero() {
+					entries.push_str(&format!("{} {} {}\n", row + 1, column + 1, value));
+					amount += 1;
+				}
+			}
+		}
+		format!("%%MatrixMarket matrix coordinate real general\n{} {} {}\n{}", rows, columns, amount, entries)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::MtxError;
+
+    fn system() -> CoefficientMatrix<f64> {
+        CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![0.0,  4.5], 1.5))
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let text = system().to_matrix_market();
+        assert_eq!(text, "%%MatrixMarket matrix array real general\n2 3\n8\n0\n-6\n4.5\n2\n1.5\n");
+        assert_eq!(CoefficientMatrix::from_matrix_market(&text).unwrap(), system());
+    }
+
+    #[test]
+    fn coordinate_round_trip() {
+        let text = system().to_matrix_market_coordinate();
+        assert_eq!(text, "%%MatrixMarket matrix coordinate real general\n2 3 5\n1 1 8\n1 2 -6\n1 3 2\n2 2 4.5\n2 3 1.5\n");
+        assert_eq!(CoefficientMatrix::from_matrix_market(&text).unwrap(), system());
+    }
+
+    #[test]
+    fn pad_short_rows() {
+        let short = CoefficientMatrix::new(2).add_equation(Equation::new(vec![8.0], 2.0));
+        assert_eq!(short.to_matrix_market(), "%%MatrixMarket matrix array real general\n1 3\n8\n0\n2\n");
+        assert_eq!(short.to_matrix_market_coordinate(), "%%MatrixMarket matrix coordinate real general\n1 3 2\n1 1 8\n1 3 2\n");
+    }
+
+    #[test]
+    fn reject_non_augmented() {
+        let text = "%%MatrixMarket matrix array real general\n% comment\n2 2\n1\n0\n0\n1\n";
+        assert_eq!(CoefficientMatrix::<f64>::from_matrix_market(text), Err(MtxError::NotAugmented(2, 2)));
+    }
+
+    #[test]
+    fn reject_oversized() {
+        let overflow = "%%MatrixMarket matrix array real general\n4294967296 4294967297 0\n";
+        assert_eq!(CoefficientMatrix::<f64>::from_matrix_market(overflow), Err(MtxError::InvalidSize(2)));
+
+        let wrapping = format!("%%MatrixMarket matrix array real general\n{} 0\n", usize::MAX);
+        assert_eq!(
+            CoefficientMatrix::<f64>::from_matrix_market(&wrapping),
+            Err(MtxError::NotAugmented(usize::MAX, 0))
+        );
+
+        // Huge sizes are refused before anything is allocated for them
+        let array = "%%MatrixMarket matrix array real general\n1000000000 1000000001\n1\n";
+        assert_eq!(
+            CoefficientMatrix::<f64>::from_matrix_market(array),
+            Err(MtxError::UnfittingEntryAmount(1, 1_000_000_001_000_000_000))
+        );
+        let coordinate = "%%MatrixMarket matrix coordinate real general\n1000000000 1000000001 1\n1 1 1\n";
+        assert_eq!(
+            CoefficientMatrix::<f64>::from_matrix_market(coordinate),
+            Err(MtxError::InvalidSize(2))
+        );
+    }
+
+    #[test]
+    fn reject_empty_rows_and_duplicates() {
+        let empty = "%%MatrixMarket matrix coordinate real general\n2 3 2\n1 1 8\n1 3 2\n";
+        assert_eq!(CoefficientMatrix::<f64>::from_matrix_market(empty), Err(MtxError::EmptyRow(2)));
+        let duplicate = "%%MatrixMarket matrix coordinate real general\n2 3 3\n1 1 8\n2 2 1\n1 1 4\n";
+        assert_eq!(CoefficientMatrix::<f64>::from_matrix_market(duplicate), Err(MtxError::InvalidEntry(5)));
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use ::nalgebra::{DMatrix, DVector, Scalar};
+use num::Num;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+// Conversions from and to nalgebra's dynamically sized matrices and
+// vectors. nalgebra stores column by column and this crate row by row, so
+// every conversion copies.
+
+impl<T: Scalar + Num + Copy> From<Matrix<T>> for DMatrix<T> {
+	fn from(matrix: Matrix<T>) -> Self {
+		DMatrix::from_fn(matrix.rows(), matrix.columns(), |i, j| matrix[(i, j)])
+	}
+}
+
+impl<T: Scalar + Num + Copy> From<DMatrix<T>> for Matrix<T> {
+	fn from(matrix: DMatrix<T>) -> Self {
+		let mut converted = Matrix::zeros(matrix.nrows(), matrix.ncols());
+		for i in 0..matrix.nrows() {
+			for j in 0..matrix.ncols() {
+				converted[(i, j)] = matrix[(i, j)];
+			}
+		}
+		converted
+	}
+}
+
+// The coefficients and the right-hand sides
+impl<T: Scalar + Num + Copy> From<CoefficientMatrix<T>> for (DMatrix<T>, DVector<T>) {
+	fn from(matrix: CoefficientMatrix<T>) -> Self {
+		let (coefficients, results) = matrix.into_parts();
+		(coefficients.into(), DVector::from_vec(results))
+	}
+}
+
+// Validated system A x = b, failing unless A is square and b fits
+impl<T: Scalar + Num + Copy> TryFrom<(DMatrix<T>, DVector<T>)> for CoefficientMatrix<T> {
+	type Error = SolveError;
+
+	fn try_from((coefficients, results): (DMatrix<T>, DVector<T>)) -> Result<Self, SolveError> {
+		CoefficientMatrix::from_parts(coefficients.into(), results.iter().copied().collect::<Vec<T>>())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::convert::TryFrom;
+    use ::nalgebra::{DMatrix, DVector};
+
+    #[test]
+    fn round_trip() {
+        let a = DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let matrix = Matrix::from(a.clone());
+        assert_eq!(matrix, Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap());
+        assert_eq!(DMatrix::from(matrix), a);
+
+        let system = CoefficientMatrix::try_from((
+            DMatrix::from_row_slice(2, 2, &[8.0, -6.0, 2.0, 3.0]),
+            DVector::from_vec(vec![2.0, 2.0]),
+        )).unwrap();
+        let (coefficients, results): (DMatrix<f64>, DVector<f64>) = system.clone().into();
+        assert_eq!(coefficients[(0, 1)], -6.0);
+        assert_eq!(results, DVector::from_vec(vec![2.0, 2.0]));
+        let solution: Vec<f64> = system.convert().unwrap().solve().unwrap().solution().collect();
+        assert_eq!(solution, vec![0.5, 1.0 / 3.0]);
+
+        assert!(matches!(
+            CoefficientMatrix::try_from((a, DVector::from_vec(vec![1.0, 2.0]))),
+            Err(SolveError::UnfittingCoefficientAmount(3, 2))
+        ));
+    }
+}
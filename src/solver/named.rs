@@ -0,0 +1,84 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, SolveError};
+
+// A system whose unknowns have names, so that solutions can be looked up by
+// name rather than position:
+//
+//     let solution = matrix.with_vars(&["x", "y"])?.solve()?;
+//     let x = solution["x"];
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedSystem<T> {
+	names: Vec<String>,
+	matrix: CoefficientMatrix<T>,
+}
+
+impl<T> CoefficientMatrix<T> {
+	// Name the unknowns in column order. There must be one distinct name
+	// per unknown.
+	pub fn with_vars<S: AsRef<str>>(self, names: &[S]) -> Result<NamedSystem<T>, SolveError> {
+		if names.len() != self.size {
+			return Err(SolveError::UnfittingCoefficientAmount(names.len(), self.size));
+		}
+		let names: Vec<String> = names.iter().map(|name| String::from(name.as_ref())).collect();
+		if let Some(index) = (1..names.len()).find(|&i| names[..i].contains(&names[i])) {
+			return Err(SolveError::DuplicateName(names[index].clone()));
+		}
+		Ok(NamedSystem { names, matrix: self })
+	}
+}
+
+impl<T> NamedSystem<T> {
+	pub fn names(&self) -> &[String] {
+		&self.names
+	}
+
+	pub fn matrix(&self) -> &CoefficientMatrix<T> {
+		&self.matrix
+	}
+
+	pub fn into_parts(self) -> (Vec<String>, CoefficientMatrix<T>) {
+		(self.names, self.matrix)
+	}
+
+	// Column of the unknown called `name`
+	pub fn index_of(&self, name: &str) -> Option<usize> {
+		self.names.iter().position(|other| other == name)
+	}
+}
+
+impl<T> NamedSystem<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	pub fn solve(&self) -> Result<BTreeMap<String, T>, SolveError> {
+		let solved = self.matrix.clone().validate()?.convert()?.solve()?;
+		Ok(self.names.iter().cloned().zip(solved.solution()).collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn solve_by_name() {
+        let named = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .with_vars(&["current", "voltage"])
+            .unwrap();
+        assert_eq!(named.index_of("voltage"), Some(1));
+        let solution = named.solve().unwrap();
+        assert_eq!(solution["current"], 0.5);
+        assert_eq!(solution["voltage"], 1.0 / 3.0);
+
+        let mat = CoefficientMatrix::<f64>::new(2);
+        assert!(matches!(mat.clone().with_vars(&["x"]), Err(SolveError::UnfittingCoefficientAmount(1, 2))));
+        assert!(matches!(mat.with_vars(&["x", "x"]), Err(SolveError::DuplicateName(name)) if name == "x"));
+    }
+}
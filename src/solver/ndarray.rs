@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use ::ndarray::{Array1, Array2};
+use num::Num;
+
+use super::{CoefficientMatrix, Equation, Matrix, SolveError};
+
+// Conversions from and to ndarray's owned arrays. Both store row by row, but
+// the conversions copy all the same.
+
+impl<T: Num + Copy> From<Matrix<T>> for Array2<T> {
+	fn from(matrix: Matrix<T>) -> Self {
+		Array2::from_shape_fn((matrix.rows(), matrix.columns()), |(i, j)| matrix[(i, j)])
+	}
+}
+
+impl<T: Num + Copy> From<Array2<T>> for Matrix<T> {
+	fn from(array: Array2<T>) -> Self {
+		let (rows, columns) = array.dim();
+		let mut matrix = Matrix::zeros(rows, columns);
+		for i in 0..rows {
+			for j in 0..columns {
+				matrix[(i, j)] = array[(i, j)];
+			}
+		}
+		matrix
+	}
+}
+
+// The coefficients and the right-hand sides
+impl<T: Num + Copy> From<CoefficientMatrix<T>> for (Array2<T>, Array1<T>) {
+	fn from(matrix: CoefficientMatrix<T>) -> Self {
+		let (coefficients, results) = matrix.into_parts();
+		(coefficients.into(), Array1::from(results))
+	}
+}
+
+// Validated system A x = b, failing unless A is square and b fits
+impl<T: Num + Copy> TryFrom<(Array2<T>, Array1<T>)> for CoefficientMatrix<T> {
+	type Error = SolveError;
+
+	fn try_from((coefficients, results): (Array2<T>, Array1<T>)) -> Result<Self, SolveError> {
+		CoefficientMatrix::from_parts(coefficients.into(), results.iter().copied().collect::<Vec<T>>())
+	}
+}
+
+// Validated system from an (n, n + 1) augmented array, the last column
+// holding the right-hand sides
+impl<T: Num + Copy> TryFrom<Array2<T>> for CoefficientMatrix<T> {
+	type Error = SolveError;
+
+	fn try_from(array: Array2<T>) -> Result<Self, SolveError> {
+		let (rows, columns) = array.dim();
+		if columns == 0 {
+			return Err(SolveError::UnfittingCoefficientAmount(0, rows));
+		}
+		let mut matrix = CoefficientMatrix::new(rows);
+		for i in 0..rows {
+			let coefficients = (0..columns - 1).map(|j| array[(i, j)]).collect();
+			matrix = matrix.add_equation(Equation::new(coefficients, array[(i, columns - 1)]));
+		}
+		matrix.validate()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use core::convert::TryFrom;
+    use ::ndarray::{array, Array1, Array2};
+
+    #[test]
+    fn round_trip() {
+        let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let matrix = Matrix::from(a.clone());
+        assert_eq!(matrix, Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap());
+        assert_eq!(Array2::from(matrix), a);
+
+        let system = CoefficientMatrix::try_from((array![[8.0, -6.0], [2.0, 3.0]], array![2.0, 2.0])).unwrap();
+        let (coefficients, results): (Array2<f64>, Array1<f64>) = system.clone().into();
+        assert_eq!(coefficients[(0, 1)], -6.0);
+        assert_eq!(results, array![2.0, 2.0]);
+        assert!(matches!(
+            CoefficientMatrix::try_from((a, array![1.0, 2.0])),
+            Err(SolveError::UnfittingCoefficientAmount(3, 2))
+        ));
+    }
+
+    #[test]
+    fn augmented() {
+        let system = CoefficientMatrix::try_from(array![[8.0, -6.0, 2.0], [2.0, 3.0, 2.0]]).unwrap();
+        let solution: Vec<f64> = system.convert().unwrap().solve().unwrap().solution().collect();
+        assert_eq!(solution, vec![0.5, 1.0 / 3.0]);
+        assert!(CoefficientMatrix::try_from(array![[1.0, 2.0], [3.0, 4.0]]).is_err());
+    }
+}
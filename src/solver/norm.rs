@@ -0,0 +1,77 @@
+use num::traits::real::Real;
+
+use super::{norm1, CoefficientMatrix, Matrix};
+
+// Induced 1- and ∞-norms and the Frobenius norm. For a coefficient matrix
+// they cover the coefficients only, not the results.
+
+impl<T: Real> Matrix<T> {
+	// Largest absolute column sum
+	pub fn norm_1(&self) -> T {
+		(0..self.columns())
+			.map(|j| (0..self.rows()).fold(T::zero(), |sum, i| sum + self[(i, j)].abs()))
+			.fold(T::zero(), T::max)
+	}
+
+	// Largest absolute row sum
+	pub fn norm_inf(&self) -> T {
+		(0..self.rows())
+			.filter_map(|i| self.row(i))
+			.map(norm1)
+			.fold(T::zero(), T::max)
+	}
+
+	// √(Σ aᵢⱼ²)
+	pub fn norm_frobenius(&self) -> T {
+		(0..self.rows())
+			.filter_map(|i| self.row(i))
+			.flatten()
+			.fold(T::zero(), |sum, &x| sum + x * x)
+			.sqrt()
+	}
+}
+
+impl<T: Real> CoefficientMatrix<T> {
+	pub fn norm_1(&self) -> T {
+		let columns = self.matrix.iter().map(|equation| equation.len()).max().unwrap_or(0);
+		(0..columns)
+			.map(|j| self.matrix.iter()
+				.filter_map(|equation| equation.coefficients.get(j))
+				.fold(T::zero(), |sum, x| sum + x.abs()))
+			.fold(T::zero(), T::max)
+	}
+
+	pub fn norm_inf(&self) -> T {
+		self.matrix.iter()
+			.map(|equation| norm1(&equation.coefficients))
+			.fold(T::zero(), T::max)
+	}
+
+	pub fn norm_frobenius(&self) -> T {
+		self.matrix.iter()
+			.flat_map(|equation| equation.coefficients.iter())
+			.fold(T::zero(), |sum, &x| sum + x * x)
+			.sqrt()
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn matrix_norms() {
+        let a = Matrix::from_rows(vec![vec![1.0, -2.0], vec![-3.0, 4.0]]).unwrap();
+        assert_eq!((a.norm_1(), a.norm_inf()), (6.0, 7.0));
+        assert_eq!(a.norm_frobenius(), 30.0f64.sqrt());
+        assert_eq!(Matrix::<f64>::zeros(0, 0).norm_1(), 0.0);
+
+        let system = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, -2.0], 100.0))
+            .add_equation(Equation::new(vec![-3.0, 4.0], 100.0));
+        assert_eq!((system.norm_1(), system.norm_inf()), (6.0, 7.0));
+        assert_eq!(system.norm_frobenius(), a.norm_frobenius());
+    }
+}
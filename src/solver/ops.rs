@@ -0,0 +1,74 @@
+use core::ops::{Add, Mul, Sub};
+use num::Num;
+
+use super::Equation;
+
+// Row operations on whole equations, acting on the coefficients and the
+// result alike, e.g. to eliminate by hand:
+//
+//     let second = second - first.clone() * (2.0 / 8.0);
+//
+// Equations of different lengths are combined as if the shorter one was
+// padded with zero coefficients.
+
+fn combine<T: Num + Copy>(mut left: Equation<T>, right: Equation<T>, op: impl Fn(T, T) -> T) -> Equation<T> {
+	if left.coefficients.len() < right.coefficients.len() {
+		left.coefficients.resize(right.coefficients.len(), T::zero());
+	}
+	for (i, value) in left.coefficients.iter_mut().enumerate() {
+		*value = op(*value, right.coefficients.get(i).copied().unwrap_or_else(T::zero));
+	}
+	left.result = op(left.result, right.result);
+	left
+}
+
+impl<T: Num + Copy> Add for Equation<T> {
+	type Output = Equation<T>;
+
+	fn add(self, other: Equation<T>) -> Equation<T> {
+		combine(self, other, |a, b| a + b)
+	}
+}
+
+impl<T: Num + Copy> Sub for Equation<T> {
+	type Output = Equation<T>;
+
+	fn sub(self, other: Equation<T>) -> Equation<T> {
+		combine(self, other, |a, b| a - b)
+	}
+}
+
+impl<T: Num + Copy> Mul<T> for Equation<T> {
+	type Output = Equation<T>;
+
+	fn mul(mut self, factor: T) -> Equation<T> {
+		for value in self.coefficients.iter_mut() {
+			*value = *value * factor;
+		}
+		self.result = self.result * factor;
+		self
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn row_operations() {
+        let first = Equation::new(vec![8.0, -6.0], 2.0);
+        let second = Equation::new(vec![2.0, 3.0], 2.0);
+        assert_eq!(second.clone() - first.clone() * 0.25, Equation::new(vec![0.0, 4.5], 1.5));
+        assert_eq!(first.clone() + second, Equation::new(vec![10.0, -3.0], 4.0));
+        assert_eq!(first * -1.0, Equation::new(vec![-8.0, 6.0], -2.0));
+    }
+
+    #[test]
+    fn pad_shorter_equation() {
+        let short = Equation::new(vec![1, 2], 3);
+        let long = Equation::new(vec![1, 1, 1], 1);
+        assert_eq!(short.clone() - long.clone(), Equation::new(vec![0, 1, -1], 2));
+        assert_eq!(long + short, Equation::new(vec![2, 3, 1], 4));
+    }
+}
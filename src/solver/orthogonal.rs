@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+use num::traits::real::Real;
+
+use super::{axpy, dot, norm2, Matrix};
+
+// Orthonormal basis of the span of `vectors` by modified Gram-Schmidt: each
+// vector is normalized and immediately projected out of all later ones,
+// which loses much less orthogonality to rounding than the classical
+// variant. Vectors that are, up to rounding, combinations of earlier ones
+// are dropped, so the result has as many vectors as the rank.
+pub fn orthonormalize<T: Real>(vectors: &[Vec<T>]) -> Vec<Vec<T>> {
+	let mut remaining: Vec<Vec<T>> = vectors.to_vec();
+	let original: Vec<T> = remaining.iter().map(|vector| norm2(vector)).collect();
+	let mut basis: Vec<Vec<T>> = Vec::with_capacity(vectors.len());
+	for index in 0..remaining.len() {
+		let mut vector = core::mem::take(&mut remaining[index]);
+		let length = norm2(&vector);
+		let dimension = T::from(vector.len()).unwrap_or_else(T::one);
+		if length <= dimension * T::epsilon() * original[index] || length.is_zero() {
+			continue;
+		}
+		for value in vector.iter_mut() {
+			*value = *value / length;
+		}
+		for later in remaining[index + 1..].iter_mut() {
+			let projection = dot(later, &vector);
+			axpy(-projection, &vector, later);
+		}
+		basis.push(vector);
+	}
+	basis
+}
+
+impl<T: Real> Matrix<T> {
+	// Orthonormal basis of the row space, one row per basis vector
+	pub fn orthonormal_rows(&self) -> Matrix<T> {
+		let rows: Vec<Vec<T>> = (0..self.rows()).map(|i| (0..self.columns()).map(|j| self[(i, j)]).collect()).collect();
+		let basis = orthonormalize(&rows);
+		if basis.is_empty() {
+			return Matrix::zeros(0, self.columns());
+		}
+		Matrix::from_rows(basis).expect("basis vectors have equal length")
+	}
+
+	// Orthonormal basis of the column space, one column per basis vector,
+	// the Q of a thin QR decomposition for matrices of full column rank
+	pub fn orthonormal_columns(&self) -> Matrix<T> {
+		let basis = self.transpose().orthonormal_rows();
+		if basis.rows() == 0 {
+			return Matrix::zeros(self.rows(), 0);
+		}
+		basis.transpose()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn orthonormal_basis() {
+        let basis = orthonormalize(&[vec![3.0, 4.0, 0.0], vec![6.0, 8.0, 0.0], vec![1.0, 1.0, 1.0]]);
+        assert_eq!(basis.len(), 2);
+        assert_eq!(basis[0], vec![0.6, 0.8, 0.0]);
+        for (i, a) in basis.iter().enumerate() {
+            for (j, b) in basis.iter().enumerate() {
+                let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                assert!((dot - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+            }
+        }
+        assert!(orthonormalize::<f64>(&[vec![0.0, 0.0]]).is_empty());
+    }
+
+    #[test]
+    fn orthonormal_matrix_spaces() {
+        let a = Matrix::from_rows(vec![vec![1.0, 1.0], vec![1.0, -1.0], vec![0.0, 0.0]]).unwrap();
+        let q = a.orthonormal_columns();
+        assert_eq!((q.rows(), q.columns()), (3, 2));
+        let gram = q.transpose().product(&q).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((gram[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+            }
+        }
+        let rows = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap().orthonormal_rows();
+        assert_eq!((rows.rows(), rows.columns()), (1, 2));
+        assert_eq!(Matrix::<f64>::zeros(2, 2).orthonormal_columns().columns(), 0);
+    }
+}
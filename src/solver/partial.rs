@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, SolveError};
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Values of only the given unknowns, in the order requested. The
+	// requested columns are moved to the end before converting, so that
+	// back substitution only needs the last rows, which for a few unknowns
+	// of a large system skips nearly all of it.
+	pub fn solve_unknowns(&self, unknowns: &[usize]) -> Result<Vec<T>, SolveError> {
+		self.ensure_validated()?;
+		let size = self.size;
+		if let Some(&index) = unknowns.iter().find(|&&index| index >= size) {
+			return Err(SolveError::IndexOutOfBounds(index, size));
+		}
+
+		// New order of the columns, the requested ones last
+		let mut order: Vec<usize> = (0..size).filter(|column| !unknowns.contains(column)).collect();
+		let skipped = order.len();
+		for &index in unknowns {
+			if !order[skipped..].contains(&index) {
+				order.push(index);
+			}
+		}
+		let mut reordered = self.clone();
+		for (equation, original) in reordered.matrix.iter_mut().zip(&self.matrix) {
+			for (value, &column) in equation.coefficients.iter_mut().zip(&order) {
+				*value = original.coefficients[column];
+			}
+		}
+
+		let converted = reordered.convert()?;
+		// A vanishing pivot anywhere makes the system singular, which `solve`
+		// classifies
+		let nonzero = |value: T| value.abs().partial_cmp(&T::zero()) == Some(core::cmp::Ordering::Greater);
+		if (0..size).any(|row| !nonzero(converted.matrix[row].coefficients[row])) {
+			converted.solve()?;
+			return Err(SolveError::DependentSolutionSet);
+		}
+
+		let mut values = alloc::vec![T::zero(); size];
+		for row in (skipped..size).rev() {
+			let equation = &converted.matrix[row];
+			let mut value = equation.result;
+			for (coefficient, x) in equation.coefficients.iter().zip(&values).skip(row + 1) {
+				value -= *coefficient * *x;
+			}
+			values[row] = value / equation.coefficients[row];
+		}
+		Ok(unknowns.iter()
+			.map(|index| values[order.iter().position(|column| column == index).unwrap_or(0)])
+			.collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn solve_selected_unknowns() {
+        let size = 8;
+        let mut mat = CoefficientMatrix::new(size);
+        for i in 0..size {
+            let coefficients = (0..size)
+                .map(|j| if i == j { 10.0 } else { ((i + 2 * j) % 5) as f64 - 2.0 })
+                .collect();
+            mat = mat.add_equation(Equation::new(coefficients, i as f64 + 1.0));
+        }
+        let expected: Vec<f64> = mat.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        let selected = mat.solve_unknowns(&[6, 2, 6]).unwrap();
+        assert_eq!(selected.len(), 3);
+        for (value, &index) in selected.iter().zip(&[6, 2, 6]) {
+            assert!((value - expected[index]).abs() < 1e-12);
+        }
+        assert!(mat.solve_unknowns(&[]).unwrap().is_empty());
+        assert!(matches!(mat.solve_unknowns(&[8]), Err(SolveError::IndexOutOfBounds(8, 8))));
+    }
+
+    #[test]
+    fn selected_unknowns_of_singular_systems() {
+        // x + y + z = 3, x + y + 2z = 4, 2x + 2y + 3z = 7 determines z alone,
+        // but the system stays dependent
+        let mat = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, 1.0, 2.0], 4.0))
+            .add_equation(Equation::new(vec![2.0, 2.0, 3.0], 7.0));
+        assert!(matches!(mat.solve_unknowns(&[2]), Err(SolveError::DependentSolutionSet)));
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+use super::{CoefficientMatrix, Equation, SolveError};
+
+// Order of the unknowns after `permute_unknowns`: unknown i of the permuted
+// system is unknown order[i] of the original one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnPermutation {
+	order: Vec<usize>,
+}
+
+impl ColumnPermutation {
+	// Check that `order` holds every index below its length exactly once
+	pub fn new(order: Vec<usize>) -> Result<ColumnPermutation, SolveError> {
+		let mut seen = vec![false; order.len()];
+		for &index in &order {
+			match seen.get_mut(index) {
+				Some(seen) if !*seen => *seen = true,
+				_ => return Err(SolveError::InvalidPermutation(index)),
+			}
+		}
+		Ok(ColumnPermutation { order })
+	}
+
+	pub fn identity(size: usize) -> ColumnPermutation {
+		ColumnPermutation { order: (0..size).collect() }
+	}
+
+	pub fn order(&self) -> &[usize] {
+		&self.order
+	}
+
+	// Permutation undoing this one
+	pub fn inverse(&self) -> ColumnPermutation {
+		let mut order = vec![0; self.order.len()];
+		for (i, &original) in self.order.iter().enumerate() {
+			order[original] = i;
+		}
+		ColumnPermutation { order }
+	}
+
+	// Values in the permuted order, such as a solution of the permuted
+	// system, put back into the original order of the unknowns
+	pub fn unpermute<T: Clone>(&self, values: &[T]) -> Vec<T> {
+		self.inverse().order.iter().map(|&i| values[i].clone()).collect()
+	}
+}
+
+impl<T: Clone> CoefficientMatrix<T> {
+	// Reorder the unknowns so that unknown i of the result is unknown
+	// order[i] of this system, e.g. for better pivots or to present them in
+	// another order. Solutions of the result map back with
+	// `ColumnPermutation::unpermute`.
+	pub fn permute_unknowns(&self, order: &[usize]) -> Result<(CoefficientMatrix<T>, ColumnPermutation), SolveError> {
+		if order.len() != self.size {
+			return Err(SolveError::UnfittingCoefficientAmount(order.len(), self.size));
+		}
+		let permutation = ColumnPermutation::new(order.to_vec())?;
+		self.check_rows()?;
+		let matrix = self.matrix.iter()
+			.map(|equation| Equation::new(
+				order.iter().map(|&column| equation.coefficients[column].clone()).collect(),
+				equation.result.clone(),
+			))
+			.collect();
+		Ok((CoefficientMatrix { size: self.size, matrix, unentered: BTreeSet::new() }, permutation))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn solution_in_original_order() {
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![0.0, 2.0, -1.0], 2.0))
+            .add_equation(Equation::new(vec![4.0, 1.0, 2.0], 12.0))
+            .add_equation(Equation::new(vec![-2.0, 5.0, 9.0], 4.0));
+        let (permuted, permutation) = matrix.permute_unknowns(&[2, 0, 1]).unwrap();
+        assert_eq!(permuted.rows().next().unwrap().coefficients(), &[-1.0, 0.0, 2.0]);
+
+        let expected: Vec<f64> = matrix.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        let solved: Vec<f64> = permuted.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        for (a, b) in permutation.unpermute(&solved).iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(permutation.inverse().inverse(), permutation);
+    }
+
+    #[test]
+    fn invalid_orders() {
+        let matrix = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![4.0, 5.0], 6.0));
+        assert!(matches!(matrix.permute_unknowns(&[0, 0]), Err(SolveError::InvalidPermutation(0))));
+        assert!(matches!(matrix.permute_unknowns(&[1, 2]), Err(SolveError::InvalidPermutation(2))));
+        assert!(matches!(matrix.permute_unknowns(&[0]), Err(SolveError::UnfittingCoefficientAmount(1, 2))));
+        assert_eq!(ColumnPermutation::identity(2).unpermute(&[1, 2]), vec![1, 2]);
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+// Sweeps of `jacobi_svd` before giving up on convergence, far more than the
+// handful needed in practice
+const MAX_SWEEPS: usize = 60;
+
+// One-sided Jacobi SVD of a matrix with at least as many rows as columns:
+// plane rotations from the right orthogonalize the columns of `u`, which
+// leaves A V = U Σ. Returns U Σ, whose column norms are the singular values,
+// and V.
+pub(super) fn jacobi_svd<T: Real>(a: &Matrix<T>) -> (Matrix<T>, Matrix<T>) {
+	let (m, n) = (a.rows(), a.columns());
+	let mut u = a.clone();
+	let mut v = Matrix::identity(n);
+	let two = T::one() + T::one();
+	for _ in 0..MAX_SWEEPS {
+		let mut rotated = false;
+		for p in 0..n {
+			for q in p + 1..n {
+				let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+				for i in 0..m {
+					alpha = alpha + u[(i, p)] * u[(i, p)];
+					beta = beta + u[(i, q)] * u[(i, q)];
+					gamma = gamma + u[(i, p)] * u[(i, q)];
+				}
+				if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+					continue;
+				}
+				rotated = true;
+				let zeta = (beta - alpha) / (two * gamma);
+				let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+				let c = T::one() / (T::one() + t * t).sqrt();
+				let s = c * t;
+				for matrix in [&mut u, &mut v] {
+					for i in 0..matrix.rows() {
+						let (x, y) = (matrix[(i, p)], matrix[(i, q)]);
+						matrix[(i, p)] = c * x - s * y;
+						matrix[(i, q)] = s * x + c * y;
+					}
+				}
+			}
+		}
+		if !rotated {
+			break;
+		}
+	}
+	(u, v)
+}
+
+impl<T: Real> Matrix<T> {
+	// Moore-Penrose pseudoinverse through the singular value decomposition.
+	// Singular values below max(m, n) ε σ_max count as zero, so
+	// rank-deficient matrices are handled as well as rectangular ones.
+	pub fn pinv(&self) -> Matrix<T> {
+		if self.rows() < self.columns() {
+			return self.transpose().pinv().transpose();
+		}
+		let (m, n) = (self.rows(), self.columns());
+		let (u, v) = jacobi_svd(self);
+		let squares: Vec<T> = (0..n)
+			.map(|k| (0..m).fold(T::zero(), |sum, i| sum + u[(i, k)] * u[(i, k)]))
+			.collect();
+		let largest = squares.iter().fold(T::zero(), |max, &square| max.max(square)).sqrt();
+		let size = T::from(m.max(n)).unwrap_or_else(T::one);
+		let threshold = size * T::epsilon() * largest;
+
+		// A⁺ = V Σ⁺ Uᵀ, with the columns of U Σ divided by σ² once more
+		let mut inverse = Matrix::zeros(n, m);
+		for k in (0..n).filter(|&k| squares[k].sqrt() > threshold) {
+			for row in 0..n {
+				let factor = v[(row, k)] / squares[k];
+				for column in 0..m {
+					inverse[(row, column)] = inverse[(row, column)] + factor * u[(column, k)];
+				}
+			}
+		}
+		inverse
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Minimum norm least squares solution x = A⁺ b for any number of
+	// equations, consistent or not
+	pub fn solve_pinv(&self) -> Result<Vec<T>, SolveError> {
+		if self.size < 1 {
+			return Err(SolveError::TooSmall(self.size));
+		}
+		if self.matrix.is_empty() {
+			return Err(SolveError::UnfittingEquationAmount(0, self.size));
+		}
+		if let Some(equation) = self.matrix.iter().find(|equation| equation.len() != self.size) {
+			return Err(SolveError::UnfittingCoefficientAmount(equation.len(), self.size));
+		}
+		let (coefficients, results) = self.clone().into_parts();
+		coefficients.pinv().apply(&results)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn close(a: &Matrix<f64>, b: &Matrix<f64>) -> bool {
+        a.rows() == b.rows() && a.columns() == b.columns()
+            && (0..a.rows()).all(|i| (0..a.columns()).all(|j| (a[(i, j)] - b[(i, j)]).abs() < 1e-10))
+    }
+
+    #[test]
+    fn penrose_conditions() {
+        let matrices = vec![
+            Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]).unwrap(),
+            Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]]).unwrap(),
+            Matrix::from_rows(vec![vec![2.0, 0.0], vec![0.0, 0.0]]).unwrap(),
+        ];
+        for a in &matrices {
+            let p = a.pinv();
+            assert!(close(&a.product(&p).unwrap().product(a).unwrap(), a));
+            assert!(close(&p.product(a).unwrap().product(&p).unwrap(), &p));
+            let ap = a.product(&p).unwrap();
+            assert!(close(&ap, &ap.transpose()));
+            let pa = p.product(a).unwrap();
+            assert!(close(&pa, &pa.transpose()));
+        }
+        let inverse = Matrix::from_rows(vec![vec![2.0, 0.0], vec![0.0, 4.0]]).unwrap().pinv();
+        assert!(close(&inverse, &Matrix::from_rows(vec![vec![0.5, 0.0], vec![0.0, 0.25]]).unwrap()));
+    }
+
+    #[test]
+    fn minimum_norm_least_squares() {
+        // x + y = 2 twice over, the dependent system `solve` rejects
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![2.0, 2.0], 4.0));
+        for value in dependent.solve_pinv().unwrap() {
+            assert!((value - 1.0).abs() < 1e-12);
+        }
+
+        // Fit y = c through 1, 2 and 3 in the least squares sense
+        let overdetermined = CoefficientMatrix::new(1)
+            .add_equation(Equation::new(vec![1.0], 1.0))
+            .add_equation(Equation::new(vec![1.0], 2.0))
+            .add_equation(Equation::new(vec![1.0], 3.0));
+        assert!((overdetermined.solve_pinv().unwrap()[0] - 2.0).abs() < 1e-12);
+        assert!(matches!(CoefficientMatrix::<f64>::new(2).solve_pinv(), Err(SolveError::UnfittingEquationAmount(0, 2))));
+    }
+}
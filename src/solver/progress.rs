@@ -0,0 +1,73 @@
+use super::{Observer, Step};
+
+// Observer reporting the completion of a solve in percent. Both `convert`
+// and `solve` take one pivot per row except the last row of `convert`, so a
+// system of `size` unknowns takes 2 * size - 1 pivots in total. The callback
+// runs after every `every` pivots and once more at the end.
+//
+//     let mut progress = Progress::new(size, 100, |percent| println!("{:.0}%", percent));
+//     matrix.convert_observed(&mut progress)?.solve_observed(&mut progress)?;
+pub struct Progress<F> {
+	callback: F,
+	every: usize,
+	total: usize,
+	done: usize,
+}
+
+impl<F: FnMut(f64)> Progress<F> {
+	pub fn new(size: usize, every: usize, callback: F) -> Self {
+		Progress {
+			callback,
+			every: every.max(1),
+			total: (2 * size).saturating_sub(1).max(1),
+			done: 0,
+		}
+	}
+
+	// Progress over `total` pivots, for methods other than `convert`
+	// followed by `solve`. Gauss-Jordan reduction takes 2 * size.
+	pub fn with_total(total: usize, every: usize, callback: F) -> Self {
+		Progress {
+			callback,
+			every: every.max(1),
+			total: total.max(1),
+			done: 0,
+		}
+	}
+}
+
+impl<T, F: FnMut(f64)> Observer<T> for Progress<F> {
+	fn record(&mut self, step: Step<T>) {
+		if let Step::Pivot(..) | Step::Scale(..) = step {
+			self.done += 1;
+			if self.done.is_multiple_of(self.every) || self.done == self.total {
+				(self.callback)(100.0 * self.done as f64 / self.total as f64);
+			}
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn report_percentages() {
+        let mut reported = Vec::new();
+        let mut progress = Progress::new(3, 2, |percent| reported.push(percent));
+        let _ = swapping_system()
+            .validate().unwrap()
+            .convert_observed(&mut progress).unwrap()
+            .solve_observed(&mut progress).unwrap();
+        assert_eq!(reported, vec![40.0, 80.0, 100.0]);
+
+        let mut reported = Vec::new();
+        let mut progress = Progress::with_total(4, 2, |percent| reported.push(percent));
+        let _ = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .solve_gauss_jordan_observed(&mut progress).unwrap();
+        assert_eq!(reported, vec![50.0, 100.0]);
+    }
+}
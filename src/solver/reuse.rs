@@ -0,0 +1,167 @@
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Cursor, Equation, SolveError};
+
+// Solving one system after another in the same matrix, e.g. once per
+// animation frame, without allocating once the storage has grown to size:
+//
+//     let mut matrix = CoefficientMatrix::new(size);
+//     loop {
+//         matrix.fill(&augmented)?;
+//         matrix.solve_in_place()?;
+//         // read matrix.solution()
+//     }
+
+// Order of the coefficients in a flat slice: row after row as in C and
+// JavaScript, or column after column as in Fortran, BLAS and WebGL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputLayout {
+	RowMajor,
+	ColumnMajor,
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Overwrite the whole system with rows of `size` coefficients, each
+	// followed by its right-hand side. The existing equations are reused.
+	pub fn fill(&mut self, values: &[T]) -> Result<(), SolveError> {
+		let size = self.size;
+		if size < 1 {
+			return Err(SolveError::TooSmall(size));
+		}
+		let width = size + 1;
+		if !values.len().is_multiple_of(width) {
+			return Err(SolveError::UnfittingCoefficientAmount(values.len() % width, size));
+		}
+		if values.len() / width != size {
+			return Err(SolveError::UnfittingEquationAmount(values.len() / width, size));
+		}
+
+		self.unentered.clear();
+		self.matrix.truncate(size);
+		while self.matrix.len() < size {
+			self.matrix.push(Equation { coefficients: alloc::vec::Vec::with_capacity(size), result: T::zero() });
+		}
+		for (equation, row) in self.matrix.iter_mut().zip(values.chunks(width)) {
+			equation.coefficients.clear();
+			equation.coefficients.extend_from_slice(&row[..size]);
+			equation.result = row[size];
+		}
+		Ok(())
+	}
+
+	// The checks of `fill_parts_with` for `size` unknowns, e.g. to check the
+	// input before resizing a matrix to fill it
+	pub fn check_parts(size: usize, coefficients: usize, results: usize) -> Result<(), SolveError> {
+		if size < 1 {
+			return Err(SolveError::TooSmall(size));
+		}
+		// The largest size whose square fits into a usize
+		let max = (1 << (usize::BITS / 2)) - 1;
+		let expected = size.checked_mul(size).ok_or(SolveError::TooLarge(size, max))?;
+		if results != size {
+			return Err(SolveError::UnfittingEquationAmount(results, size));
+		}
+		if coefficients != expected {
+			return Err(SolveError::UnfittingCoefficientAmount(coefficients, expected));
+		}
+		Ok(())
+	}
+
+	// Like `fill`, but with the coefficients as `size` rows of `size` values
+	// and the right-hand sides in a separate slice, as in A x = b
+	pub fn fill_parts(&mut self, coefficients: &[T], results: &[T]) -> Result<(), SolveError> {
+		self.fill_parts_with(coefficients, results, InputLayout::RowMajor)
+	}
+
+	// `fill_parts` with the coefficients in the given layout, transposing
+	// column-major data while copying it in
+	pub fn fill_parts_with(&mut self, coefficients: &[T], results: &[T], layout: InputLayout) -> Result<(), SolveError> {
+		let size = self.size;
+		Self::check_parts(size, coefficients.len(), results.len())?;
+
+		self.unentered.clear();
+		self.matrix.truncate(size);
+		while self.matrix.len() < size {
+			self.matrix.push(Equation { coefficients: alloc::vec::Vec::with_capacity(size), result: T::zero() });
+		}
+		for (i, (equation, &result)) in self.matrix.iter_mut().zip(results).enumerate() {
+			equation.coefficients.clear();
+			match layout {
+				InputLayout::RowMajor => equation.coefficients.extend_from_slice(&coefficients[i * size..(i + 1) * size]),
+				InputLayout::ColumnMajor => equation.coefficients.extend(coefficients.iter().skip(i).step_by(size).copied()),
+			}
+			equation.result = result;
+		}
+		Ok(())
+	}
+
+	// `convert` followed by `solve` without moving the matrix. On error
+	// the matrix is left partially eliminated.
+	pub fn solve_in_place(&mut self) -> Result<(), SolveError> {
+		self.ensure_validated()?;
+		self.check_finite()?;
+		let mut cursor = Cursor::start(self.size);
+		while cursor != Cursor::Done {
+			cursor = self.advance(cursor, &mut ())?;
+		}
+		Ok(())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn reuse_storage() {
+        let mut mat = CoefficientMatrix::new(2);
+        mat.fill(&[8.0, -6.0, 2.0, 2.0, 3.0, 2.0]).unwrap();
+        mat.solve_in_place().unwrap();
+        assert_eq!(mat.solution().collect::<Vec<_>>(), vec![0.5, 1.0 / 3.0]);
+
+        // Pivoting may have swapped the rows, but no row is reallocated
+        let mut before = mat.rows().map(|equation| equation.coefficients().as_ptr()).collect::<Vec<_>>();
+        before.sort();
+        mat.fill(&[0.0, 2.0, 4.0, 3.0, 1.0, 5.0]).unwrap();
+        mat.solve_in_place().unwrap();
+        assert_eq!(mat.solution().collect::<Vec<_>>(), vec![1.0, 2.0]);
+        let mut after = mat.rows().map(|equation| equation.coefficients().as_ptr()).collect::<Vec<_>>();
+        after.sort();
+        assert_eq!(before, after);
+
+        assert!(matches!(mat.fill(&[1.0, 2.0, 3.0]), Err(SolveError::UnfittingEquationAmount(1, 2))));
+        assert!(matches!(mat.fill(&[1.0, 2.0]), Err(SolveError::UnfittingCoefficientAmount(2, 2))));
+        mat.fill(&[1.0, 2.0, 3.0, 2.0, 4.0, 6.0]).unwrap();
+        assert!(matches!(mat.solve_in_place(), Err(SolveError::DependentSolutionSet)));
+    }
+
+    #[test]
+    fn fill_separate_parts() {
+        let mut mat = CoefficientMatrix::new(2);
+        mat.fill_parts(&[8.0, -6.0, 2.0, 3.0], &[2.0, 2.0]).unwrap();
+        mat.solve_in_place().unwrap();
+        assert_eq!(mat.solution().collect::<Vec<_>>(), vec![0.5, 1.0 / 3.0]);
+        assert!(matches!(mat.fill_parts(&[1.0, 2.0, 3.0, 4.0], &[1.0]), Err(SolveError::UnfittingEquationAmount(1, 2))));
+        assert!(matches!(mat.fill_parts(&[1.0, 2.0, 3.0], &[1.0, 2.0]), Err(SolveError::UnfittingCoefficientAmount(3, 4))));
+
+        // The number of coefficients is checked without overflowing
+        let huge = usize::MAX;
+        assert!(matches!(CoefficientMatrix::<f64>::check_parts(huge, 0, huge), Err(SolveError::TooLarge(_, _))));
+        assert!(CoefficientMatrix::<f64>::check_parts(2, 4, 2).is_ok());
+    }
+
+    #[test]
+    fn fill_column_major() {
+        let mut mat = CoefficientMatrix::new(2);
+        mat.fill_parts_with(&[8.0, 2.0, -6.0, 3.0], &[2.0, 2.0], InputLayout::ColumnMajor).unwrap();
+        assert_eq!(mat.rows().next().unwrap().coefficients(), &[8.0, -6.0]);
+        mat.solve_in_place().unwrap();
+        assert_eq!(mat.solution().collect::<Vec<_>>(), vec![0.5, 1.0 / 3.0]);
+    }
+}
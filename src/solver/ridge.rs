@@ -0,0 +1,144 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::pinv::jacobi_svd;
+use super::{dot, CoefficientMatrix, Matrix, SolveError};
+
+// Candidate values of λ for `ridge_parameter`, in half decades from
+// σ_max² ε up to σ_max²
+const STEPS: usize = 32;
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Tikhonov regularized least squares for any number of equations:
+	// minimizes ‖A x - b‖² + λ ‖x‖² by solving (AᵀA + λI) x = Aᵀb. The
+	// penalty keeps near-singular and noisy systems from producing huge,
+	// oscillating solutions at the price of a bias towards zero. Negative λ
+	// count as zero, `None` picks λ with `ridge_parameter`.
+	pub fn solve_ridge(&self, lambda: Option<T>) -> Result<Vec<T>, SolveError> {
+		let lambda = match lambda {
+			Some(lambda) => lambda.max(T::zero()),
+			None => self.ridge_parameter()?,
+		};
+		self.classify()?;
+		let (coefficients, results) = self.clone().into_parts();
+		let transposed = coefficients.transpose();
+		let mut normal = transposed.product(&coefficients)?;
+		for j in 0..self.size {
+			normal[(j, j)] = normal[(j, j)] + lambda;
+		}
+		let rhs = (0..self.size).map(|j| transposed.row(j).map_or(T::zero(), |column| dot(column, &results))).collect();
+		let solved = CoefficientMatrix::from_parts(normal, rhs)?.convert()?.solve()?;
+		Ok(solved.solution().collect())
+	}
+
+	// λ for `solve_ridge` minimizing the generalized cross-validation
+	// error ‖A x - b‖² / (m - tr H)², where H maps b to A x, which
+	// estimates how well the solution predicts left-out equations without
+	// knowing the noise level. Evaluated for a range of λ through the
+	// singular value decomposition of A.
+	pub fn ridge_parameter(&self) -> Result<T, SolveError> {
+		self.classify()?;
+		let (m, n) = (self.matrix.len(), self.size);
+		// Zero rows pad to the n rows the SVD needs without changing the
+		// singular values
+		let mut a = Matrix::zeros(m.max(n), n);
+		for (i, equation) in self.matrix.iter().enumerate() {
+			for (j, &coefficient) in equation.coefficients.iter().enumerate() {
+				a[(i, j)] = coefficient;
+			}
+		}
+		let results: Vec<T> = self.results().collect();
+		let (us, _) = jacobi_svd(&a);
+
+		// Singular values and the components of b along the left singular
+		// vectors
+		let mut components = Vec::with_capacity(n);
+		for k in 0..n {
+			let sigma = (0..us.rows()).fold(T::zero(), |sum, i| sum + us[(i, k)] * us[(i, k)]).sqrt();
+			if sigma > T::zero() {
+				let projection = (0..m).fold(T::zero(), |sum, i| sum + us[(i, k)] * results[i]) / sigma;
+				components.push((sigma, projection));
+			}
+		}
+		let largest = components.iter().fold(T::zero(), |max, &(sigma, _)| max.max(sigma));
+		if largest == T::zero() {
+			return Err(SolveError::DependentSolutionSet);
+		}
+		// Part of b outside the range of A, which no x can fit
+		let outside = (dot(&results, &results) - components.iter().fold(T::zero(), |sum, &(_, c)| sum + c * c)).max(T::zero());
+
+		let equations = T::from(m).unwrap();
+		let gcv = |lambda: T| {
+			let (residual, trace) = components.iter().fold((outside, T::zero()), |(residual, trace), &(sigma, c)| {
+				let filter = sigma * sigma / (sigma * sigma + lambda);
+				(residual + ((T::one() - filter) * c).powi(2), trace + filter)
+			});
+			let freedom = equations - trace;
+			if freedom <= T::zero() { None } else { Some(residual / (freedom * freedom)) }
+		};
+		let scale = largest * largest;
+		let step = T::from(10.0).unwrap().sqrt();
+		let mut lambda = scale * T::epsilon();
+		let mut best: Option<(T, T)> = None;
+		for _ in 0..=STEPS {
+			if let Some(error) = gcv(lambda) {
+				if best.is_none_or(|(_, smallest)| error < smallest) {
+					best = Some((lambda, error));
+				}
+			}
+			lambda = (lambda * step).min(scale);
+		}
+		Ok(best.map_or(scale, |(lambda, _)| lambda))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn shrink_towards_zero() {
+        // a x = b gives x = a b / (a² + λ)
+        let single = CoefficientMatrix::new(1).add_equation(Equation::new(vec![2.0], 4.0));
+        assert!((single.solve_ridge(Some(0.0)).unwrap()[0] - 2.0).abs() < 1e-12);
+        assert!((single.solve_ridge(Some(4.0)).unwrap()[0] - 1.0).abs() < 1e-12);
+        assert_eq!(single.solve_ridge(Some(-1.0)).unwrap(), single.solve_ridge(Some(0.0)).unwrap());
+
+        // Dependent equations have no unique solution, but a regularized one
+        let dependent = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 2.0))
+            .add_equation(Equation::new(vec![2.0, 2.0], 4.0));
+        let solution = dependent.solve_ridge(Some(1e-6)).unwrap();
+        assert!((solution[0] - 1.0).abs() < 1e-5 && (solution[1] - 1.0).abs() < 1e-5);
+        assert!(matches!(CoefficientMatrix::<f64>::new(2).solve_ridge(None), Err(SolveError::UnfittingEquationAmount(0, 2))));
+    }
+
+    #[test]
+    fn regularize_noisy_hilbert_system() {
+        // The 8 × 8 Hilbert matrix has a condition number around 10¹⁰, so
+        // noise of 10⁻⁶ in b makes the plain solution useless
+        let n = 8;
+        let exact = vec![1.0; n];
+        let mut system = CoefficientMatrix::new(n);
+        for i in 0..n {
+            let row: Vec<f64> = (0..n).map(|j| 1.0 / (i + j + 1) as f64).collect();
+            let noise = if i % 2 == 0 { 1e-6 } else { -1e-6 };
+            let result = row.iter().sum::<f64>() + noise;
+            system = system.add_equation(Equation::new(row, result));
+        }
+        let error = |x: &[f64]| x.iter().zip(&exact).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt();
+        let plain = system.solve_rectangular().unwrap();
+        let lambda = system.ridge_parameter().unwrap();
+        let ridge = system.solve_ridge(None).unwrap();
+        assert!(lambda > 0.0);
+        assert_eq!(ridge, system.solve_ridge(Some(lambda)).unwrap());
+        assert!(error(&plain) > 10.0);
+        assert!(error(&ridge) < 1.0);
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+use crate::testgen::Generator;
+
+// Spread of the solution over randomly perturbed copies of a system, the
+// sampling counterpart of `solve_uncertain` without its linearization:
+// every coefficient a_ij and result b_i is drawn uniformly from
+// a_ij ± bound_ij and b_i ± bound_i.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensitivity<T> {
+	mean: Vec<T>,
+	variance: Vec<T>,
+	min: Vec<T>,
+	max: Vec<T>,
+	samples: usize,
+	failures: usize,
+}
+
+impl<T: Real> Sensitivity<T> {
+	pub fn mean(&self) -> &[T] {
+		&self.mean
+	}
+
+	// Sample variance of each unknown
+	pub fn variance(&self) -> &[T] {
+		&self.variance
+	}
+
+	// Smallest and largest value of each unknown over the samples
+	pub fn min(&self) -> &[T] {
+		&self.min
+	}
+
+	pub fn max(&self) -> &[T] {
+		&self.max
+	}
+
+	// Samples that were solved and enter the statistics
+	pub fn samples(&self) -> usize {
+		self.samples
+	}
+
+	// Samples that turned out singular and were left out
+	pub fn failures(&self) -> usize {
+		self.failures
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve `samples` perturbed copies of the system, with the bounds of the
+	// coefficients given as a matrix of the same shape. All samples are
+	// solved in the storage of one matrix. The same seed gives the same
+	// samples. Fails with the error of the last sample if none was solvable
+	// and with `NoSamples` if there are none to solve.
+	pub fn sensitivity(&self, coefficient_bounds: &Matrix<T>, result_bounds: &[T], samples: usize, seed: u64) -> Result<Sensitivity<T>, SolveError> {
+		self.ensure_validated()?;
+		if samples == 0 {
+			return Err(SolveError::NoSamples);
+		}
+		let size = self.size;
+		let shape = (coefficient_bounds.rows(), coefficient_bounds.columns());
+		if shape != (size, size) {
+			return Err(SolveError::IncompatibleDimensions((size, size), shape));
+		}
+		if result_bounds.len() != size {
+			return Err(SolveError::IncompatibleDimensions((size, 1), (result_bounds.len(), 1)));
+		}
+
+		let coefficients: Vec<T> = self.matrix.iter().flat_map(|equation| equation.coefficients.iter().copied()).collect();
+		let results: Vec<T> = self.results().collect();
+		let bounds: Vec<T> = (0..size).flat_map(|i| coefficient_bounds.row(i).unwrap().iter().copied()).collect();
+
+		let mut random = Generator::new(seed);
+		let mut draw = |value: T, bound: T| value + bound * T::from(random.signed()).unwrap();
+		let mut sample = CoefficientMatrix::new(size);
+		let (mut perturbed, mut perturbed_results) = (coefficients.clone(), results.clone());
+		let mut mean = vec![T::zero(); size];
+		// Sums of squared deviations from the mean, updated after Welford
+		let mut squares = vec![T::zero(); size];
+		let mut min = vec![T::max_value(); size];
+		let mut max = vec![T::min_value(); size];
+		let (mut solved, mut failures, mut last_error) = (0, 0, None);
+		for _ in 0..samples {
+			for ((target, &value), &bound) in perturbed.iter_mut().zip(&coefficients).zip(&bounds) {
+				*target = draw(value, bound);
+			}
+			for ((target, &value), &bound) in perturbed_results.iter_mut().zip(&results).zip(result_bounds) {
+				*target = draw(value, bound);
+			}
+			sample.fill_parts(&perturbed, &perturbed_results)?;
+			if let Err(err) = sample.solve_in_place() {
+				failures += 1;
+				last_error = Some(err);
+				continue;
+			}
+			solved += 1;
+			let count = T::from(solved).unwrap();
+			for (i, value) in sample.solution().enumerate() {
+				let delta = value - mean[i];
+				mean[i] = mean[i] + delta / count;
+				squares[i] = squares[i] + delta * (value - mean[i]);
+				min[i] = min[i].min(value);
+				max[i] = max[i].max(value);
+			}
+		}
+		if solved == 0 {
+			if let Some(err) = last_error {
+				return Err(err);
+			}
+		}
+		let variance = match solved {
+			0 | 1 => vec![T::zero(); size],
+			_ => squares.into_iter().map(|sum| sum / T::from(solved - 1).unwrap()).collect(),
+		};
+		Ok(Sensitivity { mean, variance, min, max, samples: solved, failures })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn spread_of_samples() {
+        // 2x = 4 ± 0.2: x is uniform in [1.9, 2.1) with variance 0.1² / 3
+        let mat = CoefficientMatrix::new(1).add_equation(Equation::new(vec![2.0], 4.0));
+        let sensitivity = mat.sensitivity(&Matrix::zeros(1, 1), &[0.2], 2000, 7).unwrap();
+        assert_eq!((sensitivity.samples(), sensitivity.failures()), (2000, 0));
+        assert!((sensitivity.mean()[0] - 2.0).abs() < 0.01);
+        assert!((sensitivity.variance()[0] - 0.01 / 3.0).abs() < 0.0005);
+        assert!(sensitivity.min()[0] >= 1.9 && sensitivity.max()[0] < 2.1);
+        assert!(sensitivity.min()[0] < 1.91 && sensitivity.max()[0] > 2.09);
+        assert_eq!(mat.sensitivity(&Matrix::zeros(1, 1), &[0.2], 2000, 7).unwrap(), sensitivity);
+
+        // Exact data gives no spread
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, -1.0], 1.0));
+        let exact = mat.sensitivity(&Matrix::zeros(2, 2), &[0.0, 0.0], 10, 1).unwrap();
+        assert_eq!(exact.mean(), &[2.0, 1.0]);
+        assert_eq!(exact.variance(), &[0.0, 0.0]);
+
+        let singular = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0));
+        assert!(matches!(singular.sensitivity(&Matrix::zeros(2, 2), &[0.0, 0.0], 3, 1), Err(SolveError::DependentSolutionSet)));
+        assert!(matches!(mat.sensitivity(&Matrix::zeros(1, 2), &[0.0, 0.0], 3, 1), Err(SolveError::IncompatibleDimensions((2, 2), (1, 2)))));
+        assert!(matches!(mat.sensitivity(&Matrix::zeros(2, 2), &[0.0, 0.0], 0, 1), Err(SolveError::NoSamples)));
+    }
+}
@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{dot, CoefficientMatrix, SolveError};
+
+// Number of equations m relative to the number of unknowns n
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+	// m = n, solved exactly
+	Square,
+	// m > n, solved in the least squares sense
+	Overdetermined,
+	// m < n, solved for the solution of least norm
+	Underdetermined,
+}
+
+impl fmt::Display for Shape {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Shape::Square => write!(f, "square"),
+			Shape::Overdetermined => write!(f, "overdetermined"),
+			Shape::Underdetermined => write!(f, "underdetermined"),
+		}
+	}
+}
+
+// Square systems of at least this many unknowns are solved blocked,
+// factorizing in panels of `BLOCK_SIZE` columns
+const BLOCKED_THRESHOLD: usize = 256;
+const BLOCK_SIZE: usize = 64;
+
+// How `solve_default` goes about a system, as chosen by `strategy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+	// Not square, `solve_rectangular`
+	Rectangular,
+	// A large square system, `solve_blocked` with the given block size
+	Blocked(usize),
+	// Any other square system, `convert` followed by `solve`
+	Elimination,
+}
+
+impl<T> CoefficientMatrix<T> {
+	// Like `validate` for any number of equations: fails unless there is at
+	// least one equation and every equation has `size` coefficients
+	pub fn classify(&self) -> Result<Shape, SolveError> {
+		if self.size < 1 {
+			return Err(SolveError::TooSmall(self.size));
+		}
+		if self.matrix.is_empty() {
+			return Err(SolveError::UnfittingEquationAmount(0, self.size));
+		}
+		self.check_rows()?;
+		Ok(match self.matrix.len() {
+			m if m > self.size => Shape::Overdetermined,
+			m if m < self.size => Shape::Underdetermined,
+			_ => Shape::Square,
+		})
+	}
+
+	// The strategy of `solve_default` for this system, for callers that solve
+	// it themselves, e.g. to observe the elimination
+	pub fn strategy(&self) -> Result<Strategy, SolveError> {
+		Ok(match self.classify()? {
+			Shape::Square if self.size >= BLOCKED_THRESHOLD => Strategy::Blocked(BLOCK_SIZE),
+			Shape::Square => Strategy::Elimination,
+			Shape::Overdetermined | Shape::Underdetermined => Strategy::Rectangular,
+		})
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve m equations in n unknowns with the strategy that suits their
+	// shape: elimination for square systems, the normal equations
+	// AᵀA x = Aᵀb for overdetermined ones and `solve_min_norm` for
+	// underdetermined ones. Fails with `DependentSolutionSet` if the
+	// equations, or for overdetermined systems the unknowns, aren't
+	// independent.
+	pub fn solve_rectangular(&self) -> Result<Vec<T>, SolveError> {
+		match self.classify()? {
+			Shape::Square => Ok(self.clone().validate()?.convert()?.solve()?.solution().collect()),
+			Shape::Underdetermined => self.solve_min_norm(),
+			Shape::Overdetermined => {
+				let (coefficients, results) = self.clone().into_parts();
+				let transposed = coefficients.transpose();
+				let normal = transposed.product(&coefficients)?;
+				let rhs = (0..self.size).map(|j| transposed.row(j).map_or(T::zero(), |column| dot(column, &results))).collect();
+				let solved = CoefficientMatrix::from_parts(normal, rhs)?.convert()?.solve()?;
+				Ok(solved.solution().collect())
+			},
+		}
+	}
+
+	// Solve a system of any shape without observing it, the way the wasm
+	// `MatrixSolver` does by default
+	pub fn solve_default(self) -> Result<Vec<T>, SolveError> {
+		match self.strategy()? {
+			Strategy::Rectangular => self.solve_rectangular(),
+			Strategy::Blocked(block) => Ok(self.validate()?.solve_blocked(block)?.solution().collect()),
+			Strategy::Elimination => Ok(self.validate()?.convert()?.solve()?.solution().collect()),
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::{BLOCKED_THRESHOLD, BLOCK_SIZE};
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-10)
+    }
+
+    #[test]
+    fn classify_systems() {
+        let square = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0, 3.0], 2.0));
+        assert_eq!(square.classify().unwrap(), Shape::Square);
+        assert!(close(&square.solve_rectangular().unwrap(), &[0.5, 1.0 / 3.0]));
+
+        let under = CoefficientMatrix::new(2).add_equation(Equation::new(vec![1.0, 1.0], 2.0));
+        assert_eq!(under.classify().unwrap(), Shape::Underdetermined);
+        assert!(close(&under.solve_rectangular().unwrap(), &[1.0, 1.0]));
+
+        let invalid = CoefficientMatrix::new(2).add_equation(Equation::new(vec![1.0], 2.0));
+        assert!(matches!(invalid.classify(), Err(SolveError::UnfittingRows(_, 2))));
+        assert!(matches!(CoefficientMatrix::<f64>::new(2).classify(), Err(SolveError::UnfittingEquationAmount(0, 2))));
+    }
+
+    #[test]
+    fn least_squares() {
+        // Line y = a + b x through (0, 1), (1, 3), (2, 4): a = 7/6, b = 3/2
+        let over = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 0.0], 1.0))
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, 2.0], 4.0));
+        assert_eq!(over.classify().unwrap(), Shape::Overdetermined);
+        assert_eq!(over.classify().unwrap().to_string(), "overdetermined");
+        assert!(close(&over.solve_rectangular().unwrap(), &[7.0 / 6.0, 1.5]));
+    }
+
+    #[test]
+    fn choose_strategy() {
+        let over = CoefficientMatrix::new(1)
+            .add_equation(Equation::new(vec![1.0], 1.0))
+            .add_equation(Equation::new(vec![1.0], 3.0));
+        assert_eq!(over.strategy().unwrap(), Strategy::Rectangular);
+        assert!(close(&over.solve_default().unwrap(), &[2.0]));
+
+        let small = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0, 3.0], 2.0));
+        assert_eq!(small.strategy().unwrap(), Strategy::Elimination);
+        assert!(close(&small.solve_default().unwrap(), &[0.5, 1.0 / 3.0]));
+
+        let size = BLOCKED_THRESHOLD;
+        let mut large = CoefficientMatrix::new(size);
+        for i in 0..size {
+            let coefficients = (0..size).map(|j| if i == j { 2.0 } else { 0.0 }).collect();
+            large = large.add_equation(Equation::new(coefficients, i as f64));
+        }
+        assert_eq!(large.strategy().unwrap(), Strategy::Blocked(BLOCK_SIZE));
+        let expected: Vec<f64> = (0..size).map(|i| i as f64 / 2.0).collect();
+        assert!(close(&large.solve_default().unwrap(), &expected));
+    }
+}
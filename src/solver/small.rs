@@ -0,0 +1,115 @@
+use num::traits::real::Real;
+
+use super::SolveError;
+
+// A system with a size known at compile time, stored in arrays on the stack.
+// Solving doesn't allocate and the loops over `N` are unrolled by the
+// compiler, which makes it suitable for solving many tiny systems, e.g. per
+// frame in games and graphics code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmallSystem<T, const N: usize> {
+	coefficients: [[T; N]; N],
+	results: [T; N],
+}
+
+pub type System2<T> = SmallSystem<T, 2>;
+pub type System3<T> = SmallSystem<T, 3>;
+pub type System4<T> = SmallSystem<T, 4>;
+
+impl<T: Real, const N: usize> SmallSystem<T, N> {
+	pub fn new(coefficients: [[T; N]; N], results: [T; N]) -> Self {
+		SmallSystem {
+			coefficients,
+			results,
+		}
+	}
+
+	// Gaussian elimination with partial pivoting followed by back substitution
+	pub fn solve(self) -> Result<[T; N], SolveError> {
+		if N == 0 {
+			return Err(SolveError::TooSmall(N));
+		}
+
+		let SmallSystem { coefficients: mut a, results: mut b } = self;
+		let mut rank = 0;
+		for column in 0..N {
+			if rank == N {
+				break;
+			}
+
+			let mut pivot_row = rank;
+			for row in rank+1..N {
+				if a[row][column].abs() > a[pivot_row][column].abs() {
+					pivot_row = row;
+				}
+			}
+			// Columns without a pivot leave the system singular
+			if a[pivot_row][column].is_zero() {
+				continue;
+			}
+			a.swap(rank, pivot_row);
+			b.swap(rank, pivot_row);
+
+			let pivot = a[rank];
+			for row in rank+1..N {
+				let ratio = a[row][column] / pivot[column];
+				for (value, above) in a[row].iter_mut().zip(pivot.iter()).skip(column) {
+					*value = *value - ratio * *above;
+				}
+				b[row] = b[row] - ratio * b[rank];
+			}
+			rank += 1;
+		}
+
+		if rank < N {
+			// The remaining rows have no coefficients left
+			return Err(if b[rank..].iter().all(|result| result.is_zero()) {
+				SolveError::DependentSolutionSet
+			} else {
+				SolveError::EmptySolutionSet
+			});
+		}
+
+		let mut solution = [T::zero(); N];
+		for row in (0..N).rev() {
+			let mut sum = b[row];
+			for (coefficient, value) in a[row].iter().zip(solution.iter()).skip(row + 1) {
+				sum = sum - *coefficient * *value;
+			}
+			solution[row] = sum / a[row][row];
+		}
+		Ok(solution)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn solve_2x2() {
+        let solution = System2::new([[8.0, -6.0], [2.0, 3.0]], [2.0, 2.0]).solve().unwrap();
+        assert!((solution[0] - 0.5).abs() < 1e-12);
+        assert!((solution[1] - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn solve_3x3() {
+        let solution = System3::new(
+            [[2.0, 1.0, -1.0], [-3.0, -1.0, 2.0], [-2.0, 1.0, 2.0]],
+            [8.0, -11.0, -3.0],
+        ).solve().unwrap();
+        for (value, expected) in solution.iter().zip([2.0, 3.0, -1.0].iter()) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn singular_systems() {
+        let dependent = System2::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 6.0]).solve();
+        assert!(matches!(dependent, Err(SolveError::DependentSolutionSet)));
+        let empty = System2::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 7.0]).solve();
+        assert!(matches!(empty, Err(SolveError::EmptySolutionSet)));
+    }
+}
@@ -0,0 +1,314 @@
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::convert::TryInto;
+use core::fmt;
+
+use super::stepwise::Cursor;
+use super::{CoefficientMatrix, Elimination, Equation};
+
+// Compact binary image of a solver's state, e.g. for IndexedDB or to hand a
+// half-edited or half-solved system to a worker:
+//
+//     magic "MSNP", version
+//     matrix, flags, [original matrix], [elimination], solution, names
+//
+// Integers are u32 and values f64, both little-endian. A matrix is its size
+// and number of equations, then per equation its number of coefficients,
+// the coefficients and the result, so incomplete systems survive as well.
+// An elimination in progress is its cursor followed by its matrix.
+// Factorizations aren't stored since they are derived from the matrix.
+
+const MAGIC: &[u8; 4] = b"MSNP";
+const VERSION: u8 = 1;
+
+const HAS_ORIGINAL: u8 = 1;
+const HAS_ELIMINATION: u8 = 2;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+	InvalidHeader,
+	UnsupportedVersion(u8),
+	// The bytes end before the value at this offset
+	Truncated(usize),
+	// The value at this offset doesn't describe a valid state
+	InvalidData(usize),
+}
+
+impl fmt::Display for SnapshotError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SnapshotError::InvalidHeader =>
+				write!(f, "Not a solver snapshot"),
+			SnapshotError::UnsupportedVersion(version) =>
+				write!(f, "Unsupported snapshot version {}", version),
+			SnapshotError::Truncated(offset) =>
+				write!(f, "Snapshot ends early at byte {}", offset),
+			SnapshotError::InvalidData(offset) =>
+				write!(f, "Invalid snapshot data at byte {}", offset),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+	// The system as currently held, solved or not
+	pub matrix: CoefficientMatrix<f64>,
+	// The system as entered, if `matrix` holds its solved form
+	pub original: Option<CoefficientMatrix<f64>>,
+	// A stepwise solve in progress
+	pub elimination: Option<Elimination<f64>>,
+	pub solution: Vec<f64>,
+	pub names: Vec<String>,
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+	fn usize(&mut self, value: usize) {
+		self.0.extend_from_slice(&(value as u32).to_le_bytes());
+	}
+
+	fn values(&mut self, values: &[f64]) {
+		self.usize(values.len());
+		for value in values {
+			self.0.extend_from_slice(&value.to_le_bytes());
+		}
+	}
+
+	fn matrix(&mut self, matrix: &CoefficientMatrix<f64>) {
+		self.usize(matrix.size);
+		self.usize(matrix.matrix.len());
+		for equation in &matrix.matrix {
+			self.values(&equation.coefficients);
+			self.0.extend_from_slice(&equation.result.to_le_bytes());
+		}
+	}
+
+	fn cursor(&mut self, cursor: Cursor) {
+		let (tag, a, b) = match cursor {
+			Cursor::Search { column, row } => (0, column, row),
+			Cursor::Eliminate { column, row } => (1, column, row),
+			Cursor::Scale { row } => (2, row, 0),
+			Cursor::Back { row, target } => (3, row, target),
+			Cursor::Done => (4, 0, 0),
+		};
+		self.0.push(tag);
+		self.usize(a);
+		self.usize(b);
+	}
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	offset: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, count: usize) -> Result<&'a [u8], SnapshotError> {
+		let end = self.offset.checked_add(count)
+			.filter(|&end| end <= self.bytes.len())
+			.ok_or(SnapshotError::Truncated(self.offset))?;
+		let taken = &self.bytes[self.offset..end];
+		self.offset = end;
+		Ok(taken)
+	}
+
+	fn u8(&mut self) -> Result<u8, SnapshotError> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn usize(&mut self) -> Result<usize, SnapshotError> {
+		let bytes = self.take(4)?;
+		Ok(u32::from_le_bytes(bytes.try_into().unwrap_or_default()) as usize)
+	}
+
+	fn f64(&mut self) -> Result<f64, SnapshotError> {
+		let bytes = self.take(8)?;
+		Ok(f64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+	}
+
+	// A length, which can't exceed what is left for its items, so that
+	// corrupt lengths fail before allocating
+	fn length(&mut self, item_bytes: usize) -> Result<usize, SnapshotError> {
+		let offset = self.offset;
+		let length = self.usize()?;
+		if length.saturating_mul(item_bytes) > self.bytes.len() - self.offset {
+			return Err(SnapshotError::Truncated(offset));
+		}
+		Ok(length)
+	}
+
+	fn values(&mut self) -> Result<Vec<f64>, SnapshotError> {
+		let length = self.length(8)?;
+		(0..length).map(|_| self.f64()).collect()
+	}
+
+	fn matrix(&mut self) -> Result<CoefficientMatrix<f64>, SnapshotError> {
+		let size = self.usize()?;
+		let equations = self.length(12)?;
+		// Reserve for the equations that are there rather than for `size`,
+		// which nothing bounds before validation
+		let mut matrix = CoefficientMatrix { size, matrix: Vec::with_capacity(equations), unentered: BTreeSet::new() };
+		for _ in 0..equations {
+			let coefficients = self.values()?;
+			let result = self.f64()?;
+			matrix.push_equation(Equation::new(coefficients, result));
+		}
+		Ok(matrix)
+	}
+
+	fn cursor(&mut self) -> Result<Cursor, SnapshotError> {
+		let offset = self.offset;
+		let tag = self.u8()?;
+		let (a, b) = (self.usize()?, self.usize()?);
+		match tag {
+			0 => Ok(Cursor::Search { column: a, row: b }),
+			1 => Ok(Cursor::Eliminate { column: a, row: b }),
+			2 => Ok(Cursor::Scale { row: a }),
+			3 => Ok(Cursor::Back { row: a, target: b }),
+			4 => Ok(Cursor::Done),
+			_ => Err(SnapshotError::InvalidData(offset)),
+		}
+	}
+
+	fn string(&mut self) -> Result<String, SnapshotError> {
+		let offset = self.offset;
+		let length = self.length(1)?;
+		let bytes = self.take(length)?;
+		String::from_utf8(bytes.to_vec()).map_err(|_| SnapshotError::InvalidData(offset))
+	}
+}
+
+impl Snapshot {
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut writer = Writer(Vec::new());
+		writer.0.extend_from_slice(MAGIC);
+		writer.0.push(VERSION);
+		writer.matrix(&self.matrix);
+		let flags = if self.original.is_some() { HAS_ORIGINAL } else { 0 }
+			| if self.elimination.is_some() { HAS_ELIMINATION } else { 0 };
+		writer.0.push(flags);
+		if let Some(original) = &self.original {
+			writer.matrix(original);
+		}
+		if let Some(elimination) = &self.elimination {
+			writer.cursor(elimination.cursor());
+			writer.matrix(elimination.matrix());
+		}
+		writer.values(&self.solution);
+		writer.usize(self.names.len());
+		for name in &self.names {
+			writer.usize(name.len());
+			writer.0.extend_from_slice(name.as_bytes());
+		}
+		writer.0
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Snapshot, SnapshotError> {
+		if bytes.len() < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+			return Err(SnapshotError::InvalidHeader);
+		}
+		let mut reader = Reader { bytes, offset: MAGIC.len() };
+		let version = reader.u8()?;
+		if version != VERSION {
+			return Err(SnapshotError::UnsupportedVersion(version));
+		}
+		let matrix = reader.matrix()?;
+		let flags_offset = reader.offset;
+		let flags = reader.u8()?;
+		if flags & !(HAS_ORIGINAL | HAS_ELIMINATION) != 0 {
+			return Err(SnapshotError::InvalidData(flags_offset));
+		}
+		let original = if flags & HAS_ORIGINAL != 0 { Some(reader.matrix()?) } else { None };
+		let elimination = if flags & HAS_ELIMINATION != 0 {
+			let offset = reader.offset;
+			let cursor = reader.cursor()?;
+			let matrix = reader.matrix()?;
+			Some(Elimination::resume(matrix, cursor).map_err(|_| SnapshotError::InvalidData(offset))?)
+		} else {
+			None
+		};
+		let solution = reader.values()?;
+		let count = reader.length(4)?;
+		let names = (0..count).map(|_| reader.string()).collect::<Result<Vec<_>, _>>()?;
+		if reader.offset != bytes.len() {
+			return Err(SnapshotError::InvalidData(reader.offset));
+		}
+		Ok(Snapshot { matrix, original, elimination, solution, names })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn resume_elimination() {
+        let mut elimination = Elimination::new(swapping_system()).unwrap();
+        elimination.run(2).unwrap();
+        let snapshot = Snapshot {
+            matrix: swapping_system(),
+            original: None,
+            elimination: Some(elimination.clone()),
+            solution: Vec::new(),
+            names: vec!["a".to_string(), "b".to_string(), "ü".to_string()],
+        };
+        let restored = Snapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+        assert_eq!(restored.matrix, swapping_system());
+        assert_eq!(restored.names, snapshot.names);
+        let resumed = restored.elimination.unwrap();
+        assert_eq!(resumed.pivots_done(), elimination.pivots_done());
+        assert_eq!(resumed.matrix(), elimination.matrix());
+        let solution: Vec<f64> = resumed.finish().unwrap().solution().collect();
+        let expected: Vec<f64> = elimination.finish().unwrap().solution().collect();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn incomplete_system_and_solution() {
+        // Fewer equations than unknowns, one of them too short
+        let matrix = CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0));
+        let snapshot = Snapshot {
+            matrix: matrix.clone(),
+            original: Some(swapping_system()),
+            elimination: None,
+            solution: vec![1.5, -0.25, f64::NAN],
+            names: Vec::new(),
+        };
+        let bytes = snapshot.to_bytes();
+        let restored = Snapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.matrix, matrix);
+        assert_eq!(restored.original, Some(swapping_system()));
+        assert_eq!(restored.solution[..2], [1.5, -0.25]);
+        assert!(restored.solution[2].is_nan());
+    }
+
+    #[test]
+    fn reject_corrupt_bytes() {
+        let snapshot = Snapshot { matrix: swapping_system(), original: None, elimination: None, solution: Vec::new(), names: Vec::new() };
+        let bytes = snapshot.to_bytes();
+        assert_eq!(Snapshot::from_bytes(b"JUNK").unwrap_err(), SnapshotError::InvalidHeader);
+        assert!(matches!(Snapshot::from_bytes(&bytes[..bytes.len() - 3]), Err(SnapshotError::Truncated(_))));
+
+        let mut version = bytes.clone();
+        version[4] = 9;
+        assert_eq!(Snapshot::from_bytes(&version).unwrap_err(), SnapshotError::UnsupportedVersion(9));
+
+        // A huge equation count fails without allocating for it
+        let mut count = bytes.clone();
+        count[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(Snapshot::from_bytes(&count), Err(SnapshotError::Truncated(9))));
+
+        // So does a huge matrix size
+        let size = b"MSNP\x01\xff\xff\xff\xff\x00\x00\x00\x00";
+        assert!(matches!(Snapshot::from_bytes(size), Err(SnapshotError::Truncated(13))));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(matches!(Snapshot::from_bytes(&trailing), Err(SnapshotError::InvalidData(_))));
+    }
+}
@@ -0,0 +1,422 @@
+use num::traits::real::Real;
+use num::{Num, Zero};
+use core::fmt;
+use core::ops::SubAssign;
+
+use super::{CancelToken, CoefficientMatrix, Observer, Result, SolveError, Step};
+
+// Position of the elimination between two elementary row operations.
+// `convert` runs from the start until its last pivot is done and `solve`
+// from `Scale` of the last row to `Done`, so both are driven by the same
+// state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Cursor {
+	// Looking for a larger pivot for `column` from `row` downwards
+	Search { column: usize, row: usize },
+	// Eliminate `column` from `row` using the pivot row
+	Eliminate { column: usize, row: usize },
+	// Divide `row` by its diagonal entry
+	Scale { row: usize },
+	// Eliminate column `row` from the rows above `target`
+	Back { row: usize, target: usize },
+	Done,
+}
+
+impl Cursor {
+	pub(super) fn start(size: usize) -> Cursor {
+		if size > 1 {
+			Cursor::Search { column: 0, row: 1 }
+		} else {
+			Cursor::Scale { row: 0 }
+		}
+	}
+
+	// Pivots completed, counting one per column of `convert` and one per
+	// row of `solve`
+	pub(super) fn pivots_done(self, size: usize) -> usize {
+		match self {
+			Cursor::Eliminate { column, row } if row == size => column + 1,
+			Cursor::Search { column, .. } | Cursor::Eliminate { column, .. } => column,
+			Cursor::Back { row, target: 0 } => 2 * size - 1 - row,
+			Cursor::Scale { row } | Cursor::Back { row, .. } => 2 * size - 2 - row,
+			Cursor::Done => 2 * size - 1,
+		}
+	}
+}
+
+// `Real` has no `is_finite`; NaN fails any comparison
+pub(super) fn is_finite<T: Real>(value: T) -> bool {
+	value.abs() <= T::max_value()
+}
+
+// Arithmetic of the row operations in `advance`. `None` stops the
+// elimination with `SolveError::FixedPointOverflow` at the row being
+// changed and the pivot column.
+pub(super) trait Arithmetic<T> {
+	// Whether `a` is larger than `b` in magnitude, which picks the pivots
+	fn exceeds(a: T, b: T) -> bool;
+	fn divide(a: T, b: T) -> Option<T>;
+	// value - other * factor, the update of every elimination step
+	fn subtract_product(value: T, other: T, factor: T) -> Option<T>;
+	// Whether a value of a row that was just changed can be used further
+	fn usable(value: T) -> bool;
+}
+
+// The operators, checking changed rows for NaN and infinities
+pub(super) struct Plain;
+
+impl<T: Real + SubAssign> Arithmetic<T> for Plain {
+	fn exceeds(a: T, b: T) -> bool {
+		a.abs() > b.abs()
+	}
+
+	fn divide(a: T, b: T) -> Option<T> {
+		Some(a / b)
+	}
+
+	fn subtract_product(mut value: T, other: T, factor: T) -> Option<T> {
+		value -= other * factor;
+		Some(value)
+	}
+
+	fn usable(value: T) -> bool {
+		is_finite(value)
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
+{
+	// First NaN or infinity in `row` as (row, column), the result counting
+	// as column `size`
+	fn non_finite(&self, row: usize) -> Option<(usize, usize)> {
+		let equation = &self.matrix[row];
+		equation.coefficients.iter()
+			.chain(core::iter::once(&equation.result))
+			.position(|&value| !is_finite(value))
+			.map(|column| (row, column))
+	}
+
+	// Fail on NaN or infinite input, which elimination would silently
+	// spread into the solution
+	pub(super) fn check_finite(&self) -> core::result::Result<(), SolveError> {
+		match (0..self.matrix.len()).find_map(|row| self.non_finite(row)) {
+			Some((row, column)) => Err(SolveError::NonFiniteInput(row, column)),
+			None => Ok(()),
+		}
+	}
+
+	// Fail if a row operation on `row` overflowed or produced NaN
+	fn check_row<A: Arithmetic<T>>(&self, row: usize) -> core::result::Result<(), SolveError> {
+		let equation = &self.matrix[row];
+		match equation.coefficients.iter().chain(core::iter::once(&equation.result)).position(|&value| !A::usable(value)) {
+			Some(column) => Err(SolveError::NonFiniteResult(row, column)),
+			None => Ok(()),
+		}
+	}
+
+	// Subtract `factor` times row `source` from row `target` in columns
+	// `from` onwards and the result
+	fn subtract_row<A: Arithmetic<T>>(&mut self, source: usize, target: usize, from: usize, factor: T) -> Option<()> {
+		for c in from..self.size {
+			let value = A::subtract_product(self.matrix[target].get(c), self.matrix[source].get(c), factor)?;
+			*self.matrix[target].get_mut(c) = value;
+		}
+		let result = A::subtract_product(self.matrix[target].get_result(), self.matrix[source].get_result(), factor)?;
+		*self.matrix[target].get_result_mut() = result;
+		Some(())
+	}
+
+	// Perform the next elementary row operation, a swap, an elimination or
+	// a scaling, and return where to continue. Selecting a pivot isn't a
+	// row operation and happens along with the following one.
+	pub(super) fn advance<O: Observer<T>>(&mut self, cursor: Cursor, observer: &mut O) -> core::result::Result<Cursor, SolveError> {
+		self.advance_with::<Plain, O>(cursor, observer)
+	}
+
+	// `advance` with the given arithmetic
+	pub(super) fn advance_with<A: Arithmetic<T>, O: Observer<T>>(&mut self, mut cursor: Cursor, observer: &mut O) -> core::result::Result<Cursor, SolveError> {
+		let size = self.size;
+		loop {
+			cursor = match cursor {
+				Cursor::Search { column, row } => {
+					let pivot = self.matrix[column].get(column);
+					match (row..size).find(|&i| A::exceeds(self.matrix[i].get(column), pivot)) {
+						Some(i) => {
+							self.matrix.swap(i, column);
+							observer.record(Step::Swap(column, i));
+							return Ok(Cursor::Search { column, row: i + 1 });
+						},
+						None => {
+							observer.record(Step::Pivot(column, pivot));
+							Cursor::Eliminate { column, row: column + 1 }
+						},
+					}
+				},
+				Cursor::Eliminate { column, row } if row < size => {
+					// A zero pivot means that the whole column below it is zero
+					// already. Dividing by it would only leave NaN behind, and
+					// `solve` classifies the singular system.
+					if self.matrix[column].get(column).is_zero() {
+						return Ok(Cursor::Eliminate { column, row: row + 1 });
+					}
+					let overflow = || SolveError::FixedPointOverflow(row, column);
+					let ratio = A::divide(self.matrix[row].get(column), self.matrix[column].get(column)).ok_or_else(overflow)?;
+					observer.record(Step::Eliminate(row, column, ratio));
+					self.subtract_row::<A>(column, row, column, ratio).ok_or_else(overflow)?;
+					self.check_row::<A>(row)?;
+					return Ok(Cursor::Eliminate { column, row: row + 1 });
+				},
+				Cursor::Eliminate { column, .. } if column + 2 < size => {
+					Cursor::Search { column: column + 1, row: column + 2 }
+				},
+				Cursor::Eliminate { .. } => Cursor::Scale { row: size - 1 },
+				Cursor::Scale { row } => {
+					let divisor = self.matrix[row].get(row);
+
+					if divisor.is_zero() {
+						if self.matrix[row].get_result().is_zero() {
+							return Err(SolveError::DependentSolutionSet);
+						} else {
+							return Err(SolveError::EmptySolutionSet);
+						}
+					}
+
+					// Divide each value in the current row with the row's leading coefficient
+					let overflow = || SolveError::FixedPointOverflow(row, row);
+					observer.record(Step::Scale(row, divisor));
+					for j in 0..size {
+						let quotient = A::divide(self.matrix[row].get(j), divisor).ok_or_else(overflow)?;
+						*self.matrix[row].get_mut(j) = quotient;
+					}
+					let result_quotient = A::divide(self.matrix[row].get_result(), divisor).ok_or_else(overflow)?;
+					*self.matrix[row].get_result_mut() = result_quotient;
+					self.check_row::<A>(row)?;
+					return Ok(Cursor::Back { row, target: row });
+				},
+				Cursor::Back { row, target } if target > 0 => {
+					let target = target - 1;
+					let factor = self.matrix[target].get(row);
+					observer.record(Step::Eliminate(target, row, factor));
+					self.subtract_row::<A>(row, target, 0, factor).ok_or(SolveError::FixedPointOverflow(target, row))?;
+					self.check_row::<A>(target)?;
+					return Ok(Cursor::Back { row, target });
+				},
+				Cursor::Back { row, .. } if row > 0 => Cursor::Scale { row: row - 1 },
+				Cursor::Back { .. } | Cursor::Done => return Ok(Cursor::Done),
+			};
+		}
+	}
+}
+
+// Elimination that can be paused, so that callers like the wasm frontend
+// can spread a large solve over several time slices or show it one row
+// operation at a time:
+//
+//     let mut elimination = Elimination::new(matrix)?;
+//     while !elimination.run(64)? {
+//         // yield to the event loop
+//     }
+//     let solved = elimination.into_matrix();
+//
+// The pivots are the same as `convert` followed by `solve`, 2 * size - 1
+// in total.
+#[derive(Debug, Clone)]
+pub struct Elimination<T> {
+	matrix: CoefficientMatrix<T>,
+	cursor: Cursor,
+	cancel: Option<CancelToken>,
+}
+
+impl<T> Elimination<T>
+where
+	T: Num + Zero + Copy + fmt::Display + fmt::Debug + Real + SubAssign
+{
+	pub fn new(matrix: CoefficientMatrix<T>) -> core::result::Result<Self, SolveError> {
+		let matrix = matrix.validate()?;
+		matrix.check_finite()?;
+		Ok(Elimination {
+			cursor: Cursor::start(matrix.size),
+			matrix,
+			cancel: None,
+		})
+	}
+
+	// Check `token` before every row operation and stop with
+	// `SolveError::Cancelled` once it is cancelled
+	pub fn cancel_token(mut self, token: CancelToken) -> Self {
+		self.cancel = Some(token);
+		self
+	}
+
+	pub fn pivots_done(&self) -> usize {
+		self.cursor.pivots_done(self.matrix.size)
+	}
+
+	pub fn total_pivots(&self) -> usize {
+		2 * self.matrix.size - 1
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.pivots_done() == self.total_pivots()
+	}
+
+	// The matrix in its current state
+	pub fn matrix(&self) -> &CoefficientMatrix<T> {
+		&self.matrix
+	}
+
+	// Perform a single elementary row operation. Returns whether the solve
+	// is finished.
+	pub fn step(&mut self) -> core::result::Result<bool, SolveError> {
+		self.step_observed(&mut ())
+	}
+
+	// Like `step`, reporting the row operation to the observer
+	pub fn step_observed<O: Observer<T>>(&mut self, observer: &mut O) -> core::result::Result<bool, SolveError> {
+		if self.is_finished() {
+			return Ok(true);
+		}
+		if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+			return Err(SolveError::Cancelled);
+		}
+		self.cursor = self.matrix.advance(self.cursor, observer)?;
+		Ok(self.is_finished())
+	}
+
+	// Perform up to `pivots` pivots. Returns whether the solve is finished.
+	pub fn run(&mut self, pivots: usize) -> core::result::Result<bool, SolveError> {
+		self.run_observed(pivots, &mut ())
+	}
+
+	// Like `run`, reporting each row operation to the observer
+	pub fn run_observed<O: Observer<T>>(&mut self, pivots: usize, observer: &mut O) -> core::result::Result<bool, SolveError> {
+		let target = (self.pivots_done() + pivots).min(self.total_pivots());
+		while !self.is_finished() && self.pivots_done() < target {
+			self.step_observed(observer)?;
+		}
+		Ok(self.is_finished())
+	}
+
+	// The matrix in its current state, which is solved once all pivots are
+	// done
+	pub fn into_matrix(self) -> CoefficientMatrix<T> {
+		self.matrix
+	}
+
+	// Elimination resumed at `cursor`, e.g. from a snapshot, after checking
+	// that the cursor lies within the matrix
+	pub(super) fn resume(matrix: CoefficientMatrix<T>, cursor: Cursor) -> core::result::Result<Self, SolveError> {
+		let matrix = matrix.validate()?;
+		let size = matrix.size;
+		let valid = match cursor {
+			Cursor::Search { column, row } | Cursor::Eliminate { column, row } => column < size && row <= size,
+			Cursor::Scale { row } => row < size,
+			Cursor::Back { row, target } => row < size && target <= row,
+			Cursor::Done => true,
+		};
+		if !valid {
+			return Err(SolveError::IndexOutOfBounds(size, size));
+		}
+		Ok(Elimination { matrix, cursor, cancel: None })
+	}
+
+	pub(super) fn cursor(&self) -> Cursor {
+		self.cursor
+	}
+
+	// Run the remaining pivots
+	pub fn finish(mut self) -> Result<T> {
+		let remaining = self.total_pivots() - self.pivots_done();
+		self.run(remaining)?;
+		Ok(self.matrix)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn resume_between_pivots() {
+        let expected = swapping_system().validate().unwrap().convert().unwrap().solve().unwrap();
+        let mut elimination = Elimination::new(swapping_system()).unwrap();
+        assert_eq!(elimination.total_pivots(), 5);
+        assert!(!elimination.run(2).unwrap());
+        assert_eq!(elimination.pivots_done(), 2);
+        assert!(!elimination.run(2).unwrap());
+        assert!(elimination.run(2).unwrap());
+        assert_eq!(elimination.pivots_done(), 5);
+        assert_eq!(elimination.into_matrix(), expected);
+
+        let elimination = Elimination::new(swapping_system()).unwrap();
+        assert_eq!(elimination.finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn single_row_operations() {
+        let mut expected = Trace::new();
+        let _ = swapping_system().validate().unwrap()
+            .convert_observed(&mut expected).unwrap()
+            .solve_observed(&mut expected).unwrap();
+
+        let mut elimination = Elimination::new(swapping_system()).unwrap();
+        let mut steps = Vec::new();
+        loop {
+            let mut trace = Trace::new();
+            let finished = elimination.step_observed(&mut trace).unwrap();
+            let operations: Vec<_> = trace.steps().iter()
+                .filter(|step| !matches!(step, Step::Pivot(..)))
+                .collect();
+            assert_eq!(operations.len(), 1);
+            steps.extend(trace.steps().iter().cloned());
+            if finished {
+                break;
+            }
+        }
+        assert_eq!(steps, expected.steps());
+        assert_eq!(elimination.into_matrix(), swapping_system().validate().unwrap().convert().unwrap().solve().unwrap());
+    }
+
+    #[test]
+    fn report_errors() {
+        let singular = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![2.0, 4.0], 7.0));
+        let mut elimination = Elimination::new(singular).unwrap();
+        assert!(matches!(elimination.run(3), Err(SolveError::EmptySolutionSet)));
+        assert!(matches!(Elimination::new(CoefficientMatrix::<f64>::new(2)), Err(SolveError::UnfittingEquationAmount(0, 2))));
+    }
+
+    #[test]
+    fn cancel_between_pivots() {
+        let token = CancelToken::new();
+        let mut elimination = Elimination::new(swapping_system()).unwrap().cancel_token(token.clone());
+        assert!(!elimination.run(2).unwrap());
+        token.cancel();
+        assert!(matches!(elimination.run(1), Err(SolveError::Cancelled)));
+        assert_eq!(elimination.pivots_done(), 2);
+    }
+
+    #[test]
+    fn non_finite_values() {
+        let nan = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 2.0], 3.0))
+            .add_equation(Equation::new(vec![4.0, f64::NAN], 6.0))
+            .validate().unwrap();
+        assert!(matches!(nan.clone().convert(), Err(SolveError::NonFiniteInput(1, 1))));
+        assert!(matches!(Elimination::new(nan), Err(SolveError::NonFiniteInput(1, 1))));
+        let infinite = CoefficientMatrix::new(1)
+            .add_equation(Equation::new(vec![1.0], f64::INFINITY))
+            .validate().unwrap();
+        assert!(matches!(infinite.convert(), Err(SolveError::NonFiniteInput(0, 1))));
+
+        // Eliminating the first column overflows the second row
+        let overflow = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1e308], 1.0))
+            .add_equation(Equation::new(vec![1.0, -1e308], 1.0))
+            .validate().unwrap();
+        assert!(matches!(overflow.convert(), Err(SolveError::NonFiniteResult(1, 1))));
+    }
+}
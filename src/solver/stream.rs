@@ -0,0 +1,192 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Equation, Result, SolveError};
+
+// Elimination of equations as they arrive, e.g. chunk by chunk from a
+// streamed file, so that the full system never needs to be held twice:
+//
+//     let mut stream = EquationStream::new(size);
+//     for chunk in chunks {
+//         stream.push_rows(&chunk)?;
+//     }
+//     let solved = stream.finish()?;
+//
+// Each incoming row is reduced by the pivots of the rows before it and
+// then pivots on its own largest remaining coefficient. Choosing pivots by
+// column rather than by row keeps the elimination stable without waiting
+// for later rows.
+#[derive(Debug, Clone)]
+pub struct EquationStream<T> {
+	size: usize,
+	// Reduced rows and the column each one pivots on
+	rows: Vec<(Vec<T>, T, usize)>,
+	pivoted: Vec<bool>,
+	// Whether a row reduced to 0 = 0, or to 0 = c for nonzero c
+	dependent: bool,
+	inconsistent: bool,
+	received: usize,
+}
+
+impl<T> EquationStream<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	pub fn new(size: usize) -> EquationStream<T> {
+		EquationStream {
+			size,
+			rows: Vec::with_capacity(size),
+			pivoted: alloc::vec![false; size],
+			dependent: false,
+			inconsistent: false,
+			received: 0,
+		}
+	}
+
+	// Number of equations received so far
+	pub fn received(&self) -> usize {
+		self.received
+	}
+
+	pub fn push(&mut self, equation: Equation<T>) -> core::result::Result<(), SolveError> {
+		if equation.len() != self.size {
+			return Err(SolveError::UnfittingCoefficientAmount(equation.len(), self.size));
+		}
+		self.push_row(equation.coefficients, equation.result)
+	}
+
+	// Push whole rows of `size` coefficients followed by the right-hand
+	// side. A chunk must not end in the middle of a row.
+	pub fn push_rows(&mut self, chunk: &[T]) -> core::result::Result<(), SolveError> {
+		let width = self.size + 1;
+		if !chunk.len().is_multiple_of(width) {
+			return Err(SolveError::UnfittingCoefficientAmount(chunk.len() % width, self.size));
+		}
+		for row in chunk.chunks(width) {
+			self.push_row(row[..self.size].to_vec(), row[self.size])?;
+		}
+		Ok(())
+	}
+
+	fn push_row(&mut self, mut coefficients: Vec<T>, mut result: T) -> core::result::Result<(), SolveError> {
+		if self.received == self.size {
+			return Err(SolveError::UnfittingEquationAmount(self.received + 1, self.size));
+		}
+		self.received += 1;
+
+		for (pivot_row, pivot_result, column) in &self.rows {
+			let ratio = coefficients[*column] / pivot_row[*column];
+			if ratio.is_zero() {
+				continue;
+			}
+			for (value, pivot_value) in coefficients.iter_mut().zip(pivot_row) {
+				*value -= *pivot_value * ratio;
+			}
+			coefficients[*column] = T::zero();
+			result -= *pivot_result * ratio;
+		}
+
+		let pivot = (0..self.size)
+			.filter(|&column| !self.pivoted[column])
+			.fold(None, |best: Option<usize>, column| match best {
+				Some(best) if coefficients[best].abs() >= coefficients[column].abs() => Some(best),
+				_ => Some(column),
+			})
+			.filter(|&column| !coefficients[column].is_zero());
+		match pivot {
+			Some(column) => {
+				self.pivoted[column] = true;
+				self.rows.push((coefficients, result, column));
+			},
+			None if result.is_zero() => self.dependent = true,
+			None => self.inconsistent = true,
+		}
+		Ok(())
+	}
+
+	// Back substitute once all equations have arrived, returning the solved
+	// matrix as `convert` followed by `solve` would
+	pub fn finish(self) -> Result<T> {
+		if self.size < 1 {
+			return Err(SolveError::TooSmall(self.size));
+		}
+		if self.received != self.size {
+			return Err(SolveError::UnfittingEquationAmount(self.received, self.size));
+		}
+		if self.inconsistent {
+			return Err(SolveError::EmptySolutionSet);
+		}
+		if self.dependent {
+			return Err(SolveError::DependentSolutionSet);
+		}
+
+		let mut solution = alloc::vec![T::zero(); self.size];
+		for (coefficients, result, column) in self.rows.iter().rev() {
+			let mut value = *result;
+			for (other, coefficient) in coefficients.iter().enumerate() {
+				if other != *column {
+					value -= *coefficient * solution[other];
+				}
+			}
+			solution[*column] = value / coefficients[*column];
+		}
+
+		let mut solved = CoefficientMatrix::new(self.size);
+		for (row, value) in solution.into_iter().enumerate() {
+			let coefficients = (0..self.size)
+				.map(|column| if row == column { T::one() } else { T::zero() })
+				.collect();
+			solved = solved.add_equation(Equation::new(coefficients, value));
+		}
+		Ok(solved)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn stream_in_chunks() {
+        let rows = [
+            [1.0, 2.0, -1.0, 2.0],
+            [4.0, 1.0, 2.0, 12.0],
+            [-2.0, 5.0, 1.0, 4.0],
+        ];
+        let mut mat = CoefficientMatrix::new(3);
+        for row in &rows {
+            mat = mat.add_equation(Equation::new(row[..3].to_vec(), row[3]));
+        }
+        let expected: Vec<f64> = mat.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+
+        let flat: Vec<f64> = rows.iter().flatten().copied().collect();
+        let mut stream = EquationStream::new(3);
+        stream.push_rows(&flat[..4]).unwrap();
+        assert_eq!(stream.received(), 1);
+        assert!(matches!(stream.push_rows(&flat[4..6]), Err(SolveError::UnfittingCoefficientAmount(2, 3))));
+        stream.push_rows(&flat[4..]).unwrap();
+        let solved = stream.finish().unwrap();
+        for (x, y) in solved.solution().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        assert_eq!(solved.get(1, 1), Some(1.0));
+    }
+
+    #[test]
+    fn stream_errors() {
+        let mut stream = EquationStream::new(2);
+        stream.push(Equation::new(vec![1.0, 2.0], 3.0)).unwrap();
+        assert!(matches!(stream.push(Equation::new(vec![1.0], 3.0)), Err(SolveError::UnfittingCoefficientAmount(1, 2))));
+        assert!(matches!(stream.clone().finish(), Err(SolveError::UnfittingEquationAmount(1, 2))));
+        stream.push(Equation::new(vec![2.0, 4.0], 6.0)).unwrap();
+        assert!(matches!(stream.push(Equation::new(vec![0.0, 1.0], 1.0)), Err(SolveError::UnfittingEquationAmount(3, 2))));
+        assert!(matches!(stream.finish(), Err(SolveError::DependentSolutionSet)));
+
+        let mut stream = EquationStream::new(2);
+        stream.push_rows(&[1.0, 2.0, 3.0, 2.0, 4.0, 7.0]).unwrap();
+        assert!(matches!(stream.finish(), Err(SolveError::EmptySolutionSet)));
+    }
+}
@@ -0,0 +1,85 @@
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Cursor, SolveError};
+
+// Time spent in each phase of a solve, in the unit of the clock passed to
+// `solve_timed`, e.g. milliseconds of `performance.now()`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+	pub validate: f64,
+	// Finding the pivots and swapping rows for them
+	pub pivot_search: f64,
+	// Eliminating below the pivots
+	pub elimination: f64,
+	// Scaling the rows and eliminating above the pivots
+	pub back_substitution: f64,
+}
+
+impl Timings {
+	pub fn total(&self) -> f64 {
+		self.validate + self.pivot_search + self.elimination + self.back_substitution
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// `validate`, `convert` and `solve` with the time of each phase measured
+	// by `clock`. The clock is read around every row operation, which costs
+	// little next to the operation itself on larger systems but makes this
+	// slower than a plain solve on tiny ones. A pivot search without a swap
+	// runs together with the first elimination below it, which then counts
+	// as pivot search.
+	pub fn solve_timed<C: FnMut() -> f64>(self, mut clock: C) -> Result<(CoefficientMatrix<T>, Timings), SolveError> {
+		let mut timings = Timings::default();
+		let start = clock();
+		let mut matrix = self.validate()?;
+		matrix.check_finite()?;
+		let mut last = clock();
+		timings.validate = last - start;
+
+		let mut cursor = Cursor::start(matrix.size);
+		while cursor != Cursor::Done {
+			let phase = match cursor {
+				Cursor::Search { .. } => &mut timings.pivot_search,
+				Cursor::Eliminate { .. } => &mut timings.elimination,
+				Cursor::Scale { .. } | Cursor::Back { .. } | Cursor::Done => &mut timings.back_substitution,
+			};
+			cursor = matrix.advance(cursor, &mut ())?;
+			let now = clock();
+			*phase += now - last;
+			last = now;
+		}
+		Ok((matrix, timings))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn phases_add_up() {
+        let system = swapping_system();
+        // A clock ticking once per reading counts the readings per phase
+        let mut ticks = 0.0;
+        let (solved, timings) = system.clone().solve_timed(|| {
+            ticks += 1.0;
+            ticks
+        }).unwrap();
+        let expected: Vec<f64> = system.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect();
+        assert_eq!(solved.solution().collect::<Vec<_>>(), expected);
+        assert_eq!(timings.validate, 1.0);
+        assert!(timings.pivot_search >= 2.0 && timings.elimination >= 1.0);
+        // Three scalings and three eliminations above the pivots
+        assert_eq!(timings.back_substitution, 6.0);
+        assert_eq!(timings.total(), ticks - 1.0);
+
+        let incomplete = CoefficientMatrix::new(2).add_equation(Equation::new(vec![1.0, 2.0], 3.0));
+        assert!(matches!(incomplete.solve_timed(|| 0.0), Err(SolveError::UnfittingEquationAmount(1, 2))));
+    }
+}
@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::pinv::jacobi_svd;
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Total least squares solution, which unlike `solve_rectangular`
+	// allows for errors in the coefficients as well as in the results: the
+	// smallest perturbation of [A | b], measured by its Frobenius norm, that
+	// makes the system consistent. For a line fit it minimizes the
+	// orthogonal instead of the vertical distances.
+	//
+	// The perturbed system is spanned by the right singular vector v of
+	// [A | b] with the smallest singular value, so x = -v[..n] / v[n]. If
+	// v[n] vanishes no perturbation of b alone can be consistent and there
+	// is no solution; if the smallest singular value repeats it isn't
+	// unique.
+	pub fn solve_tls(&self) -> Result<Vec<T>, SolveError> {
+		if self.size < 1 {
+			return Err(SolveError::TooSmall(self.size));
+		}
+		if let Some(equation) = self.matrix.iter().find(|equation| equation.coefficients.len() != self.size) {
+			return Err(SolveError::UnfittingCoefficientAmount(equation.coefficients.len(), self.size));
+		}
+		let n = self.size;
+		if self.matrix.len() < n {
+			return Err(SolveError::UnfittingEquationAmount(self.matrix.len(), n));
+		}
+
+		// Zero rows pad a square system to the n + 1 rows the SVD needs
+		// without changing its singular vectors
+		let mut augmented = Matrix::zeros(self.matrix.len().max(n + 1), n + 1);
+		for (i, equation) in self.matrix.iter().enumerate() {
+			for (j, &coefficient) in equation.coefficients.iter().enumerate() {
+				augmented[(i, j)] = coefficient;
+			}
+			augmented[(i, n)] = equation.result;
+		}
+		let (u, v) = jacobi_svd(&augmented);
+		let singular: Vec<T> = (0..=n)
+			.map(|k| (0..u.rows()).fold(T::zero(), |sum, i| sum + u[(i, k)] * u[(i, k)]).sqrt())
+			.collect();
+		let largest = singular.iter().fold(T::zero(), |max, &value| max.max(value));
+		let smallest = (0..=n)
+			.min_by(|&a, &b| singular[a].partial_cmp(&singular[b]).unwrap_or(core::cmp::Ordering::Equal))
+			.unwrap_or(n);
+
+		let tolerance = T::epsilon().sqrt() * largest;
+		let repeated = (0..=n).any(|k| k != smallest && singular[k] - singular[smallest] <= tolerance);
+		if repeated {
+			return Err(SolveError::DependentSolutionSet);
+		}
+		let last = v[(n, smallest)];
+		if last.abs() <= T::epsilon().sqrt() {
+			return Err(SolveError::EmptySolutionSet);
+		}
+		Ok((0..n).map(|j| -v[(j, smallest)] / last).collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn orthogonal_line_fit() {
+        // Points scattered symmetrically around the line y = x through the
+        // origin, shifted along its normal. Ordinary least squares flattens
+        // the slope; total least squares recovers it.
+        let points = [(1.0, 1.5), (2.0, 1.5), (3.0, 3.5), (4.0, 3.5), (-1.0, -1.5), (-2.0, -1.5), (-3.0, -3.5), (-4.0, -3.5)];
+        let system = points.iter()
+            .fold(CoefficientMatrix::new(1), |system, &(x, y)| system.add_equation(Equation::new(vec![x], y)));
+        let ordinary = system.solve_rectangular().unwrap()[0];
+        let total = system.solve_tls().unwrap()[0];
+        // Minimizes the orthogonal distances over all slopes
+        let orthogonal = |slope: f64| points.iter().map(|&(x, y)| (y - slope * x).powi(2) / (1.0 + slope * slope)).sum::<f64>();
+        assert!(orthogonal(total) <= orthogonal(total + 1e-4));
+        assert!(orthogonal(total) <= orthogonal(total - 1e-4));
+        assert!(orthogonal(total) < orthogonal(ordinary));
+        assert!(ordinary < total);
+    }
+
+    #[test]
+    fn consistent_and_invalid_systems() {
+        let square = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0, 3.0], 2.0));
+        let solution = square.solve_tls().unwrap();
+        assert!((solution[0] - 0.5).abs() < 1e-10 && (solution[1] - 1.0 / 3.0).abs() < 1e-10);
+
+        let one = CoefficientMatrix::new(2).add_equation(Equation::new(vec![1.0, 1.0], 1.0));
+        assert!(matches!(one.solve_tls(), Err(SolveError::UnfittingEquationAmount(1, 2))));
+        // b can't be reached by perturbing only along x: the solution would
+        // be infinite
+        let vertical = CoefficientMatrix::new(1)
+            .add_equation(Equation::new(vec![0.0], 1.0))
+            .add_equation(Equation::new(vec![0.0], 2.0));
+        assert!(matches!(vertical.solve_tls(), Err(SolveError::EmptySolutionSet)));
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Elementary row operations performed while converting and solving a matrix.
+// Rows are zero-based indices into the matrix at the time of the step.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Step<T> {
+	// Rows were exchanged to obtain a better pivot
+	Swap(usize, usize),
+	// The pivot of the given row/column was selected
+	Pivot(usize, T),
+	// Target row minus factor times source row
+	Eliminate(usize, usize, T),
+	// Row divided by its leading coefficient
+	Scale(usize, T),
+}
+
+// Receives each step as it is performed. The unit type ignores all steps,
+// so the plain `convert`/`solve` don't pay for recording.
+pub trait Observer<T> {
+	fn record(&mut self, step: Step<T>);
+}
+
+impl<T> Observer<T> for () {
+	fn record(&mut self, _step: Step<T>) {}
+}
+
+// Lend an observer without giving it up
+impl<T, O: Observer<T> + ?Sized> Observer<T> for &mut O {
+	fn record(&mut self, step: Step<T>) {
+		(**self).record(step);
+	}
+}
+
+// An absent observer costs only the check
+impl<T, O: Observer<T>> Observer<T> for Option<O> {
+	fn record(&mut self, step: Step<T>) {
+		if let Some(observer) = self {
+			observer.record(step);
+		}
+	}
+}
+
+// Report every step to both observers
+impl<T: Clone, A: Observer<T>, B: Observer<T>> Observer<T> for (A, B) {
+	fn record(&mut self, step: Step<T>) {
+		self.0.record(step.clone());
+		self.1.record(step);
+	}
+}
+
+// A recorded sequence of steps. Its Display output is a stable, line based
+// format that frontends and snapshot tests can rely on:
+//
+//     trace v1
+//     swap 0 1
+//     pivot 0 8
+//     eliminate 1 0 0.25
+//     scale 1 4.5
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trace<T> {
+	steps: Vec<Step<T>>,
+}
+
+const TRACE_HEADER: &str = "trace v1";
+
+impl<T> Trace<T> {
+	pub fn new() -> Self {
+		Trace { steps: Vec::new() }
+	}
+
+	pub fn steps(&self) -> &[Step<T>] {
+		&self.steps
+	}
+}
+
+impl<T> Default for Trace<T> {
+	fn default() -> Self {
+		Trace::new()
+	}
+}
+
+impl<T> Observer<T> for Trace<T> {
+	fn record(&mut self, step: Step<T>) {
+		self.steps.push(step);
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Step<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Step::Swap(a, b) => write!(f, "swap {} {}", a, b),
+			Step::Pivot(row, value) => write!(f, "pivot {} {}", row, value),
+			Step::Eliminate(target, source, factor) => write!(f, "eliminate {} {} {}", target, source, factor),
+			Step::Scale(row, divisor) => write!(f, "scale {} {}", row, divisor),
+		}
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Trace<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "{}", TRACE_HEADER)?;
+		for step in self.steps.iter() {
+			writeln!(f, "{}", step)?;
+		}
+		Ok(())
+	}
+}
+
+// Error returned when parsing a trace fails, carrying the offending line
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseTraceError(pub usize);
+
+impl fmt::Display for ParseTraceError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Invalid trace at line {}", self.0)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseTraceError {}
+
+impl<T: FromStr> FromStr for Step<T> {
+	type Err = ();
+
+	fn from_str(line: &str) -> Result<Self, ()> {
+		let fields: Vec<&str> = line.split_whitespace().collect();
+		let index = |idx: usize| fields[idx].parse::<usize>().map_err(|_| ());
+		let value = |idx: usize| fields[idx].parse::<T>().map_err(|_| ());
+		match (fields.first(), fields.len()) {
+			(Some(&"swap"), 3) => Ok(Step::Swap(index(1)?, index(2)?)),
+			(Some(&"pivot"), 3) => Ok(Step::Pivot(index(1)?, value(2)?)),
+			(Some(&"eliminate"), 4) => Ok(Step::Eliminate(index(1)?, index(2)?, value(3)?)),
+			(Some(&"scale"), 3) => Ok(Step::Scale(index(1)?, value(2)?)),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<T: FromStr> FromStr for Trace<T> {
+	type Err = ParseTraceError;
+
+	fn from_str(input: &str) -> Result<Self, ParseTraceError> {
+		let mut lines = input.lines().enumerate();
+		match lines.next() {
+			Some((_, header)) if header.trim() == TRACE_HEADER => (),
+			_ => return Err(ParseTraceError(1)),
+		}
+
+		let mut steps = Vec::new();
+		for (idx, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+			steps.push(line.parse().map_err(|_| ParseTraceError(idx + 1))?);
+		}
+		Ok(Trace { steps })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::Trace;
+    use std::{env, fs};
+
+    // Compare against the snapshot in tests/golden. Run the tests with
+    // UPDATE_GOLDEN=1 to rewrite the snapshots after an intended change.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name);
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, actual).unwrap();
+        }
+        let expected = fs::read_to_string(&path).unwrap();
+        assert_eq!(actual, expected, "trace differs from golden file {}", path);
+    }
+
+    fn traced(matrix: CoefficientMatrix<f64>) -> Trace<f64> {
+        let mut trace = Trace::new();
+        let _ = matrix
+            .validate().unwrap()
+            .convert_observed(&mut trace).unwrap()
+            .solve_observed(&mut trace).unwrap();
+        trace
+    }
+
+    #[test]
+    fn golden_2x2() {
+        let trace = traced(CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0)));
+        assert_golden("solve_2x2.trace", &trace.to_string());
+    }
+
+    #[test]
+    fn golden_3x3_with_swaps() {
+        let trace = traced(swapping_system());
+        assert_golden("solve_3x3.trace", &trace.to_string());
+    }
+
+    #[test]
+    fn combined_observers() {
+        let mut both = (Trace::new(), Some(Trace::new()));
+        let mut none: (Trace<f64>, Option<Trace<f64>>) = (Trace::new(), None);
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0))
+            .validate().unwrap();
+        let _ = mat.clone().convert_observed(&mut both).unwrap().solve_observed(&mut both).unwrap();
+        let _ = mat.convert_observed(&mut none).unwrap().solve_observed(&mut none).unwrap();
+        assert_eq!(Some(both.0.clone()), both.1);
+        assert_eq!(none.0, both.0);
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let trace = traced(CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![8.0, -6.0], 2.0))
+            .add_equation(Equation::new(vec![2.0,  3.0], 2.0)));
+        assert_eq!(trace.to_string().parse::<Trace<f64>>().unwrap(), trace);
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::SubAssign;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Matrix, SolveError};
+
+// Solution of a system whose coefficients and results are measured values
+// with independent standard uncertainties, propagated to first order:
+//
+//     dx = A⁻¹ (db - dA x),  Cov(x) = A⁻¹ diag(s) A⁻ᵀ
+//
+// where s_i = σ(b_i)² + Σ_j x_j² σ(a_ij)² is the variance of row i.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncertainSolution<T> {
+	values: Vec<T>,
+	covariance: Matrix<T>,
+}
+
+impl<T: Real> UncertainSolution<T> {
+	pub fn values(&self) -> &[T] {
+		&self.values
+	}
+
+	// Standard uncertainty of each unknown
+	pub fn uncertainties(&self) -> Vec<T> {
+		(0..self.values.len()).map(|i| self.covariance[(i, i)].sqrt()).collect()
+	}
+
+	pub fn covariance(&self) -> &Matrix<T> {
+		&self.covariance
+	}
+}
+
+impl<T> CoefficientMatrix<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Solve and propagate the standard uncertainties of the coefficients,
+	// given as a matrix of the same shape, and of the results
+	pub fn solve_uncertain(&self, coefficient_uncertainties: &Matrix<T>, result_uncertainties: &[T]) -> Result<UncertainSolution<T>, SolveError> {
+		self.ensure_validated()?;
+		let size = self.size;
+		let shape = (coefficient_uncertainties.rows(), coefficient_uncertainties.columns());
+		if shape != (size, size) {
+			return Err(SolveError::IncompatibleDimensions((size, size), shape));
+		}
+		if result_uncertainties.len() != size {
+			return Err(SolveError::IncompatibleDimensions((size, 1), (result_uncertainties.len(), 1)));
+		}
+
+		let lu = self.lu()?;
+		let results: Vec<T> = self.results().collect();
+		let values = lu.solve(&results)?;
+
+		let variances: Vec<T> = (0..size)
+			.map(|i| (0..size).fold(result_uncertainties[i].powi(2), |sum, j| {
+				sum + (values[j] * coefficient_uncertainties[(i, j)]).powi(2)
+			}))
+			.collect();
+
+		// Columns of A⁻¹ solve A g = e_i
+		let mut inverse = Matrix::zeros(size, size);
+		let mut unit = alloc::vec![T::zero(); size];
+		for column in 0..size {
+			unit[column] = T::one();
+			for (row, value) in lu.solve(&unit)?.into_iter().enumerate() {
+				inverse[(row, column)] = value;
+			}
+			unit[column] = T::zero();
+		}
+
+		let mut covariance = Matrix::zeros(size, size);
+		for k in 0..size {
+			for l in 0..=k {
+				let value = (0..size).fold(T::zero(), |sum, i| sum + inverse[(k, i)] * inverse[(l, i)] * variances[i]);
+				covariance[(k, l)] = value;
+				covariance[(l, k)] = value;
+			}
+		}
+		Ok(UncertainSolution { values, covariance })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn propagate_uncertainties() {
+        // 2x = 4 ± 0.2 gives x = 2 ± 0.1
+        let mat = CoefficientMatrix::new(1).add_equation(Equation::new(vec![2.0], 4.0));
+        let solution = mat.solve_uncertain(&Matrix::zeros(1, 1), &[0.2]).unwrap();
+        assert_eq!(solution.values(), &[2.0]);
+        assert!((solution.uncertainties()[0] - 0.1).abs() < 1e-12);
+
+        // An uncertain coefficient: x = b / a, σ(x) = x σ(a) / a
+        let solution = mat.solve_uncertain(&Matrix::from_rows(vec![vec![0.1]]).unwrap(), &[0.0]).unwrap();
+        assert!((solution.uncertainties()[0] - 0.1).abs() < 1e-12);
+
+        // x + y = 3 ± 0.3, x - y = 1: x and y share the uncertainty and are
+        // correlated
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 1.0], 3.0))
+            .add_equation(Equation::new(vec![1.0, -1.0], 1.0));
+        let solution = mat.solve_uncertain(&Matrix::zeros(2, 2), &[0.3, 0.0]).unwrap();
+        let uncertainties = solution.uncertainties();
+        assert!((uncertainties[0] - 0.15).abs() < 1e-12 && (uncertainties[1] - 0.15).abs() < 1e-12);
+        assert!((solution.covariance()[(0, 1)] - 0.0225).abs() < 1e-12);
+        assert!(matches!(mat.solve_uncertain(&Matrix::zeros(1, 2), &[0.0, 0.0]), Err(SolveError::IncompatibleDimensions((2, 2), (1, 2)))));
+    }
+}
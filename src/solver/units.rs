@@ -0,0 +1,307 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::ops::{Div, Mul, SubAssign};
+use core::str::FromStr;
+use num::traits::real::Real;
+
+use super::{CoefficientMatrix, Equation, SolveError};
+
+// Physical dimensions as exponents of the SI base units, so that a system
+// mixing volts, ohms and amperes can be checked before it is solved:
+//
+//     let volt: Unit = "V".parse()?;
+//     assert_eq!(volt, "kg m^2 s^-3 A^-1".parse()?);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Unit([i8; 7]);
+
+const BASE: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+// Derived units by their exponents of the base units
+const DERIVED: [(&str, [i8; 7]); 11] = [
+	("N", [1, 1, -2, 0, 0, 0, 0]),
+	("J", [2, 1, -2, 0, 0, 0, 0]),
+	("W", [2, 1, -3, 0, 0, 0, 0]),
+	("Pa", [-1, 1, -2, 0, 0, 0, 0]),
+	("Hz", [0, 0, -1, 0, 0, 0, 0]),
+	("C", [0, 0, 1, 1, 0, 0, 0]),
+	("V", [2, 1, -3, -1, 0, 0, 0]),
+	("Ohm", [2, 1, -3, -2, 0, 0, 0]),
+	("Ω", [2, 1, -3, -2, 0, 0, 0]),
+	("F", [-2, -1, 4, 2, 0, 0, 0]),
+	("H", [2, 1, -2, -2, 0, 0, 0]),
+];
+
+impl Unit {
+	pub fn dimensionless() -> Unit {
+		Unit([0; 7])
+	}
+
+	pub fn is_dimensionless(&self) -> bool {
+		self.0 == [0; 7]
+	}
+
+	// Exponents of m, kg, s, A, K, mol and cd
+	pub fn exponents(&self) -> [i8; 7] {
+		self.0
+	}
+
+	pub fn powi(self, exponent: i8) -> Unit {
+		let mut exponents = self.0;
+		for value in exponents.iter_mut() {
+			*value *= exponent;
+		}
+		Unit(exponents)
+	}
+
+	fn symbol(symbol: &str) -> Option<Unit> {
+		if symbol == "1" {
+			return Some(Unit::dimensionless());
+		}
+		if let Some(index) = BASE.iter().position(|&base| base == symbol) {
+			let mut exponents = [0; 7];
+			exponents[index] = 1;
+			return Some(Unit(exponents));
+		}
+		DERIVED.iter().find(|(name, _)| *name == symbol).map(|&(_, exponents)| Unit(exponents))
+	}
+}
+
+impl Mul for Unit {
+	type Output = Unit;
+
+	// Exponents add up
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn mul(self, other: Unit) -> Unit {
+		let mut exponents = self.0;
+		for (value, other) in exponents.iter_mut().zip(other.0.iter()) {
+			*value += other;
+		}
+		Unit(exponents)
+	}
+}
+
+impl Div for Unit {
+	type Output = Unit;
+
+	fn div(self, other: Unit) -> Unit {
+		self * other.powi(-1)
+	}
+}
+
+// Base units with their exponents, e.g. "kg m^2 s^-3 A^-1", or "1"
+impl fmt::Display for Unit {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_dimensionless() {
+			return write!(f, "1");
+		}
+		let mut first = true;
+		// Conventional order with kg first
+		for &index in &[1, 0, 2, 3, 4, 5, 6] {
+			let exponent = self.0[index];
+			if exponent == 0 {
+				continue;
+			}
+			if !first {
+				write!(f, " ")?;
+			}
+			first = false;
+			write!(f, "{}", BASE[index])?;
+			if exponent != 1 {
+				write!(f, "^{}", exponent)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+// Products of symbols separated by spaces or "*", each with an optional
+// integer exponent after "^". A "/" divides by the symbol after it, so
+// "m / s^2" and "J/kg/K" read as usual.
+impl FromStr for Unit {
+	type Err = UnitError;
+
+	fn from_str(s: &str) -> Result<Unit, UnitError> {
+		let invalid = || UnitError::Invalid(String::from(s));
+		let mut unit = Unit::dimensionless();
+		let mut divide = false;
+		let spaced = s.replace('/', " / ").replace('*', " ");
+		for token in spaced.split_whitespace() {
+			if token == "/" {
+				if divide {
+					return Err(invalid());
+				}
+				divide = true;
+				continue;
+			}
+			let (symbol, exponent) = match token.find('^') {
+				Some(caret) => (&token[..caret], token[caret + 1..].parse::<i8>().map_err(|_| invalid())?),
+				None => (token, 1),
+			};
+			let factor = Unit::symbol(symbol).ok_or_else(invalid)?.powi(exponent);
+			unit = if divide { unit / factor } else { unit * factor };
+			divide = false;
+		}
+		if divide {
+			return Err(invalid());
+		}
+		Ok(unit)
+	}
+}
+
+#[derive(Debug)]
+pub enum UnitError {
+	Invalid(String),
+	// The term of `unknown` in `equation` would give it the unit `found`,
+	// but other terms require `expected`
+	Inconsistent { equation: usize, unknown: usize, expected: Unit, found: Unit },
+	// No equation involves the unknown, so its unit is unknown as well
+	Undetermined(usize),
+	Solve(SolveError),
+}
+
+impl fmt::Display for UnitError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			UnitError::Invalid(text) =>
+				write!(f, "Invalid unit '{}'", text),
+			UnitError::Inconsistent { equation, unknown, expected, found } =>
+				write!(f, "Equation {} gives unknown {} the unit {} instead of {}", equation + 1, unknown + 1, found, expected),
+			UnitError::Undetermined(unknown) =>
+				write!(f, "The unit of unknown {} is undetermined", unknown + 1),
+			UnitError::Solve(err) =>
+				write!(f, "{}", err),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnitError {}
+
+impl From<SolveError> for UnitError {
+	fn from(err: SolveError) -> Self {
+		UnitError::Solve(err)
+	}
+}
+
+// A system whose coefficients and results carry units. The units of the
+// unknowns follow from the equations: each term a x must have the unit of
+// the result, so x has the unit of the result divided by that of a.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitSystem<T> {
+	matrix: CoefficientMatrix<T>,
+	coefficient_units: Vec<Vec<Unit>>,
+	result_units: Vec<Unit>,
+}
+
+impl<T> UnitSystem<T> {
+	pub fn new(size: usize) -> UnitSystem<T> {
+		UnitSystem {
+			matrix: CoefficientMatrix::new(size),
+			coefficient_units: Vec::with_capacity(size),
+			result_units: Vec::with_capacity(size),
+		}
+	}
+
+	pub fn add_equation(mut self, terms: Vec<(T, Unit)>, (result, unit): (T, Unit)) -> Self {
+		let (coefficients, units) = terms.into_iter().unzip();
+		self.matrix.push_equation(Equation::new(coefficients, result));
+		self.coefficient_units.push(units);
+		self.result_units.push(unit);
+		self
+	}
+
+	pub fn matrix(&self) -> &CoefficientMatrix<T> {
+		&self.matrix
+	}
+}
+
+impl<T> UnitSystem<T>
+where
+	T: Real + SubAssign + fmt::Display + fmt::Debug
+{
+	// Units of the unknowns, checking that every equation agrees on them.
+	// Terms with a zero coefficient don't constrain anything.
+	pub fn check(&self) -> Result<Vec<Unit>, UnitError> {
+		self.matrix.ensure_validated()?;
+		let size = self.matrix.size;
+		let mut units: Vec<Option<Unit>> = alloc::vec![None; size];
+		for (equation, (row, coefficient_units)) in self.matrix.matrix.iter().zip(&self.coefficient_units).enumerate() {
+			for (unknown, (coefficient, &coefficient_unit)) in row.coefficients.iter().zip(coefficient_units).enumerate() {
+				if coefficient.is_zero() {
+					continue;
+				}
+				let found = self.result_units[equation] / coefficient_unit;
+				match units[unknown] {
+					Some(expected) if expected != found => {
+						return Err(UnitError::Inconsistent { equation, unknown, expected, found });
+					},
+					Some(_) => (),
+					None => units[unknown] = Some(found),
+				}
+			}
+		}
+		units.into_iter()
+			.enumerate()
+			.map(|(unknown, unit)| unit.ok_or(UnitError::Undetermined(unknown)))
+			.collect()
+	}
+
+	// Check the units, then solve, pairing each value with its unit
+	pub fn solve(&self) -> Result<Vec<(T, Unit)>, UnitError> {
+		let units = self.check()?;
+		let solved = self.matrix.clone().convert()?.solve()?;
+		Ok(solved.solution().zip(units).collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn unit(text: &str) -> Unit {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_print_units() {
+        assert_eq!(unit("V"), unit("kg m^2 s^-3 A^-1"));
+        assert_eq!(unit("V / A"), unit("Ohm"));
+        assert_eq!(unit("J/kg/K"), unit("m^2 s^-2 K^-1"));
+        assert_eq!(unit("m*s^-1"), unit("m") / unit("s"));
+        assert_eq!(unit("V").to_string(), "kg m^2 s^-3 A^-1");
+        assert_eq!(unit("1").to_string(), "1");
+        assert!(unit("").is_dimensionless());
+        assert!(matches!("furlong".parse::<Unit>(), Err(UnitError::Invalid(_))));
+        assert!(matches!("m /".parse::<Unit>(), Err(UnitError::Invalid(_))));
+    }
+
+    #[test]
+    fn check_circuit_units() {
+        // Two resistors: 10 Ω I1 + 5 Ω I2 = 20 V, I1 - I2 = 0 A
+        let system = UnitSystem::new(2)
+            .add_equation(vec![(10.0, unit("Ohm")), (5.0, unit("Ohm"))], (20.0, unit("V")))
+            .add_equation(vec![(1.0, unit("1")), (-1.0, unit("1"))], (0.0, unit("A")));
+        let solution = system.solve().unwrap();
+        assert!((solution[0].0 - 4.0 / 3.0).abs() < 1e-12);
+        assert_eq!(solution[0].1, unit("A"));
+        assert_eq!(solution[1].1, unit("A"));
+
+        // Volts where amperes are needed
+        let wrong = UnitSystem::new(2)
+            .add_equation(vec![(10.0, unit("Ohm")), (5.0, unit("Ohm"))], (20.0, unit("V")))
+            .add_equation(vec![(1.0, unit("1")), (-1.0, unit("1"))], (0.0, unit("V")));
+        match wrong.check() {
+            Err(UnitError::Inconsistent { equation: 1, unknown: 0, expected, found }) => {
+                assert_eq!(expected, unit("A"));
+                assert_eq!(found, unit("V"));
+            },
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let unused = UnitSystem::new(2)
+            .add_equation(vec![(1.0, unit("1")), (0.0, unit("1"))], (1.0, unit("m")))
+            .add_equation(vec![(2.0, unit("1")), (0.0, unit("1"))], (2.0, unit("m")));
+        assert!(matches!(unused.check(), Err(UnitError::Undetermined(1))));
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use num::traits::real::Real;
+
+use super::{dot, Lu, SolveError};
+
+// Factors of A followed by rank-1 updates A + u vᵀ, solved by the
+// Sherman-Morrison formula
+//
+//     (A + u vᵀ)⁻¹ b = A⁻¹ b - A⁻¹ u (vᵀ A⁻¹ b) / (1 + vᵀ A⁻¹ u)
+//
+// in O(n²) per update instead of refactorizing. Each update adds a term to
+// every later solve, so after many of them a fresh factorization pays off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdatedLu<T> {
+	lu: Lu<T>,
+	updates: Vec<RankOne<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RankOne<T> {
+	u: Vec<T>,
+	v: Vec<T>,
+	// A⁻¹ u and 1 + vᵀ A⁻¹ u with A as before the update
+	inverse_u: Vec<T>,
+	denominator: T,
+}
+
+impl<T: Real> From<Lu<T>> for UpdatedLu<T> {
+	fn from(lu: Lu<T>) -> Self {
+		UpdatedLu { lu, updates: Vec::new() }
+	}
+}
+
+impl<T: Real> UpdatedLu<T> {
+	// Factors of the matrix before any update
+	pub fn lu(&self) -> &Lu<T> {
+		&self.lu
+	}
+
+	pub fn update_count(&self) -> usize {
+		self.updates.len()
+	}
+
+	// Replace the matrix by A + u vᵀ. Fails if the result is singular, in
+	// which case nothing changes.
+	pub fn update(&mut self, u: &[T], v: &[T]) -> Result<(), SolveError> {
+		let size = self.lu.permutation().len();
+		if u.len() != size || v.len() != size {
+			return Err(SolveError::IncompatibleDimensions((size, size), (u.len(), v.len())));
+		}
+		let w = self.solve(u)?;
+		let denominator = T::one() + dot(v, &w);
+		if denominator.is_zero() {
+			return Err(SolveError::DependentSolutionSet);
+		}
+		self.updates.push(RankOne { u: u.to_vec(), v: v.to_vec(), inverse_u: w, denominator });
+		Ok(())
+	}
+
+	// Add `delta` to the coefficient in `row` and `column`
+	pub fn update_coefficient(&mut self, row: usize, column: usize, delta: T) -> Result<(), SolveError> {
+		let size = self.lu.permutation().len();
+		if row >= size || column >= size {
+			return Err(SolveError::IndexOutOfBounds(row.max(column), size));
+		}
+		let mut u = alloc::vec![T::zero(); size];
+		let mut v = alloc::vec![T::zero(); size];
+		u[row] = delta;
+		v[column] = T::one();
+		self.update(&u, &v)
+	}
+
+	// Coefficients of `row` of the current matrix, rebuilt from the factors
+	// and updates in O(n²)
+	pub fn row(&self, row: usize) -> Result<Vec<T>, SolveError> {
+		let size = self.lu.permutation().len();
+		let position = self.lu.permutation().iter().position(|&original| original == row)
+			.ok_or(SolveError::IndexOutOfBounds(row, size))?;
+		let (lower, upper) = (self.lu.l(), self.lu.u());
+		let mut values: Vec<T> = (0..size)
+			.map(|column| (0..=position.min(column))
+				.fold(T::zero(), |sum, k| sum + lower[(position, k)] * upper[(k, column)]))
+			.collect();
+		for update in &self.updates {
+			for (value, &v) in values.iter_mut().zip(&update.v) {
+				*value = *value + update.u[row] * v;
+			}
+		}
+		Ok(values)
+	}
+
+	// Replace the coefficients of `row`, as a rank-1 update by the
+	// difference to the current ones
+	pub fn replace_row(&mut self, row: usize, coefficients: &[T]) -> Result<(), SolveError> {
+		let size = self.lu.permutation().len();
+		if coefficients.len() != size {
+			return Err(SolveError::UnfittingCoefficientAmount(coefficients.len(), size));
+		}
+		let current = self.row(row)?;
+		let difference: Vec<T> = coefficients.iter().zip(&current).map(|(&new, &old)| new - old).collect();
+		let mut unit = alloc::vec![T::zero(); size];
+		unit[row] = T::one();
+		self.update(&unit, &difference)
+	}
+
+	pub fn determinant(&self) -> T {
+		self.updates.iter().fold(self.lu.determinant(), |det, update| det * update.denominator)
+	}
+
+	pub fn solve(&self, b: &[T]) -> Result<Vec<T>, SolveError> {
+		let mut x = self.lu.solve(b)?;
+		for update in &self.updates {
+			let factor = dot(&update.v, &x) / update.denominator;
+			for (value, &correction) in x.iter_mut().zip(&update.inverse_u) {
+				*value = *value - correction * factor;
+			}
+		}
+		Ok(x)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn system(a01: f64, a20: f64) -> CoefficientMatrix<f64> {
+        CoefficientMatrix::new(3)
+            .add_equation(Equation::new(vec![4.0, a01, 1.0], 1.0))
+            .add_equation(Equation::new(vec![2.0, 5.0, 2.0], 2.0))
+            .add_equation(Equation::new(vec![a20, 1.0, 3.0], 3.0))
+    }
+
+    fn solve(mat: CoefficientMatrix<f64>) -> Vec<f64> {
+        mat.validate().unwrap().convert().unwrap().solve().unwrap().solution().collect()
+    }
+
+    #[test]
+    fn update_coefficients() {
+        let mut updated = UpdatedLu::from(system(1.0, 0.0).lu().unwrap());
+        updated.update_coefficient(0, 1, 2.0).unwrap();
+        updated.update_coefficient(2, 0, -1.5).unwrap();
+        assert_eq!(updated.update_count(), 2);
+
+        let expected = solve(system(3.0, -1.5));
+        for (x, y) in updated.solve(&[1.0, 2.0, 3.0]).unwrap().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        let determinant = system(3.0, -1.5).lu().unwrap().determinant();
+        assert!((updated.determinant() - determinant).abs() < 1e-10);
+        assert!(matches!(updated.update_coefficient(3, 0, 1.0), Err(SolveError::IndexOutOfBounds(3, 3))));
+    }
+
+    #[test]
+    fn replace_rows() {
+        let mut updated = UpdatedLu::from(system(1.0, 0.0).lu().unwrap());
+        for (row, expected) in [vec![4.0, 1.0, 1.0], vec![2.0, 5.0, 2.0], vec![0.0, 1.0, 3.0]].iter().enumerate() {
+            for (x, y) in updated.row(row).unwrap().iter().zip(expected) {
+                assert!((x - y).abs() < 1e-12);
+            }
+        }
+        updated.replace_row(2, &[-1.5, 1.0, 3.0]).unwrap();
+        updated.replace_row(0, &[4.0, 3.0, 1.0]).unwrap();
+        let expected = solve(system(3.0, -1.5));
+        for (x, y) in updated.solve(&[1.0, 2.0, 3.0]).unwrap().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        assert!((updated.row(2).unwrap()[0] - -1.5).abs() < 1e-12);
+        assert!(matches!(updated.replace_row(0, &[1.0]), Err(SolveError::UnfittingCoefficientAmount(1, 3))));
+        assert!(matches!(updated.row(3), Err(SolveError::IndexOutOfBounds(3, 3))));
+    }
+
+    #[test]
+    fn reject_singular_update() {
+        let mat = CoefficientMatrix::new(2)
+            .add_equation(Equation::new(vec![1.0, 0.0], 1.0))
+            .add_equation(Equation::new(vec![0.0, 1.0], 1.0));
+        let mut updated = UpdatedLu::from(mat.lu().unwrap());
+        // Subtracting the identity's first diagonal entry leaves a zero row
+        assert!(matches!(updated.update_coefficient(0, 0, -1.0), Err(SolveError::DependentSolutionSet)));
+        assert_eq!(updated.update_count(), 0);
+    }
+}
@@ -0,0 +1,58 @@
+use num::traits::real::Real;
+
+// Level-1 helpers on slices, shared by the residual and refinement code.
+// Slices of different length are treated as cut to the shorter one.
+
+// Σ aᵢ bᵢ
+pub fn dot<T: Real>(a: &[T], b: &[T]) -> T {
+	a.iter().zip(b).fold(T::zero(), |sum, (&x, &y)| sum + x * y)
+}
+
+// x ← alpha x
+pub fn scale<T: Real>(alpha: T, x: &mut [T]) {
+	for value in x.iter_mut() {
+		*value = *value * alpha;
+	}
+}
+
+// y ← alpha x + y
+pub fn axpy<T: Real>(alpha: T, x: &[T], y: &mut [T]) {
+	for (target, &value) in y.iter_mut().zip(x) {
+		*target = *target + alpha * value;
+	}
+}
+
+// Σ |xᵢ|
+pub fn norm1<T: Real>(x: &[T]) -> T {
+	x.iter().fold(T::zero(), |sum, value| sum + value.abs())
+}
+
+// √(Σ xᵢ²)
+pub fn norm2<T: Real>(x: &[T]) -> T {
+	dot(x, x).sqrt()
+}
+
+// max |xᵢ|, zero for an empty slice
+pub fn norm_inf<T: Real>(x: &[T]) -> T {
+	x.iter().fold(T::zero(), |largest, value| largest.max(value.abs()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn norms_and_updates() {
+        let x = vec![3.0, -4.0, 0.0];
+        assert_eq!(dot(&x, &[1.0, 1.0, 5.0]), -1.0);
+        assert_eq!((norm1(&x), norm2(&x), norm_inf(&x)), (7.0, 5.0, 4.0));
+        assert_eq!(norm_inf::<f64>(&[]), 0.0);
+
+        let mut y = vec![1.0, 1.0, 1.0];
+        axpy(2.0, &x, &mut y);
+        assert_eq!(y, vec![7.0, -7.0, 1.0]);
+        scale(-0.5, &mut y);
+        assert_eq!(y, vec![-3.5, 3.5, -0.5]);
+    }
+}
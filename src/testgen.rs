@@ -0,0 +1,148 @@
+use alloc::{vec, vec::Vec};
+use num::traits::real::Real;
+
+use crate::solver::{CoefficientMatrix, Equation};
+
+// Random systems with a known solution, for tests and benchmarks:
+//
+//     let system = Generator::new(42).condition(1e6).density(0.1).system::<f64>(100);
+//     let solved = system.matrix().clone().validate()?.convert()?.solve()?;
+//     // compare solved.solution() against system.solution()
+//
+// The solution x and the matrix A are drawn first and the results computed
+// as b = A x. A is a diagonally dominant matrix with the requested share of
+// off-diagonal entries, which is well conditioned, with its rows scaled
+// geometrically from 1 down to 1 / condition. Its condition number is then
+// within a small factor of `condition`. The same seed always gives the same
+// system.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    state: u64,
+    condition: f64,
+    density: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSystem<T> {
+    matrix: CoefficientMatrix<T>,
+    solution: Vec<T>,
+}
+
+impl<T> TestSystem<T> {
+    pub fn matrix(&self) -> &CoefficientMatrix<T> {
+        &self.matrix
+    }
+
+    pub fn solution(&self) -> &[T] {
+        &self.solution
+    }
+
+    pub fn into_parts(self) -> (CoefficientMatrix<T>, Vec<T>) {
+        (self.matrix, self.solution)
+    }
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Generator {
+        Generator { state: seed, condition: 1.0, density: 1.0 }
+    }
+
+    // Approximate condition number, at least 1
+    pub fn condition(mut self, condition: f64) -> Self {
+        self.condition = condition.max(1.0);
+        self
+    }
+
+    // Share of nonzero off-diagonal coefficients between 0 and 1
+    pub fn density(mut self, density: f64) -> Self {
+        self.density = density.clamp(0.0, 1.0);
+        self
+    }
+
+    // SplitMix64, small and good enough for test data
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in [-1, 1)
+    pub(crate) fn signed(&mut self) -> f64 {
+        2.0 * self.uniform() - 1.0
+    }
+
+    pub fn system<T: Real>(&mut self, size: usize) -> TestSystem<T> {
+        let solution: Vec<f64> = (0..size).map(|_| self.signed()).collect();
+        let mut matrix = CoefficientMatrix::new(size);
+        for i in 0..size {
+            let mut row = vec![0.0; size];
+            for (j, value) in row.iter_mut().enumerate() {
+                if j != i && self.uniform() < self.density {
+                    *value = self.signed();
+                }
+            }
+            // A diagonal above twice the off-diagonal sum keeps the unscaled
+            // matrix well conditioned
+            let off_diagonal: f64 = row.iter().map(|&value| Real::abs(value)).sum();
+            let sign = if self.uniform() < 0.5 { -1.0 } else { 1.0 };
+            row[i] = sign * (2.0 * off_diagonal + 1.0);
+
+            let exponent = if size > 1 { i as f64 / (size - 1) as f64 } else { 0.0 };
+            let scale = Real::powf(self.condition, -exponent);
+            let result = row.iter().zip(&solution).map(|(a, x)| a * x).sum::<f64>() * scale;
+            let coefficients = row.iter().map(|value| convert(value * scale)).collect();
+            matrix = matrix.add_equation(Equation::new(coefficients, convert(result)));
+        }
+        TestSystem { matrix, solution: solution.into_iter().map(convert).collect() }
+    }
+}
+
+fn convert<T: Real>(value: f64) -> T {
+    T::from(value).unwrap_or_else(T::zero)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::CoefficientMatrix;
+
+    fn solve(matrix: &CoefficientMatrix<f64>) -> Vec<f64> {
+        matrix.clone().validate().unwrap().convert().unwrap().solve().unwrap().solution().collect()
+    }
+
+    #[test]
+    fn known_solutions() {
+        let system = Generator::new(7).system::<f64>(20);
+        for (actual, expected) in solve(system.matrix()).iter().zip(system.solution()) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert_eq!(Generator::new(7).system::<f64>(20), system);
+        assert_ne!(Generator::new(8).system::<f64>(20), system);
+    }
+
+    #[test]
+    fn sparsity_and_conditioning() {
+        let system = Generator::new(1).density(0.0).condition(1e8).system::<f64>(10);
+        let nonzero = system.matrix().rows()
+            .flat_map(|equation| equation.coefficients().iter())
+            .filter(|value| **value != 0.0)
+            .count();
+        assert_eq!(nonzero, 10);
+        let diagonal: Vec<f64> = system.matrix().rows().enumerate()
+            .map(|(i, equation)| equation.coefficients()[i].abs())
+            .collect();
+        assert!((diagonal[0] / diagonal[9] - 1e8).abs() < 1.0);
+        for (actual, expected) in solve(system.matrix()).iter().zip(system.solution()) {
+            assert!((actual - expected).abs() < 1e-8);
+        }
+    }
+}
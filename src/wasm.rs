@@ -0,0 +1,1842 @@
+
+use wasm_bindgen::prelude::*;
+use core::convert::TryFrom;
+use wasm_bindgen::JsCast;
+#[cfg(feature = "gpu")]
+use std::{cell::RefCell, rc::Rc};
+
+use crate::chemistry;
+use crate::circuits;
+use crate::function::{self, Evaluate};
+use crate::markov;
+use crate::regression as linreg;
+use crate::solver;
+use crate::testgen;
+
+// Promise resolving after the current task, letting the event loop run
+async fn yield_now() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[cfg(feature = "gpu")]
+thread_local! {
+    // Context of `solve_gpu`, set by the first solve large enough for the
+    // GPU and holding `None` if there is no WebGPU adapter
+    static GPU: RefCell<Option<Option<Rc<solver::GpuContext>>>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "gpu")]
+async fn gpu_context() -> Option<Rc<solver::GpuContext>> {
+    if let Some(context) = GPU.with(|gpu| gpu.borrow().clone()) {
+        return context;
+    }
+    let context = solver::GpuContext::new().await.map(Rc::new);
+    GPU.with(|gpu| *gpu.borrow_mut() = Some(context.clone()));
+    context
+}
+
+#[wasm_bindgen]
+extern {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn log_error(s: &str);
+
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(callback: &js_sys::Function, milliseconds: i32);
+
+    #[wasm_bindgen(js_namespace = performance)]
+    fn now() -> f64;
+}
+
+// Types of the values crossing the boundary that wasm-bindgen would
+// otherwise declare as `any`
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = r#"
+export type Coefficients = number[];
+
+export type ProgressCallback = (percent: number) => void;
+
+export type PivotHook = (row: number, column: number, value: number) => void;
+export type RowSwapHook = (i: number, j: number) => void;
+export type RowEliminatedHook = (row: number) => void;
+
+// Errors carry a stable `code`, also used as their `name`, and the values
+// involved in `details`
+export type SolveErrorInfo =
+    | { code: "TooSmall"; details: { size: number } }
+    | { code: "TooLarge"; details: { size: number; max: number } }
+    | { code: "UnfittingEquationAmount"; details: { actual: number; expected: number } }
+    | { code: "UnfittingCoefficientAmount"; details: { actual: number; expected: number } }
+    | { code: "DependentSolutionSet"; details: { dependency?: Dependency } }
+    | { code: "EmptySolutionSet"; details: { dependency?: Dependency } }
+    | { code: "IncompatibleDimensions"; details: { left: [number, number]; right: [number, number] } }
+    | { code: "Cancelled"; details: {} }
+    | { code: "IndexOutOfBounds"; details: { index: number; length: number } }
+    | { code: "NotValidated"; details: {} }
+    | { code: "DuplicateName"; details: { name: string } }
+    | { code: "UnfittingRows"; details: { rows: { row: number; actual: number }[]; expected: number } }
+    | { code: "InvalidPermutation"; details: { index: number } }
+    | { code: "NonFiniteInput"; details: { row: number; column: number } }
+    | { code: "NonFiniteResult"; details: { row: number; column: number } }
+    | { code: "FixedPointOverflow"; details: { row: number; column: number } }
+    | { code: "Overflow"; details: { row: number; column: number } }
+    | { code: "UnenteredCells"; details: { cells: { row: number; column: number }[] } }
+    | { code: "NoSamples"; details: {} };
+
+export type SolveErrorCode = SolveErrorInfo["code"];
+
+// Attached to failed solves: the coefficients of equation `row` are the sum
+// of those of the equations in `combination` times their factors
+export interface Dependency {
+    row: number;
+    combination: { row: number; factor: number }[];
+    consistent: boolean;
+    message: string;
+}
+
+// Thrown by failing solves, or the rejection of `solve_async`
+export type SolveError = Error & { name: SolveErrorCode } & SolveErrorInfo;
+
+// Equations entered so far, one row of coefficients per result
+export interface MatrixContents {
+    size: number;
+    coefficients: number[][];
+    results: number[];
+}
+
+// Format of `to_json` and `from_json`
+export interface SystemJson {
+    size: number;
+    rows: number[][];
+    rhs: number[];
+}
+
+// Outcome of one system of `solve_batch`
+export type BatchResult = { solution: Float64Array } | { error: SolveError };
+
+// State after `step`: the row operation performed, if any, and the matrix
+export interface StepState {
+    step: Step | null;
+    finished: boolean;
+    matrix: MatrixContents;
+}
+
+// Determinants of `solve_cramer`: the solution is
+// `determinants[i] / determinant`
+export interface CramerSteps {
+    determinant: number;
+    determinants: number[];
+}
+
+// Values of the unknowns with their standard uncertainties, see
+// `solve_uncertain`
+export interface UncertainSolution {
+    values: Float64Array;
+    uncertainties: Float64Array;
+}
+
+// Statistics of the unknowns over randomly perturbed systems, see
+// `sensitivity`. `samples` counts the solved samples, `failures` the
+// singular ones left out.
+export interface Sensitivity {
+    mean: Float64Array;
+    variance: Float64Array;
+    min: Float64Array;
+    max: Float64Array;
+    samples: number;
+    failures: number;
+}
+
+// Node voltages, starting with ground, and voltage source currents of
+// `solve_circuit`
+export interface CircuitSolution {
+    voltages: Float64Array;
+    sourceCurrents: Float64Array;
+}
+
+// Fit of `regression`: the intercept followed by one coefficient per
+// predictor column, with their standard errors
+export interface RegressionFit {
+    coefficients: Float64Array;
+    standardErrors: Float64Array;
+    residuals: Float64Array;
+    rSquared: number;
+}
+
+// Fit of `CurveFitter`: one coefficient per basis function, for
+// polynomials the constant term first
+export interface CurveFit {
+    coefficients: Float64Array;
+    rSquared: number;
+}
+
+// Random system of `generate_system`, load it with
+// `MatrixSolver.from_json(JSON.stringify(generated.system))`
+export interface GeneratedSystem {
+    system: SystemJson;
+    solution: Float64Array;
+}
+
+// Result of `cross_check`, with `reference` null if the reference method
+// found the system singular
+export interface CrossCheck {
+    solution: Float64Array;
+    reference: Float64Array | null;
+    discrepancies: number[];
+}
+
+// Outcome of `validate`, with the error `solve` would throw for the shape
+// of the system, or null if it is complete and well-formed
+export interface Validation {
+    valid: boolean;
+    size: number;
+    equations: number;
+    shape: "square" | "overdetermined" | "underdetermined" | null;
+    error: SolveError | null;
+}
+
+// Problem found by `analyze`, with the indices of the equations involved
+export interface Issue {
+    kind: "zeroRow" | "contradictoryRow" | "duplicate" | "contradictory";
+    rows: number[];
+    contradiction: boolean;
+    message: string;
+}
+
+// Minimum, maximum or stationary inflection point of a polynomial
+export interface CriticalPoint {
+    x: number;
+    y: number;
+    kind: "minimum" | "maximum" | "inflection";
+}
+
+// Values of the unknowns by name, see `solution_map`
+export type SolutionMap = Record<string, number>;
+
+// Bytes held by a `MatrixSolver`, see `memory_stats`
+export interface MemoryStats {
+    matrix: number;
+    factorization: number;
+    solution: number;
+    total: number;
+    // Size of the whole wasm memory, which never shrinks
+    wasmMemory: number;
+}
+
+// Milliseconds spent in each phase of the last `solve`, see `set_timing`
+export interface Timings {
+    validate: number;
+    pivotSearch: number;
+    elimination: number;
+    backSubstitution: number;
+    total: number;
+}
+
+export type Step =
+    | { kind: "swap"; rows: [number, number] }
+    | { kind: "pivot"; row: number; value: number }
+    | { kind: "eliminate"; target: number; source: number; factor: number }
+    | { kind: "scale"; row: number; divisor: number };
+"#;
+
+#[wasm_bindgen]
+extern {
+    #[wasm_bindgen(typescript_type = "Coefficients")]
+    pub type Coefficients;
+
+    #[wasm_bindgen(extends = js_sys::Function, typescript_type = "ProgressCallback")]
+    pub type ProgressCallback;
+
+    #[wasm_bindgen(extends = js_sys::Function, typescript_type = "PivotHook")]
+    pub type PivotHook;
+
+    #[wasm_bindgen(extends = js_sys::Function, typescript_type = "RowSwapHook")]
+    pub type RowSwapHook;
+
+    #[wasm_bindgen(extends = js_sys::Function, typescript_type = "RowEliminatedHook")]
+    pub type RowEliminatedHook;
+
+    #[wasm_bindgen(extends = js_sys::Promise, typescript_type = "Promise<Float64Array>")]
+    pub type SolutionPromise;
+
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "Step[]")]
+    pub type Steps;
+
+    #[wasm_bindgen(typescript_type = "MatrixContents")]
+    pub type MatrixContents;
+
+    #[wasm_bindgen(typescript_type = "StepState")]
+    pub type StepState;
+
+    #[wasm_bindgen(typescript_type = "Timings")]
+    pub type Timings;
+
+    #[wasm_bindgen(typescript_type = "CramerSteps")]
+    pub type CramerSteps;
+
+    #[wasm_bindgen(typescript_type = "UncertainSolution")]
+    pub type UncertainSolution;
+
+    #[wasm_bindgen(typescript_type = "Sensitivity")]
+    pub type Sensitivity;
+
+    #[wasm_bindgen(typescript_type = "CircuitSolution")]
+    pub type CircuitSolution;
+
+    #[wasm_bindgen(typescript_type = "RegressionFit")]
+    pub type RegressionFit;
+
+    #[wasm_bindgen(typescript_type = "CurveFit")]
+    pub type CurveFit;
+
+    #[wasm_bindgen(typescript_type = "GeneratedSystem")]
+    pub type GeneratedSystem;
+
+    #[wasm_bindgen(typescript_type = "CrossCheck")]
+    pub type CrossCheck;
+
+    #[wasm_bindgen(typescript_type = "Validation")]
+    pub type Validation;
+
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "Issue[]")]
+    pub type Issues;
+
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "CriticalPoint[]")]
+    pub type CriticalPoints;
+
+    #[wasm_bindgen(typescript_type = "SolutionMap")]
+    pub type SolutionMap;
+
+    #[wasm_bindgen(typescript_type = "MemoryStats")]
+    pub type MemoryStats;
+
+    #[wasm_bindgen(typescript_type = "SystemJson[]")]
+    pub type Systems;
+
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "BatchResult[]")]
+    pub type BatchResults;
+}
+
+// JS `Error` with the `code` and `details` of `SolveErrorInfo` above
+fn solve_error(err: solver::SolveError) -> JsValue {
+    let number = |value: usize| JsValue::from_f64(value as f64);
+    let pair = |(a, b): (usize, usize)| JsValue::from(js_sys::Array::of2(&number(a), &number(b)));
+    let details = match err {
+        solver::SolveError::TooSmall(size) => object(&[("size", number(size))]),
+        solver::SolveError::TooLarge(size, max) => object(&[("size", number(size)), ("max", number(max))]),
+        solver::SolveError::UnfittingEquationAmount(actual, expected)
+        | solver::SolveError::UnfittingCoefficientAmount(actual, expected) =>
+            object(&[("actual", number(actual)), ("expected", number(expected))]),
+        solver::SolveError::IncompatibleDimensions(left, right) =>
+            object(&[("left", pair(left)), ("right", pair(right))]),
+        solver::SolveError::IndexOutOfBounds(index, length) =>
+            object(&[("index", number(index)), ("length", number(length))]),
+        solver::SolveError::DuplicateName(ref name) => object(&[("name", JsValue::from_str(name))]),
+        solver::SolveError::UnfittingRows(ref rows, expected) => {
+            let rows: js_sys::Array = rows.iter()
+                .map(|&(row, actual)| object(&[("row", number(row)), ("actual", number(actual))]))
+                .collect();
+            object(&[("rows", rows.into()), ("expected", number(expected))])
+        },
+        solver::SolveError::UnenteredCells(ref cells) => {
+            let cells: js_sys::Array = cells.iter()
+                .map(|&(row, column)| object(&[("row", number(row)), ("column", number(column))]))
+                .collect();
+            object(&[("cells", cells.into())])
+        },
+        solver::SolveError::InvalidPermutation(index) => object(&[("index", number(index))]),
+        solver::SolveError::NonFiniteInput(row, column)
+        | solver::SolveError::NonFiniteResult(row, column)
+        | solver::SolveError::FixedPointOverflow(row, column)
+        | solver::SolveError::Overflow(row, column) =>
+            object(&[("row", number(row)), ("column", number(column))]),
+        solver::SolveError::DependentSolutionSet
+        | solver::SolveError::EmptySolutionSet
+        | solver::SolveError::Cancelled
+        | solver::SolveError::NotValidated
+        | solver::SolveError::NoSamples => object(&[]),
+    };
+    let error = js_sys::Error::new(&err.to_string());
+    error.set_name(err.code());
+    let _ = js_sys::Reflect::set(&error, &JsValue::from_str("code"), &JsValue::from_str(err.code()));
+    let _ = js_sys::Reflect::set(&error, &JsValue::from_str("details"), &details);
+    error.into()
+}
+
+// Plain object of the given fields
+fn object(fields: &[(&str, JsValue)]) -> JsValue {
+    let object = js_sys::Object::new();
+    for (key, value) in fields {
+        let _ = js_sys::Reflect::set(&object, &JsValue::from_str(key), value);
+    }
+    object.into()
+}
+
+// Array of objects matching `CriticalPoint`
+fn critical_points(points: &[function::CriticalPoint<f64>]) -> CriticalPoints {
+    let points: js_sys::Array = points.iter()
+        .map(|point| {
+            let kind = match point.kind() {
+                function::CriticalKind::Minimum => "minimum",
+                function::CriticalKind::Maximum => "maximum",
+                function::CriticalKind::Inflection => "inflection",
+            };
+            object(&[
+                ("x", JsValue::from_f64(point.x())),
+                ("y", JsValue::from_f64(point.y())),
+                ("kind", JsValue::from_str(kind)),
+            ])
+        })
+        .collect();
+    points.unchecked_into()
+}
+
+// Object matching `MatrixContents`
+fn matrix_contents(matrix: &solver::CoefficientMatrix<f64>) -> JsValue {
+    let coefficients: js_sys::Array = matrix.rows()
+        .map(|equation| equation.coefficients().iter()
+            .map(|&value| JsValue::from_f64(value))
+            .collect::<js_sys::Array>())
+        .collect();
+    let results: js_sys::Array = matrix.rows()
+        .map(|equation| JsValue::from_f64(*equation.result()))
+        .collect();
+    object(&[
+        ("size", JsValue::from_f64(matrix.size() as f64)),
+        ("coefficients", coefficients.into()),
+        ("results", results.into()),
+    ])
+}
+
+fn step_object(step: &solver::Step<f64>) -> JsValue {
+    let number = |value: usize| JsValue::from_f64(value as f64);
+    match *step {
+        solver::Step::Swap(a, b) => object(&[
+            ("kind", "swap".into()),
+            ("rows", js_sys::Array::of2(&number(a), &number(b)).into()),
+        ]),
+        solver::Step::Pivot(row, value) => object(&[
+            ("kind", "pivot".into()),
+            ("row", number(row)),
+            ("value", value.into()),
+        ]),
+        solver::Step::Eliminate(target, source, factor) => object(&[
+            ("kind", "eliminate".into()),
+            ("target", number(target)),
+            ("source", number(source)),
+            ("factor", factor.into()),
+        ]),
+        solver::Step::Scale(row, divisor) => object(&[
+            ("kind", "scale".into()),
+            ("row", number(row)),
+            ("divisor", divisor.into()),
+        ]),
+    }
+}
+
+#[macro_export]
+macro_rules! console_log {
+    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
+}
+
+#[wasm_bindgen(start)]
+pub fn run() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
+
+// Callbacks subscribed to the steps of a solve
+#[derive(Default)]
+struct Hooks {
+    pivot: Option<js_sys::Function>,
+    swap: Option<js_sys::Function>,
+    eliminated: Option<js_sys::Function>,
+}
+
+impl Hooks {
+    fn is_empty(&self) -> bool {
+        self.pivot.is_none() && self.swap.is_none() && self.eliminated.is_none()
+    }
+}
+
+impl solver::Observer<f64> for &Hooks {
+    fn record(&mut self, step: solver::Step<f64>) {
+        let number = |value: usize| JsValue::from_f64(value as f64);
+        let _ = match (step, &self.pivot, &self.swap, &self.eliminated) {
+            // Pivots are always on the diagonal
+            (solver::Step::Pivot(row, value), Some(hook), _, _) =>
+                hook.call3(&JsValue::NULL, &number(row), &number(row), &JsValue::from_f64(value)),
+            (solver::Step::Swap(i, j), _, Some(hook), _) =>
+                hook.call2(&JsValue::NULL, &number(i), &number(j)),
+            (solver::Step::Eliminate(row, ..), _, _, Some(hook)) =>
+                hook.call1(&JsValue::NULL, &number(row)),
+            _ => Ok(JsValue::UNDEFINED),
+        };
+    }
+}
+
+// Refinement rounds of `solve_mixed` before falling back to f64
+const MIXED_ITERATIONS: usize = 10;
+
+// Smaller systems are solved faster on the CPU than it takes to upload them
+// to the GPU
+#[cfg(feature = "gpu")]
+const GPU_THRESHOLD: usize = 512;
+
+// How much `MatrixSolver` writes to the console. `Error` reports failed
+// solves, `Debug` also prints the matrix before and after solving.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off = 0,
+    Error = 1,
+    Debug = 2,
+}
+
+// Algorithm of `solve` and `solve_traced`, see `solver::Method`
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Substitution = 0,
+    GaussJordan = 1,
+    Compensated = 2,
+}
+
+impl From<Method> for solver::Method {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Substitution => solver::Method::Substitution,
+            Method::GaussJordan => solver::Method::GaussJordan,
+            Method::Compensated => solver::Method::Compensated,
+        }
+    }
+}
+
+// Order of the coefficients passed to `set_system`, see
+// `solver::InputLayout`
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    RowMajor = 0,
+    ColumnMajor = 1,
+}
+
+impl From<Layout> for solver::InputLayout {
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::RowMajor => solver::InputLayout::RowMajor,
+            Layout::ColumnMajor => solver::InputLayout::ColumnMajor,
+        }
+    }
+}
+
+// Solve many independent systems in one call, which avoids crossing the
+// boundary once per system. Failures are reported per system, so one
+// singular system doesn't abort the batch.
+#[wasm_bindgen]
+pub fn solve_batch(systems: Systems) -> Result<BatchResults, JsValue> {
+    let systems: Vec<solver::SystemData<f64>> = serde_wasm_bindgen::from_value(systems.into())?;
+    let results: js_sys::Array = systems.into_iter()
+        .map(|data| {
+            let solved = solver::CoefficientMatrix::try_from(data)
+                .and_then(solver::CoefficientMatrix::validate)
+                .and_then(solver::CoefficientMatrix::convert)
+                .and_then(solver::CoefficientMatrix::solve);
+            match solved {
+                Ok(matrix) => {
+                    let solution: Vec<f64> = matrix.solution().collect();
+                    object(&[("solution", js_sys::Float64Array::from(&solution[..]).into())])
+                },
+                Err(err) => object(&[("error", solve_error(err))]),
+            }
+        })
+        .collect();
+    Ok(results.unchecked_into())
+}
+
+// Solve A x = b with `n` unknowns, A given row by row, without keeping a
+// `MatrixSolver` around
+#[wasm_bindgen]
+pub fn solve_system(coefficients: js_sys::Float64Array, rhs: js_sys::Float64Array, n: usize) -> Result<js_sys::Float64Array, JsValue> {
+    let mut matrix = solver::CoefficientMatrix::new(n);
+    matrix.fill_parts(&coefficients.to_vec(), &rhs.to_vec()).map_err(solve_error)?;
+    matrix.solve_in_place().map_err(solve_error)?;
+    let solution: Vec<f64> = matrix.solution().collect();
+    Ok(js_sys::Float64Array::from(&solution[..]))
+}
+
+// Stationary distribution of a Markov chain, from the transition
+// probabilities of `size` states flattened row by row
+#[wasm_bindgen]
+pub fn steady_state(size: usize, probabilities: Vec<f64>) -> Result<Vec<f64>, JsValue> {
+    if probabilities.len() != size * size {
+        return Err(solve_error(solver::SolveError::UnfittingCoefficientAmount(probabilities.len(), size * size)));
+    }
+    let rows = probabilities.chunks(size.max(1)).map(<[f64]>::to_vec).collect();
+    let p = solver::Matrix::from_rows(rows).map_err(solve_error)?;
+    markov::steady_state(&p).map_err(|err| match err {
+        markov::MarkovError::SolveError(err) => solve_error(err),
+        err => JsValue::from_str(&err.to_string()),
+    })
+}
+
+// Least-squares affine transform between 2D or 3D point sets, flattened
+// as x, y(, z) per point. Returns the homogeneous matrix row by row.
+#[wasm_bindgen]
+pub fn fit_affine(dimension: usize, from: Vec<f64>, to: Vec<f64>) -> Result<Vec<f64>, JsValue> {
+    fn pairs<const N: usize>(from: &[f64], to: &[f64]) -> Vec<([f64; N], [f64; N])> {
+        let point = |chunk: &[f64]| <[f64; N]>::try_from(chunk).unwrap();
+        from.chunks_exact(N).map(point).zip(to.chunks_exact(N).map(point)).collect()
+    }
+    if from.len() != to.len() || !from.len().is_multiple_of(dimension.max(1)) {
+        return Err(solve_error(solver::SolveError::IncompatibleDimensions((from.len(), 1), (to.len(), 1))));
+    }
+    let transform = match dimension {
+        2 => solver::fit_affine(&pairs::<2>(&from, &to)),
+        3 => solver::fit_affine(&pairs::<3>(&from, &to)),
+        _ => return Err(JsValue::from_str("Only 2D and 3D points are supported")),
+    }.map_err(solve_error)?;
+    Ok((0..transform.rows()).flat_map(|i| transform.row(i).unwrap().to_vec()).collect())
+}
+
+// Random system with a known solution for demos and benchmarks, see
+// `testgen::Generator`
+#[wasm_bindgen]
+pub fn generate_system(size: usize, condition: f64, density: f64, seed: u32) -> Result<GeneratedSystem, JsValue> {
+    let (matrix, solution) = testgen::Generator::new(u64::from(seed))
+        .condition(condition)
+        .density(density)
+        .system::<f64>(size)
+        .into_parts();
+    let system = serde_wasm_bindgen::to_value(&solver::SystemData::from(matrix))?;
+    Ok(object(&[
+        ("system", system),
+        ("solution", js_sys::Float64Array::from(&solution[..]).into()),
+    ]).unchecked_into())
+}
+
+// Multiple linear regression of column `target` on the other columns of
+// the observations, flattened row by row with `columns` values each
+#[wasm_bindgen]
+pub fn regression(columns: usize, observations: Vec<f64>, target: usize) -> Result<RegressionFit, JsValue> {
+    if columns == 0 || !observations.len().is_multiple_of(columns) {
+        return Err(solve_error(solver::SolveError::UnfittingCoefficientAmount(observations.len(), columns)));
+    }
+    let rows: Vec<Vec<f64>> = observations.chunks(columns).map(<[f64]>::to_vec).collect();
+    let fit = linreg::Regression::fit(&rows, target).map_err(solve_error)?;
+    Ok(object(&[
+        ("coefficients", js_sys::Float64Array::from(fit.coefficients()).into()),
+        ("standardErrors", js_sys::Float64Array::from(fit.standard_errors()).into()),
+        ("residuals", js_sys::Float64Array::from(fit.residuals()).into()),
+        ("rSquared", JsValue::from_f64(*fit.r_squared())),
+    ]).unchecked_into())
+}
+
+// Truncated Fourier series fitted to samples, see `function::Fourier`
+#[wasm_bindgen]
+pub struct JsFourier {
+    fourier: function::Fourier<f64>,
+}
+
+#[wasm_bindgen]
+impl JsFourier {
+    // Fit `harmonics` harmonics of the angular frequency `omega`, e.g.
+    // 2π / period, to the points (xs[i], ys[i])
+    pub fn fit(xs: &[f64], ys: &[f64], harmonics: usize, omega: f64) -> Result<JsFourier, JsValue> {
+        if xs.len() != ys.len() {
+            return Err(solve_error(solver::SolveError::UnfittingCoefficientAmount(ys.len(), xs.len())));
+        }
+        let points: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+        let fourier = function::Fourier::fit(&points, harmonics, omega)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        Ok(JsFourier { fourier })
+    }
+
+    pub fn constant(&self) -> f64 {
+        self.fourier.constant()
+    }
+
+    pub fn cosines(&self) -> Vec<f64> {
+        self.fourier.cosines().to_vec()
+    }
+
+    pub fn sines(&self) -> Vec<f64> {
+        self.fourier.sines().to_vec()
+    }
+
+    pub fn eval(&self, x: f64) -> Result<f64, JsValue> {
+        self.fourier.eval(x)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    pub fn eval_range(&self, start: f64, end: f64, samples: usize) -> Result<Vec<f64>, JsValue> {
+        self.fourier.eval_range(start, end, samples)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.fourier.to_string()
+    }
+}
+
+// A JavaScript function of x as a basis function of `CurveFitter`. Throwing
+// or returning something other than a number fails the evaluation.
+struct JsBasis(js_sys::Function);
+
+impl Evaluate<f64> for JsBasis {
+    fn eval(&self, x: f64) -> Result<f64, function::Error> {
+        self.0.call1(&JsValue::NULL, &JsValue::from_f64(x))
+            .ok()
+            .and_then(|value| value.as_f64())
+            .ok_or(function::Error::EvaluationError)
+    }
+}
+
+impl core::fmt::Display for JsBasis {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}(x)", String::from(self.0.name()))
+    }
+}
+
+enum CurveModel {
+    Polynomial(function::Polynomial<f64>),
+    Basis(Vec<JsBasis>, Vec<f64>),
+}
+
+// Least-squares fits to (x, y) samples without assembling design matrices
+// by hand:
+//
+//     const fitter = new CurveFitter(xs, ys);
+//     const { coefficients, rSquared } = fitter.fit_polynomial(2);
+//     const smooth = fitter.predict(grid);
+//
+// Each fit replaces the previous one, which `predict` evaluates.
+#[wasm_bindgen]
+pub struct CurveFitter {
+    points: Vec<(f64, f64)>,
+    model: Option<CurveModel>,
+}
+
+#[wasm_bindgen]
+impl CurveFitter {
+    #[wasm_bindgen(constructor)]
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<CurveFitter, JsValue> {
+        if xs.len() != ys.len() {
+            return Err(solve_error(solver::SolveError::UnfittingCoefficientAmount(ys.len(), xs.len())));
+        }
+        let points = xs.iter().copied().zip(ys.iter().copied()).collect();
+        Ok(CurveFitter { points, model: None })
+    }
+
+    // Polynomial of the given degree, see `Polynomial::fit`
+    pub fn fit_polynomial(&mut self, degree: usize) -> Result<CurveFit, JsValue> {
+        let fit = function::Polynomial::fit(&self.points, degree)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        let coefficients = fit.polynomial().coefficients_in(function::Order::Ascending);
+        let result = curve_fit(&coefficients, *fit.r_squared());
+        self.model = Some(CurveModel::Polynomial(fit.polynomial().clone()));
+        Ok(result)
+    }
+
+    // Linear combination c1 f1(x) + ... + cn fn(x) of the given functions of
+    // x, e.g. [x => 1, Math.sin, Math.cos], see `LinearModel`
+    pub fn fit_basis(&mut self, basis: js_sys::Array) -> Result<CurveFit, JsValue> {
+        let basis = basis.iter()
+            .map(|f| f.dyn_into::<js_sys::Function>().map(JsBasis))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| JsValue::from_str("The basis has to be an array of functions"))?;
+        let functions: Vec<&dyn Evaluate<f64>> = basis.iter().map(|f| f as &dyn Evaluate<f64>).collect();
+        let (coefficients, r_squared) = function::LinearModel::fit(&functions, &self.points)
+            .map(|model| (model.coefficients().to_vec(), *model.r_squared()))
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        let result = curve_fit(&coefficients, r_squared);
+        self.model = Some(CurveModel::Basis(basis, coefficients));
+        Ok(result)
+    }
+
+    // Values of the last fit at the given x
+    pub fn predict(&self, xs: &[f64]) -> Result<Vec<f64>, JsValue> {
+        let model = self.model.as_ref()
+            .ok_or_else(|| JsValue::from_str("Nothing fitted yet, call fit_polynomial or fit_basis first"))?;
+        let eval = |x: f64| match model {
+            CurveModel::Polynomial(polynomial) => polynomial.eval(x),
+            CurveModel::Basis(basis, coefficients) => basis.iter().zip(coefficients)
+                .try_fold(0.0, |sum, (f, c)| -> Result<f64, function::Error> { Ok(sum + c * f.eval(x)?) }),
+        };
+        xs.iter().map(|&x| eval(x)).collect::<Result<_, function::Error>>()
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+}
+
+fn curve_fit(coefficients: &[f64], r_squared: f64) -> CurveFit {
+    object(&[
+        ("coefficients", js_sys::Float64Array::from(coefficients).into()),
+        ("rSquared", JsValue::from_f64(r_squared)),
+    ]).unchecked_into()
+}
+
+// Chemical equation like "Fe + O2 -> Fe2O3" with the smallest integer
+// coefficients that balance it, "4 Fe + 3 O2 -> 2 Fe2O3"
+#[wasm_bindgen]
+pub fn balance_equation(equation: &str) -> Result<String, JsValue> {
+    chemistry::balance(equation)
+        .map(|balanced| balanced.to_string())
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+// Node voltages and source currents of a circuit given as a SPICE-like
+// netlist, see `circuits::Circuit`
+#[wasm_bindgen]
+pub fn solve_circuit(netlist: &str) -> Result<CircuitSolution, JsValue> {
+    let circuit: circuits::Circuit<f64> = netlist.parse()
+        .map_err(|err: circuits::ParseNetlistError| JsValue::from_str(&err.to_string()))?;
+    let solution = circuit.solve().map_err(|err| JsValue::from_str(&err.to_string()))?;
+    Ok(object(&[
+        ("voltages", js_sys::Float64Array::from(solution.voltages()).into()),
+        ("sourceCurrents", js_sys::Float64Array::from(solution.source_currents()).into()),
+    ]).unchecked_into())
+}
+
+#[wasm_bindgen]
+pub struct MatrixSolver {
+    matrix: solver::CoefficientMatrix<f64>,
+    progress: Option<(js_sys::Function, usize)>,
+    cancel: solver::CancelToken,
+    // Solution of the last `solve`, kept contiguous for `solution_view`
+    solution: Vec<f64>,
+    log_level: LogLevel,
+    hooks: Hooks,
+    // Elimination driven by `step`, dropped whenever the system changes
+    stepping: Option<solver::Elimination<f64>>,
+    // Factors of the last `solve`, for `resolve`
+    factorization: Option<solver::UpdatedLu<f64>>,
+    pending_results: Vec<f64>,
+    method: Method,
+    // Names of the unknowns, empty for the default x, y, z or x 1, x 2, ...
+    names: Vec<String>,
+    // The system as entered while `matrix` holds its solved form, for
+    // `reset` and further edits
+    original: Option<solver::CoefficientMatrix<f64>>,
+    timing: bool,
+    // Phases of the last `solve` with timing enabled
+    timings: Option<solver::Timings>,
+}
+
+impl MatrixSolver {
+    fn with_matrix(matrix: solver::CoefficientMatrix<f64>) -> MatrixSolver {
+        MatrixSolver {
+            matrix,
+            progress: None,
+            cancel: solver::CancelToken::new(),
+            solution: Vec::new(),
+            log_level: LogLevel::Error,
+            hooks: Hooks::default(),
+            stepping: None,
+            factorization: None,
+            pending_results: Vec::new(),
+            method: Method::Substitution,
+            names: Vec::new(),
+            original: None,
+            timing: false,
+            timings: None,
+        }
+    }
+
+    // Observer calling the progress callback, if one is set
+    fn progress(&self, size: usize) -> Option<solver::Progress<impl FnMut(f64) + '_>> {
+        let total = match self.method {
+            Method::Substitution | Method::Compensated => (2 * size).saturating_sub(1),
+            Method::GaussJordan => 2 * size,
+        };
+        self.progress.as_ref().map(|(callback, every)| {
+            solver::Progress::with_total(total, *every, move |percent| {
+                let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(percent));
+            })
+        })
+    }
+
+    // Leave the matrix as `solve` would for the given solution
+    fn set_solution(&mut self, solution: Vec<f64>) -> Result<(), JsValue> {
+        let size = self.matrix.size();
+        let mut rows = Vec::with_capacity(size * (size + 1));
+        for (row, value) in solution.iter().enumerate() {
+            rows.extend((0..size).map(|column| if row == column { 1.0 } else { 0.0 }));
+            rows.push(*value);
+        }
+        if self.original.is_none() {
+            self.original = Some(self.matrix.clone());
+        }
+        self.matrix.fill(&rows).map_err(solve_error)?;
+        self.solution = solution;
+        Ok(())
+    }
+
+    // Replace the matrix by its solved form, keeping the entered system
+    fn set_solved(&mut self, solved: solver::CoefficientMatrix<f64>) {
+        let entered = core::mem::replace(&mut self.matrix, solved);
+        if self.original.is_none() {
+            self.original = Some(entered);
+        }
+        self.solution = self.matrix.solution().collect();
+    }
+
+    // `solve_error` with the equations that depend on each other attached to
+    // the details of dependent or contradictory systems
+    fn solve_failure(&self, err: solver::SolveError) -> JsValue {
+        let explain = matches!(err, solver::SolveError::DependentSolutionSet | solver::SolveError::EmptySolutionSet);
+        let error = solve_error(err);
+        if let Some(dependency) = self.matrix.dependency().filter(|_| explain) {
+            let combination: js_sys::Array = dependency.combination().iter()
+                .map(|&(row, factor)| object(&[
+                    ("row", JsValue::from_f64(row as f64)),
+                    ("factor", JsValue::from_f64(factor)),
+                ]))
+                .collect();
+            let dependency = object(&[
+                ("row", JsValue::from_f64(dependency.row() as f64)),
+                ("combination", combination.into()),
+                ("consistent", JsValue::from_bool(dependency.is_consistent())),
+                ("message", JsValue::from_str(&dependency.to_string())),
+            ]);
+            if let Ok(details) = js_sys::Reflect::get(&error, &JsValue::from_str("details")) {
+                let _ = js_sys::Reflect::set(&details, &JsValue::from_str("dependency"), &dependency);
+            }
+        }
+        error
+    }
+
+    // Go back to the system as entered before solving it, so that it can be
+    // edited or solved again
+    fn restore(&mut self) {
+        if let Some(original) = self.original.take() {
+            self.matrix = original;
+        }
+    }
+
+    // Drop what was derived from the current system after it changes
+    fn invalidate(&mut self) {
+        self.stepping = None;
+        self.factorization = None;
+    }
+}
+
+#[wasm_bindgen]
+impl MatrixSolver {
+    pub fn new(size: usize) -> MatrixSolver {
+        MatrixSolver::with_matrix(solver::CoefficientMatrix::<f64>::new(size))
+    }
+
+    // Load an augmented system from Matrix Market text
+    pub fn from_matrix_market(text: &str) -> Result<MatrixSolver, JsValue> {
+        let matrix = solver::CoefficientMatrix::<f64>::from_matrix_market(text)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        Ok(MatrixSolver::with_matrix(matrix))
+    }
+
+    // Load a system saved by `to_json`, see `SystemJson`
+    pub fn from_json(text: &str) -> Result<MatrixSolver, JsValue> {
+        let data: solver::SystemData<f64> = serde_json::from_str(text)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        let matrix = solver::CoefficientMatrix::try_from(data).map_err(solve_error)?;
+        Ok(MatrixSolver::with_matrix(matrix))
+    }
+
+    // Parse equations such as "2x + 3y = 5", naming the unknowns after the
+    // variables in alphabetical order
+    pub fn from_equations(equations: Vec<String>) -> Result<MatrixSolver, JsValue> {
+        let (names, matrix) = crate::expr::parse_system::<f64, _>(&equations)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?
+            .into_parts();
+        let mut solver = MatrixSolver::with_matrix(matrix);
+        solver.names = names;
+        Ok(solver)
+    }
+
+    // Name the unknowns in column order for `solution_map`
+    pub fn set_vars(&mut self, names: Vec<String>) -> Result<(), JsValue> {
+        let size = self.matrix.size();
+        if names.len() != size {
+            return Err(solve_error(solver::SolveError::UnfittingCoefficientAmount(names.len(), size)));
+        }
+        if let Some(index) = (1..names.len()).find(|&i| names[..i].contains(&names[i])) {
+            return Err(solve_error(solver::SolveError::DuplicateName(names[index].clone())));
+        }
+        self.names = names;
+        Ok(())
+    }
+
+    pub fn vars(&self) -> Vec<String> {
+        if self.names.is_empty() {
+            let size = self.matrix.size();
+            (0..size).map(|index| solver::variable_name(index, size)).collect()
+        } else {
+            self.names.clone()
+        }
+    }
+
+    // The solution of the last solve keyed by the names of the unknowns
+    pub fn solution_map(&self) -> SolutionMap {
+        let fields: Vec<(String, JsValue)> = self.vars().into_iter()
+            .zip(&self.solution)
+            .map(|(name, &value)| (name, JsValue::from_f64(value)))
+            .collect();
+        let fields: Vec<(&str, JsValue)> = fields.iter().map(|(name, value)| (name.as_str(), value.clone())).collect();
+        object(&fields).unchecked_into()
+    }
+
+    // The equations entered so far as JSON, e.g. for localStorage or URLs
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&solver::SystemData::from(self.matrix.clone()))
+            .unwrap_or_default()
+    }
+
+    // Current coefficients and results, so that a UI can show and edit
+    // what was entered. After `solve` this is the solved system until the
+    // next edit or `reset`.
+    pub fn get_matrix(&self) -> MatrixContents {
+        matrix_contents(&self.matrix).unchecked_into()
+    }
+
+    // "square", "overdetermined" or "underdetermined", throwing if the
+    // equations don't all have one coefficient per unknown
+    pub fn shape(&self) -> Result<String, JsValue> {
+        self.matrix.classify()
+            .map(|shape| shape.to_string())
+            .map_err(solve_error)
+    }
+
+    // Check that the system is complete and well-formed without solving it,
+    // e.g. while the user is still typing. Never throws.
+    pub fn validate(&self) -> Validation {
+        let (shape, error) = match self.matrix.classify() {
+            Ok(shape) => (JsValue::from_str(&shape.to_string()), JsValue::NULL),
+            Err(err) => (JsValue::NULL, solve_error(err)),
+        };
+        object(&[
+            ("valid", JsValue::from_bool(error.is_null())),
+            ("size", JsValue::from_f64(self.matrix.size() as f64)),
+            ("equations", JsValue::from_f64(self.matrix.rows().len() as f64)),
+            ("shape", shape),
+            ("error", error),
+        ]).unchecked_into()
+    }
+
+    // Zero equations and pairs of multiple or contradictory equations, to
+    // explain to users why a solve would fail, see `CoefficientMatrix::analyze`
+    pub fn analyze(&self) -> Issues {
+        let number = |value: usize| JsValue::from_f64(value as f64);
+        let issues: js_sys::Array = self.matrix.analyze().iter()
+            .map(|issue| {
+                let (kind, rows) = match *issue {
+                    solver::Issue::ZeroRow(row) => ("zeroRow", js_sys::Array::of1(&number(row))),
+                    solver::Issue::ContradictoryRow(row) => ("contradictoryRow", js_sys::Array::of1(&number(row))),
+                    solver::Issue::Duplicate(first, second) => ("duplicate", js_sys::Array::of2(&number(first), &number(second))),
+                    solver::Issue::Contradictory(first, second) => ("contradictory", js_sys::Array::of2(&number(first), &number(second))),
+                };
+                object(&[
+                    ("kind", JsValue::from_str(kind)),
+                    ("rows", rows.into()),
+                    ("contradiction", JsValue::from_bool(issue.is_contradiction())),
+                    ("message", JsValue::from_str(&issue.to_string())),
+                ])
+            })
+            .collect();
+        issues.unchecked_into()
+    }
+
+    // Current system as aligned text, e.g. for a debugging pane, with
+    // `precision` decimal places or else the shortest representation
+    pub fn to_string(&self, precision: Option<usize>) -> String {
+        match precision {
+            Some(precision) => self.matrix.format().precision(precision).to_string(),
+            None => self.matrix.to_string(),
+        }
+    }
+
+    pub fn to_matrix_market(&self) -> String {
+        self.matrix.to_matrix_market()
+    }
+
+    // Replace the whole system by A x = b with `n` unknowns, A given row by
+    // row in `a`, or column by column with `Layout.ColumnMajor`, and the
+    // right-hand sides in `b`
+    pub fn set_system(&mut self, a: &[f64], b: &[f64], n: usize, layout: Option<Layout>) -> Result<(), JsValue> {
+        // Throws without changes if the lengths don't fit `n`
+        solver::CoefficientMatrix::<f64>::check_parts(n, a.len(), b.len()).map_err(solve_error)?;
+        self.original = None;
+        if n != self.matrix.size() {
+            self.matrix.resize(n);
+            self.names.clear();
+        }
+        self.invalidate();
+        self.matrix.fill_parts_with(a, b, layout.unwrap_or(Layout::RowMajor).into()).map_err(solve_error)
+    }
+
+    pub fn add_eq(&mut self, val: Coefficients, result: f64) -> Result<(), JsValue> {
+        let coefficients: Vec<f64> = serde_wasm_bindgen::from_value(val.into())?;
+        self.restore();
+        self.matrix.push_equation(solver::Equation::new(coefficients, result));
+        self.invalidate();
+        Ok(())
+    }
+
+    // Replace the equation at `index`, e.g. to fix a typo in one row. On a
+    // solved system this updates the factors and the solution in O(n²)
+    // instead of requiring another `solve`.
+    pub fn set_eq(&mut self, index: usize, val: Coefficients, result: f64) -> Result<(), JsValue> {
+        let coefficients: Vec<f64> = serde_wasm_bindgen::from_value(val.into())?;
+        if let Some(factorization) = self.factorization.as_mut() {
+            // Throws without changes if the new system would be singular
+            factorization.replace_row(index, &coefficients).map_err(solve_error)?;
+            self.pending_results[index] = result;
+            if let Some(original) = self.original.as_mut() {
+                original.set_equation(index, solver::Equation::new(coefficients, result)).map_err(solve_error)?;
+            }
+            return self.resolve();
+        }
+        self.restore();
+        self.invalidate();
+        self.matrix.set_equation(index, solver::Equation::new(coefficients, result))
+            .map_err(solve_error)
+    }
+
+    pub fn remove_eq(&mut self, index: usize) -> Result<(), JsValue> {
+        self.restore();
+        self.invalidate();
+        self.matrix.remove_equation(index)
+            .map(|_| ())
+            .map_err(solve_error)
+    }
+
+    // Set one coefficient, e.g. from a spreadsheet-like editor, without
+    // sending the whole equation. Missing rows are created as they're
+    // needed, so cells can be filled in any order; `solve` fails with
+    // `UnenteredCells` until every one of them has been set.
+    pub fn set(&mut self, row: usize, col: usize, value: f64) -> Result<(), JsValue> {
+        self.restore();
+        self.invalidate();
+        self.matrix.set(row, col, value).map_err(solve_error)
+    }
+
+    // Coefficient in the given cell of the current matrix, undefined if it
+    // hasn't been entered
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        self.matrix.get(row, col)
+    }
+
+    // Set the right-hand side of one equation, see `set`
+    pub fn set_rhs(&mut self, row: usize, value: f64) -> Result<(), JsValue> {
+        self.restore();
+        self.invalidate();
+        self.matrix.set_rhs(row, value).map_err(solve_error)
+    }
+
+    // Substitute a known value for unknown `index` and drop it from the
+    // system, see `CoefficientMatrix::fix_variable`. The equations are kept,
+    // so a square system becomes overdetermined, which `solve` handles.
+    pub fn fix_variable(&mut self, index: usize, value: f64) -> Result<(), JsValue> {
+        self.restore();
+        self.matrix = self.matrix.fix_variable(index, value).map_err(solve_error)?;
+        if !self.names.is_empty() {
+            self.names.remove(index);
+        }
+        self.invalidate();
+        Ok(())
+    }
+
+    // Change the number of unknowns, zero-padding or truncating the
+    // equations entered so far
+    pub fn resize(&mut self, new_size: usize) {
+        self.restore();
+        self.matrix.resize(new_size);
+        self.names.clear();
+        self.invalidate();
+    }
+
+    // Measure the phases of the following solves with `performance.now()`,
+    // see `timings`. Timed solves keep no factors for `resolve`, and only
+    // plain substitution solves without hooks or a progress callback are
+    // timed.
+    pub fn set_timing(&mut self, enabled: bool) {
+        self.timing = enabled;
+    }
+
+    // Breakdown of the last `solve` if it was timed, null otherwise
+    pub fn timings(&self) -> Option<Timings> {
+        self.timings.map(|timings| object(&[
+            ("validate", JsValue::from_f64(timings.validate)),
+            ("pivotSearch", JsValue::from_f64(timings.pivot_search)),
+            ("elimination", JsValue::from_f64(timings.elimination)),
+            ("backSubstitution", JsValue::from_f64(timings.back_substitution)),
+            ("total", JsValue::from_f64(timings.total())),
+        ]).unchecked_into())
+    }
+
+    // Have `solve` call `callback` with the completion in percent after
+    // every `every` pivots, so that pages can show progress on large systems
+    pub fn set_progress_callback(&mut self, callback: ProgressCallback, every: usize) {
+        self.progress = Some((callback.unchecked_into(), every));
+    }
+
+    pub fn clear_progress_callback(&mut self) {
+        self.progress = None;
+    }
+
+    // Called with the row, column and value of every pivot during `solve`
+    pub fn on_pivot(&mut self, hook: PivotHook) {
+        self.hooks.pivot = Some(hook.unchecked_into());
+    }
+
+    // Called when rows i and j are exchanged for a better pivot
+    pub fn on_row_swap(&mut self, hook: RowSwapHook) {
+        self.hooks.swap = Some(hook.unchecked_into());
+    }
+
+    // Called whenever a multiple of the pivot row is subtracted from a row
+    pub fn on_row_eliminated(&mut self, hook: RowEliminatedHook) {
+        self.hooks.eliminated = Some(hook.unchecked_into());
+    }
+
+    pub fn clear_hooks(&mut self) {
+        self.hooks = Hooks::default();
+    }
+
+    // Gauss-Jordan and compensated solves keep no factors for `resolve` and
+    // are never blocked
+    pub fn set_method(&mut self, method: Method) {
+        self.method = method;
+    }
+
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+    }
+
+    pub fn log_level(&self) -> LogLevel {
+        self.log_level
+    }
+
+    // Throws a `SolveError` if the system can't be solved. The system as
+    // entered is kept, so that it can be edited and solved again, or
+    // restored with `reset`. Systems with more equations than unknowns are
+    // solved in the least squares sense and those with fewer for the
+    // solution of least norm, see `shape`; these leave the matrix as
+    // entered and keep no factors for `resolve`.
+    pub fn solve(&mut self) -> Result<(), JsValue> {
+        self.restore();
+        self.invalidate();
+        self.timings = None;
+        if let Ok(solver::Strategy::Rectangular) = self.matrix.strategy() {
+            self.solution = self.matrix.solve_rectangular().map_err(|err| self.solve_failure(err))?;
+            return Ok(());
+        }
+        if self.log_level >= LogLevel::Debug {
+            console_log!("Before:\n{}", self.matrix);
+        }
+        if self.timing && self.method == Method::Substitution && self.hooks.is_empty() && self.progress.is_none() {
+            let (matrix, timings) = self.matrix.clone().solve_timed(now)
+                .map_err(|err| self.solve_failure(err))?;
+            self.pending_results = self.matrix.results().collect();
+            self.set_solved(matrix);
+            self.timings = Some(timings);
+            return Ok(());
+        }
+        let solved = self.matrix.clone().validate().and_then(|temp| {
+            // Without hooks or a progress callback nothing is observed
+            if self.method != Method::Substitution {
+                let progress = self.progress(temp.size());
+                return Ok((temp.solve_with(self.method.into(), &mut (&self.hooks, progress))?, None));
+            }
+            if self.hooks.is_empty() && self.progress.is_none() {
+                if let solver::Strategy::Blocked(block) = temp.strategy()? {
+                    return Ok((temp.solve_blocked(block)?, None));
+                }
+                return temp.solve_factorized().map(|(matrix, lu)| (matrix, Some(lu.into())));
+            }
+            let mut observer = (&self.hooks, self.progress(temp.size()));
+            temp.solve_factorized_observed(&mut observer).map(|(matrix, lu)| (matrix, Some(lu.into())))
+        });
+        let (matrix, factorization) = match solved {
+            Ok(solved) => solved,
+            Err(err) => {
+                if self.log_level >= LogLevel::Error {
+                    log_error(&format!("Failed to solve: {}", err));
+                }
+                return Err(self.solve_failure(err));
+            },
+        };
+        self.pending_results = self.matrix.results().collect();
+        self.set_solved(matrix);
+        self.factorization = factorization;
+        if self.log_level >= LogLevel::Debug {
+            console_log!("Solved:\n{}", self.matrix);
+        }
+        Ok(())
+    }
+
+    // Set new right-hand sides for `resolve`, keeping the coefficients
+    pub fn update_results(&mut self, results: &[f64]) -> Result<(), JsValue> {
+        let size = self.matrix.size();
+        if results.len() != size {
+            return Err(solve_error(solver::SolveError::UnfittingEquationAmount(results.len(), size)));
+        }
+        self.pending_results.clear();
+        self.pending_results.extend_from_slice(results);
+        Ok(())
+    }
+
+    // Solve for the right-hand sides of `update_results`, or else those of
+    // the last `solve`, with the factors of the last `solve`, which costs
+    // only the O(n²) substitutions. Throws if
+    // there are no factors: before the first `solve`, after editing and
+    // after large or mixed precision solves, which don't keep them.
+    pub fn resolve(&mut self) -> Result<(), JsValue> {
+        let lu = self.factorization.as_ref()
+            .ok_or_else(|| JsValue::from_str("No factorization to reuse, call solve first"))?;
+        let solution = lu.solve(&self.pending_results).map_err(solve_error)?;
+        self.set_solution(solution)
+    }
+
+    // Propagate standard uncertainties of the coefficients, row by row, and
+    // of the results to the unknowns. The matrix is left as entered.
+    pub fn solve_uncertain(&self, coefficient_uncertainties: &[f64], result_uncertainties: &[f64]) -> Result<UncertainSolution, JsValue> {
+        let size = self.matrix.size().max(1);
+        let rows = coefficient_uncertainties.chunks(size).map(<[f64]>::to_vec).collect();
+        let uncertainties = solver::Matrix::from_rows(rows).map_err(solve_error)?;
+        let solution = self.matrix.clone().validate()
+            .and_then(|matrix| matrix.solve_uncertain(&uncertainties, result_uncertainties))
+            .map_err(solve_error)?;
+        Ok(object(&[
+            ("values", js_sys::Float64Array::from(solution.values()).into()),
+            ("uncertainties", js_sys::Float64Array::from(&solution.uncertainties()[..]).into()),
+        ]).unchecked_into())
+    }
+
+    // Re-solve `samples` copies of the system with every coefficient and
+    // result moved randomly within ± its bound, the coefficient bounds given
+    // row by row. The same seed gives the same samples. The matrix is left
+    // as entered.
+    pub fn sensitivity(&self, coefficient_bounds: &[f64], result_bounds: &[f64], samples: usize, seed: u32) -> Result<Sensitivity, JsValue> {
+        let size = self.matrix.size().max(1);
+        let rows = coefficient_bounds.chunks(size).map(<[f64]>::to_vec).collect();
+        let bounds = solver::Matrix::from_rows(rows).map_err(solve_error)?;
+        let sensitivity = self.matrix.clone().validate()
+            .and_then(|matrix| matrix.sensitivity(&bounds, result_bounds, samples, u64::from(seed)))
+            .map_err(solve_error)?;
+        Ok(object(&[
+            ("mean", js_sys::Float64Array::from(sensitivity.mean()).into()),
+            ("variance", js_sys::Float64Array::from(sensitivity.variance()).into()),
+            ("min", js_sys::Float64Array::from(sensitivity.min()).into()),
+            ("max", js_sys::Float64Array::from(sensitivity.max()).into()),
+            ("samples", JsValue::from_f64(sensitivity.samples() as f64)),
+            ("failures", JsValue::from_f64(sensitivity.failures() as f64)),
+        ]).unchecked_into())
+    }
+
+    // Minimum norm least squares solution through the pseudoinverse, for
+    // any number of equations including dependent or inconsistent ones.
+    // The matrix is left as entered.
+    pub fn solve_pinv(&self) -> Result<Vec<f64>, JsValue> {
+        self.matrix.solve_pinv().map_err(solve_error)
+    }
+
+    // Total least squares solution, allowing for errors in the
+    // coefficients as well as the results. The matrix is left as entered.
+    pub fn solve_tls(&self) -> Result<Vec<f64>, JsValue> {
+        self.matrix.solve_tls().map_err(solve_error)
+    }
+
+    // Tikhonov regularized least squares minimizing ‖A x - b‖² + λ ‖x‖²,
+    // for near-singular or noisy systems. Without λ it is chosen by
+    // generalized cross-validation. The matrix is left as entered.
+    pub fn solve_ridge(&self, lambda: Option<f64>) -> Result<Vec<f64>, JsValue> {
+        self.matrix.solve_ridge(lambda).map_err(solve_error)
+    }
+
+    // Values of only the given unknowns, in the order requested, without
+    // solving for the rest. The matrix is left as entered.
+    pub fn solve_unknowns(&self, unknowns: &[usize]) -> Result<Vec<f64>, JsValue> {
+        self.matrix.clone().validate()
+            .and_then(|matrix| matrix.solve_unknowns(unknowns))
+            .map_err(solve_error)
+    }
+
+    // Solve a system of up to four unknowns by Cramer's rule, returning the
+    // determinants involved so that pages can show the method
+    pub fn solve_cramer(&mut self) -> Result<CramerSteps, JsValue> {
+        self.restore();
+        let cramer = self.matrix.clone().validate()
+            .and_then(|matrix| matrix.cramer())
+            .map_err(solve_error)?;
+        self.invalidate();
+        self.set_solution(cramer.solution())?;
+        let determinants: js_sys::Array = cramer.replaced().iter().map(|&value| JsValue::from_f64(value)).collect();
+        Ok(object(&[
+            ("determinant", JsValue::from_f64(cramer.determinant())),
+            ("determinants", determinants.into()),
+        ]).unchecked_into())
+    }
+
+    // Solve without changing the system and compare against an independent
+    // reference method, see `CoefficientMatrix::cross_check`
+    #[cfg(feature = "cross-check")]
+    pub fn cross_check(&self, tolerance: f64) -> Result<CrossCheck, JsValue> {
+        let check = self.matrix.clone().validate()
+            .and_then(|matrix| matrix.cross_check(tolerance))
+            .map_err(solve_error)?;
+        let discrepancies: js_sys::Array = check.discrepancies().iter()
+            .map(|&index| JsValue::from_f64(index as f64))
+            .collect();
+        Ok(object(&[
+            ("solution", js_sys::Float64Array::from(check.solution()).into()),
+            ("reference", check.reference().map_or(JsValue::NULL, |reference| js_sys::Float64Array::from(reference).into())),
+            ("discrepancies", discrepancies.into()),
+        ]).unchecked_into())
+    }
+
+    // Add `delta` to one coefficient of the solved system and solve again
+    // in O(n²) through the Sherman-Morrison formula. Throws like `resolve`.
+    pub fn update_coefficient(&mut self, row: usize, column: usize, delta: f64) -> Result<(), JsValue> {
+        self.factorization.as_mut()
+            .ok_or_else(|| JsValue::from_str("No factorization to reuse, call solve first"))?
+            .update_coefficient(row, column, delta)
+            .map_err(solve_error)?;
+        if let Some(original) = self.original.as_mut() {
+            original[(row, column)] += delta;
+        }
+        self.resolve()
+    }
+
+    // Replace the coefficients A by A + u vᵀ and solve again, see
+    // `update_coefficient`
+    pub fn rank_one_update(&mut self, u: &[f64], v: &[f64]) -> Result<(), JsValue> {
+        self.factorization.as_mut()
+            .ok_or_else(|| JsValue::from_str("No factorization to reuse, call solve first"))?
+            .update(u, v)
+            .map_err(solve_error)?;
+        if let Some(original) = self.original.as_mut() {
+            for (row, &ui) in u.iter().enumerate() {
+                for (column, &vj) in v.iter().enumerate() {
+                    original[(row, column)] += ui * vj;
+                }
+            }
+        }
+        self.resolve()
+    }
+
+    // Replace the system with rows of `size` coefficients, each followed by
+    // its right-hand side, and solve it in place. Once the storage has grown
+    // to size, repeated calls don't allocate, so it suits solving a new
+    // system every frame. Read the result with `solution_view`.
+    pub fn solve_values(&mut self, values: &[f64]) -> Result<(), JsValue> {
+        self.invalidate();
+        // Keeping a copy would allocate on every call, so `reset` has
+        // nothing to go back to afterwards
+        self.original = None;
+        let solved = self.matrix.fill(values).and_then(|_| self.matrix.solve_in_place());
+        if let Err(err) = solved {
+            if self.log_level >= LogLevel::Error {
+                log_error(&format!("Failed to solve: {}", err));
+            }
+            return Err(solve_error(err));
+        }
+        self.solution.clear();
+        self.solution.extend(self.matrix.solution());
+        Ok(())
+    }
+
+    // Solve by factorizing in single precision and refining the result to
+    // double precision, which is faster on large systems. Hooks and the
+    // progress callback aren't called.
+    pub fn solve_mixed(&mut self) -> Result<(), JsValue> {
+        self.restore();
+        self.invalidate();
+        let solved = self.matrix.clone().validate().and_then(|temp| temp.solve_mixed(MIXED_ITERATIONS));
+        match solved {
+            Ok(matrix) => self.set_solved(matrix),
+            Err(err) => {
+                if self.log_level >= LogLevel::Error {
+                    log_error(&format!("Failed to solve: {}", err));
+                }
+                return Err(self.solve_failure(err));
+            },
+        }
+        Ok(())
+    }
+
+    // Solve like `solve`, returning the row operations performed
+    pub fn solve_traced(&mut self) -> Result<Steps, JsValue> {
+        self.restore();
+        self.invalidate();
+        let mut trace = solver::Trace::new();
+        let solved = self.matrix.clone()
+            .validate()
+            .and_then(|matrix| matrix.solve_with(self.method.into(), &mut trace))
+            .map_err(|err| self.solve_failure(err))?;
+        self.set_solved(solved);
+        let steps: js_sys::Array = trace.steps().iter().map(step_object).collect();
+        Ok(steps.unchecked_into())
+    }
+
+    // Advance the solve by a single row operation, e.g. from a "next"
+    // button. The first call starts from the entered system; once finished
+    // the solver holds the solution as after `solve`.
+    pub fn step(&mut self) -> Result<StepState, JsValue> {
+        let mut elimination = match self.stepping.take() {
+            Some(elimination) => elimination,
+            None => {
+                self.restore();
+                solver::Elimination::new(self.matrix.clone()).map_err(solve_error)?
+            },
+        };
+        let mut trace = solver::Trace::new();
+        let finished = elimination.step_observed(&mut trace).map_err(solve_error)?;
+        let step = trace.steps().iter()
+            .find(|step| !matches!(step, solver::Step::Pivot(..)))
+            .map_or(JsValue::NULL, step_object);
+        let matrix = matrix_contents(elimination.matrix());
+        if finished {
+            self.set_solved(elimination.into_matrix());
+        } else {
+            self.stepping = Some(elimination);
+        }
+        Ok(object(&[
+            ("step", step),
+            ("finished", JsValue::from_bool(finished)),
+            ("matrix", matrix),
+        ]).unchecked_into())
+    }
+
+    // Solve without blocking the page: elimination runs `pivots_per_slice`
+    // pivots at a time and yields to the event loop in between. The promise
+    // resolves to the solution as a Float64Array, or rejects with a
+    // `SolveError`, named "Cancelled" after `abort`. The solver itself is
+    // left unchanged, so `solution` doesn't reflect an async solve.
+    pub fn solve_async(&self, pivots_per_slice: usize) -> SolutionPromise {
+        let matrix = self.matrix.clone();
+        let size = matrix.size();
+        let cancel = self.cancel.clone();
+        let (callback, every) = match &self.progress {
+            Some((callback, every)) => (Some(callback.clone()), *every),
+            None => (None, 1),
+        };
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            let mut elimination = solver::Elimination::new(matrix).map_err(solve_error)?.cancel_token(cancel);
+            let mut progress = solver::Progress::new(size, every, |percent| {
+                if let Some(callback) = &callback {
+                    let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(percent));
+                }
+            });
+            while !elimination.run_observed(pivots_per_slice.max(1), &mut progress).map_err(solve_error)? {
+                yield_now().await;
+            }
+            let solution: Vec<f64> = elimination.into_matrix().solution().collect();
+            Ok(js_sys::Float64Array::from(&solution[..]).into())
+        });
+        promise.unchecked_into()
+    }
+
+    // Solve with WebGPU compute shaders, like `solve_mixed` but with the
+    // single precision elimination on the GPU, for systems of thousands of
+    // unknowns. Without WebGPU and below `GPU_THRESHOLD` unknowns it solves
+    // on the CPU. The promise resolves and rejects like `solve_async`'s,
+    // and the solver is left unchanged as well.
+    #[cfg(feature = "gpu")]
+    pub fn solve_gpu(&self) -> SolutionPromise {
+        let matrix = self.matrix.clone();
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            let matrix = matrix.validate().map_err(solve_error)?;
+            let context = if matrix.size() >= GPU_THRESHOLD { gpu_context().await } else { None };
+            let solved = matrix.solve_gpu(context.as_deref(), MIXED_ITERATIONS).await.map_err(solve_error)?;
+            let solution: Vec<f64> = solved.solution().collect();
+            Ok(js_sys::Float64Array::from(&solution[..]).into())
+        });
+        promise.unchecked_into()
+    }
+
+    // Cancel the async solves in flight. Solves started afterwards run
+    // normally.
+    pub fn abort(&mut self) {
+        self.cancel.cancel();
+        self.cancel = solver::CancelToken::new();
+    }
+
+    // Values of the unknowns once `solve` has run
+    pub fn solution(&self) -> Vec<f64> {
+        self.solution.clone()
+    }
+
+    // Undo the last solve: go back to the system as entered and drop the
+    // solution and the factors kept for `resolve`
+    pub fn reset(&mut self) {
+        self.restore();
+        self.invalidate();
+        self.solution.clear();
+    }
+
+    // Solution of the last `solve` as a Float64Array over the wasm memory,
+    // without copying. The view is only valid until the next call into this
+    // module: any call may grow the memory, which detaches the view, and the
+    // next solve overwrites or moves the values. Copy it with `slice()` to
+    // keep it longer. Empty before the first solve.
+    pub fn solution_view(&self) -> js_sys::Float64Array {
+        // Safe as long as the documented rules are followed: nothing
+        // allocates or touches `self.solution` while JS holds the view
+        unsafe { js_sys::Float64Array::view(&self.solution) }
+    }
+
+    // Solution rendered with simple fractions, e.g. `x = 1/3`, for values
+    // within `tolerance` of one
+    pub fn solution_fractions(&self, tolerance: f64) -> String {
+        self.matrix.solution_fractions(tolerance)
+    }
+
+    // Bytes held by the matrix, a paused `step` elimination or the factors
+    // kept for `resolve`, and the last solution. Traces aren't kept;
+    // `solve_traced` hands them to JS.
+    pub fn memory_stats(&self) -> MemoryStats {
+        let matrix = self.matrix.heap_bytes();
+        let factorization = self.stepping.as_ref().map_or(0, solver::Elimination::heap_bytes)
+            + self.factorization.as_ref().map_or(0, solver::UpdatedLu::heap_bytes);
+        let solution = self.solution.capacity() * core::mem::size_of::<f64>();
+        let memory: js_sys::WebAssembly::Memory = wasm_bindgen::memory().unchecked_into();
+        let wasm_memory = memory.buffer().unchecked_into::<js_sys::ArrayBuffer>().byte_length();
+        object(&[
+            ("matrix", JsValue::from_f64(matrix as f64)),
+            ("factorization", JsValue::from_f64(factorization as f64)),
+            ("solution", JsValue::from_f64(solution as f64)),
+            ("total", JsValue::from_f64((matrix + factorization + solution) as f64)),
+            ("wasmMemory", JsValue::from_f64(f64::from(wasm_memory))),
+        ]).unchecked_into()
+    }
+
+    // Bytes a system of `size` equations needs, to check before building it
+    pub fn estimated_bytes(size: usize) -> f64 {
+        solver::CoefficientMatrix::<f64>::estimated_bytes(size) as f64
+    }
+
+    // Sentence form of the current system for screen readers. After `solve`
+    // this reads out the solution.
+    pub fn describe(&self) -> String {
+        self.matrix.describe()
+    }
+
+    // The system as held and as entered, a `step` solve in progress, the
+    // solution and the names of the unknowns as compact bytes, e.g. for
+    // IndexedDB or a worker. Callbacks, hooks and settings aren't included,
+    // nor the factors for `resolve`.
+    pub fn snapshot(&self) -> js_sys::Uint8Array {
+        let snapshot = solver::Snapshot {
+            matrix: self.matrix.clone(),
+            original: self.original.clone(),
+            elimination: self.stepping.clone(),
+            solution: self.solution.clone(),
+            names: self.names.clone(),
+        };
+        js_sys::Uint8Array::from(&snapshot.to_bytes()[..])
+    }
+
+    // Replace the state by one saved with `snapshot`, keeping callbacks,
+    // hooks and settings. Throws on bytes that aren't a valid snapshot and
+    // leaves the solver unchanged then.
+    #[wasm_bindgen(js_name = restore)]
+    pub fn restore_snapshot(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        let snapshot = solver::Snapshot::from_bytes(bytes)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        self.invalidate();
+        self.matrix = snapshot.matrix;
+        self.original = snapshot.original;
+        self.stepping = snapshot.elimination;
+        self.solution = snapshot.solution;
+        self.names = snapshot.names;
+        self.pending_results.clear();
+        Ok(())
+    }
+}
+
+// Single precision counterpart of `MatrixSolver` for large systems where
+// half the memory matters more than the last digits. It has the editing and
+// solving methods but none of the observation and export ones.
+#[wasm_bindgen]
+pub struct MatrixSolverF32 {
+    matrix: solver::CoefficientMatrix<f32>,
+    solution: Vec<f32>,
+}
+
+#[wasm_bindgen]
+impl MatrixSolverF32 {
+    pub fn new(size: usize) -> MatrixSolverF32 {
+        MatrixSolverF32 {
+            matrix: solver::CoefficientMatrix::new(size),
+            solution: Vec::new(),
+        }
+    }
+
+    pub fn add_eq(&mut self, coefficients: Vec<f32>, result: f32) {
+        self.matrix.push_equation(solver::Equation::new(coefficients, result));
+    }
+
+    pub fn set_eq(&mut self, index: usize, coefficients: Vec<f32>, result: f32) -> Result<(), JsValue> {
+        self.matrix.set_equation(index, solver::Equation::new(coefficients, result))
+            .map_err(solve_error)
+    }
+
+    pub fn remove_eq(&mut self, index: usize) -> Result<(), JsValue> {
+        self.matrix.remove_equation(index)
+            .map(|_| ())
+            .map_err(solve_error)
+    }
+
+    pub fn resize(&mut self, new_size: usize) {
+        self.matrix.resize(new_size);
+    }
+
+    pub fn solve(&mut self) -> Result<(), JsValue> {
+        self.matrix = self.matrix.clone()
+            .validate()
+            .and_then(solver::CoefficientMatrix::convert)
+            .and_then(solver::CoefficientMatrix::solve)
+            .map_err(solve_error)?;
+        self.solution = self.matrix.solution().collect();
+        Ok(())
+    }
+
+    // See `MatrixSolver.solve_values`
+    pub fn solve_values(&mut self, values: &[f32]) -> Result<(), JsValue> {
+        self.matrix.fill(values)
+            .and_then(|_| self.matrix.solve_in_place())
+            .map_err(solve_error)?;
+        self.solution.clear();
+        self.solution.extend(self.matrix.solution());
+        Ok(())
+    }
+
+    pub fn solution(&self) -> Vec<f32> {
+        self.solution.clone()
+    }
+
+    // Same rules as `MatrixSolver.solution_view`
+    pub fn solution_view(&self) -> js_sys::Float32Array {
+        unsafe { js_sys::Float32Array::view(&self.solution) }
+    }
+
+    pub fn estimated_bytes(size: usize) -> f64 {
+        solver::CoefficientMatrix::<f32>::estimated_bytes(size) as f64
+    }
+}
+
+// Equations fed in chunks, e.g. from a streamed response, and eliminated
+// as they arrive instead of building the whole system in JS first
+#[wasm_bindgen]
+pub struct MatrixStream {
+    stream: solver::EquationStream<f64>,
+}
+
+#[wasm_bindgen]
+impl MatrixStream {
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: usize) -> MatrixStream {
+        MatrixStream {
+            stream: solver::EquationStream::new(size),
+        }
+    }
+
+    // Whole rows of `size` coefficients, each followed by its right-hand side
+    pub fn push_rows(&mut self, chunk: &[f64]) -> Result<(), JsValue> {
+        self.stream.push_rows(chunk).map_err(solve_error)
+    }
+
+    pub fn received(&self) -> usize {
+        self.stream.received()
+    }
+
+    // Solve once every equation has been pushed, consuming the stream
+    pub fn finish(self) -> Result<MatrixSolver, JsValue> {
+        let matrix = self.stream.finish().map_err(solve_error)?;
+        let mut solver = MatrixSolver::with_matrix(matrix);
+        solver.solution = solver.matrix.solution().collect();
+        Ok(solver)
+    }
+}
+
+// Polynomial with coefficients ordered from the highest power down
+#[wasm_bindgen]
+pub struct JsPolynomial {
+    polynomial: function::Polynomial<f64>,
+}
+
+#[wasm_bindgen]
+impl JsPolynomial {
+    #[wasm_bindgen(constructor)]
+    pub fn new(coefficients: Vec<f64>) -> JsPolynomial {
+        JsPolynomial {
+            polynomial: function::Polynomial::new(coefficients),
+        }
+    }
+
+    // Coefficients ordered from the constant term up
+    pub fn from_ascending(coefficients: Vec<f64>) -> JsPolynomial {
+        JsPolynomial {
+            polynomial: function::Polynomial::with_order(coefficients, function::Order::Ascending),
+        }
+    }
+
+    // Monic polynomial vanishing at the given roots
+    pub fn from_roots(roots: Vec<f64>) -> JsPolynomial {
+        JsPolynomial {
+            polynomial: function::Polynomial::from_roots(&roots),
+        }
+    }
+
+    // Parse the usual notation, e.g. `3x^2 - 2x + 1`
+    pub fn parse(text: &str) -> Result<JsPolynomial, JsValue> {
+        let polynomial = text.parse::<function::Polynomial<f64>>()
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        Ok(JsPolynomial { polynomial })
+    }
+
+    pub fn coefficients(&self) -> Vec<f64> {
+        self.polynomial.coefficients().clone()
+    }
+
+    pub fn eval(&self, x: f64) -> Result<f64, JsValue> {
+        self.polynomial.eval(x)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    // Values at `samples` evenly spaced points between `start` and `end`,
+    // returned as one Float64Array instead of one call per point
+    pub fn eval_range(&self, start: f64, end: f64, samples: usize) -> Result<Vec<f64>, JsValue> {
+        self.polynomial.eval_range(start, end, samples)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    // Real roots in increasing order, multiple roots once
+    pub fn real_roots(&self) -> Vec<f64> {
+        self.polynomial.real_roots()
+    }
+
+    // Points with a horizontal tangent, e.g. to annotate a graph
+    pub fn critical_points(&self) -> CriticalPoints {
+        critical_points(&self.polynomial.critical_points())
+    }
+
+    // Local minima and maxima between `a` and `b`, including both ends
+    pub fn extrema_on(&self, a: f64, b: f64) -> CriticalPoints {
+        critical_points(&self.polynomial.extrema_on(a, b))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.polynomial.to_string()
+    }
+
+    // Like `toString` with superscript powers and a proper minus sign, for
+    // display rather than parsing
+    pub fn to_unicode(&self) -> String {
+        alloc::format!("{:#}", self.polynomial)
+    }
+}
@@ -0,0 +1,134 @@
+// Differential test between the native and the wasm32 build of the solver.
+//
+// The same pseudo-random corpus of systems is solved natively and by the
+// wasm package running in node, and the results are compared bit for bit.
+// The test needs node and a nodejs build of the package, so it is ignored by
+// default:
+//
+//     wasm-pack build --target nodejs --out-dir pkg-node
+//     cargo test --test differential -- --ignored
+//
+// Set DIFF_WASM_PKG to use a package in another directory and DIFF_TOLERANCE
+// to compare with a relative tolerance instead of bitwise.
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use mat_solve::solver::{CoefficientMatrix, Equation};
+
+const SYSTEMS: usize = 200;
+const MAX_SIZE: usize = 12;
+const SEED: u64 = 0x5eed_1234_abcd_ef01;
+
+struct System {
+    rows: Vec<Vec<f64>>,
+    rhs: Vec<f64>,
+}
+
+// xorshift64*, so the corpus is identical on every run without pulling in
+// a random number crate
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let bits = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        // Uniform in [-10, 10)
+        (bits >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+    }
+}
+
+// Diagonally dominant systems, so every one of them is solvable
+fn corpus() -> Vec<System> {
+    let mut rng = Rng(SEED);
+    (0..SYSTEMS)
+        .map(|idx| {
+            let size = idx % MAX_SIZE + 1;
+            let mut rows: Vec<Vec<f64>> = (0..size)
+                .map(|_| (0..size).map(|_| rng.next()).collect())
+                .collect();
+            for (idx, row) in rows.iter_mut().enumerate() {
+                let sum: f64 = row.iter().map(|value| value.abs()).sum();
+                row[idx] = row[idx].signum() * (sum + 1.0);
+            }
+            let rhs = (0..size).map(|_| rng.next()).collect();
+            System { rows, rhs }
+        })
+        .collect()
+}
+
+fn solve_native(system: &System) -> Vec<f64> {
+    let mut matrix = CoefficientMatrix::new(system.rows.len());
+    for (row, rhs) in system.rows.iter().zip(system.rhs.iter()) {
+        matrix = matrix.add_equation(Equation::new(row.clone(), *rhs));
+    }
+    matrix.validate().unwrap()
+        .convert().unwrap()
+        .solve().unwrap()
+        .solution()
+        .collect()
+}
+
+fn solve_wasm(corpus: &[System]) -> Vec<Vec<f64>> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let pkg = env::var_os("DIFF_WASM_PKG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("pkg-node"));
+    assert!(pkg.exists(), "wasm package not found at {}, build it with \
+        `wasm-pack build --target nodejs --out-dir pkg-node`", pkg.display());
+
+    let dir = env::temp_dir().join(format!("mat-solve-differential-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("corpus.json");
+    let output = dir.join("results.json");
+    let systems: Vec<_> = corpus.iter()
+        .map(|system| serde_json::json!({ "rows": system.rows, "rhs": system.rhs }))
+        .collect();
+    fs::write(&input, serde_json::to_string(&systems).unwrap()).unwrap();
+
+    let status = Command::new("node")
+        .arg(root.join("tests/differential/run.js"))
+        .arg(&pkg)
+        .arg(&input)
+        .arg(&output)
+        .status()
+        .expect("failed to run node");
+    assert!(status.success(), "node exited with {}", status);
+
+    // The script reports every value as the hex string of its bits
+    let results: Vec<Vec<String>> = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    results.iter()
+        .map(|solution| solution.iter()
+            .map(|bits| f64::from_bits(u64::from_str_radix(bits, 16).unwrap()))
+            .collect())
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn native_matches_wasm() {
+    let corpus = corpus();
+    let tolerance: Option<f64> = env::var("DIFF_TOLERANCE").ok().map(|value| value.parse().unwrap());
+    let wasm = solve_wasm(&corpus);
+    assert_eq!(wasm.len(), corpus.len());
+
+    let mut mismatches = Vec::new();
+    for (idx, (system, wasm)) in corpus.iter().zip(wasm.iter()).enumerate() {
+        let native = solve_native(system);
+        let equal = native.len() == wasm.len() && native.iter().zip(wasm.iter()).all(|(a, b)| match tolerance {
+            Some(tolerance) => (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1.0),
+            None => a.to_bits() == b.to_bits(),
+        });
+        if !equal {
+            mismatches.push(format!("system {}: native {:?}, wasm {:?}", idx, native, wasm));
+        }
+    }
+    assert!(mismatches.is_empty(), "{} of {} systems differ:\n{}",
+        mismatches.len(), corpus.len(), mismatches.join("\n"));
+}
@@ -0,0 +1,29 @@
+// Solve a corpus of systems with the nodejs build of the wasm package.
+// Usage: node run.js <pkg dir> <corpus.json> <results.json>
+//
+// Results are written to a file because the solver logs to the console.
+// Every value is reported as the hex string of its bits so that the
+// comparison on the Rust side is exact.
+const fs = require("fs");
+const path = require("path");
+
+const [pkg, input, output] = process.argv.slice(2);
+const wasm = require(path.resolve(pkg));
+
+const bits = (value) => {
+  const buffer = new DataView(new ArrayBuffer(8));
+  buffer.setFloat64(0, value);
+  return buffer.getBigUint64(0).toString(16).padStart(16, "0");
+};
+
+const corpus = JSON.parse(fs.readFileSync(input, "utf8"));
+const results = corpus.map(({ rows, rhs }) => {
+  const solver = wasm.MatrixSolver.new(rows.length);
+  rows.forEach((row, idx) => solver.add_eq(row, rhs[idx]));
+  solver.solve();
+  const solution = Array.from(solver.solution(), bits);
+  solver.free();
+  return solution;
+});
+
+fs.writeFileSync(output, JSON.stringify(results));
@@ -0,0 +1,6 @@
+// The JavaScript bindings of `mat-solve` as the cdylib wasm-pack packages:
+//
+//     wasm-pack build wasm --out-dir ../pkg
+//
+// `mat-solve` itself stays an rlib, so that it builds for no_std targets.
+pub use mat_solve::*;
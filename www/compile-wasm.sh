@@ -1,3 +1,3 @@
 #!/usr/bin/env sh
 rm -rf ../pkg/
-wasm-pack build
+wasm-pack build ../wasm --out-dir ../pkg
@@ -12,7 +12,7 @@ END
 }
 
 rm -rf ../pkg/
-wasm-pack build
+wasm-pack build ../wasm --out-dir ../pkg
 
 # Replace the node module with the new wasm files
 rm -rf node_modules/$(project_name)/